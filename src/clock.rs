@@ -0,0 +1,87 @@
+//! The clocks telling the time to a file system and to the commands run on it.
+//!
+//! Every timestamp, from the times of the inodes to the output of `date`, is read from the clock
+//! of the file system. Replacing the [`SystemClock`] with a [`FixedClock`] or a [`ManualClock`]
+//! makes the output of the commands reproducible, and lets files be staged in the past.
+
+use std::{fmt::Debug, sync::Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::locks::MutexExt;
+
+pub trait Clock: Debug + Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The time of the machine running the shell.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock stopped at a given time.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// A clock that only moves when it is told to.
+#[derive(Debug)]
+pub struct ManualClock {
+    time: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    #[must_use]
+    pub fn new(time: DateTime<Utc>) -> Self {
+        Self {
+            time: Mutex::new(time),
+        }
+    }
+
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self.time.lock_unpoisoned() = time;
+    }
+
+    /// Moves the clock forward, or backward for a negative duration.
+    pub fn advance(&self, duration: Duration) {
+        *self.time.lock_unpoisoned() += duration;
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.time.lock_unpoisoned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn test_manual_clock() {
+        let start = Utc
+            .with_ymd_and_hms(2024, 1, 1, 0, 0, 0)
+            .single()
+            .expect("Invalid date");
+        let clock = ManualClock::new(start);
+        assert_eq!(clock.now(), start);
+        clock.advance(Duration::minutes(90));
+        assert_eq!(clock.now(), start + Duration::minutes(90));
+        clock.set(start);
+        assert_eq!(clock.now(), start);
+    }
+}
@@ -1,10 +1,12 @@
 //! Representations of commands, elements that can be executed in the shell context.
 
-use std::{collections::HashMap, sync::OnceLock};
+use std::{collections::HashMap, path::Path, sync::OnceLock};
 
 use crate::{
+    FileSystem, GroupId, User, UserId,
     commands::args::{ArgumentKind, BasicArgument, parse_string_argument},
-    errors::ShellError,
+    errors::{FileSystemError, ShellError},
+    shell::Shell,
 };
 use flags::FlagDefinition;
 use strum::IntoEnumIterator;
@@ -34,6 +36,28 @@ pub trait ExecutableCommand {
     /// The arguments of the command
     fn args(&self) -> Option<ArgumentKind>;
 
+    /// Whether flags can follow the arguments of the command, like with most GNU utilities
+    /// (`rm file -f`).
+    ///
+    /// When disabled, everything following the first argument is an argument.
+    fn permute_arguments(&self) -> bool {
+        true
+    }
+
+    /// Whether the words starting with a dash are parsed as flags.
+    ///
+    /// When disabled, every word is an argument, for commands with their own grammar like the
+    /// expressions of `find`.
+    fn parse_flags(&self) -> bool {
+        true
+    }
+
+    /// Whether a word starting with a dash is an argument rather than flags, like the modes of
+    /// `chmod -x`.
+    fn is_dash_argument(&self, _arg: &str) -> bool {
+        false
+    }
+
     /// Executes a command
     ///
     /// # Returns
@@ -52,6 +76,102 @@ pub trait ExecutableCommand {
 #[derive(Debug)]
 pub struct CommandOutput(pub Option<String>);
 
+/// Returns the user-facing message of a file system error, like the C library `strerror`.
+///
+/// Errors that are not expected to be shown to the user are given back.
+pub fn error_message(error: ShellError) -> Result<&'static str, ShellError> {
+    match error {
+        ShellError::FileSystem(FileSystemError::EntryNotFound(_)) => {
+            Ok("No such file or directory")
+        }
+        ShellError::FileSystem(FileSystemError::EntryAlreadyExists(_)) => Ok("File exists"),
+        ShellError::FileSystem(FileSystemError::NotADirectory(_)) => Ok("Not a directory"),
+        ShellError::FileSystem(FileSystemError::IsADirectory(_)) => Ok("Is a directory"),
+        ShellError::FileSystem(FileSystemError::PermissionDenied(_)) => Ok("Permission denied"),
+        ShellError::FileSystem(FileSystemError::OperationNotPermitted(_)) => {
+            Ok("Operation not permitted")
+        }
+        ShellError::FileSystem(FileSystemError::NoSpaceLeft(_)) => Ok("No space left on device"),
+        ShellError::FileSystem(FileSystemError::QuotaExceeded(_)) => Ok("Disk quota exceeded"),
+        error => Err(error),
+    }
+}
+
+/// Resolves a user by name, or by numeric id.
+pub fn resolve_user(fs: &FileSystem, user: &str) -> Option<UserId> {
+    fs.find_user_by_name(user)
+        .map(|user| user.id)
+        .or_else(|| user.parse().ok())
+}
+
+/// Resolves a group by name, or by numeric id.
+pub fn resolve_group(fs: &FileSystem, group: &str) -> Option<GroupId> {
+    fs.find_group_by_name(group)
+        .map(|group| group.id)
+        .or_else(|| group.parse().ok())
+}
+
+/// Resolves a comma-separated list of group names or ids, giving back the first unknown group.
+pub fn resolve_groups<'a>(fs: &FileSystem, groups: &'a str) -> Result<Vec<GroupId>, &'a str> {
+    groups
+        .split(',')
+        .filter(|group| !group.is_empty())
+        .map(|group| {
+            resolve_group(fs, group)
+                .filter(|group_id| fs.get_group(*group_id).is_some())
+                .ok_or(group)
+        })
+        .collect()
+}
+
+/// Finds a user by name, or by id like the coreutils do when no user has that name.
+pub fn find_user<'a>(fs: &'a FileSystem, username: &str) -> Option<&'a User> {
+    fs.find_user_by_name(username).or_else(|| {
+        username
+            .parse()
+            .ok()
+            .and_then(|user_id| fs.get_user(user_id))
+    })
+}
+
+/// Returns the output of a user administration command run by a regular user, or `None` when
+/// the session belongs to root.
+pub fn require_superuser(shell: &Shell, command: &str) -> Option<CommandOutput> {
+    let is_root = shell
+        .fs
+        .lock()
+        .get_user(shell.current_session.current_user())
+        .is_some_and(|user| user.is_root());
+    (!is_root).then(|| CommandOutput(Some(format!("{command}: Permission denied."))))
+}
+
+/// Checks a user or group name against the rules of the shadow utilities: a lowercase letter or
+/// an underscore, followed by lowercase letters, digits, underscores or dashes, and optionally
+/// ending with a dollar sign.
+pub fn is_valid_name(name: &str) -> bool {
+    let name = name.strip_suffix('$').unwrap_or(name);
+    let mut chars = name.chars();
+    name.len() <= 32
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// Reads an operand of a command filtering text, `-` being the standard input.
+pub fn read_input(shell: &mut Shell, path: &str) -> Result<String, ShellError> {
+    if path == "-" {
+        return Ok(shell
+            .read_lines()
+            .into_iter()
+            .map(|line| line + "\n")
+            .collect());
+    }
+    shell
+        .current_session
+        .read_file(&mut shell.fs.lock(), Path::new(path))
+}
+
 pub struct Command {
     pub command: Commands,
     pub flags: Flags,
@@ -102,8 +222,20 @@ impl CommandParser {
 
     pub fn parse(mut self) -> Result<Command, Error> {
         let mut args_iter = self.args.iter().peekable();
+        let mut only_arguments = !self.command.parse_flags();
         while let Some(arg) = args_iter.next() {
-            match arg_kind(arg) {
+            let kind = if only_arguments {
+                ArgKind::Argument
+            } else if arg == "--" {
+                // Everything after `--` is an argument, even if it starts with a dash.
+                only_arguments = true;
+                continue;
+            } else if self.command.is_dash_argument(arg) {
+                ArgKind::Argument
+            } else {
+                arg_kind(arg)
+            };
+            match kind {
                 ArgKind::ShorthandFlag => {
                     let mut flag_iter = arg.chars().skip(1).peekable();
                     while let Some(flag) = flag_iter.next() {
@@ -112,35 +244,22 @@ impl CommandParser {
                         match self.flag_defs.get_flag_shorthand(flag) {
                             Some(flag_spec) => match &flag_spec.arg_type {
                                 ArgumentKind::Basic(_) => {
-                                    if has_next_flag {
-                                        // Technically this means that we combined flags but this
-                                        // one expected an argument.
-                                        // TODO: Search up what is the proper sanction for this.
+                                    // Like getopt, the rest of the word is the argument of the
+                                    // flag (`-n5`), otherwise the next word is.
+                                    let flag_arg = if has_next_flag {
+                                        flag_iter.by_ref().collect::<String>()
+                                    } else if has_next_arg {
+                                        args_iter.next().cloned().unwrap_or_default()
+                                    } else {
                                         return Err(Error::InvalidFlagArgument(
                                             flag.to_string(),
                                             "No argument provided".to_string(),
                                         ));
-                                    }
-                                    if !has_next_arg {
-                                        return Err(Error::InvalidFlagArgument(
-                                            flag.to_string(),
-                                            "No argument provided".to_string(),
-                                        ));
-                                    }
-
-                                    let flag_arg = args_iter.next().ok_or_else(|| {
-                                        Error::InvalidFlagArgument(
-                                            flag.to_string(),
-                                            "No argument provided".to_string(),
-                                        )
-                                    })?;
+                                    };
                                     let parsed_arg =
-                                        parse_string_argument(flag_arg, &flag_spec.arg_type)
+                                        parse_string_argument(&flag_arg, &flag_spec.arg_type)
                                             .map_err(|e| {
-                                                Error::ArgumentParsing(
-                                                    flag_arg.to_string(),
-                                                    e.to_string(),
-                                                )
+                                                Error::ArgumentParsing(flag_arg, e.to_string())
                                             })?;
                                     self.parsed_flags
                                         .insert(flag_spec.name, Argument::Basic(parsed_arg));
@@ -160,11 +279,34 @@ impl CommandParser {
                     }
                 }
                 ArgKind::LonghandFlag => {
-                    let flag_name = arg
+                    let flag = arg
                         .strip_prefix("--")
                         .expect("A longhand flag should start with --");
+                    // The argument of a longhand flag can be attached with `=`.
+                    let (flag_name, attached_arg) = match flag.split_once('=') {
+                        Some((flag_name, flag_arg)) => (flag_name, Some(flag_arg)),
+                        None => (flag, None),
+                    };
                     match self.flag_defs.get_flag_longhand(flag_name) {
                         Some(flag_spec) => match &flag_spec.arg_type {
+                            ArgumentKind::Basic(_) if attached_arg.is_some() => {
+                                let flag_arg = attached_arg.unwrap_or_default();
+                                let parsed_arg = parse_string_argument(
+                                    flag_arg,
+                                    &flag_spec.arg_type,
+                                )
+                                .map_err(|e| {
+                                    Error::ArgumentParsing(flag_arg.to_string(), e.to_string())
+                                })?;
+                                self.parsed_flags
+                                    .insert(flag_name, Argument::Basic(parsed_arg));
+                            }
+                            _ if attached_arg.is_some() => {
+                                return Err(Error::InvalidFlagArgument(
+                                    flag_name.to_string(),
+                                    "Flag doesn't allow an argument".to_string(),
+                                ));
+                            }
                             ArgumentKind::Basic(_) => {
                                 match args_iter.peek() {
                                     Some(arg) if arg.starts_with('-') => {
@@ -277,7 +419,11 @@ impl CommandParser {
                                 self.parsed_args = Some(Argument::List(list_args));
                             }
                             ArgumentKind::Enumeration(argument_kind) => {
-                                let mut list_args: Vec<BasicArgument> = Vec::new();
+                                let mut list_args: Vec<BasicArgument> =
+                                    match self.parsed_args.take() {
+                                        Some(Argument::List(list_args)) => list_args,
+                                        _ => Vec::new(),
+                                    };
                                 let argument = parse_string_argument(
                                     arg,
                                     &ArgumentKind::Basic(argument_kind.clone()),
@@ -286,6 +432,11 @@ impl CommandParser {
                                     Error::ArgumentParsing(arg.to_string(), e.to_string())
                                 })?;
                                 list_args.push(argument);
+                                if self.command.permute_arguments() && !only_arguments {
+                                    // Flags can still follow, each argument is added on its own.
+                                    self.parsed_args = Some(Argument::List(list_args));
+                                    continue;
+                                }
                                 for arg in args_iter.by_ref() {
                                     let argument = parse_string_argument(
                                         arg,
@@ -2,6 +2,8 @@
 
 use strum_macros::Display;
 
+use crate::{commands::Flags, errors::ShellError};
+
 /// Possible arguments of a command or flag, with their values
 #[derive(Debug, PartialEq)]
 pub enum Argument {
@@ -54,3 +56,27 @@ pub fn parse_string_argument(arg: &str, kind: &ArgumentKind) -> Result<BasicArgu
         argument => Err(format!("Invalid argument kind: {argument}")),
     }
 }
+
+/// Returns the arguments of a command taking strings.
+pub fn string_arguments(args: Option<Argument>) -> Result<Vec<String>, ShellError> {
+    match args {
+        Some(Argument::List(args)) => args.into_iter().try_fold(Vec::new(), |mut acc, arg| {
+            match arg {
+                BasicArgument::String(arg) => acc.push(arg),
+                _ => return Err(ShellError::Internal("Invalid argument".to_string())),
+            }
+            Ok(acc)
+        }),
+        Some(_) => Err(ShellError::Internal("Invalid argument".to_string())),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Returns the value of a flag taking a string.
+pub fn string_flag<'a>(flags: &'a Flags, name: &str) -> Result<Option<&'a str>, ShellError> {
+    match flags.flag(name) {
+        Some(Argument::Basic(BasicArgument::String(value))) => Ok(Some(value)),
+        Some(_) => Err(ShellError::Internal(format!("Invalid {name} flag"))),
+        None => Ok(None),
+    }
+}
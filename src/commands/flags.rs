@@ -6,7 +6,8 @@ use std::collections::{HashMap, HashSet};
 use crate::commands::args::{Argument, ArgumentKind};
 
 pub struct Flags {
-    flags: HashMap<String, Argument>,
+    /// The arguments of each flag, in the order they were given.
+    flags: HashMap<String, Vec<Argument>>,
 }
 
 impl Flags {
@@ -17,14 +18,20 @@ impl Flags {
     }
 
     pub fn insert(&mut self, name: &str, arg: Argument) {
-        self.flags.insert(name.to_string(), arg);
+        self.flags.entry(name.to_string()).or_default().push(arg);
     }
 
+    /// Returns the argument of a flag, the last one if it was given several times.
     pub fn flag(&self, name: &str) -> Option<&Argument> {
-        self.flags.get(name)
+        self.flags.get(name).and_then(|args| args.last())
     }
 
-    pub fn flags(&self) -> &HashMap<String, Argument> {
+    /// Returns every argument given to a flag that can be repeated, like the keys of `sort`.
+    pub fn flag_all(&self, name: &str) -> &[Argument] {
+        self.flags.get(name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn flags(&self) -> &HashMap<String, Vec<Argument>> {
         &self.flags
     }
 }
@@ -4,15 +4,58 @@ use strum_macros::EnumIter;
 
 use crate::commands::list;
 
+pub mod awk;
+pub mod cat;
 pub mod cd;
+pub mod chgrp;
+pub mod chmod;
+pub mod chown;
+pub mod cmp;
+pub mod cut;
+pub mod date;
+pub mod df;
+pub mod diff;
+pub mod du;
 pub mod echo;
 pub mod exit;
+pub mod find;
+pub mod getfacl;
+pub mod gpasswd;
+pub mod grep;
+pub mod groupadd;
+pub mod groupdel;
+pub mod groups;
+pub mod head;
 pub mod history;
+pub mod id;
+pub mod ln;
 pub mod ls;
 pub mod mkdir;
+pub mod passwd;
+pub mod paste;
+pub mod patch;
 pub mod pwd;
 pub mod rm;
+pub mod sed;
+pub mod setfacl;
+pub mod sort;
+pub mod stat;
+pub mod su;
+pub mod sudo;
+pub mod tail;
 pub mod touch;
+pub mod tr;
+pub mod tree;
+pub mod umask;
+pub mod uniq;
+pub mod useradd;
+pub mod userdel;
+pub mod usermod;
+pub mod w;
+pub mod wc;
+pub mod who;
+pub mod whoami;
+pub mod write;
 
 #[derive(EnumIter, Clone, Copy)]
 #[enum_dispatch::enum_dispatch]
@@ -26,4 +69,47 @@ pub enum Commands {
     Remove(list::rm::RemoveCommand),
     Touch(list::touch::TouchCommand),
     List(list::ls::LsCommand),
+    Stat(list::stat::StatCommand),
+    Link(list::ln::LinkCommand),
+    ChangeMode(list::chmod::ChangeModeCommand),
+    ChangeOwner(list::chown::ChangeOwnerCommand),
+    ChangeGroup(list::chgrp::ChangeGroupCommand),
+    Umask(list::umask::UmaskCommand),
+    GetFileAcl(list::getfacl::GetFileAclCommand),
+    SetFileAcl(list::setfacl::SetFileAclCommand),
+    UserAdd(list::useradd::UserAddCommand),
+    UserDel(list::userdel::UserDelCommand),
+    UserMod(list::usermod::UserModCommand),
+    GroupAdd(list::groupadd::GroupAddCommand),
+    GroupDel(list::groupdel::GroupDelCommand),
+    GroupPassword(list::gpasswd::GroupPasswordCommand),
+    WhoAmI(list::whoami::WhoAmICommand),
+    Id(list::id::IdCommand),
+    Groups(list::groups::GroupsCommand),
+    Who(list::who::WhoCommand),
+    W(list::w::WCommand),
+    SwitchUser(list::su::SwitchUserCommand),
+    Sudo(list::sudo::SudoCommand),
+    Password(list::passwd::PasswordCommand),
+    Cat(list::cat::CatCommand),
+    Write(list::write::WriteCommand),
+    Date(list::date::DateCommand),
+    Grep(list::grep::GrepCommand),
+    Find(list::find::FindCommand),
+    Head(list::head::HeadCommand),
+    Tail(list::tail::TailCommand),
+    Wc(list::wc::WcCommand),
+    Sort(list::sort::SortCommand),
+    Uniq(list::uniq::UniqCommand),
+    Cut(list::cut::CutCommand),
+    Tr(list::tr::TrCommand),
+    Paste(list::paste::PasteCommand),
+    Sed(list::sed::SedCommand),
+    Awk(list::awk::AwkCommand),
+    Diff(list::diff::DiffCommand),
+    Compare(list::cmp::CompareCommand),
+    Patch(list::patch::PatchCommand),
+    Tree(list::tree::TreeCommand),
+    DiskUsage(list::du::DiskUsageCommand),
+    DiskFree(list::df::DiskFreeCommand),
 }
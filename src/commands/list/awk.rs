@@ -0,0 +1,172 @@
+//! The awk command, running programs made of pattern-action rules over the records of files.
+//!
+//! Programs support `BEGIN` and `END` rules, patterns and ranges, fields, arrays, the usual
+//! statements and operators, `print`, `printf`, and the common built-in functions. Output can't be
+//! redirected, and user-defined functions and `getline` aren't supported.
+
+mod format;
+mod interpreter;
+mod lexer;
+mod parser;
+mod value;
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::awk::{
+            interpreter::{Input, Interpreter},
+            lexer::unescape,
+        },
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct AwkCommand;
+
+impl ExecutableCommand for AwkCommand {
+    fn name(&self) -> &'static str {
+        "awk"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "field-separator",
+                Some('F'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "assign",
+                Some('v'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Runs the program given as the first operand over the files given, or the standard input.
+    ///
+    /// Operands like `name=value` assign variables when they are reached, and `-v` assigns them
+    /// before the program starts.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?;
+        if operands.is_empty() {
+            shell.set_exit_status(2);
+            return Ok(CommandOutput(Some(
+                "Usage: awk [-F fs][-v var=value][prog | -f progfile][file ...]".to_string(),
+            )));
+        }
+        let source = operands.remove(0);
+        let program = match parser::parse(&source) {
+            Ok(program) => program,
+            Err(line) => {
+                shell.set_exit_status(2);
+                return Ok(CommandOutput(Some(format!(
+                    "awk: syntax error at source line {line}"
+                ))));
+            }
+        };
+
+        let mut interpreter = Interpreter::new(&program);
+        if let Some(Argument::Basic(BasicArgument::String(separator))) =
+            flags.flag("field-separator")
+        {
+            interpreter.assign("FS", &unescape_all(separator));
+        }
+        for assignment in flags.flag_all("assign") {
+            let Argument::Basic(BasicArgument::String(assignment)) = assignment else {
+                continue;
+            };
+            let Some((name, value)) = parse_assignment(assignment) else {
+                shell.set_exit_status(2);
+                return Ok(CommandOutput(Some(format!(
+                    "awk: invalid -v argument: {assignment}"
+                ))));
+            };
+            interpreter.assign(name, &value);
+        }
+
+        let mut inputs = Vec::new();
+        if interpreter.reads_input() {
+            if !operands
+                .iter()
+                .any(|operand| parse_assignment(operand).is_none())
+            {
+                operands.push("-".to_string());
+            }
+            for operand in operands {
+                if let Some((name, value)) = parse_assignment(&operand) {
+                    inputs.push(Input::Assignment(name.to_string(), value));
+                    continue;
+                }
+                inputs.push(match read_input(shell, &operand) {
+                    Ok(content) => Input::File(operand, content),
+                    Err(error) => Input::Unreadable(format!(
+                        "fatal: cannot open file '{operand}' for reading: {}",
+                        error_message(error)?
+                    )),
+                });
+            }
+        }
+
+        let result = interpreter.run(inputs);
+        let mut output = interpreter.output;
+        match result {
+            Ok(status) => shell.set_exit_status(status),
+            Err(message) => {
+                shell.set_exit_status(2);
+                output.push_str(&format!("awk: {message}"));
+            }
+        }
+        if output.ends_with('\n') {
+            output.pop();
+        }
+        Ok(CommandOutput((!output.is_empty()).then_some(output)))
+    }
+}
+
+/// Splits an assignment operand like `name=value`, the value being unescaped.
+fn parse_assignment(operand: &str) -> Option<(&str, String)> {
+    let (name, value) = operand.split_once('=')?;
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|char| char.is_ascii_alphabetic() || char == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_');
+    valid.then(|| (name, unescape_all(value)))
+}
+
+/// Replaces the escape sequences of a string given on the command line, like `\t`.
+fn unescape_all(string: &str) -> String {
+    let chars = string.chars().collect::<Vec<_>>();
+    let mut unescaped = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] == '\\'
+            && let Some((char, end)) = unescape(&chars, index + 1)
+        {
+            unescaped.push(char);
+            index = end;
+        } else {
+            unescaped.push(chars[index]);
+            index += 1;
+        }
+    }
+    unescaped
+}
@@ -0,0 +1,348 @@
+//! The formatting of numbers and `printf` arguments.
+
+use crate::commands::list::awk::value::Value;
+
+/// Converts a number to a string, integers being printed as such and others with a format like
+/// `%.6g`.
+pub(super) fn format_number(number: f64, format: &str) -> String {
+    if number.fract() == 0.0 && number.abs() < 1e16 {
+        format!("{}", number as i64)
+    } else {
+        sprintf(format, &[Value::Number(number)], format)
+    }
+}
+
+/// Formats arguments like the C `printf`, supporting the `d`, `i`, `o`, `x`, `X`, `u`, `c`, `s`,
+/// `e`, `E`, `f`, `F`, `g` and `G` conversions, the `-+ #0` flags, widths and precisions.
+///
+/// Missing arguments are uninitialized values, and numbers formatted as strings use `convfmt`.
+pub(super) fn sprintf(format: &str, arguments: &[Value], convfmt: &str) -> String {
+    let chars = format.chars().collect::<Vec<_>>();
+    let mut arguments = arguments.iter();
+    let mut next_argument = || arguments.next().cloned().unwrap_or_default();
+    let mut output = String::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] != '%' {
+            output.push(chars[index]);
+            index += 1;
+            continue;
+        }
+        let start = index;
+        index += 1;
+        let mut spec = Spec::default();
+        while let Some(flag) = chars.get(index) {
+            match flag {
+                '-' => spec.left = true,
+                '+' => spec.plus = true,
+                ' ' => spec.space = true,
+                '#' => spec.alternate = true,
+                '0' => spec.zero = true,
+                _ => break,
+            }
+            index += 1;
+        }
+        let mut read_number = |index: &mut usize| {
+            if chars.get(*index) == Some(&'*') {
+                *index += 1;
+                return Some(next_argument().number() as i64);
+            }
+            let digits = chars[*index..]
+                .iter()
+                .take_while(|char| char.is_ascii_digit())
+                .collect::<String>();
+            *index += digits.len();
+            digits.parse().ok()
+        };
+        if let Some(width) = read_number(&mut index) {
+            if width < 0 {
+                spec.left = true;
+            }
+            spec.width = width.unsigned_abs() as usize;
+        }
+        if chars.get(index) == Some(&'.') {
+            index += 1;
+            spec.precision = Some(read_number(&mut index).unwrap_or(0).max(0) as usize);
+        }
+        let Some(&conversion) = chars.get(index) else {
+            output.extend(&chars[start..]);
+            break;
+        };
+        index += 1;
+        let formatted = match conversion {
+            '%' => "%".to_string(),
+            'd' | 'i' => {
+                let number = next_argument().number().trunc();
+                let digits = format!("{}", number.abs() as u64);
+                spec.integer(number < 0.0, digits, "")
+            }
+            'o' | 'x' | 'X' | 'u' => {
+                let number = next_argument().number().trunc();
+                let number = if number < 0.0 {
+                    number as i64 as u64
+                } else {
+                    number as u64
+                };
+                let (digits, prefix) = match conversion {
+                    'o' => (format!("{number:o}"), "0"),
+                    'x' => (format!("{number:x}"), "0x"),
+                    'X' => (format!("{number:X}"), "0X"),
+                    _ => (number.to_string(), ""),
+                };
+                let prefix = if spec.alternate && number != 0 {
+                    prefix
+                } else {
+                    ""
+                };
+                spec.integer(false, digits, prefix)
+            }
+            'c' => {
+                let char = match next_argument() {
+                    Value::Number(number) => char::from_u32(number as u32)
+                        .map(String::from)
+                        .unwrap_or_default(),
+                    value => value.string(convfmt).chars().take(1).collect(),
+                };
+                spec.pad("", char)
+            }
+            's' => {
+                let mut string = next_argument().string(convfmt);
+                if let Some(precision) = spec.precision {
+                    string = string.chars().take(precision).collect();
+                }
+                spec.pad("", string)
+            }
+            'e' | 'E' | 'f' | 'F' | 'g' | 'G' => {
+                let number = next_argument().number();
+                let body = if number.is_nan() {
+                    "nan".to_string()
+                } else if number.is_infinite() {
+                    "inf".to_string()
+                } else {
+                    format_float(number.abs(), conversion.to_ascii_lowercase(), &spec)
+                };
+                let body = if conversion.is_ascii_uppercase() {
+                    body.to_uppercase()
+                } else {
+                    body
+                };
+                let sign = spec.sign(number < 0.0);
+                if spec.zero && !spec.left && number.is_finite() {
+                    let zeros = spec.width.saturating_sub(sign.len() + body.len());
+                    format!("{sign}{}{body}", "0".repeat(zeros))
+                } else {
+                    spec.pad(sign, body)
+                }
+            }
+            _ => chars[start..index].iter().collect(),
+        };
+        output.push_str(&formatted);
+    }
+    output
+}
+
+/// A conversion specification, like `%-10.2f`.
+#[derive(Default)]
+struct Spec {
+    left: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+impl Spec {
+    fn sign(&self, negative: bool) -> &'static str {
+        if negative {
+            "-"
+        } else if self.plus {
+            "+"
+        } else if self.space {
+            " "
+        } else {
+            ""
+        }
+    }
+
+    /// Pads a formatted value to the width with spaces.
+    fn pad(&self, sign: &str, body: String) -> String {
+        let padding = " ".repeat(
+            self.width
+                .saturating_sub(sign.chars().count() + body.chars().count()),
+        );
+        if self.left {
+            format!("{sign}{body}{padding}")
+        } else {
+            format!("{padding}{sign}{body}")
+        }
+    }
+
+    /// Formats the digits of an integer, padded with zeros to the precision, or to the width with
+    /// the `0` flag.
+    fn integer(&self, negative: bool, mut digits: String, prefix: &str) -> String {
+        let sign = format!("{}{prefix}", self.sign(negative));
+        match self.precision {
+            Some(0) if digits == "0" => digits.clear(),
+            Some(precision) if digits.len() < precision => {
+                digits = format!("{}{digits}", "0".repeat(precision - digits.len()));
+            }
+            Some(_) => {}
+            None if self.zero && !self.left => {
+                let zeros = self.width.saturating_sub(sign.len() + digits.len());
+                digits = format!("{}{digits}", "0".repeat(zeros));
+            }
+            None => {}
+        }
+        self.pad(&sign, digits)
+    }
+}
+
+/// Formats a positive number in the `e`, `f` or `g` style.
+fn format_float(number: f64, style: char, spec: &Spec) -> String {
+    let precision = spec.precision.unwrap_or(6);
+    match style {
+        'f' => {
+            let formatted = format!("{number:.precision$}");
+            if spec.alternate && precision == 0 {
+                formatted + "."
+            } else {
+                formatted
+            }
+        }
+        'e' => format_exponent(number, precision),
+        _ => {
+            let precision = precision.max(1);
+            let exponent = if number == 0.0 {
+                0
+            } else {
+                let formatted = format!("{number:.*e}", precision - 1);
+                formatted
+                    .split_once('e')
+                    .and_then(|(_, exponent)| exponent.parse::<i64>().ok())
+                    .unwrap_or(0)
+            };
+            let formatted = if exponent < -4 || exponent >= precision as i64 {
+                format_exponent(number, precision - 1)
+            } else {
+                let decimals = (precision as i64 - 1 - exponent) as usize;
+                format!("{number:.decimals$}")
+            };
+            if spec.alternate {
+                return formatted;
+            }
+            // Trailing zeros of the fraction are removed, with the point if nothing is left.
+            let (mantissa, exponent) = match formatted.split_once('e') {
+                Some((mantissa, exponent)) => (mantissa.to_string(), format!("e{exponent}")),
+                None => (formatted, String::new()),
+            };
+            let mantissa = if mantissa.contains('.') {
+                mantissa.trim_end_matches('0').trim_end_matches('.')
+            } else {
+                &mantissa
+            };
+            format!("{mantissa}{exponent}")
+        }
+    }
+}
+
+/// Formats a positive number like `1.500000e+03`.
+fn format_exponent(number: f64, precision: usize) -> String {
+    let formatted = format!("{number:.precision$e}");
+    let (mantissa, exponent) = formatted.split_once('e').unwrap_or((&formatted, "0"));
+    let exponent = exponent.parse::<i64>().unwrap_or(0);
+    let sign = if exponent < 0 { '-' } else { '+' };
+    format!("{mantissa}e{sign}{:02}", exponent.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(format: &str, arguments: &[Value]) -> String {
+        sprintf(format, arguments, "%.6g")
+    }
+
+    #[test]
+    fn test_sprintf_integers_and_strings() {
+        let number = Value::Number(42.9);
+        let string = Value::String("awk".to_string());
+        assert_eq!(
+            format(
+                "%d|%5d|%-5d|%05d",
+                &[
+                    number.clone(),
+                    number.clone(),
+                    number.clone(),
+                    number.clone()
+                ]
+            ),
+            "42|   42|42   |00042"
+        );
+        assert_eq!(
+            format(
+                "%+d %.4d %x %#o",
+                &[
+                    Value::Number(7.0),
+                    Value::Number(-7.0),
+                    Value::Number(255.0),
+                    Value::Number(8.0)
+                ]
+            ),
+            "+7 -0007 ff 010"
+        );
+        assert_eq!(
+            format(
+                "[%s] [%5s] [%-5s] [%.2s]",
+                &[string.clone(), string.clone(), string.clone(), string]
+            ),
+            "[awk] [  awk] [awk  ] [aw]"
+        );
+        assert_eq!(
+            format(
+                "%c%c %d%%",
+                &[Value::Number(65.0), Value::String("bc".to_string())]
+            ),
+            "Ab 0%"
+        );
+        assert_eq!(
+            format("%*d", &[Value::Number(4.0), Value::Number(1.0)]),
+            "   1"
+        );
+    }
+
+    #[test]
+    fn test_sprintf_floats() {
+        let number = |number| Value::Number(number);
+        assert_eq!(
+            format("%f %.2f %8.3f", &[number(1.5), number(2.345), number(-1.0)]),
+            "1.500000 2.35   -1.000"
+        );
+        assert_eq!(
+            format("%e %.2E", &[number(1234.5), number(0.000123)]),
+            "1.234500e+03 1.23E-04"
+        );
+        assert_eq!(
+            format(
+                "%g %g %g %g",
+                &[
+                    number(100000.0),
+                    number(1000000.0),
+                    number(0.0001),
+                    number(0.00001)
+                ]
+            ),
+            "100000 1e+06 0.0001 1e-05"
+        );
+        assert_eq!(format("%.3g %g", &[number(12.3456), number(0.0)]), "12.3 0");
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number(3.0, "%.6g"), "3");
+        assert_eq!(format_number(-2.5, "%.6g"), "-2.5");
+        assert_eq!(format_number(1.0 / 3.0, "%.6g"), "0.333333");
+        assert_eq!(format_number(1e20, "%.6g"), "1e+20");
+    }
+}
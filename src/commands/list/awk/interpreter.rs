@@ -0,0 +1,813 @@
+//! The execution of awk programs over records.
+
+use std::{cmp::Ordering, collections::HashMap};
+
+use regex::Regex;
+
+use crate::commands::list::{
+    awk::{
+        format::{format_number, sprintf},
+        parser::{Expression, LValue, Pattern, Program, Statement},
+        value::Value,
+    },
+    grep::{Syntax, build_regex},
+};
+
+/// An operand of awk, read in turn.
+pub(super) enum Input {
+    /// A file, or the standard input, with its name and content.
+    File(String, String),
+    /// A file which can't be read, with the message explaining why.
+    Unreadable(String),
+    /// A `name=value` operand, assigning a variable before the following files are read.
+    Assignment(String, String),
+}
+
+/// What is done after a statement.
+enum Flow {
+    Normal,
+    Next,
+    Exit,
+    Break,
+    Continue,
+}
+
+type Executed<T> = Result<T, String>;
+
+/// An array, iterated in the order its elements were added.
+#[derive(Default)]
+struct Array {
+    keys: Vec<String>,
+    values: HashMap<String, Value>,
+}
+
+impl Array {
+    fn entry(&mut self, key: String) -> &mut Value {
+        if !self.values.contains_key(&key) {
+            self.keys.push(key.clone());
+        }
+        self.values.entry(key).or_default()
+    }
+
+    fn remove(&mut self, key: &str) {
+        if self.values.remove(key).is_some() {
+            self.keys.retain(|candidate| candidate != key);
+        }
+    }
+}
+
+pub(super) struct Interpreter<'a> {
+    program: &'a Program,
+    variables: HashMap<String, Value>,
+    arrays: HashMap<String, Array>,
+    /// The record, then its fields.
+    fields: Vec<String>,
+    /// Whether each rule with a range pattern is inside its range.
+    in_range: Vec<bool>,
+    regexes: HashMap<String, Regex>,
+    exit_status: i32,
+    pub output: String,
+}
+
+impl<'a> Interpreter<'a> {
+    pub fn new(program: &'a Program) -> Self {
+        let variables = [
+            ("FS", Value::String(" ".to_string())),
+            ("OFS", Value::String(" ".to_string())),
+            ("ORS", Value::String("\n".to_string())),
+            ("SUBSEP", Value::String("\x1c".to_string())),
+            ("CONVFMT", Value::String("%.6g".to_string())),
+            ("OFMT", Value::String("%.6g".to_string())),
+            ("NR", Value::Number(0.0)),
+            ("FNR", Value::Number(0.0)),
+            ("RSTART", Value::Number(0.0)),
+            ("RLENGTH", Value::Number(-1.0)),
+            ("FILENAME", Value::String(String::new())),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+        Self {
+            program,
+            variables,
+            arrays: HashMap::new(),
+            fields: vec![String::new()],
+            in_range: vec![false; program.rules.len()],
+            regexes: HashMap::new(),
+            exit_status: 0,
+            output: String::new(),
+        }
+    }
+
+    /// Assigns a variable from the command line, the value being a string from the input.
+    pub fn assign(&mut self, name: &str, value: &str) {
+        self.variables
+            .insert(name.to_string(), Value::StrNum(value.to_string()));
+    }
+
+    /// Checks if the program reads records, which it doesn't if it only has `BEGIN` rules.
+    pub fn reads_input(&self) -> bool {
+        !self.program.rules.is_empty() || !self.program.end.is_empty()
+    }
+
+    /// Runs the program over the inputs, returning the exit status.
+    pub fn run(&mut self, inputs: Vec<Input>) -> Executed<i32> {
+        let program = self.program;
+        let mut exited = false;
+        for action in &program.begin {
+            if let Flow::Exit = self.execute_all(action)? {
+                exited = true;
+                break;
+            }
+        }
+        if !exited && self.reads_input() {
+            'inputs: for input in inputs {
+                let (name, content) = match input {
+                    Input::File(name, content) => (name, content),
+                    Input::Unreadable(message) => return Err(message),
+                    Input::Assignment(name, value) => {
+                        self.assign(&name, &value);
+                        continue;
+                    }
+                };
+                self.variables
+                    .insert("FILENAME".to_string(), Value::String(name));
+                self.variables.insert("FNR".to_string(), Value::Number(0.0));
+                for line in content.split_terminator('\n') {
+                    for counter in ["NR", "FNR"] {
+                        let count = self.variables.get(counter).map_or(0.0, Value::number);
+                        self.variables
+                            .insert(counter.to_string(), Value::Number(count + 1.0));
+                    }
+                    self.set_record(line.to_string())?;
+                    if let Flow::Exit = self.run_rules()? {
+                        break 'inputs;
+                    }
+                }
+            }
+        }
+        // `exit` outside of `END` still runs it.
+        for action in &program.end {
+            if let Flow::Exit = self.execute_all(action)? {
+                break;
+            }
+        }
+        Ok(self.exit_status)
+    }
+
+    fn run_rules(&mut self) -> Executed<Flow> {
+        let program = self.program;
+        for (index, rule) in program.rules.iter().enumerate() {
+            let selected = match &rule.pattern {
+                Pattern::All => true,
+                Pattern::Expression(pattern) => self.evaluate(pattern)?.truthy(),
+                Pattern::Range(start, end) => {
+                    let selected = self.in_range[index] || self.evaluate(start)?.truthy();
+                    if selected {
+                        self.in_range[index] = !self.evaluate(end)?.truthy();
+                    }
+                    selected
+                }
+            };
+            if !selected {
+                continue;
+            }
+            let Some(action) = &rule.action else {
+                let record = format!("{}{}", self.fields[0], self.string_variable("ORS"));
+                self.output.push_str(&record);
+                continue;
+            };
+            match self.execute_all(action)? {
+                Flow::Next => return Ok(Flow::Normal),
+                Flow::Exit => return Ok(Flow::Exit),
+                _ => {}
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn string_variable(&self, name: &str) -> String {
+        self.variables
+            .get(name)
+            .map(|value| value.string("%.6g"))
+            .unwrap_or_default()
+    }
+
+    fn convfmt(&self) -> String {
+        self.string_variable("CONVFMT")
+    }
+
+    fn execute_all(&mut self, statements: &[Statement]) -> Executed<Flow> {
+        for statement in statements {
+            let flow = self.execute(statement)?;
+            if !matches!(flow, Flow::Normal) {
+                return Ok(flow);
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn execute(&mut self, statement: &Statement) -> Executed<Flow> {
+        match statement {
+            Statement::Print(arguments) => {
+                let line = if arguments.is_empty() {
+                    self.fields[0].clone()
+                } else {
+                    let ofmt = self.string_variable("OFMT");
+                    let mut strings = Vec::new();
+                    for argument in arguments {
+                        strings.push(match self.evaluate(argument)? {
+                            Value::Number(number) => format_number(number, &ofmt),
+                            value => value.string(&ofmt),
+                        });
+                    }
+                    strings.join(&self.string_variable("OFS"))
+                };
+                let ors = self.string_variable("ORS");
+                self.output.push_str(&line);
+                self.output.push_str(&ors);
+            }
+            Statement::Printf(arguments) => {
+                let mut values = Vec::new();
+                for argument in arguments {
+                    values.push(self.evaluate(argument)?);
+                }
+                let convfmt = self.convfmt();
+                let format = values.remove(0).string(&convfmt);
+                let formatted = sprintf(&format, &values, &convfmt);
+                self.output.push_str(&formatted);
+            }
+            Statement::Expression(expression) => {
+                self.evaluate(expression)?;
+            }
+            Statement::If(condition, then, otherwise) => {
+                if self.evaluate(condition)?.truthy() {
+                    return self.execute(then);
+                } else if let Some(otherwise) = otherwise {
+                    return self.execute(otherwise);
+                }
+            }
+            Statement::While(condition, body) => {
+                while self.evaluate(condition)?.truthy() {
+                    match self.execute(body)? {
+                        Flow::Break => break,
+                        Flow::Normal | Flow::Continue => {}
+                        flow => return Ok(flow),
+                    }
+                }
+            }
+            Statement::DoWhile(body, condition) => loop {
+                match self.execute(body)? {
+                    Flow::Break => break,
+                    Flow::Normal | Flow::Continue => {}
+                    flow => return Ok(flow),
+                }
+                if !self.evaluate(condition)?.truthy() {
+                    break;
+                }
+            },
+            Statement::For(init, condition, step, body) => {
+                if let Some(init) = init {
+                    self.evaluate(init)?;
+                }
+                loop {
+                    if let Some(condition) = condition
+                        && !self.evaluate(condition)?.truthy()
+                    {
+                        break;
+                    }
+                    match self.execute(body)? {
+                        Flow::Break => break,
+                        Flow::Normal | Flow::Continue => {}
+                        flow => return Ok(flow),
+                    }
+                    if let Some(step) = step {
+                        self.evaluate(step)?;
+                    }
+                }
+            }
+            Statement::ForIn(key, array, body) => {
+                let keys = self.array(array)?.keys.clone();
+                for name in keys {
+                    self.set(&LValue::Variable(key.clone()), Value::String(name))?;
+                    match self.execute(body)? {
+                        Flow::Break => break,
+                        Flow::Normal | Flow::Continue => {}
+                        flow => return Ok(flow),
+                    }
+                }
+            }
+            Statement::Block(statements) => return self.execute_all(statements),
+            Statement::Delete(array, subscripts) => match subscripts {
+                Some(subscripts) => {
+                    let key = self.subscript(subscripts)?;
+                    self.array(array)?.remove(&key);
+                }
+                None => *self.array(array)? = Array::default(),
+            },
+            Statement::Next => return Ok(Flow::Next),
+            Statement::Exit(status) => {
+                if let Some(status) = status {
+                    self.exit_status = self.evaluate(status)?.number() as i32;
+                }
+                return Ok(Flow::Exit);
+            }
+            Statement::Break => return Ok(Flow::Break),
+            Statement::Continue => return Ok(Flow::Continue),
+        }
+        Ok(Flow::Normal)
+    }
+
+    /// Returns an array, creating it if it doesn't exist.
+    fn array(&mut self, name: &str) -> Executed<&mut Array> {
+        if self.variables.contains_key(name) {
+            return Err(format!("can't use scalar {name} as array"));
+        }
+        Ok(self.arrays.entry(name.to_string()).or_default())
+    }
+
+    /// Returns the key of an array element, its subscripts being joined with `SUBSEP`.
+    fn subscript(&mut self, subscripts: &[Expression]) -> Executed<String> {
+        let convfmt = self.convfmt();
+        let mut keys = Vec::new();
+        for subscript in subscripts {
+            keys.push(self.evaluate(subscript)?.string(&convfmt));
+        }
+        Ok(keys.join(&self.string_variable("SUBSEP")))
+    }
+
+    fn regex(&mut self, pattern: &str) -> Executed<Regex> {
+        if let Some(regex) = self.regexes.get(pattern) {
+            return Ok(regex.clone());
+        }
+        let regex = build_regex(pattern, Syntax::Extended, false, false, false)
+            .map_err(|message| format!("{message}: /{pattern}/"))?;
+        self.regexes.insert(pattern.to_string(), regex.clone());
+        Ok(regex)
+    }
+
+    /// Returns the regular expression of an operand of `~` or of a function, which is either a
+    /// literal or a string.
+    fn dynamic_regex(&mut self, expression: &Expression) -> Executed<Regex> {
+        match expression {
+            Expression::Regex(pattern) => self.regex(pattern),
+            expression => {
+                let convfmt = self.convfmt();
+                let pattern = self.evaluate(expression)?.string(&convfmt);
+                self.regex(&pattern)
+            }
+        }
+    }
+
+    /// Replaces the record, splitting it into fields.
+    fn set_record(&mut self, record: String) -> Executed<()> {
+        let separator = self.string_variable("FS");
+        let fields = self.split(&record, &separator)?;
+        self.fields = vec![record];
+        self.fields.extend(fields);
+        Ok(())
+    }
+
+    /// Rebuilds the record from the fields, after one of them changed.
+    fn rebuild_record(&mut self) {
+        self.fields[0] = self.fields[1..].join(&self.string_variable("OFS"));
+    }
+
+    /// Splits a text like a record with a field separator.
+    ///
+    /// A space separates fields by runs of blanks, ignoring the leading and trailing ones, another
+    /// single character separates them literally, and longer separators are regular expressions.
+    fn split(&mut self, text: &str, separator: &str) -> Executed<Vec<String>> {
+        if text.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut chars = separator.chars();
+        Ok(match (chars.next(), chars.next()) {
+            (Some(' '), None) => text
+                .split([' ', '\t', '\n'])
+                .filter(|field| !field.is_empty())
+                .map(str::to_string)
+                .collect(),
+            (Some(separator), None) => text.split(separator).map(str::to_string).collect(),
+            (None, _) => text.chars().map(String::from).collect(),
+            _ => self
+                .regex(separator)?
+                .split(text)
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+
+    fn field_index(&mut self, index: &Expression) -> Executed<usize> {
+        let index = self.evaluate(index)?.number();
+        if index < 0.0 {
+            return Err(format!(
+                "trying to access out of range field {}",
+                index as i64
+            ));
+        }
+        Ok(index as usize)
+    }
+
+    fn get(&mut self, target: &LValue) -> Executed<Value> {
+        match target {
+            LValue::Variable(name) if name == "NF" => {
+                Ok(Value::Number((self.fields.len() - 1) as f64))
+            }
+            LValue::Variable(name) => {
+                if self.arrays.contains_key(name) {
+                    return Err(format!("can't use array {name} in scalar context"));
+                }
+                Ok(self.variables.get(name).cloned().unwrap_or_default())
+            }
+            LValue::Field(index) => {
+                let index = self.field_index(index)?;
+                Ok(self
+                    .fields
+                    .get(index)
+                    .map_or(Value::Uninitialized, |field| Value::StrNum(field.clone())))
+            }
+            LValue::Element(name, subscripts) => {
+                let key = self.subscript(subscripts)?;
+                Ok(self.array(name)?.entry(key).clone())
+            }
+        }
+    }
+
+    fn set(&mut self, target: &LValue, value: Value) -> Executed<()> {
+        match target {
+            LValue::Variable(name) if name == "NF" => {
+                let count = value.number();
+                if count < 0.0 {
+                    return Err(format!("NF set to negative value {count}"));
+                }
+                self.fields.resize(count as usize + 1, String::new());
+                self.rebuild_record();
+            }
+            LValue::Variable(name) => {
+                if self.arrays.contains_key(name) {
+                    return Err(format!("can't assign to {name}; it's an array name."));
+                }
+                self.variables.insert(name.clone(), value);
+            }
+            LValue::Field(index) => {
+                let index = self.field_index(index)?;
+                let value = value.string(&self.convfmt());
+                if index == 0 {
+                    self.set_record(value)?;
+                } else {
+                    if self.fields.len() <= index {
+                        self.fields.resize(index + 1, String::new());
+                    }
+                    self.fields[index] = value;
+                    self.rebuild_record();
+                }
+            }
+            LValue::Element(name, subscripts) => {
+                let key = self.subscript(subscripts)?;
+                *self.array(name)?.entry(key) = value;
+            }
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self, expression: &Expression) -> Executed<Value> {
+        Ok(match expression {
+            Expression::Number(number) => Value::Number(*number),
+            Expression::String(string) => Value::String(string.clone()),
+            Expression::Regex(pattern) => {
+                let regex = self.regex(pattern)?;
+                Value::from_bool(regex.is_match(&self.fields[0]))
+            }
+            Expression::LValue(target) => self.get(target)?,
+            Expression::Assign(target, operator, value) => {
+                let mut value = self.evaluate(value)?;
+                if let Some(operator) = operator {
+                    let current = self.get(target)?.number();
+                    value = Value::Number(arithmetic(*operator, current, value.number())?);
+                }
+                self.set(target, value.clone())?;
+                value
+            }
+            Expression::Conditional(condition, then, otherwise) => {
+                if self.evaluate(condition)?.truthy() {
+                    self.evaluate(then)?
+                } else {
+                    self.evaluate(otherwise)?
+                }
+            }
+            Expression::Or(left, right) => {
+                Value::from_bool(self.evaluate(left)?.truthy() || self.evaluate(right)?.truthy())
+            }
+            Expression::And(left, right) => {
+                Value::from_bool(self.evaluate(left)?.truthy() && self.evaluate(right)?.truthy())
+            }
+            Expression::In(subscripts, array) => {
+                let key = self.subscript(subscripts)?;
+                Value::from_bool(self.array(array)?.values.contains_key(&key))
+            }
+            Expression::Match(negated, text, pattern) => {
+                let text = self.evaluate(text)?.string(&self.convfmt());
+                let regex = self.dynamic_regex(pattern)?;
+                Value::from_bool(regex.is_match(&text) != *negated)
+            }
+            Expression::Compare(operator, left, right) => {
+                let left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                let ordering = left.compare(&right, &self.convfmt());
+                Value::from_bool(match *operator {
+                    "<" => ordering == Ordering::Less,
+                    "<=" => ordering != Ordering::Greater,
+                    "==" => ordering == Ordering::Equal,
+                    "!=" => ordering != Ordering::Equal,
+                    ">=" => ordering != Ordering::Less,
+                    _ => ordering == Ordering::Greater,
+                })
+            }
+            Expression::Concat(left, right) => {
+                let convfmt = self.convfmt();
+                let left = self.evaluate(left)?.string(&convfmt);
+                let right = self.evaluate(right)?.string(&convfmt);
+                Value::String(left + &right)
+            }
+            Expression::Arithmetic(operator, left, right) => {
+                let left = self.evaluate(left)?.number();
+                let right = self.evaluate(right)?.number();
+                Value::Number(arithmetic(*operator, left, right)?)
+            }
+            Expression::Not(operand) => Value::from_bool(!self.evaluate(operand)?.truthy()),
+            Expression::Negate(operand) => Value::Number(-self.evaluate(operand)?.number()),
+            Expression::Plus(operand) => Value::Number(self.evaluate(operand)?.number()),
+            Expression::Increment {
+                target,
+                delta,
+                prefix,
+            } => {
+                let current = self.get(target)?.number();
+                self.set(target, Value::Number(current + delta))?;
+                Value::Number(if *prefix { current + delta } else { current })
+            }
+            Expression::Call(name, arguments) => self.call(name, arguments)?,
+            Expression::Group(expression) => self.evaluate(expression)?,
+        })
+    }
+
+    fn call(&mut self, name: &str, arguments: &[Expression]) -> Executed<Value> {
+        let (minimum, maximum) = match name {
+            "length" => (0, 1),
+            "substr" | "split" | "sub" | "gsub" => (2, 3),
+            "index" | "match" => (2, 2),
+            "sprintf" => (1, usize::MAX),
+            _ => (1, 1),
+        };
+        if !(minimum..=maximum).contains(&arguments.len()) {
+            return Err(format!(
+                "function {name} called with wrong number of arguments"
+            ));
+        }
+        let convfmt = self.convfmt();
+        Ok(match name {
+            "length" => match arguments.first() {
+                None => Value::Number(self.fields[0].chars().count() as f64),
+                Some(Expression::LValue(LValue::Variable(array)))
+                    if self.arrays.contains_key(array) =>
+                {
+                    Value::Number(self.array(array)?.keys.len() as f64)
+                }
+                Some(argument) => {
+                    Value::Number(self.evaluate(argument)?.string(&convfmt).chars().count() as f64)
+                }
+            },
+            "substr" => {
+                let chars = self
+                    .evaluate(&arguments[0])?
+                    .string(&convfmt)
+                    .chars()
+                    .collect::<Vec<_>>();
+                // Positions are rounded, and the substring clipped to the string.
+                let start = self.evaluate(&arguments[1])?.number().round();
+                let end = match arguments.get(2) {
+                    Some(length) => start + self.evaluate(length)?.number().round(),
+                    None => f64::INFINITY,
+                };
+                let start = start.max(1.0);
+                let end = end.min(chars.len() as f64 + 1.0);
+                if end <= start {
+                    Value::String(String::new())
+                } else {
+                    Value::String(chars[start as usize - 1..end as usize - 1].iter().collect())
+                }
+            }
+            "index" => {
+                let text = self.evaluate(&arguments[0])?.string(&convfmt);
+                let target = self.evaluate(&arguments[1])?.string(&convfmt);
+                Value::Number(match text.find(&target) {
+                    Some(index) if !target.is_empty() => text[..index].chars().count() as f64 + 1.0,
+                    _ => 0.0,
+                })
+            }
+            "split" => {
+                let Expression::LValue(LValue::Variable(array)) = &arguments[1] else {
+                    return Err("split: second argument is not an array".to_string());
+                };
+                let text = self.evaluate(&arguments[0])?.string(&convfmt);
+                let elements = match arguments.get(2) {
+                    None => {
+                        let separator = self.string_variable("FS");
+                        self.split(&text, &separator)?
+                    }
+                    Some(Expression::Regex(pattern)) if !text.is_empty() => self
+                        .regex(pattern)?
+                        .split(&text)
+                        .map(str::to_string)
+                        .collect(),
+                    Some(separator) => {
+                        let separator = self.evaluate(separator)?.string(&convfmt);
+                        self.split(&text, &separator)?
+                    }
+                };
+                let array = self.array(array)?;
+                *array = Array::default();
+                for (index, element) in elements.iter().enumerate() {
+                    *array.entry((index + 1).to_string()) = Value::StrNum(element.clone());
+                }
+                Value::Number(elements.len() as f64)
+            }
+            "sub" | "gsub" => {
+                let record = LValue::Field(Box::new(Expression::Number(0.0)));
+                let target = match arguments.get(2) {
+                    None => &record,
+                    Some(Expression::LValue(target)) => target,
+                    Some(_) => return Err(format!("{name}: third argument is not assignable")),
+                };
+                let regex = self.dynamic_regex(&arguments[0])?;
+                let replacement = self.evaluate(&arguments[1])?.string(&convfmt);
+                let text = self.get(target)?.string(&convfmt);
+                let mut result = String::new();
+                let mut last = 0;
+                let mut count = 0;
+                for matched in regex.find_iter(&text) {
+                    result.push_str(&text[last..matched.start()]);
+                    result.push_str(&expand_replacement(&replacement, matched.as_str()));
+                    last = matched.end();
+                    count += 1;
+                    if name == "sub" {
+                        break;
+                    }
+                }
+                if count > 0 {
+                    result.push_str(&text[last..]);
+                    self.set(target, Value::String(result))?;
+                }
+                Value::Number(count as f64)
+            }
+            "match" => {
+                let text = self.evaluate(&arguments[0])?.string(&convfmt);
+                let regex = self.dynamic_regex(&arguments[1])?;
+                let (start, length) = match regex.find(&text) {
+                    Some(matched) => (
+                        text[..matched.start()].chars().count() as f64 + 1.0,
+                        matched.as_str().chars().count() as f64,
+                    ),
+                    None => (0.0, -1.0),
+                };
+                self.variables
+                    .insert("RSTART".to_string(), Value::Number(start));
+                self.variables
+                    .insert("RLENGTH".to_string(), Value::Number(length));
+                Value::Number(start)
+            }
+            "sprintf" => {
+                let mut values = Vec::new();
+                for argument in arguments {
+                    values.push(self.evaluate(argument)?);
+                }
+                let format = values.remove(0).string(&convfmt);
+                Value::String(sprintf(&format, &values, &convfmt))
+            }
+            "toupper" => Value::String(
+                self.evaluate(&arguments[0])?
+                    .string(&convfmt)
+                    .to_uppercase(),
+            ),
+            "tolower" => Value::String(
+                self.evaluate(&arguments[0])?
+                    .string(&convfmt)
+                    .to_lowercase(),
+            ),
+            "int" => Value::Number(self.evaluate(&arguments[0])?.number().trunc()),
+            "sqrt" => Value::Number(self.evaluate(&arguments[0])?.number().sqrt()),
+            _ => Value::Number(self.evaluate(&arguments[0])?.number().exp()),
+        })
+    }
+}
+
+fn arithmetic(operator: char, left: f64, right: f64) -> Executed<f64> {
+    Ok(match operator {
+        '+' => left + right,
+        '-' => left - right,
+        '*' => left * right,
+        '/' if right == 0.0 => return Err("division by zero".to_string()),
+        '/' => left / right,
+        '%' if right == 0.0 => return Err("division by zero in %".to_string()),
+        '%' => left % right,
+        _ => left.powf(right),
+    })
+}
+
+/// Expands the replacement of `sub` and `gsub`, where `&` is the match and `\&` a literal `&`.
+fn expand_replacement(replacement: &str, matched: &str) -> String {
+    let mut expanded = String::new();
+    let mut chars = replacement.chars().peekable();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' if matches!(chars.peek(), Some('&' | '\\')) => {
+                expanded.extend(chars.next());
+            }
+            '&' => expanded.push_str(matched),
+            char => expanded.push(char),
+        }
+    }
+    expanded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::list::awk::parser::parse;
+
+    fn run(program: &str, input: &str) -> String {
+        let program = parse(program).expect("Invalid program");
+        let mut interpreter = Interpreter::new(&program);
+        interpreter
+            .run(vec![Input::File("-".to_string(), input.to_string())])
+            .expect("Failed to run the program");
+        interpreter.output
+    }
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(run("{ print $2, NF }", "  a b  c \n"), "b 3\n");
+        assert_eq!(
+            run("{ $2 = \"x\"; print; print NF }", "a b c\n"),
+            "a x c\n3\n"
+        );
+        assert_eq!(run("{ NF = 2; print }", "a b c\n"), "a b\n");
+        assert_eq!(run("{ $5 = \"e\"; print }", "a\n"), "a    e\n");
+        assert_eq!(run("BEGIN { FS = \",\" } { print $2 }", "a,,c\n"), "\n");
+    }
+
+    #[test]
+    fn test_functions() {
+        assert_eq!(
+            run(
+                "{ print length(), substr($0, 2, 3), substr($0, 0, 2), index($0, \"c\") }",
+                "abcdef\n"
+            ),
+            "6 bcd a 3\n"
+        );
+        assert_eq!(
+            run(
+                "{ n = split($0, parts, \"-\"); print n, parts[3] }",
+                "a-b-c\n"
+            ),
+            "3 c\n"
+        );
+        assert_eq!(
+            run(
+                "{ gsub(/o/, \"[&]\"); sub(\"l+\", \"L\"); print }",
+                "hello world\n"
+            ),
+            "heL[o] w[o]rld\n"
+        );
+        assert_eq!(
+            run("{ print match($0, /[0-9]+/), RSTART, RLENGTH }", "ab123c\n"),
+            "3 3 3\n"
+        );
+        assert_eq!(
+            run("{ print toupper($1) tolower($2), int(-3.7) }", "ab CD\n"),
+            "ABcd -3\n"
+        );
+    }
+
+    #[test]
+    fn test_control_flow() {
+        assert_eq!(
+            run(
+                "{ for (i = NF; i > 0; i--) { if (i == 2) continue; out = out $i } } END { print \
+                 out }",
+                "a b c\n"
+            ),
+            "ca\n"
+        );
+        assert_eq!(
+            run(
+                "{ count[$1]++ } END { for (k in count) print k, count[k] }",
+                "x\ny\nx\n"
+            ),
+            "x 2\ny 1\n"
+        );
+        assert_eq!(run("NR == 2 { exit } { print }", "1\n2\n3\n"), "1\n");
+        assert_eq!(run("/b/,/c/", "a\nb\nx\nc\nd\n"), "b\nx\nc\n");
+    }
+}
@@ -0,0 +1,300 @@
+//! Splitting of awk programs into tokens.
+
+/// The names of the built-in functions.
+pub(super) const BUILTINS: [&str; 13] = [
+    "length", "substr", "index", "split", "sub", "gsub", "match", "sprintf", "toupper", "tolower",
+    "int", "sqrt", "exp",
+];
+
+const KEYWORDS: [&str; 15] = [
+    "BEGIN", "END", "if", "else", "while", "for", "do", "break", "continue", "next", "exit",
+    "delete", "in", "print", "printf",
+];
+
+/// Operators and punctuation, the longest first so that they are matched greedily.
+const SYMBOLS: [&str; 37] = [
+    "&&", "||", "==", "!=", "<=", ">=", "!~", "++", "--", "+=", "-=", "*=", "/=", "%=", "^=", ">>",
+    "{", "}", "(", ")", "[", "]", ";", ",", "+", "-", "*", "/", "%", "^", "!", ">", "<", "|", "?",
+    ":", "~",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    Number(f64),
+    String(String),
+    Regex(String),
+    Name(String),
+    Builtin(&'static str),
+    Keyword(&'static str),
+    Symbol(&'static str),
+    /// `=`, kept apart from the symbols as `==` must not be read as two of them.
+    Assign,
+    /// `$`.
+    Dollar,
+    Newline,
+    Eof,
+}
+
+impl Token {
+    /// Checks if the token ends an operand, after which `/` is a division rather than the start of
+    /// a regular expression.
+    fn ends_operand(&self) -> bool {
+        matches!(
+            self,
+            Token::Number(_)
+                | Token::String(_)
+                | Token::Name(_)
+                | Token::Builtin(_)
+                | Token::Symbol(")" | "]" | "++" | "--")
+        )
+    }
+}
+
+/// Splits a program into tokens, each with its line number.
+pub(super) fn tokenize(program: &str) -> Result<Vec<(Token, usize)>, usize> {
+    let chars = program.chars().collect::<Vec<_>>();
+    let mut tokens: Vec<(Token, usize)> = Vec::new();
+    let mut line = 1;
+    let mut index = 0;
+    while index < chars.len() {
+        let char = chars[index];
+        let token = match char {
+            ' ' | '\t' | '\r' => {
+                index += 1;
+                continue;
+            }
+            '\\' if chars.get(index + 1) == Some(&'\n') => {
+                index += 2;
+                line += 1;
+                continue;
+            }
+            '#' => {
+                while index < chars.len() && chars[index] != '\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            '\n' => {
+                index += 1;
+                Token::Newline
+            }
+            '"' => {
+                let (string, end) = read_string(&chars, index + 1).ok_or(line)?;
+                index = end;
+                Token::String(string)
+            }
+            '/' if !tokens.last().is_some_and(|(token, _)| token.ends_operand()) => {
+                let (regex, end) = read_regex(&chars, index + 1).ok_or(line)?;
+                index = end;
+                Token::Regex(regex)
+            }
+            '0'..='9' | '.'
+                if chars.get(index + 1).is_some_and(char::is_ascii_digit) || char != '.' =>
+            {
+                let start = index;
+                while index < chars.len() && (chars[index].is_ascii_digit() || chars[index] == '.')
+                {
+                    index += 1;
+                }
+                if matches!(chars.get(index), Some('e' | 'E'))
+                    && (chars.get(index + 1).is_some_and(char::is_ascii_digit)
+                        || (matches!(chars.get(index + 1), Some('+' | '-'))
+                            && chars.get(index + 2).is_some_and(char::is_ascii_digit)))
+                {
+                    index += 2;
+                    while index < chars.len() && chars[index].is_ascii_digit() {
+                        index += 1;
+                    }
+                }
+                let number = chars[start..index].iter().collect::<String>();
+                Token::Number(number.parse().map_err(|_| line)?)
+            }
+            char if char.is_ascii_alphabetic() || char == '_' => {
+                let start = index;
+                while index < chars.len()
+                    && (chars[index].is_ascii_alphanumeric() || chars[index] == '_')
+                {
+                    index += 1;
+                }
+                let name = chars[start..index].iter().collect::<String>();
+                if let Some(keyword) = KEYWORDS.iter().find(|keyword| **keyword == name) {
+                    Token::Keyword(keyword)
+                } else if let Some(builtin) = BUILTINS.iter().find(|builtin| **builtin == name) {
+                    Token::Builtin(builtin)
+                } else {
+                    Token::Name(name)
+                }
+            }
+            '$' => {
+                index += 1;
+                Token::Dollar
+            }
+            '=' if chars.get(index + 1) != Some(&'=') => {
+                index += 1;
+                Token::Assign
+            }
+            _ => {
+                let symbol = SYMBOLS
+                    .iter()
+                    .find(|symbol| {
+                        symbol
+                            .chars()
+                            .enumerate()
+                            .all(|(offset, char)| chars.get(index + offset) == Some(&char))
+                    })
+                    .ok_or(line)?;
+                index += symbol.len();
+                Token::Symbol(symbol)
+            }
+        };
+        tokens.push((token, line));
+        if char == '\n' {
+            line += 1;
+        }
+    }
+    tokens.push((Token::Eof, line));
+    Ok(tokens)
+}
+
+/// Reads a string literal up to its closing quote, returning it unescaped with the index following
+/// the quote.
+fn read_string(chars: &[char], mut index: usize) -> Option<(String, usize)> {
+    let mut string = String::new();
+    loop {
+        match *chars.get(index)? {
+            '"' => return Some((string, index + 1)),
+            '\n' => return None,
+            '\\' => {
+                let (char, end) = unescape(chars, index + 1)?;
+                string.push(char);
+                index = end;
+                continue;
+            }
+            char => string.push(char),
+        }
+        index += 1;
+    }
+}
+
+/// Reads a regular expression literal up to its closing slash, which is not part of a bracket
+/// expression.
+fn read_regex(chars: &[char], mut index: usize) -> Option<(String, usize)> {
+    let mut regex = String::new();
+    let mut in_bracket = false;
+    loop {
+        match *chars.get(index)? {
+            '/' if !in_bracket => return Some((regex, index + 1)),
+            '\n' => return None,
+            '\\' => {
+                let escaped = *chars.get(index + 1)?;
+                if escaped != '/' {
+                    regex.push('\\');
+                }
+                regex.push(escaped);
+                index += 2;
+                continue;
+            }
+            '[' if !in_bracket => {
+                in_bracket = true;
+                regex.push('[');
+                // A closing bracket first in the expression is literal.
+                for literal in ['^', ']'] {
+                    if chars.get(index + 1) == Some(&literal) {
+                        regex.push(literal);
+                        index += 1;
+                    }
+                }
+            }
+            ']' if in_bracket => {
+                in_bracket = false;
+                regex.push(']');
+            }
+            char => regex.push(char),
+        }
+        index += 1;
+    }
+}
+
+/// Returns the character escaped at an index, following a backslash, and the index following it.
+pub(super) fn unescape(chars: &[char], index: usize) -> Option<(char, usize)> {
+    let char = match *chars.get(index)? {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        'a' => '\x07',
+        'b' => '\x08',
+        'f' => '\x0c',
+        'v' => '\x0b',
+        '0'..='7' => {
+            let digits = chars[index..]
+                .iter()
+                .take(3)
+                .take_while(|char| ('0'..='7').contains(*char))
+                .collect::<String>();
+            let value = u32::from_str_radix(&digits, 8).ok()?;
+            return Some((char::from_u32(value)?, index + digits.len()));
+        }
+        char => char,
+    };
+    Some((char, index + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tokens(program: &str) -> Vec<Token> {
+        tokenize(program)
+            .expect("Invalid program")
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokens("$1 >= 10 { n++ }"),
+            vec![
+                Token::Dollar,
+                Token::Number(1.0),
+                Token::Symbol(">="),
+                Token::Number(10.0),
+                Token::Symbol("{"),
+                Token::Name("n".to_string()),
+                Token::Symbol("++"),
+                Token::Symbol("}"),
+                Token::Eof,
+            ]
+        );
+        assert_eq!(
+            tokens(r#"x = "a\tb" # comment"#),
+            vec![
+                Token::Name("x".to_string()),
+                Token::Assign,
+                Token::String("a\tb".to_string()),
+                Token::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_regex_or_division() {
+        assert_eq!(
+            tokens("/a\\/[/]/"),
+            vec![Token::Regex("a/[/]".to_string()), Token::Eof]
+        );
+        assert_eq!(
+            tokens("n / 2 / 1"),
+            vec![
+                Token::Name("n".to_string()),
+                Token::Symbol("/"),
+                Token::Number(2.0),
+                Token::Symbol("/"),
+                Token::Number(1.0),
+                Token::Eof,
+            ]
+        );
+        assert_eq!(tokenize("\n\"open"), Err(2));
+    }
+}
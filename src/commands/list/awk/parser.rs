@@ -0,0 +1,754 @@
+//! Parsing of awk programs into rules of statements and expressions.
+
+use crate::commands::list::awk::lexer::{Token, tokenize};
+
+/// A program, made of rules run on each record, and on the start and end of the input.
+#[derive(Debug, Default)]
+pub(super) struct Program {
+    pub begin: Vec<Vec<Statement>>,
+    pub rules: Vec<Rule>,
+    pub end: Vec<Vec<Statement>>,
+}
+
+#[derive(Debug)]
+pub(super) struct Rule {
+    pub pattern: Pattern,
+    /// The action of the rule, printing the record if there is none.
+    pub action: Option<Vec<Statement>>,
+}
+
+#[derive(Debug)]
+pub(super) enum Pattern {
+    All,
+    Expression(Expression),
+    /// `start, end`, selecting the records from one matching `start` to one matching `end`.
+    Range(Expression, Expression),
+}
+
+#[derive(Debug)]
+pub(super) enum Statement {
+    Print(Vec<Expression>),
+    Printf(Vec<Expression>),
+    Expression(Expression),
+    If(Expression, Box<Statement>, Option<Box<Statement>>),
+    While(Expression, Box<Statement>),
+    DoWhile(Box<Statement>, Expression),
+    For(
+        Option<Expression>,
+        Option<Expression>,
+        Option<Expression>,
+        Box<Statement>,
+    ),
+    /// `for (key in array)`.
+    ForIn(String, String, Box<Statement>),
+    Block(Vec<Statement>),
+    Delete(String, Option<Vec<Expression>>),
+    Next,
+    Exit(Option<Expression>),
+    Break,
+    Continue,
+}
+
+#[derive(Debug)]
+pub(super) enum Expression {
+    Number(f64),
+    String(String),
+    /// A regular expression literal, matching the record when used as a value.
+    Regex(String),
+    LValue(LValue),
+    /// An assignment, with the arithmetic operator of compound ones like `+=`.
+    Assign(LValue, Option<char>, Box<Expression>),
+    Conditional(Box<Expression>, Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    /// `(key) in array`, with the subscripts of the key.
+    In(Vec<Expression>, String),
+    /// `~`, or `!~` if negated.
+    Match(bool, Box<Expression>, Box<Expression>),
+    Compare(&'static str, Box<Expression>, Box<Expression>),
+    Concat(Box<Expression>, Box<Expression>),
+    Arithmetic(char, Box<Expression>, Box<Expression>),
+    Not(Box<Expression>),
+    Negate(Box<Expression>),
+    Plus(Box<Expression>),
+    /// `++` or `--`, adding the delta before or after reading the value.
+    Increment {
+        target: LValue,
+        delta: f64,
+        prefix: bool,
+    },
+    Call(&'static str, Vec<Expression>),
+    Group(Box<Expression>),
+}
+
+/// What can be assigned to.
+#[derive(Debug)]
+pub(super) enum LValue {
+    Variable(String),
+    Field(Box<Expression>),
+    Element(String, Vec<Expression>),
+}
+
+/// Parses a program, returning the line of the syntax error if there is one.
+pub(super) fn parse(program: &str) -> Result<Program, usize> {
+    let tokens = tokenize(program)?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        in_print: false,
+    };
+    parser.program().map_err(|_| parser.line())
+}
+
+struct SyntaxError;
+
+type Parsed<T> = Result<T, SyntaxError>;
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    index: usize,
+    /// Whether an unparenthesized expression list of `print` is parsed, where `>` is a
+    /// redirection rather than a comparison.
+    in_print: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn line(&self) -> usize {
+        self.tokens[self.index.min(self.tokens.len() - 1)].1
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.index].0.clone();
+        if token != Token::Eof {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn is_symbol(&self, symbol: &str) -> bool {
+        matches!(self.peek(), Token::Symbol(found) if *found == symbol)
+    }
+
+    fn eat_symbol(&mut self, symbol: &str) -> bool {
+        let found = self.is_symbol(symbol);
+        if found {
+            self.index += 1;
+        }
+        found
+    }
+
+    fn expect_symbol(&mut self, symbol: &str) -> Parsed<()> {
+        if self.eat_symbol(symbol) {
+            Ok(())
+        } else {
+            Err(SyntaxError)
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = matches!(self.peek(), Token::Keyword(found) if *found == keyword);
+        if found {
+            self.index += 1;
+        }
+        found
+    }
+
+    fn skip_newlines(&mut self) {
+        while *self.peek() == Token::Newline {
+            self.index += 1;
+        }
+    }
+
+    fn skip_terminators(&mut self) {
+        while *self.peek() == Token::Newline || self.is_symbol(";") {
+            self.index += 1;
+        }
+    }
+
+    fn program(&mut self) -> Parsed<Program> {
+        let mut program = Program::default();
+        loop {
+            self.skip_terminators();
+            if *self.peek() == Token::Eof {
+                return Ok(program);
+            }
+            if self.eat_keyword("BEGIN") {
+                program.begin.push(self.block()?);
+            } else if self.eat_keyword("END") {
+                program.end.push(self.block()?);
+            } else {
+                let pattern = if self.is_symbol("{") {
+                    Pattern::All
+                } else {
+                    let start = self.expression()?;
+                    if self.eat_symbol(",") {
+                        self.skip_newlines();
+                        Pattern::Range(start, self.expression()?)
+                    } else {
+                        Pattern::Expression(start)
+                    }
+                };
+                let action = if self.is_symbol("{") {
+                    Some(self.block()?)
+                } else {
+                    None
+                };
+                // A rule without action ends with its line, while others can be followed by one.
+                if action.is_none()
+                    && !matches!(
+                        self.peek(),
+                        Token::Newline | Token::Eof | Token::Symbol(";")
+                    )
+                {
+                    return Err(SyntaxError);
+                }
+                program.rules.push(Rule { pattern, action });
+            }
+        }
+    }
+
+    fn block(&mut self) -> Parsed<Vec<Statement>> {
+        self.expect_symbol("{")?;
+        let mut statements = Vec::new();
+        loop {
+            self.skip_terminators();
+            if self.eat_symbol("}") {
+                return Ok(statements);
+            }
+            statements.push(self.statement()?);
+        }
+    }
+
+    /// Parses a statement, with its terminator if it is a simple one.
+    fn statement(&mut self) -> Parsed<Statement> {
+        match self.peek().clone() {
+            Token::Symbol("{") => Ok(Statement::Block(self.block()?)),
+            Token::Symbol(";") => {
+                self.advance();
+                Ok(Statement::Block(Vec::new()))
+            }
+            Token::Keyword("if") => {
+                self.advance();
+                let condition = self.condition()?;
+                let then = self.statement()?;
+                // `else` may follow on the next lines, or after the `;` ending a simple statement.
+                let before = self.index;
+                self.skip_terminators();
+                let otherwise = if self.eat_keyword("else") {
+                    self.skip_newlines();
+                    Some(Box::new(self.statement()?))
+                } else {
+                    self.index = before;
+                    None
+                };
+                Ok(Statement::If(condition, Box::new(then), otherwise))
+            }
+            Token::Keyword("while") => {
+                self.advance();
+                let condition = self.condition()?;
+                Ok(Statement::While(condition, Box::new(self.statement()?)))
+            }
+            Token::Keyword("do") => {
+                self.advance();
+                self.skip_newlines();
+                let body = self.statement()?;
+                self.skip_terminators();
+                if !self.eat_keyword("while") {
+                    return Err(SyntaxError);
+                }
+                let condition = self.condition_header()?;
+                self.end_simple_statement()?;
+                Ok(Statement::DoWhile(Box::new(body), condition))
+            }
+            Token::Keyword("for") => {
+                self.advance();
+                self.expect_symbol("(")?;
+                if let [
+                    (Token::Name(key), _),
+                    (Token::Keyword("in"), _),
+                    (Token::Name(array), _),
+                    (Token::Symbol(")"), _),
+                    ..,
+                ] = &self.tokens[self.index..]
+                {
+                    let (key, array) = (key.clone(), array.clone());
+                    self.index += 4;
+                    self.skip_newlines();
+                    return Ok(Statement::ForIn(key, array, Box::new(self.statement()?)));
+                }
+                let init = self.optional_expression(";")?;
+                self.expect_symbol(";")?;
+                self.skip_newlines();
+                let condition = self.optional_expression(";")?;
+                self.expect_symbol(";")?;
+                self.skip_newlines();
+                let step = self.optional_expression(")")?;
+                self.expect_symbol(")")?;
+                self.skip_newlines();
+                Ok(Statement::For(
+                    init,
+                    condition,
+                    step,
+                    Box::new(self.statement()?),
+                ))
+            }
+            _ => {
+                let statement = self.simple_statement()?;
+                self.end_simple_statement()?;
+                Ok(statement)
+            }
+        }
+    }
+
+    /// Parses the parenthesized condition of `if` and `while`, and the newlines following it.
+    fn condition(&mut self) -> Parsed<Expression> {
+        let condition = self.condition_header()?;
+        self.skip_newlines();
+        Ok(condition)
+    }
+
+    fn condition_header(&mut self) -> Parsed<Expression> {
+        self.expect_symbol("(")?;
+        let condition = self.expression()?;
+        self.expect_symbol(")")?;
+        Ok(condition)
+    }
+
+    fn optional_expression(&mut self, terminator: &str) -> Parsed<Option<Expression>> {
+        if self.is_symbol(terminator) {
+            Ok(None)
+        } else {
+            self.expression().map(Some)
+        }
+    }
+
+    fn end_simple_statement(&mut self) -> Parsed<()> {
+        match self.peek() {
+            Token::Symbol(";") | Token::Newline => {
+                self.advance();
+                Ok(())
+            }
+            Token::Symbol("}") | Token::Eof => Ok(()),
+            _ => Err(SyntaxError),
+        }
+    }
+
+    fn simple_statement(&mut self) -> Parsed<Statement> {
+        match self.peek().clone() {
+            Token::Keyword(keyword @ ("print" | "printf")) => {
+                self.advance();
+                let arguments = self.print_arguments()?;
+                if matches!(self.peek(), Token::Symbol(">" | ">>" | "|")) {
+                    return Err(SyntaxError);
+                }
+                if keyword == "print" {
+                    Ok(Statement::Print(arguments))
+                } else if arguments.is_empty() {
+                    Err(SyntaxError)
+                } else {
+                    Ok(Statement::Printf(arguments))
+                }
+            }
+            Token::Keyword("next") => {
+                self.advance();
+                Ok(Statement::Next)
+            }
+            Token::Keyword("break") => {
+                self.advance();
+                Ok(Statement::Break)
+            }
+            Token::Keyword("continue") => {
+                self.advance();
+                Ok(Statement::Continue)
+            }
+            Token::Keyword("exit") => {
+                self.advance();
+                if matches!(
+                    self.peek(),
+                    Token::Symbol(";" | "}") | Token::Newline | Token::Eof
+                ) {
+                    Ok(Statement::Exit(None))
+                } else {
+                    Ok(Statement::Exit(Some(self.expression()?)))
+                }
+            }
+            Token::Keyword("delete") => {
+                self.advance();
+                let Token::Name(array) = self.advance() else {
+                    return Err(SyntaxError);
+                };
+                if self.eat_symbol("[") {
+                    let subscripts = self.expression_list()?;
+                    self.expect_symbol("]")?;
+                    Ok(Statement::Delete(array, Some(subscripts)))
+                } else {
+                    Ok(Statement::Delete(array, None))
+                }
+            }
+            _ => Ok(Statement::Expression(self.expression()?)),
+        }
+    }
+
+    /// Parses the arguments of `print` and `printf`, which may be parenthesized.
+    fn print_arguments(&mut self) -> Parsed<Vec<Expression>> {
+        if matches!(
+            self.peek(),
+            Token::Symbol(";" | "}" | ">" | ">>" | "|") | Token::Newline | Token::Eof
+        ) {
+            return Ok(Vec::new());
+        }
+        if self.is_symbol("(") {
+            // `print (a, b)` is a parenthesized list, but `print (a) b` starts an expression.
+            let before = self.index;
+            self.advance();
+            if let Ok(arguments) = self.expression_list()
+                && self.eat_symbol(")")
+                && matches!(
+                    self.peek(),
+                    Token::Symbol(";" | "}" | ">" | ">>" | "|") | Token::Newline | Token::Eof
+                )
+            {
+                return Ok(arguments);
+            }
+            self.index = before;
+        }
+        self.in_print = true;
+        let arguments = self.expression_list();
+        self.in_print = false;
+        arguments
+    }
+
+    fn expression_list(&mut self) -> Parsed<Vec<Expression>> {
+        let mut expressions = vec![self.expression()?];
+        while self.eat_symbol(",") {
+            self.skip_newlines();
+            expressions.push(self.expression()?);
+        }
+        Ok(expressions)
+    }
+
+    fn expression(&mut self) -> Parsed<Expression> {
+        let expression = self.conditional()?;
+        let operator = match self.peek() {
+            Token::Assign => None,
+            Token::Symbol(symbol @ ("+=" | "-=" | "*=" | "/=" | "%=" | "^=")) => {
+                symbol.chars().next()
+            }
+            _ => return Ok(expression),
+        };
+        let Expression::LValue(target) = expression else {
+            return Err(SyntaxError);
+        };
+        self.advance();
+        self.skip_newlines();
+        let value = self.expression()?;
+        Ok(Expression::Assign(target, operator, Box::new(value)))
+    }
+
+    fn conditional(&mut self) -> Parsed<Expression> {
+        let condition = self.or()?;
+        if !self.eat_symbol("?") {
+            return Ok(condition);
+        }
+        self.skip_newlines();
+        let then = self.expression()?;
+        self.skip_newlines();
+        self.expect_symbol(":")?;
+        self.skip_newlines();
+        let otherwise = self.expression()?;
+        Ok(Expression::Conditional(
+            Box::new(condition),
+            Box::new(then),
+            Box::new(otherwise),
+        ))
+    }
+
+    fn or(&mut self) -> Parsed<Expression> {
+        let mut expression = self.and()?;
+        while self.eat_symbol("||") {
+            self.skip_newlines();
+            expression = Expression::Or(Box::new(expression), Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Parsed<Expression> {
+        let mut expression = self.membership()?;
+        while self.eat_symbol("&&") {
+            self.skip_newlines();
+            expression = Expression::And(Box::new(expression), Box::new(self.membership()?));
+        }
+        Ok(expression)
+    }
+
+    fn membership(&mut self) -> Parsed<Expression> {
+        let mut expression = self.matching()?;
+        while self.eat_keyword("in") {
+            let Token::Name(array) = self.advance() else {
+                return Err(SyntaxError);
+            };
+            expression = Expression::In(vec![expression], array);
+        }
+        Ok(expression)
+    }
+
+    fn matching(&mut self) -> Parsed<Expression> {
+        let mut expression = self.comparison()?;
+        loop {
+            let negated = if self.eat_symbol("~") {
+                false
+            } else if self.eat_symbol("!~") {
+                true
+            } else {
+                return Ok(expression);
+            };
+            expression =
+                Expression::Match(negated, Box::new(expression), Box::new(self.comparison()?));
+        }
+    }
+
+    fn comparison(&mut self) -> Parsed<Expression> {
+        let expression = self.concatenation()?;
+        let operator = match self.peek() {
+            Token::Symbol(">") if self.in_print => return Ok(expression),
+            Token::Symbol(operator @ ("<" | "<=" | "==" | "!=" | ">=" | ">")) => *operator,
+            _ => return Ok(expression),
+        };
+        self.advance();
+        Ok(Expression::Compare(
+            operator,
+            Box::new(expression),
+            Box::new(self.concatenation()?),
+        ))
+    }
+
+    fn concatenation(&mut self) -> Parsed<Expression> {
+        let mut expression = self.additive()?;
+        while matches!(
+            self.peek(),
+            Token::Number(_)
+                | Token::String(_)
+                | Token::Regex(_)
+                | Token::Name(_)
+                | Token::Builtin(_)
+                | Token::Dollar
+                | Token::Symbol("(" | "++" | "--")
+        ) {
+            expression = Expression::Concat(Box::new(expression), Box::new(self.additive()?));
+        }
+        Ok(expression)
+    }
+
+    fn additive(&mut self) -> Parsed<Expression> {
+        let mut expression = self.multiplicative()?;
+        loop {
+            let operator = match self.peek() {
+                Token::Symbol("+") => '+',
+                Token::Symbol("-") => '-',
+                _ => return Ok(expression),
+            };
+            self.advance();
+            expression = Expression::Arithmetic(
+                operator,
+                Box::new(expression),
+                Box::new(self.multiplicative()?),
+            );
+        }
+    }
+
+    fn multiplicative(&mut self) -> Parsed<Expression> {
+        let mut expression = self.unary()?;
+        loop {
+            let operator = match self.peek() {
+                Token::Symbol("*") => '*',
+                Token::Symbol("/") => '/',
+                Token::Symbol("%") => '%',
+                _ => return Ok(expression),
+            };
+            self.advance();
+            expression =
+                Expression::Arithmetic(operator, Box::new(expression), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Parsed<Expression> {
+        if self.eat_symbol("!") {
+            Ok(Expression::Not(Box::new(self.unary()?)))
+        } else if self.eat_symbol("-") {
+            Ok(Expression::Negate(Box::new(self.unary()?)))
+        } else if self.eat_symbol("+") {
+            Ok(Expression::Plus(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> Parsed<Expression> {
+        let base = self.increment()?;
+        if self.eat_symbol("^") {
+            // The exponent may be negated, and `^` is right associative.
+            let exponent = self.unary()?;
+            return Ok(Expression::Arithmetic(
+                '^',
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn increment(&mut self) -> Parsed<Expression> {
+        for (symbol, delta) in [("++", 1.0), ("--", -1.0)] {
+            if self.eat_symbol(symbol) {
+                let Expression::LValue(target) = self.increment()? else {
+                    return Err(SyntaxError);
+                };
+                return Ok(Expression::Increment {
+                    target,
+                    delta,
+                    prefix: true,
+                });
+            }
+        }
+        let expression = self.primary()?;
+        if let Expression::LValue(_) = expression {
+            for (symbol, delta) in [("++", 1.0), ("--", -1.0)] {
+                if self.is_symbol(symbol) {
+                    self.advance();
+                    let Expression::LValue(target) = expression else {
+                        unreachable!("The expression is an lvalue");
+                    };
+                    return Ok(Expression::Increment {
+                        target,
+                        delta,
+                        prefix: false,
+                    });
+                }
+            }
+        }
+        Ok(expression)
+    }
+
+    fn primary(&mut self) -> Parsed<Expression> {
+        match self.advance() {
+            Token::Number(number) => Ok(Expression::Number(number)),
+            Token::String(string) => Ok(Expression::String(string)),
+            Token::Regex(regex) => Ok(Expression::Regex(regex)),
+            Token::Dollar => {
+                let index = if self.is_symbol("++") || self.is_symbol("--") || self.is_symbol("-") {
+                    self.unary()?
+                } else {
+                    self.primary()?
+                };
+                Ok(Expression::LValue(LValue::Field(Box::new(index))))
+            }
+            Token::Symbol("(") => {
+                let in_print = std::mem::replace(&mut self.in_print, false);
+                let expressions = self.expression_list();
+                self.in_print = in_print;
+                let mut expressions = expressions?;
+                self.expect_symbol(")")?;
+                if expressions.len() > 1 {
+                    // `(a, b) in array` is the only use of a parenthesized list.
+                    if !self.eat_keyword("in") {
+                        return Err(SyntaxError);
+                    }
+                    let Token::Name(array) = self.advance() else {
+                        return Err(SyntaxError);
+                    };
+                    return Ok(Expression::In(expressions, array));
+                }
+                Ok(Expression::Group(Box::new(expressions.remove(0))))
+            }
+            Token::Name(name) => {
+                if self.eat_symbol("[") {
+                    let subscripts = self.expression_list()?;
+                    self.expect_symbol("]")?;
+                    Ok(Expression::LValue(LValue::Element(name, subscripts)))
+                } else {
+                    Ok(Expression::LValue(LValue::Variable(name)))
+                }
+            }
+            Token::Builtin(name) => {
+                if !self.eat_symbol("(") {
+                    // `length` without parentheses is the length of the record.
+                    return if name == "length" {
+                        Ok(Expression::Call(name, Vec::new()))
+                    } else {
+                        Err(SyntaxError)
+                    };
+                }
+                let arguments = if self.is_symbol(")") {
+                    Vec::new()
+                } else {
+                    let in_print = std::mem::replace(&mut self.in_print, false);
+                    let arguments = self.expression_list();
+                    self.in_print = in_print;
+                    arguments?
+                };
+                self.expect_symbol(")")?;
+                Ok(Expression::Call(name, arguments))
+            }
+            _ => Err(SyntaxError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let program = parse("BEGIN { FS = \":\" }\n$3 > 10\n/x/, /y/ { n++ }\nEND { print n }")
+            .expect("Invalid program");
+        assert_eq!(program.begin.len(), 1);
+        assert_eq!(program.end.len(), 1);
+        assert_eq!(program.rules.len(), 2);
+        assert!(matches!(
+            program.rules[0],
+            Rule {
+                pattern: Pattern::Expression(Expression::Compare(">", _, _)),
+                action: None,
+            }
+        ));
+        assert!(matches!(program.rules[1].pattern, Pattern::Range(_, _)));
+    }
+
+    #[test]
+    fn test_parse_precedence() {
+        let program = parse("{ x = 1 + 2 * 3 \" \" -4 ^ 2 $1 }").expect("Invalid program");
+        let Some(action) = &program.rules[0].action else {
+            panic!("The rule has no action");
+        };
+        let Statement::Expression(Expression::Assign(_, None, value)) = &action[0] else {
+            panic!("The statement is not an assignment");
+        };
+        // The minus is binary, like in `"a" - 16`, then the field is concatenated.
+        let Expression::Concat(left, right) = value.as_ref() else {
+            panic!("The value is not a concatenation");
+        };
+        assert!(matches!(**right, Expression::LValue(LValue::Field(_))));
+        let Expression::Concat(sum, difference) = left.as_ref() else {
+            panic!("The value is not a concatenation");
+        };
+        assert!(matches!(**sum, Expression::Arithmetic('+', _, _)));
+        let Expression::Arithmetic('-', _, power) = difference.as_ref() else {
+            panic!("The operand is not a difference");
+        };
+        assert!(matches!(**power, Expression::Arithmetic('^', _, _)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("{ print $1 ").err(), Some(1));
+        assert_eq!(parse("BEGIN {\n x = = 1 }").err(), Some(2));
+        assert_eq!(parse("{ print > \"file\" }").err(), Some(1));
+        assert!(parse("{ if (x) print; else print \"no\" }").is_ok());
+        assert!(parse("{ printf(\"%d\\n\", $1) }").is_ok());
+    }
+}
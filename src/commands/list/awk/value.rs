@@ -0,0 +1,153 @@
+//! The values of awk, both strings and numbers.
+
+use std::cmp::Ordering;
+
+use crate::commands::list::awk::format::format_number;
+
+#[derive(Debug, Clone, Default)]
+pub(super) enum Value {
+    /// The value of variables never assigned, both `""` and `0`.
+    #[default]
+    Uninitialized,
+    Number(f64),
+    String(String),
+    /// A string coming from the input, like a field, compared as a number if it looks like one.
+    StrNum(String),
+}
+
+impl Value {
+    pub fn from_bool(value: bool) -> Self {
+        Value::Number(if value { 1.0 } else { 0.0 })
+    }
+
+    pub fn number(&self) -> f64 {
+        match self {
+            Value::Uninitialized => 0.0,
+            Value::Number(number) => *number,
+            Value::String(string) | Value::StrNum(string) => leading_number(string),
+        }
+    }
+
+    /// Returns the value as a string, numbers being converted with a format like `CONVFMT`.
+    pub fn string(&self, format: &str) -> String {
+        match self {
+            Value::Uninitialized => String::new(),
+            Value::Number(number) => format_number(*number, format),
+            Value::String(string) | Value::StrNum(string) => string.clone(),
+        }
+    }
+
+    pub fn truthy(&self) -> bool {
+        match self {
+            Value::Uninitialized => false,
+            Value::Number(number) => *number != 0.0,
+            Value::String(string) => !string.is_empty(),
+            Value::StrNum(string) => match numeric_string(string) {
+                Some(number) => number != 0.0,
+                None => !string.is_empty(),
+            },
+        }
+    }
+
+    /// Returns the number a value is compared as, if it is compared numerically.
+    fn comparable_number(&self) -> Option<f64> {
+        match self {
+            Value::Uninitialized => Some(0.0),
+            Value::Number(number) => Some(*number),
+            Value::String(_) => None,
+            Value::StrNum(string) => numeric_string(string),
+        }
+    }
+
+    /// Compares values as numbers if both are numeric, and as strings otherwise.
+    pub fn compare(&self, other: &Value, format: &str) -> Ordering {
+        match (self.comparable_number(), other.comparable_number()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            _ => self.string(format).cmp(&other.string(format)),
+        }
+    }
+}
+
+/// Returns the number a whole string is, surrounding blanks aside, if it is one.
+fn numeric_string(string: &str) -> Option<f64> {
+    let trimmed = string.trim_matches([' ', '\t', '\n']);
+    let length = number_prefix_length(trimmed);
+    (length > 0 && length == trimmed.len())
+        .then(|| trimmed.parse().ok())
+        .flatten()
+}
+
+/// Returns the number a string starts with, after blanks, `0` if it doesn't start with one.
+fn leading_number(string: &str) -> f64 {
+    let trimmed = string.trim_start_matches([' ', '\t', '\n']);
+    trimmed[..number_prefix_length(trimmed)]
+        .parse()
+        .unwrap_or(0.0)
+}
+
+/// Returns the length of the decimal number a string starts with.
+fn number_prefix_length(string: &str) -> usize {
+    let bytes = string.as_bytes();
+    let digits_from = |mut index: usize| {
+        while bytes.get(index).is_some_and(u8::is_ascii_digit) {
+            index += 1;
+        }
+        index
+    };
+    let start = usize::from(matches!(bytes.first(), Some(b'+' | b'-')));
+    let mut end = digits_from(start);
+    let mut has_digits = end > start;
+    if bytes.get(end) == Some(&b'.') {
+        let fraction_end = digits_from(end + 1);
+        has_digits |= fraction_end > end + 1;
+        end = fraction_end;
+    }
+    if !has_digits {
+        return 0;
+    }
+    if matches!(bytes.get(end), Some(b'e' | b'E')) {
+        let exponent_start = end + 1 + usize::from(matches!(bytes.get(end + 1), Some(b'+' | b'-')));
+        let exponent_end = digits_from(exponent_start);
+        if exponent_end > exponent_start {
+            end = exponent_end;
+        }
+    }
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Value::String(" 12abc".to_string()).number(), 12.0);
+        assert_eq!(Value::String("-1.5e2x".to_string()).number(), -150.0);
+        assert_eq!(Value::String(".".to_string()).number(), 0.0);
+        assert_eq!(Value::Number(0.1).string("%.6g"), "0.1");
+        assert_eq!(Value::Number(3.0).string("%.6g"), "3");
+        assert!(!Value::StrNum(" 0 ".to_string()).truthy());
+        assert!(Value::String("0".to_string()).truthy());
+    }
+
+    #[test]
+    fn test_compare() {
+        let strnum = |string: &str| Value::StrNum(string.to_string());
+        assert_eq!(
+            strnum("10").compare(&strnum("9"), "%.6g"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            Value::String("10".to_string()).compare(&strnum("9"), "%.6g"),
+            Ordering::Less
+        );
+        assert_eq!(
+            strnum("abc").compare(&Value::Number(1.0), "%.6g"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            Value::Uninitialized.compare(&Value::Number(0.0), "%.6g"),
+            Ordering::Equal
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! The cat command, concatenating files to the output.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct CatCommand;
+
+impl ExecutableCommand for CatCommand {
+    fn name(&self) -> &'static str {
+        "cat"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "number",
+                Some('n'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Concatenates the files given, or the standard input.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut output = String::new();
+        let mut errors = Vec::new();
+        let mut paths = string_arguments(args)?;
+        if paths.is_empty() {
+            paths.push("-".to_string());
+        }
+        for path in paths {
+            match read_input(shell, &path) {
+                Ok(content) => output.push_str(&content),
+                Err(error) => errors.push(format!("cat: {path}: {}", error_message(error)?)),
+            }
+        }
+        if !errors.is_empty() {
+            shell.set_exit_status(1);
+        }
+
+        // The output is displayed as lines, so the final line terminator is implied.
+        let output = output.strip_suffix('\n').unwrap_or(&output);
+        let mut lines = Vec::new();
+        if !output.is_empty() {
+            if flags.flag("number").is_some() {
+                lines.extend(
+                    output
+                        .split('\n')
+                        .enumerate()
+                        .map(|(index, line)| format!("{:>6}\t{line}", index + 1)),
+                );
+            } else {
+                lines.push(output.to_string());
+            }
+        }
+        lines.extend(errors);
+
+        Ok(CommandOutput((!lines.is_empty()).then(|| lines.join("\n"))))
+    }
+}
@@ -38,7 +38,10 @@ impl ExecutableCommand for ChangeDirectoryCommand {
             _ => return Err(ShellError::Internal("Invalid argument".to_string())),
         };
         let path = PathBuf::from(path);
-        match shell.current_session.change_directory(&shell.fs, &path) {
+        match shell
+            .current_session
+            .change_directory(&shell.fs.lock(), &path)
+        {
             Ok(()) => Ok(CommandOutput(None)),
             Err(ShellError::FileSystem(FileSystemError::DirectoryNotFound(_))) => {
                 Ok(CommandOutput(Some(format!(
@@ -46,6 +49,12 @@ impl ExecutableCommand for ChangeDirectoryCommand {
                     path.display()
                 ))))
             }
+            Err(ShellError::FileSystem(FileSystemError::NotADirectory(_))) => Ok(CommandOutput(
+                Some(format!("cd: {}: Not a directory", path.display())),
+            )),
+            Err(ShellError::FileSystem(FileSystemError::PermissionDenied(_))) => Ok(CommandOutput(
+                Some(format!("cd: {}: Permission denied", path.display())),
+            )),
             Err(e) => Err(e),
         }
     }
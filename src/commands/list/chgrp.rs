@@ -0,0 +1,88 @@
+//! The chgrp command, changing the group of files.
+
+use std::path::Path;
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::chown::{Ownership, change_ownership},
+        resolve_group,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct ChangeGroupCommand;
+
+impl ExecutableCommand for ChangeGroupCommand {
+    fn name(&self) -> &'static str {
+        "chgrp"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "recursive",
+                Some('R'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut fs = shell.fs.lock();
+        let recursive = flags.flag("recursive").is_some();
+        let mut args = string_arguments(args)?.into_iter();
+
+        let Some(group) = args.next() else {
+            return Ok(CommandOutput(Some("chgrp: missing operand".to_string())));
+        };
+        if args.len() == 0 {
+            return Ok(CommandOutput(Some(format!(
+                "chgrp: missing operand after '{group}'"
+            ))));
+        }
+        let Some(group_id) = resolve_group(&fs, &group) else {
+            return Ok(CommandOutput(Some(format!(
+                "chgrp: invalid group: '{group}'"
+            ))));
+        };
+
+        let mut current_session = shell.current_session.clone();
+        let mut error_messages = Vec::new();
+        for path in args {
+            change_ownership(
+                &mut fs,
+                &mut current_session,
+                &Ownership {
+                    command: "chgrp",
+                    action: "changing group of",
+                    owner: None,
+                    group: Some(group_id),
+                },
+                Path::new(&path),
+                recursive,
+                &mut error_messages,
+            )?;
+        }
+
+        if error_messages.is_empty() {
+            Ok(CommandOutput(None))
+        } else {
+            Ok(CommandOutput(Some(error_messages.join("\n"))))
+        }
+    }
+}
@@ -0,0 +1,200 @@
+//! The chmod command, changing the mode of files.
+
+use std::path::Path;
+
+use crate::{
+    FileSystem, InodeContent,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::ShellError,
+    fs::permissions::mode::ModeChange,
+    sessions::Session,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct ChangeModeCommand;
+
+impl ExecutableCommand for ChangeModeCommand {
+    fn name(&self) -> &'static str {
+        "chmod"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "recursive",
+                Some('R'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "reference",
+                None,
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Takes the symbolic modes removing permissions from every class, like `-x` or `-w,+r`, as
+    /// the mode instead of flags.
+    fn is_dash_argument(&self, arg: &str) -> bool {
+        arg.strip_prefix('-')
+            .and_then(|permissions| permissions.chars().next())
+            .is_some_and(|permission| "rwxXst".contains(permission))
+            && ModeChange::parse(arg).is_some()
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut fs = shell.fs.lock();
+        let recursive = flags.flag("recursive").is_some();
+
+        let mut args = match args {
+            Some(Argument::List(args)) => {
+                args.into_iter().try_fold(Vec::new(), |mut acc, arg| {
+                    match arg {
+                        BasicArgument::String(arg) => acc.push(arg),
+                        _ => return Err(ShellError::Internal("Invalid argument".to_string())),
+                    }
+                    Ok(acc)
+                })?
+            }
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => Vec::new(),
+        }
+        .into_iter();
+
+        let mode = match flags.flag("reference") {
+            Some(Argument::Basic(BasicArgument::String(reference))) => {
+                match shell
+                    .current_session
+                    .lookup_inode(&fs, Path::new(reference))
+                {
+                    Ok(inode) => ModeChange::Octal {
+                        mode: inode.metadata.permissions.mode(),
+                        exact: true,
+                    },
+                    Err(error) => {
+                        return Ok(CommandOutput(Some(format!(
+                            "chmod: failed to get attributes of '{reference}': {}",
+                            error_message(error)?
+                        ))));
+                    }
+                }
+            }
+            Some(_) => return Err(ShellError::Internal("Invalid reference".to_string())),
+            None => {
+                let Some(mode) = args.next() else {
+                    return Ok(CommandOutput(Some("chmod: missing operand".to_string())));
+                };
+                match ModeChange::parse(&mode) {
+                    Some(parsed_mode) => {
+                        if args.len() == 0 {
+                            return Ok(CommandOutput(Some(format!(
+                                "chmod: missing operand after '{mode}'"
+                            ))));
+                        }
+                        parsed_mode
+                    }
+                    None => {
+                        return Ok(CommandOutput(Some(format!(
+                            "chmod: invalid mode: '{mode}'"
+                        ))));
+                    }
+                }
+            }
+        };
+
+        let mut current_session = shell.current_session.clone();
+        let mut error_messages = Vec::new();
+        for path in args {
+            change_mode(
+                &mut fs,
+                &mut current_session,
+                Path::new(&path),
+                &mode,
+                recursive,
+                &mut error_messages,
+            )?;
+        }
+
+        if error_messages.is_empty() {
+            Ok(CommandOutput(None))
+        } else {
+            Ok(CommandOutput(Some(error_messages.join("\n"))))
+        }
+    }
+}
+
+/// Changes the mode of a file, and of every entry below it when recursive.
+fn change_mode(
+    fs: &mut FileSystem,
+    session: &mut Session,
+    path: &Path,
+    mode: &ModeChange,
+    recursive: bool,
+    error_messages: &mut Vec<String>,
+) -> Result<(), ShellError> {
+    let (current_mode, is_directory) = match session.lookup_inode(fs, path) {
+        Ok(inode) => (
+            inode.metadata.permissions.mode(),
+            matches!(inode.content, InodeContent::Directory(_)),
+        ),
+        Err(error) => {
+            error_messages.push(format!(
+                "chmod: cannot access '{}': {}",
+                path.display(),
+                error_message(error)?
+            ));
+            return Ok(());
+        }
+    };
+
+    let new_mode = mode.apply(current_mode, is_directory, session.umask());
+    if let Err(error) = session.change_mode(fs, path, new_mode) {
+        error_messages.push(format!(
+            "chmod: changing permissions of '{}': {}",
+            path.display(),
+            error_message(error)?
+        ));
+        return Ok(());
+    }
+
+    if recursive && is_directory {
+        match session.list_directory(fs, path) {
+            Ok(children) => {
+                for child in children {
+                    change_mode(
+                        fs,
+                        session,
+                        &path.join(child),
+                        mode,
+                        recursive,
+                        error_messages,
+                    )?;
+                }
+            }
+            Err(error) => error_messages.push(format!(
+                "chmod: cannot read directory '{}': {}",
+                path.display(),
+                error_message(error)?
+            )),
+        }
+    }
+
+    Ok(())
+}
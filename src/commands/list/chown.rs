@@ -0,0 +1,184 @@
+//! The chown command, changing the owner and group of files.
+
+use std::path::Path;
+
+use crate::{
+    FileSystem, GroupId, InodeContent, UserId,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        resolve_group, resolve_user,
+    },
+    errors::ShellError,
+    sessions::Session,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct ChangeOwnerCommand;
+
+impl ExecutableCommand for ChangeOwnerCommand {
+    fn name(&self) -> &'static str {
+        "chown"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "recursive",
+                Some('R'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut fs = shell.fs.lock();
+        let recursive = flags.flag("recursive").is_some();
+        let mut args = string_arguments(args)?.into_iter();
+
+        let Some(spec) = args.next() else {
+            return Ok(CommandOutput(Some("chown: missing operand".to_string())));
+        };
+        if args.len() == 0 {
+            return Ok(CommandOutput(Some(format!(
+                "chown: missing operand after '{spec}'"
+            ))));
+        }
+
+        let (user, group) = match spec.split_once(':') {
+            Some((user, group)) => (Some(user), Some(group)),
+            None => (Some(spec.as_str()), None),
+        };
+        let owner = match user.filter(|user| !user.is_empty()) {
+            Some(user) => match resolve_user(&fs, user) {
+                Some(owner) => Some(owner),
+                None => {
+                    return Ok(CommandOutput(Some(format!(
+                        "chown: invalid user: '{spec}'"
+                    ))));
+                }
+            },
+            None => None,
+        };
+        let group = match group {
+            // `user:` changes the group to the login group of the user.
+            Some("") => {
+                owner.and_then(|owner| fs.get_user(owner).and_then(|owner| owner.primary_group()))
+            }
+            Some(group) => match resolve_group(&fs, group) {
+                Some(group) => Some(group),
+                None => {
+                    return Ok(CommandOutput(Some(format!(
+                        "chown: invalid group: '{spec}'"
+                    ))));
+                }
+            },
+            None => None,
+        };
+
+        let mut current_session = shell.current_session.clone();
+        let mut error_messages = Vec::new();
+        for path in args {
+            change_ownership(
+                &mut fs,
+                &mut current_session,
+                &Ownership {
+                    command: "chown",
+                    action: "changing ownership of",
+                    owner,
+                    group,
+                },
+                Path::new(&path),
+                recursive,
+                &mut error_messages,
+            )?;
+        }
+
+        if error_messages.is_empty() {
+            Ok(CommandOutput(None))
+        } else {
+            Ok(CommandOutput(Some(error_messages.join("\n"))))
+        }
+    }
+}
+
+/// The ownership change applied by `chown` or `chgrp`.
+pub struct Ownership {
+    /// The name of the command, prefixing error messages.
+    pub command: &'static str,
+    /// The description of the change in error messages.
+    pub action: &'static str,
+    pub owner: Option<UserId>,
+    pub group: Option<GroupId>,
+}
+
+/// Changes the ownership of a file, and of every entry below it when recursive.
+pub fn change_ownership(
+    fs: &mut FileSystem,
+    session: &mut Session,
+    ownership: &Ownership,
+    path: &Path,
+    recursive: bool,
+    error_messages: &mut Vec<String>,
+) -> Result<(), ShellError> {
+    let is_directory = match session.lookup_inode(fs, path) {
+        Ok(inode) => matches!(inode.content, InodeContent::Directory(_)),
+        Err(error) => {
+            error_messages.push(format!(
+                "{}: cannot access '{}': {}",
+                ownership.command,
+                path.display(),
+                error_message(error)?
+            ));
+            return Ok(());
+        }
+    };
+
+    if let Err(error) = session.change_owner(fs, path, ownership.owner, ownership.group) {
+        error_messages.push(format!(
+            "{}: {} '{}': {}",
+            ownership.command,
+            ownership.action,
+            path.display(),
+            error_message(error)?
+        ));
+    }
+
+    if recursive && is_directory {
+        match session.list_directory(fs, path) {
+            Ok(children) => {
+                for child in children {
+                    change_ownership(
+                        fs,
+                        session,
+                        ownership,
+                        &path.join(child),
+                        recursive,
+                        error_messages,
+                    )?;
+                }
+            }
+            Err(error) => error_messages.push(format!(
+                "{}: cannot read directory '{}': {}",
+                ownership.command,
+                path.display(),
+                error_message(error)?
+            )),
+        }
+    }
+
+    Ok(())
+}
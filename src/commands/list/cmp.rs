@@ -0,0 +1,119 @@
+//! The cmp command, finding the first byte at which two files differ.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct CompareCommand;
+
+impl ExecutableCommand for CompareCommand {
+    fn name(&self) -> &'static str {
+        "cmp"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "silent",
+                Some('s'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Compares two files byte by byte, the second being the standard input if it is missing.
+    ///
+    /// The exit status is 0 if they are the same, 1 if they differ, and 2 on errors.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?;
+        match operands.len() {
+            0 => {
+                shell.set_exit_status(2);
+                return Ok(CommandOutput(Some(
+                    "cmp: missing operand after 'cmp'".to_string(),
+                )));
+            }
+            1 => operands.push("-".to_string()),
+            2 => {}
+            _ => {
+                shell.set_exit_status(2);
+                return Ok(CommandOutput(Some(format!(
+                    "cmp: extra operand '{}'",
+                    operands[2]
+                ))));
+            }
+        }
+        let mut contents = Vec::new();
+        for path in &operands {
+            match read_input(shell, path) {
+                Ok(content) => contents.push(content.into_bytes()),
+                Err(error) => {
+                    shell.set_exit_status(2);
+                    return Ok(CommandOutput(Some(format!(
+                        "cmp: {path}: {}",
+                        error_message(error)?
+                    ))));
+                }
+            }
+        }
+        let (old, new) = (&contents[0], &contents[1]);
+        let common = old
+            .iter()
+            .zip(new)
+            .take_while(|(old, new)| old == new)
+            .count();
+        if common == old.len() && common == new.len() {
+            shell.set_exit_status(0);
+            return Ok(CommandOutput(None));
+        }
+        shell.set_exit_status(1);
+        if flags.flag("silent").is_some() {
+            return Ok(CommandOutput(None));
+        }
+        let line = |bytes: &[u8]| bytes.iter().filter(|byte| **byte == b'\n').count() + 1;
+        let message = if common < old.len() && common < new.len() {
+            format!(
+                "{} {} differ: byte {}, line {}",
+                operands[0],
+                operands[1],
+                common + 1,
+                line(&old[..common])
+            )
+        } else {
+            let shorter = if common == old.len() {
+                &operands[0]
+            } else {
+                &operands[1]
+            };
+            if common == 0 {
+                format!("cmp: EOF on {shorter} which is empty")
+            } else {
+                // The line is the one of the last byte read.
+                format!(
+                    "cmp: EOF on {shorter} after byte {common}, line {}",
+                    line(&old[..common - 1])
+                )
+            }
+        };
+        Ok(CommandOutput(Some(message)))
+    }
+}
@@ -0,0 +1,213 @@
+//! The cut command, printing selected parts of lines.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct CutCommand;
+
+impl ExecutableCommand for CutCommand {
+    fn name(&self) -> &'static str {
+        "cut"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        let mut builder = FlagDefinitionBuilder::new();
+        for (name, short_hand) in [
+            ("bytes", 'b'),
+            ("characters", 'c'),
+            ("fields", 'f'),
+            ("delimiter", 'd'),
+        ] {
+            builder = builder.with_flag(FlagSpecification::new(
+                name,
+                Some(short_hand),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ));
+        }
+        builder
+            .with_flag(FlagSpecification::new(
+                "only-delimited",
+                Some('s'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the bytes, characters or fields of each line selected by a list like `1,3-5,7-`.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let string_flag = |name| match flags.flag(name) {
+            Some(Argument::Basic(BasicArgument::String(value))) => Some(value.as_str()),
+            _ => None,
+        };
+        let selections = [
+            string_flag("bytes").map(|list| (Mode::Bytes, list)),
+            string_flag("characters").map(|list| (Mode::Characters, list)),
+            string_flag("fields").map(|list| (Mode::Fields, list)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+        let (mode, list) = match selections.as_slice() {
+            [selection] => *selection,
+            [] => {
+                return Ok(error(
+                    shell,
+                    "you must specify a list of bytes, characters, or fields",
+                ));
+            }
+            _ => return Ok(error(shell, "only one type of list may be specified")),
+        };
+        let delimiter = match string_flag("delimiter") {
+            Some(delimiter) => {
+                let mut chars = delimiter.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(delimiter), None) => delimiter,
+                    _ => return Ok(error(shell, "the delimiter must be a single character")),
+                }
+            }
+            None => '\t',
+        };
+        if mode != Mode::Fields
+            && (string_flag("delimiter").is_some() || flags.flag("only-delimited").is_some())
+        {
+            return Ok(error(
+                shell,
+                "an input delimiter may be specified only when operating on fields",
+            ));
+        }
+        let ranges = match parse_list(list) {
+            Ok(ranges) => ranges,
+            Err(message) => return Ok(error(shell, &message)),
+        };
+
+        let mut paths = string_arguments(args)?;
+        if paths.is_empty() {
+            paths.push("-".to_string());
+        }
+        let mut lines = Vec::new();
+        for path in paths {
+            let content = match read_input(shell, &path) {
+                Ok(content) => content,
+                Err(error) => {
+                    shell.set_exit_status(1);
+                    lines.push(format!("cut: {path}: {}", error_message(error)?));
+                    continue;
+                }
+            };
+            for line in content.split_terminator('\n') {
+                let cut = match mode {
+                    Mode::Bytes => {
+                        let bytes = line.as_bytes();
+                        let selected = (0..bytes.len())
+                            .filter(|index| selected(&ranges, index + 1))
+                            .map(|index| bytes[index])
+                            .collect::<Vec<_>>();
+                        Some(String::from_utf8_lossy(&selected).into_owned())
+                    }
+                    Mode::Characters => Some(
+                        line.chars()
+                            .enumerate()
+                            .filter(|(index, _)| selected(&ranges, index + 1))
+                            .map(|(_, char)| char)
+                            .collect(),
+                    ),
+                    Mode::Fields if !line.contains(delimiter) => {
+                        (flags.flag("only-delimited").is_none()).then(|| line.to_string())
+                    }
+                    Mode::Fields => Some(
+                        line.split(delimiter)
+                            .enumerate()
+                            .filter(|(index, _)| selected(&ranges, index + 1))
+                            .map(|(_, field)| field)
+                            .collect::<Vec<_>>()
+                            .join(&delimiter.to_string()),
+                    ),
+                };
+                lines.extend(cut);
+            }
+        }
+        Ok(CommandOutput((!lines.is_empty()).then(|| lines.join("\n"))))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Bytes,
+    Characters,
+    Fields,
+}
+
+fn error(shell: &mut Shell, message: &str) -> CommandOutput {
+    shell.set_exit_status(1);
+    CommandOutput(Some(format!("cut: {message}")))
+}
+
+/// Parses a list of positions counted from 1, like `1,3-5,7-` or `-2`, into inclusive ranges.
+fn parse_list(list: &str) -> Result<Vec<(usize, usize)>, String> {
+    let position = |number: &str| match number.parse::<usize>() {
+        Ok(0) => Err("fields and positions are numbered from 1".to_string()),
+        Ok(number) => Ok(number),
+        Err(_) => Err(format!("invalid field value '{number}'")),
+    };
+    list.split(',')
+        .map(|range| match range.split_once('-') {
+            None => position(range).map(|number| (number, number)),
+            Some(("", "")) => Err("invalid range with no endpoint: -".to_string()),
+            Some(("", end)) => position(end).map(|end| (1, end)),
+            Some((start, "")) => position(start).map(|start| (start, usize::MAX)),
+            Some((start, end)) => {
+                let (start, end) = (position(start)?, position(end)?);
+                if start > end {
+                    Err("invalid decreasing range".to_string())
+                } else {
+                    Ok((start, end))
+                }
+            }
+        })
+        .collect()
+}
+
+fn selected(ranges: &[(usize, usize)], position: usize) -> bool {
+    ranges
+        .iter()
+        .any(|(start, end)| (*start..=*end).contains(&position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            parse_list("1,3-5,7-"),
+            Ok(vec![(1, 1), (3, 5), (7, usize::MAX)])
+        );
+        assert_eq!(parse_list("-2"), Ok(vec![(1, 2)]));
+        assert!(parse_list("0").is_err());
+        assert!(parse_list("3-1").is_err());
+        assert!(parse_list("a").is_err());
+        assert!(parse_list("-").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! The date command, displaying the time of the clock of the file system.
+
+use std::fmt::Write;
+
+use chrono::{
+    DateTime, Utc,
+    format::{Item, StrftimeItems},
+};
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::touch::parse_date,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct DateCommand;
+
+/// The format of `date` without operand, like `Sat Oct 18 14:03:27 UTC 2026`.
+const DEFAULT_FORMAT: &str = "%a %b %e %H:%M:%S %Z %Y";
+
+impl ExecutableCommand for DateCommand {
+    fn name(&self) -> &'static str {
+        "date"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "date",
+                Some('d'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "utc",
+                Some('u'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the current time, or the time described by `-d`, in the format given by an operand
+    /// starting with `+`.
+    ///
+    /// Every time is in UTC, so `-u` changes nothing.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let args = string_arguments(args)?;
+        let format = match args.as_slice() {
+            [] => DEFAULT_FORMAT,
+            [format] => match format.strip_prefix('+') {
+                Some(format) => format,
+                None => {
+                    shell.set_exit_status(1);
+                    return Ok(CommandOutput(Some(
+                        "date: cannot set date: Operation not permitted".to_string(),
+                    )));
+                }
+            },
+            [_, extra, ..] => {
+                shell.set_exit_status(1);
+                return Ok(CommandOutput(Some(format!(
+                    "date: extra operand '{extra}'"
+                ))));
+            }
+        };
+
+        let now = shell.fs.lock().now();
+        let time = match flags.flag("date") {
+            Some(Argument::Basic(BasicArgument::String(date))) => match parse_date(date, now) {
+                Some(time) => time,
+                None => {
+                    shell.set_exit_status(1);
+                    return Ok(CommandOutput(Some(format!("date: invalid date '{date}'"))));
+                }
+            },
+            _ => now,
+        };
+
+        match format_time(time, format) {
+            Some(output) => Ok(CommandOutput(Some(output))),
+            None => {
+                shell.set_exit_status(1);
+                Ok(CommandOutput(Some(format!(
+                    "date: invalid format '+{format}'"
+                ))))
+            }
+        }
+    }
+}
+
+/// Formats a time like `strftime`, `%N` being the nanoseconds like in GNU date.
+///
+/// Returns `None` if the format has an unknown conversion.
+fn format_time(time: DateTime<Utc>, format: &str) -> Option<String> {
+    let mut translated = String::new();
+    let mut chars = format.chars();
+    while let Some(char) = chars.next() {
+        translated.push(char);
+        if char == '%' {
+            match chars.next() {
+                Some('N') => translated.push_str("9f"),
+                Some(next) => translated.push(next),
+                None => {}
+            }
+        }
+    }
+
+    let items = StrftimeItems::new(&translated).collect::<Vec<_>>();
+    if items.contains(&Item::Error) {
+        return None;
+    }
+    let mut output = String::new();
+    write!(output, "{}", time.format_with_items(items.into_iter())).ok()?;
+    Some(output)
+}
@@ -0,0 +1,99 @@
+//! The df command, reporting the space used on the disk holding the file system.
+
+use std::path::Path;
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::du::human_size,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+/// The device the file system is on, matching the device shown by `stat`.
+const DEVICE: &str = "/dev/sda1";
+
+#[derive(Default, Clone, Copy)]
+pub struct DiskFreeCommand;
+
+impl ExecutableCommand for DiskFreeCommand {
+    fn name(&self) -> &'static str {
+        "df"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "human-readable",
+                Some('h'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the size of the disk, the space allocated to the inodes and the space left, in
+    /// kibibytes.
+    ///
+    /// There is a single file system, mounted on `/`, which holds the files given as operands.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?;
+        if operands.is_empty() {
+            operands.push("/".to_string());
+        }
+        let fs = shell.fs.lock();
+        let mut output = Vec::new();
+        for operand in &operands {
+            if let Err(error) = shell.current_session.lookup_inode(&fs, Path::new(operand)) {
+                output.push(format!("df: {operand}: {}", error_message(error)?));
+            }
+        }
+        let errors = output.len();
+        if errors < operands.len() {
+            let size = fs.capacity();
+            let used = fs.used_space().min(size);
+            let available = size - used;
+            // The percentage is rounded up, so that a disk barely used isn't shown as empty.
+            let percentage = (used * 100).div_ceil(size.max(1));
+            if flags.flag("human-readable").is_some() {
+                output.push(format!(
+                    "{:<14} {:>5} {:>5} {:>5} {:>4} Mounted on",
+                    "Filesystem", "Size", "Used", "Avail", "Use%"
+                ));
+                output.push(format!(
+                    "{DEVICE:<14} {:>5} {:>5} {:>5} {percentage:>3}% /",
+                    human_size(size),
+                    human_size(used),
+                    human_size(available)
+                ));
+            } else {
+                output.push(format!(
+                    "{:<14} {:>9} {:>8} {:>9} {:>4} Mounted on",
+                    "Filesystem", "1K-blocks", "Used", "Available", "Use%"
+                ));
+                output.push(format!(
+                    "{DEVICE:<14} {:>9} {:>8} {:>9} {percentage:>3}% /",
+                    size / 1024,
+                    used.div_ceil(1024),
+                    available / 1024
+                ));
+            }
+        }
+        drop(fs);
+        shell.set_exit_status(i32::from(errors > 0));
+        Ok(CommandOutput(Some(output.join("\n"))))
+    }
+}
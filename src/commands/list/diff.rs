@@ -0,0 +1,292 @@
+//! The diff command, comparing files line by line, and directories file by file.
+
+use std::{collections::BTreeSet, path::Path};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    diff::Diff,
+    errors::ShellError,
+    fs::inode::content::InodeType,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct DiffCommand;
+
+impl ExecutableCommand for DiffCommand {
+    fn name(&self) -> &'static str {
+        "diff"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "unified",
+                Some('u'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "brief",
+                Some('q'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "recursive",
+                Some('r'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Compares two files, or the files of two directories, printing how to change the first
+    /// into the second.
+    ///
+    /// The exit status is 0 if they are the same, 1 if they differ, and 2 on errors.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let operands = string_arguments(args)?;
+        let [old, new] = operands.as_slice() else {
+            shell.set_exit_status(2);
+            return Ok(CommandOutput(Some(match operands.get(2) {
+                Some(extra) => format!("diff: extra operand '{extra}'"),
+                None => match operands.first() {
+                    Some(operand) => format!("diff: missing operand after '{operand}'"),
+                    None => "diff: missing operand".to_string(),
+                },
+            })));
+        };
+        let mut differ = Differ {
+            unified: flags.flag("unified").is_some(),
+            brief: flags.flag("brief").is_some(),
+            recursive: flags.flag("recursive").is_some(),
+            output: Vec::new(),
+            status: 0,
+        };
+        differ.compare(shell, old, new, true)?;
+        shell.set_exit_status(differ.status);
+        Ok(CommandOutput(
+            (!differ.output.is_empty()).then(|| differ.output.join("\n")),
+        ))
+    }
+}
+
+struct Differ {
+    unified: bool,
+    brief: bool,
+    recursive: bool,
+    output: Vec<String>,
+    status: i32,
+}
+
+impl Differ {
+    fn report(&mut self, message: String, status: i32) {
+        self.output.push(message);
+        self.status = self.status.max(status);
+    }
+
+    /// Compares two paths, which are the operands at the top of the comparison, or entries of
+    /// directories compared below it.
+    fn compare(
+        &mut self,
+        shell: &mut Shell,
+        old: &str,
+        new: &str,
+        top: bool,
+    ) -> Result<(), ShellError> {
+        let (Some(mut old_type), Some(mut new_type)) =
+            (self.kind(shell, old)?, self.kind(shell, new)?)
+        else {
+            return Ok(());
+        };
+        let (mut old, mut new) = (old.to_string(), new.to_string());
+        // A file compared with a directory at the top is compared with the file of the same name
+        // in it.
+        if top && (old_type == InodeType::Directory) != (new_type == InodeType::Directory) {
+            if old_type == InodeType::Directory {
+                old = join(&old, base_name(&new));
+                let Some(kind) = self.kind(shell, &old)? else {
+                    return Ok(());
+                };
+                old_type = kind;
+            } else {
+                new = join(&new, base_name(&old));
+                let Some(kind) = self.kind(shell, &new)? else {
+                    return Ok(());
+                };
+                new_type = kind;
+            }
+        }
+        match (
+            old_type == InodeType::Directory,
+            new_type == InodeType::Directory,
+        ) {
+            (true, true) if top || self.recursive => self.compare_directories(shell, &old, &new),
+            (true, true) => {
+                self.report(format!("Common subdirectories: {old} and {new}"), 0);
+                Ok(())
+            }
+            (true, false) => {
+                self.report(
+                    format!("File {old} is a directory while file {new} is a regular file"),
+                    1,
+                );
+                Ok(())
+            }
+            (false, true) => {
+                self.report(
+                    format!("File {old} is a regular file while file {new} is a directory"),
+                    1,
+                );
+                Ok(())
+            }
+            (false, false) => self.compare_files(shell, &old, &new, top),
+        }
+    }
+
+    /// Returns the type of the inode at a path, reporting it if it can't be found.
+    fn kind(&mut self, shell: &Shell, path: &str) -> Result<Option<InodeType>, ShellError> {
+        if path == "-" {
+            return Ok(Some(InodeType::File));
+        }
+        let inode_type = {
+            let fs = shell.fs.lock();
+            shell
+                .current_session
+                .lookup_inode(&fs, Path::new(path))
+                .map(|inode| inode.inode_type())
+        };
+        match inode_type {
+            Ok(inode_type) => Ok(Some(inode_type)),
+            Err(error) => {
+                self.report(format!("diff: {path}: {}", error_message(error)?), 2);
+                Ok(None)
+            }
+        }
+    }
+
+    fn compare_directories(
+        &mut self,
+        shell: &mut Shell,
+        old: &str,
+        new: &str,
+    ) -> Result<(), ShellError> {
+        let mut entries = Vec::new();
+        for path in [old, new] {
+            let listed = {
+                let fs = shell.fs.lock();
+                shell.current_session.list_directory(&fs, Path::new(path))
+            };
+            match listed {
+                Ok(names) => entries.push(names.into_iter().collect::<BTreeSet<_>>()),
+                Err(error) => {
+                    self.report(format!("diff: {path}: {}", error_message(error)?), 2);
+                    return Ok(());
+                }
+            }
+        }
+        for name in entries[0].union(&entries[1]) {
+            match (entries[0].contains(name), entries[1].contains(name)) {
+                (true, true) => self.compare(shell, &join(old, name), &join(new, name), false)?,
+                (true, false) => self.report(format!("Only in {old}: {name}"), 1),
+                _ => self.report(format!("Only in {new}: {name}"), 1),
+            }
+        }
+        Ok(())
+    }
+
+    fn compare_files(
+        &mut self,
+        shell: &mut Shell,
+        old: &str,
+        new: &str,
+        top: bool,
+    ) -> Result<(), ShellError> {
+        let mut contents = Vec::new();
+        for path in [old, new] {
+            match read_input(shell, path) {
+                Ok(content) => contents.push(content),
+                Err(error) => {
+                    self.report(format!("diff: {path}: {}", error_message(error)?), 2);
+                    return Ok(());
+                }
+            }
+        }
+        let diff = Diff::new(&contents[0], &contents[1]);
+        if diff.is_empty() {
+            return Ok(());
+        }
+        self.status = self.status.max(1);
+        if self.brief {
+            self.output.push(format!("Files {old} and {new} differ"));
+            return Ok(());
+        }
+        if !top {
+            // The header of a file found in the directories repeats the options given.
+            let options = [(self.recursive, 'r'), (self.unified, 'u')]
+                .into_iter()
+                .filter_map(|(given, option)| given.then_some(option))
+                .collect::<String>();
+            if options.is_empty() {
+                self.output.push(format!("diff {old} {new}"));
+            } else {
+                self.output.push(format!("diff -{options} {old} {new}"));
+            }
+        }
+        if self.unified {
+            for (prefix, path) in [("---", old), ("+++", new)] {
+                let modified_at = modified_at(shell, path);
+                self.output.push(format!(
+                    "{prefix} {path}\t{}",
+                    modified_at.format("%Y-%m-%d %H:%M:%S%.9f %z")
+                ));
+            }
+            self.output.extend(diff.unified(3));
+        } else {
+            self.output.extend(diff.normal());
+        }
+        Ok(())
+    }
+}
+
+/// Returns the time a file was modified, the standard input being modified now.
+fn modified_at(shell: &Shell, path: &str) -> DateTime<Utc> {
+    let fs = shell.fs.lock();
+    match shell.current_session.find_inode(&fs, Path::new(path)) {
+        Some(inode) if path != "-" => inode.metadata.modified_at,
+        _ => fs.now(),
+    }
+}
+
+fn join(directory: &str, name: &str) -> String {
+    if directory.ends_with('/') {
+        format!("{directory}{name}")
+    } else {
+        format!("{directory}/{name}")
+    }
+}
+
+fn base_name(path: &str) -> &str {
+    path.trim_end_matches('/')
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+}
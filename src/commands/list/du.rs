@@ -0,0 +1,226 @@
+//! The du command, summing the space allocated to the files below directories.
+
+use std::{collections::HashSet, path::Path};
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::ShellError,
+    fs::inode::{content::InodeType, size::Size, table::InodeNumber},
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct DiskUsageCommand;
+
+impl ExecutableCommand for DiskUsageCommand {
+    fn name(&self) -> &'static str {
+        "du"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "summarize",
+                Some('s'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "human-readable",
+                Some('h'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "all",
+                Some('a'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "max-depth",
+                Some('d'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the space allocated to each directory below the operands, or the current directory,
+    /// in kibibytes, after the directories below it.
+    ///
+    /// Space is allocated in whole blocks, and the inodes linked several times are only counted
+    /// once.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?;
+        if operands.is_empty() {
+            operands.push(".".to_string());
+        }
+        let max_depth = match flags.flag("max-depth") {
+            Some(Argument::Basic(BasicArgument::String(depth))) => match depth.parse() {
+                Ok(depth) => Some(depth),
+                Err(_) => {
+                    shell.set_exit_status(1);
+                    return Ok(CommandOutput(Some(format!(
+                        "du: invalid maximum depth '{depth}'"
+                    ))));
+                }
+            },
+            _ => None,
+        };
+        let mut usage = Usage {
+            summarize: flags.flag("summarize").is_some(),
+            human: flags.flag("human-readable").is_some(),
+            all: flags.flag("all").is_some(),
+            max_depth,
+            seen: HashSet::new(),
+            output: Vec::new(),
+            status: 0,
+        };
+        for operand in operands {
+            usage.visit(shell, &operand, 0)?;
+        }
+        shell.set_exit_status(usage.status);
+        Ok(CommandOutput(
+            (!usage.output.is_empty()).then(|| usage.output.join("\n")),
+        ))
+    }
+}
+
+struct Usage {
+    summarize: bool,
+    human: bool,
+    all: bool,
+    max_depth: Option<usize>,
+    /// The inodes already counted.
+    seen: HashSet<InodeNumber>,
+    output: Vec<String>,
+    status: i32,
+}
+
+impl Usage {
+    /// Returns the space allocated to a path and everything below it, printing it if needed.
+    fn visit(&mut self, shell: &Shell, path: &str, depth: usize) -> Result<u64, ShellError> {
+        let inode = {
+            let fs = shell.fs.lock();
+            shell
+                .current_session
+                .lookup_inode(&fs, Path::new(path))
+                .map(|inode| (inode.number, inode.inode_type(), inode.allocated_size()))
+        };
+        let (number, inode_type, mut total) = match inode {
+            Ok(inode) => inode,
+            Err(error) => {
+                self.report(format!(
+                    "du: cannot access '{path}': {}",
+                    error_message(error)?
+                ));
+                return Ok(0);
+            }
+        };
+        if !self.seen.insert(number) {
+            return Ok(0);
+        }
+        if inode_type != InodeType::Directory {
+            if depth == 0 || self.all {
+                self.print(total, path, depth);
+            }
+            return Ok(total);
+        }
+        let children = {
+            let fs = shell.fs.lock();
+            shell.current_session.list_directory(&fs, Path::new(path))
+        };
+        match children {
+            Ok(children) => {
+                for child in children {
+                    let child = if path.ends_with('/') {
+                        format!("{path}{child}")
+                    } else {
+                        format!("{path}/{child}")
+                    };
+                    total += self.visit(shell, &child, depth + 1)?;
+                }
+            }
+            Err(error) => {
+                let message = error_message(error)?;
+                self.report(format!("du: cannot read directory '{path}': {message}"));
+            }
+        }
+        self.print(total, path, depth);
+        Ok(total)
+    }
+
+    fn print(&mut self, size: u64, path: &str, depth: usize) {
+        if (self.summarize && depth > 0) || self.max_depth.is_some_and(|max| depth > max) {
+            return;
+        }
+        let size = if self.human {
+            human_size(size)
+        } else {
+            size.div_ceil(1024).to_string()
+        };
+        self.output.push(format!("{size}\t{path}"));
+    }
+
+    fn report(&mut self, message: String) {
+        self.output.push(message);
+        self.status = 1;
+    }
+}
+
+/// Formats a size in bytes with powers of 1024, rounding up to a single decimal below ten, like
+/// `4.0K` or `12M`.
+pub(crate) fn human_size(bytes: u64) -> String {
+    const UNITS: [char; 6] = ['K', 'M', 'G', 'T', 'P', 'E'];
+    if bytes < 1024 {
+        return bytes.to_string();
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if value < 10.0 {
+        let rounded = (value * 10.0).ceil() / 10.0;
+        if rounded < 10.0 {
+            return format!("{rounded:.1}{}", UNITS[unit]);
+        }
+    }
+    let rounded = value.ceil();
+    if rounded >= 1024.0 && unit < UNITS.len() - 1 {
+        return format!("1.0{}", UNITS[unit + 1]);
+    }
+    format!("{rounded}{}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_human_size() {
+        assert_eq!(human_size(0), "0");
+        assert_eq!(human_size(4096), "4.0K");
+        assert_eq!(human_size(1536), "1.5K");
+        assert_eq!(human_size(10_239), "10K");
+        assert_eq!(human_size(12 * 1024 + 1), "13K");
+        assert_eq!(human_size(1024 * 1024 - 1), "1.0M");
+        assert_eq!(human_size(20 * 1024 * 1024 * 1024), "20G");
+    }
+}
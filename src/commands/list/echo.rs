@@ -25,6 +25,10 @@ impl ExecutableCommand for EchoCommand {
         Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
     }
 
+    fn permute_arguments(&self) -> bool {
+        false
+    }
+
     fn execute(
         &self,
         _: Flags,
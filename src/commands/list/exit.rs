@@ -31,7 +31,10 @@ impl ExecutableCommand for ExitCommand {
         _: Option<Argument>,
         shell: &mut crate::shell::Shell,
     ) -> Result<CommandOutput, ShellError> {
-        shell.active = false;
+        // Leaving a shell opened by `su` or `sudo` returns to the previous user.
+        if !shell.current_session.restore_user() {
+            shell.logout();
+        }
         Ok(CommandOutput(None))
     }
 }
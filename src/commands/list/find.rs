@@ -0,0 +1,741 @@
+//! The find command, walking directory trees and evaluating an expression on every entry.
+//!
+//! The expression follows GNU find: tests (`-name`, `-type`...), actions (`-print`, `-exec`...)
+//! and options (`-maxdepth`...) combined with `!`, `-a`, `-o` and parentheses, two primaries in a
+//! row being implicitly joined by `-a`. Without any action, `-print` is applied to the entries for
+//! which the expression is true.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    FileSystem, GroupId, InodeContent, UserId,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder},
+        list::grep::glob_matches,
+    },
+    errors::ShellError,
+    fs::{
+        inode::{content::InodeType, size::Size},
+        permissions::mode::ModeChange,
+    },
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct FindCommand;
+
+impl ExecutableCommand for FindCommand {
+    fn name(&self) -> &'static str {
+        "find"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new().into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn parse_flags(&self) -> bool {
+        false
+    }
+
+    /// Walks the starting points, the working directory by default, evaluating the expression on
+    /// every entry below them.
+    ///
+    /// The exit status is `1` if an entry couldn't be reached or an action failed.
+    fn execute(
+        &self,
+        _: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let args = string_arguments(args)?;
+        let start = args
+            .iter()
+            .position(|arg| is_expression_start(arg))
+            .unwrap_or(args.len());
+        let (paths, expression) = args.split_at(start);
+
+        let parsed = {
+            let fs = shell.fs.lock();
+            ExpressionParser::new(&fs, expression).parse()
+        };
+        let (mut expression, options) = match parsed {
+            Ok(parsed) => parsed,
+            Err(message) => {
+                shell.set_exit_status(1);
+                return Ok(CommandOutput(Some(format!("find: {message}"))));
+            }
+        };
+
+        let mut walk = Walk {
+            options,
+            output: String::new(),
+            failed: false,
+        };
+        if paths.is_empty() {
+            walk.visit(shell, &mut expression, ".", 0)?;
+        }
+        for path in paths {
+            walk.visit(shell, &mut expression, path, 0)?;
+        }
+        walk.flush_batches(shell, &mut expression)?;
+
+        shell.set_exit_status(i32::from(walk.failed));
+        let output = walk.output.strip_suffix('\n').unwrap_or(&walk.output);
+        Ok(CommandOutput(
+            (!output.is_empty()).then(|| output.to_string()),
+        ))
+    }
+}
+
+/// Checks if a word starts the expression, the words before it being the starting points.
+fn is_expression_start(word: &str) -> bool {
+    (word.starts_with('-') && word.len() > 1) || word == "(" || word == "!"
+}
+
+#[derive(Debug)]
+enum Expression {
+    Not(Box<Expression>),
+    And(Box<Expression>, Box<Expression>),
+    Or(Box<Expression>, Box<Expression>),
+    Test(Test),
+    Action(Action),
+}
+
+#[derive(Debug)]
+enum Test {
+    Name {
+        pattern: String,
+        ignore_case: bool,
+    },
+    Path {
+        pattern: String,
+        ignore_case: bool,
+    },
+    Type(Vec<char>),
+    /// The size, rounded up to a number of units of the given size in bytes.
+    Size(Comparison, u64),
+    ModifiedDays(Comparison),
+    ModifiedMinutes(Comparison),
+    User(UserId),
+    Group(GroupId),
+    Permissions(PermissionsMatch, u32),
+    Empty,
+    Constant(bool),
+}
+
+#[derive(Debug)]
+enum Action {
+    Print,
+    Print0,
+    Delete,
+    /// `-exec command ;`, running the command for every entry with `{}` replaced by its path.
+    Exec(Vec<String>),
+    /// `-exec command {} +`, running the command once with the paths of every entry appended.
+    ExecBatch {
+        command: Vec<String>,
+        paths: Vec<String>,
+    },
+}
+
+/// A numeric argument, greater than `+N`, less than `-N` or exactly `N`.
+#[derive(Debug, Clone, Copy)]
+enum Comparison {
+    GreaterThan(i64),
+    LessThan(i64),
+    Exactly(i64),
+}
+
+impl Comparison {
+    fn parse(argument: &str) -> Option<(Self, &str)> {
+        let (constructor, rest): (fn(i64) -> Self, _) = match argument.split_at_checked(1) {
+            Some(("+", rest)) => (Comparison::GreaterThan, rest),
+            Some(("-", rest)) => (Comparison::LessThan, rest),
+            _ => (Comparison::Exactly, argument),
+        };
+        let digits = rest
+            .find(|char: char| !char.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (number, suffix) = rest.split_at(digits);
+        Some((constructor(number.parse().ok()?), suffix))
+    }
+
+    fn matches(self, value: i64) -> bool {
+        match self {
+            Comparison::GreaterThan(number) => value > number,
+            Comparison::LessThan(number) => value < number,
+            Comparison::Exactly(number) => value == number,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PermissionsMatch {
+    /// `-perm MODE`, the permission bits being exactly the mode.
+    Exactly,
+    /// `-perm -MODE`, every bit of the mode being set.
+    All,
+    /// `-perm /MODE`, any bit of the mode being set.
+    Any,
+}
+
+/// The options of the walk, given as primaries of the expression.
+#[derive(Debug)]
+struct Options {
+    max_depth: usize,
+    min_depth: usize,
+    /// Whether the entries of a directory are processed before it, implied by `-delete`.
+    depth_first: bool,
+}
+
+struct ExpressionParser<'a> {
+    fs: &'a FileSystem,
+    words: &'a [String],
+    position: usize,
+    options: Options,
+    has_action: bool,
+}
+
+impl<'a> ExpressionParser<'a> {
+    fn new(fs: &'a FileSystem, words: &'a [String]) -> Self {
+        Self {
+            fs,
+            words,
+            position: 0,
+            options: Options {
+                max_depth: usize::MAX,
+                min_depth: 0,
+                depth_first: false,
+            },
+            has_action: false,
+        }
+    }
+
+    /// Parses the whole expression, adding the implicit `-print`.
+    fn parse(mut self) -> Result<(Expression, Options), String> {
+        let expression = if self.words.is_empty() {
+            None
+        } else {
+            Some(self.parse_or()?)
+        };
+        if let Some(word) = self.peek() {
+            return Err(match word {
+                ")" => "invalid expression; you have too many ')'".to_string(),
+                word => format!("paths must precede expression: '{word}'"),
+            });
+        }
+        let expression = match expression {
+            Some(expression) if self.has_action => expression,
+            Some(expression) => Expression::And(
+                Box::new(expression),
+                Box::new(Expression::Action(Action::Print)),
+            ),
+            None => Expression::Action(Action::Print),
+        };
+        Ok((expression, self.options))
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.words.get(self.position).map(String::as_str)
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let word = self.peek()?;
+        self.position += 1;
+        Some(word)
+    }
+
+    /// Returns the argument of a primary.
+    fn argument(&mut self, primary: &str) -> Result<&'a str, String> {
+        self.next()
+            .ok_or_else(|| format!("missing argument to '{primary}'"))
+    }
+
+    fn parse_or(&mut self) -> Result<Expression, String> {
+        let mut expression = self.parse_and()?;
+        while let Some(operator @ ("-o" | "-or")) = self.peek() {
+            self.next();
+            if matches!(self.peek(), None | Some(")")) {
+                return Err(format!("expected an expression after '{operator}'"));
+            }
+            expression = Expression::Or(Box::new(expression), Box::new(self.parse_and()?));
+        }
+        Ok(expression)
+    }
+
+    fn parse_and(&mut self) -> Result<Expression, String> {
+        let mut expression = self.parse_unary()?;
+        loop {
+            match self.peek() {
+                None | Some("-o" | "-or" | ")") => return Ok(expression),
+                Some(operator @ ("-a" | "-and")) => {
+                    self.next();
+                    if matches!(self.peek(), None | Some(")" | "-o" | "-or")) {
+                        return Err(format!("expected an expression after '{operator}'"));
+                    }
+                }
+                Some(_) => {}
+            }
+            expression = Expression::And(Box::new(expression), Box::new(self.parse_unary()?));
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, String> {
+        match self.peek() {
+            Some(operator @ ("!" | "-not")) => {
+                self.next();
+                if matches!(self.peek(), None | Some(")" | "-o" | "-or" | "-a" | "-and")) {
+                    return Err(format!("expected an expression after '{operator}'"));
+                }
+                Ok(Expression::Not(Box::new(self.parse_unary()?)))
+            }
+            Some("(") => {
+                self.next();
+                if self.peek() == Some(")") {
+                    return Err(
+                        "invalid expression; empty parentheses are not allowed.".to_string()
+                    );
+                }
+                let expression = self.parse_or()?;
+                if self.next() != Some(")") {
+                    return Err(
+                        "invalid expression; I was expecting to find a ')' somewhere but \
+                                did not see one."
+                            .to_string(),
+                    );
+                }
+                Ok(expression)
+            }
+            Some(operator @ ("-o" | "-or" | "-a" | "-and")) => Err(format!(
+                "invalid expression; you have used a binary operator '{operator}' with nothing \
+                 before it."
+            )),
+            _ => self.parse_primary(),
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, String> {
+        let Some(primary) = self.next() else {
+            return Err("invalid expression".to_string());
+        };
+        let invalid = |argument: &str| format!("invalid argument '{argument}' to '{primary}'");
+        let test = match primary {
+            "-name" | "-iname" => Test::Name {
+                pattern: self.argument(primary)?.to_string(),
+                ignore_case: primary == "-iname",
+            },
+            "-path" | "-wholename" | "-ipath" => Test::Path {
+                pattern: self.argument(primary)?.to_string(),
+                ignore_case: primary == "-ipath",
+            },
+            "-type" => {
+                let argument = self.argument(primary)?;
+                let types = argument.split(',').collect::<Vec<_>>();
+                if !types
+                    .iter()
+                    .all(|kind| matches!(*kind, "f" | "d" | "l" | "b" | "c" | "p" | "s"))
+                {
+                    return Err(format!("Unknown argument to -type: {argument}"));
+                }
+                Test::Type(
+                    types
+                        .iter()
+                        .filter_map(|kind| kind.chars().next())
+                        .collect(),
+                )
+            }
+            "-size" => {
+                let argument = self.argument(primary)?;
+                let (comparison, unit) =
+                    Comparison::parse(argument).ok_or_else(|| invalid(argument))?;
+                let unit = match unit {
+                    "" | "b" => 512,
+                    "c" => 1,
+                    "w" => 2,
+                    "k" => 1 << 10,
+                    "M" => 1 << 20,
+                    "G" => 1 << 30,
+                    _ => return Err(invalid(argument)),
+                };
+                Test::Size(comparison, unit)
+            }
+            "-mtime" | "-mmin" => {
+                let argument = self.argument(primary)?;
+                let comparison = match Comparison::parse(argument) {
+                    Some((comparison, "")) => comparison,
+                    _ => return Err(invalid(argument)),
+                };
+                if primary == "-mtime" {
+                    Test::ModifiedDays(comparison)
+                } else {
+                    Test::ModifiedMinutes(comparison)
+                }
+            }
+            "-user" => {
+                let argument = self.argument(primary)?;
+                let user = self
+                    .fs
+                    .find_user_by_name(argument)
+                    .map(|user| user.id)
+                    .or_else(|| argument.parse().ok())
+                    .ok_or_else(|| format!("'{argument}' is not the name of a known user"))?;
+                Test::User(user)
+            }
+            "-group" => {
+                let argument = self.argument(primary)?;
+                let group = self
+                    .fs
+                    .find_group_by_name(argument)
+                    .map(|group| group.id)
+                    .or_else(|| argument.parse().ok())
+                    .ok_or_else(|| format!("'{argument}' is not the name of an existing group"))?;
+                Test::Group(group)
+            }
+            "-perm" => {
+                let argument = self.argument(primary)?;
+                let (kind, mode) = match argument.split_at_checked(1) {
+                    Some(("-", mode)) => (PermissionsMatch::All, mode),
+                    Some(("/", mode)) => (PermissionsMatch::Any, mode),
+                    _ => (PermissionsMatch::Exactly, argument),
+                };
+                let mode = ModeChange::parse(mode).ok_or_else(|| invalid(argument))?;
+                Test::Permissions(kind, mode.apply(0, false, 0))
+            }
+            "-empty" => Test::Empty,
+            "-true" => Test::Constant(true),
+            "-false" => Test::Constant(false),
+            "-maxdepth" | "-mindepth" => {
+                let argument = self.argument(primary)?;
+                let depth = argument.parse().map_err(|_| invalid(argument))?;
+                if primary == "-maxdepth" {
+                    self.options.max_depth = depth;
+                } else {
+                    self.options.min_depth = depth;
+                }
+                Test::Constant(true)
+            }
+            "-depth" => {
+                self.options.depth_first = true;
+                Test::Constant(true)
+            }
+            "-print" | "-print0" | "-delete" | "-exec" => {
+                self.has_action = true;
+                return Ok(Expression::Action(self.parse_action(primary)?));
+            }
+            primary if primary.starts_with('-') => {
+                return Err(format!("unknown predicate '{primary}'"));
+            }
+            word => return Err(format!("paths must precede expression: '{word}'")),
+        };
+        Ok(Expression::Test(test))
+    }
+
+    fn parse_action(&mut self, primary: &str) -> Result<Action, String> {
+        let action = match primary {
+            "-print" => Action::Print,
+            "-print0" => Action::Print0,
+            "-delete" => {
+                self.options.depth_first = true;
+                Action::Delete
+            }
+            _ => {
+                let mut command = Vec::new();
+                loop {
+                    match self.next() {
+                        Some(";") if !command.is_empty() => return Ok(Action::Exec(command)),
+                        Some("+") if command.last().is_some_and(|word| word == "{}") => {
+                            command.pop();
+                            if command.is_empty() {
+                                break;
+                            }
+                            return Ok(Action::ExecBatch {
+                                command,
+                                paths: Vec::new(),
+                            });
+                        }
+                        Some(word) => command.push(word.to_string()),
+                        None => break,
+                    }
+                }
+                return Err(format!("missing argument to '{primary}'"));
+            }
+        };
+        Ok(action)
+    }
+}
+
+/// What the expression is evaluated on, read from the inode of an entry.
+struct Entry {
+    path: String,
+    inode_type: InodeType,
+    size: u64,
+    modified_at: DateTime<Utc>,
+    owner: UserId,
+    group: GroupId,
+    mode: u32,
+    empty: bool,
+    now: DateTime<Utc>,
+}
+
+impl Entry {
+    /// Returns the last component of the path, ignoring trailing slashes.
+    fn name(&self) -> &str {
+        let path = self.path.trim_end_matches('/');
+        if path.is_empty() {
+            return "/";
+        }
+        path.rsplit('/').next().unwrap_or(path)
+    }
+}
+
+struct Walk {
+    options: Options,
+    output: String,
+    /// Whether an error was reported, making the exit status `1`.
+    failed: bool,
+}
+
+impl Walk {
+    /// Evaluates the expression on an entry and, for a directory, on the entries below it.
+    fn visit(
+        &mut self,
+        shell: &mut Shell,
+        expression: &mut Expression,
+        path: &str,
+        depth: usize,
+    ) -> Result<(), ShellError> {
+        let entry = match self.entry(shell, path) {
+            Ok(entry) => entry,
+            Err(error) => {
+                let message = error_message(error)?;
+                self.report(format!("find: '{path}': {message}"));
+                return Ok(());
+            }
+        };
+        let evaluated = depth >= self.options.min_depth;
+        if evaluated && !self.options.depth_first {
+            self.evaluate(shell, expression, &entry)?;
+        }
+
+        if entry.inode_type == InodeType::Directory && depth < self.options.max_depth {
+            let children = {
+                let fs = shell.fs.lock();
+                shell.current_session.list_directory(&fs, Path::new(path))
+            };
+            match children {
+                Ok(children) => {
+                    for child in children {
+                        let child = if path.ends_with('/') {
+                            format!("{path}{child}")
+                        } else {
+                            format!("{path}/{child}")
+                        };
+                        self.visit(shell, expression, &child, depth + 1)?;
+                    }
+                }
+                Err(error) => {
+                    let message = error_message(error)?;
+                    self.report(format!("find: '{path}': {message}"));
+                }
+            }
+        }
+
+        if evaluated && self.options.depth_first {
+            // The entries below were processed, and maybe deleted, since the entry was read.
+            let entry = match self.entry(shell, path) {
+                Ok(entry) => entry,
+                Err(_) => return Ok(()),
+            };
+            self.evaluate(shell, expression, &entry)?;
+        }
+        Ok(())
+    }
+
+    fn entry(&self, shell: &Shell, path: &str) -> Result<Entry, ShellError> {
+        let fs = shell.fs.lock();
+        let inode = shell.current_session.lookup_inode(&fs, Path::new(path))?;
+        let metadata = &inode.metadata;
+        Ok(Entry {
+            path: path.to_string(),
+            inode_type: inode.inode_type(),
+            size: inode.size(),
+            modified_at: metadata.modified_at,
+            owner: metadata.owner,
+            group: metadata.group,
+            mode: metadata.permissions.mode(),
+            empty: match &inode.content {
+                InodeContent::Directory(directory) => directory.is_empty(),
+                content => content.size() == 0,
+            },
+            now: fs.now(),
+        })
+    }
+
+    fn evaluate(
+        &mut self,
+        shell: &mut Shell,
+        expression: &mut Expression,
+        entry: &Entry,
+    ) -> Result<bool, ShellError> {
+        Ok(match expression {
+            Expression::Not(expression) => !self.evaluate(shell, expression, entry)?,
+            Expression::And(left, right) => {
+                self.evaluate(shell, left, entry)? && self.evaluate(shell, right, entry)?
+            }
+            Expression::Or(left, right) => {
+                self.evaluate(shell, left, entry)? || self.evaluate(shell, right, entry)?
+            }
+            Expression::Test(test) => test_entry(test, entry),
+            Expression::Action(action) => self.act(shell, action, entry)?,
+        })
+    }
+
+    fn act(
+        &mut self,
+        shell: &mut Shell,
+        action: &mut Action,
+        entry: &Entry,
+    ) -> Result<bool, ShellError> {
+        match action {
+            Action::Print => {
+                self.output.push_str(&entry.path);
+                self.output.push('\n');
+            }
+            Action::Print0 => {
+                self.output.push_str(&entry.path);
+                self.output.push('\0');
+            }
+            Action::Delete => return self.delete(shell, entry),
+            Action::Exec(command) => {
+                let command = command
+                    .iter()
+                    .map(|word| word.replace("{}", &entry.path))
+                    .collect();
+                return self.run(shell, command);
+            }
+            Action::ExecBatch { paths, .. } => paths.push(entry.path.clone()),
+        }
+        Ok(true)
+    }
+
+    fn delete(&mut self, shell: &mut Shell, entry: &Entry) -> Result<bool, ShellError> {
+        // The starting point `.` is never deleted.
+        if matches!(entry.name(), "." | "..") {
+            return Ok(true);
+        }
+        if entry.inode_type == InodeType::Directory && !entry.empty {
+            self.report(format!(
+                "find: cannot delete '{}': Directory not empty",
+                entry.path
+            ));
+            return Ok(false);
+        }
+        let removed = shell
+            .current_session
+            .remove_file(&mut shell.fs.lock(), Path::new(&entry.path));
+        match removed {
+            Ok(()) => Ok(true),
+            Err(error) => {
+                let message = error_message(error)?;
+                self.report(format!("find: cannot delete '{}': {message}", entry.path));
+                Ok(false)
+            }
+        }
+    }
+
+    /// Runs a command of `-exec`, which is true if it succeeds.
+    fn run(&mut self, shell: &mut Shell, command: Vec<String>) -> Result<bool, ShellError> {
+        if let Some(output) = shell.execute_tokens(command)?.0 {
+            self.output.push_str(&output);
+            self.output.push('\n');
+        }
+        Ok(shell.exit_status() == 0)
+    }
+
+    /// Runs the commands of `-exec ... {} +` on the paths collected during the walk.
+    fn flush_batches(
+        &mut self,
+        shell: &mut Shell,
+        expression: &mut Expression,
+    ) -> Result<(), ShellError> {
+        match expression {
+            Expression::Not(expression) => self.flush_batches(shell, expression),
+            Expression::And(left, right) | Expression::Or(left, right) => {
+                self.flush_batches(shell, left)?;
+                self.flush_batches(shell, right)
+            }
+            Expression::Action(Action::ExecBatch { command, paths }) if !paths.is_empty() => {
+                let mut command = command.clone();
+                command.append(paths);
+                if !self.run(shell, command)? {
+                    self.failed = true;
+                }
+                Ok(())
+            }
+            Expression::Test(_) | Expression::Action(_) => Ok(()),
+        }
+    }
+
+    fn report(&mut self, message: String) {
+        self.failed = true;
+        self.output.push_str(&message);
+        self.output.push('\n');
+    }
+}
+
+fn test_entry(test: &Test, entry: &Entry) -> bool {
+    match test {
+        Test::Name {
+            pattern,
+            ignore_case,
+        } => matches_pattern(pattern, entry.name(), *ignore_case),
+        Test::Path {
+            pattern,
+            ignore_case,
+        } => matches_pattern(pattern, &entry.path, *ignore_case),
+        Test::Type(types) => types.contains(&match entry.inode_type {
+            InodeType::File => 'f',
+            InodeType::Directory => 'd',
+            InodeType::Link => 'l',
+        }),
+        Test::Size(comparison, unit) => {
+            comparison.matches(i64::try_from(entry.size.div_ceil(*unit)).unwrap_or(i64::MAX))
+        }
+        Test::ModifiedDays(comparison) => {
+            let age = (entry.now - entry.modified_at).num_seconds();
+            comparison.matches(age.div_euclid(86_400))
+        }
+        Test::ModifiedMinutes(comparison) => {
+            let age = (entry.now - entry.modified_at).num_seconds();
+            comparison.matches(age.div_euclid(60))
+        }
+        Test::User(user) => entry.owner == *user,
+        Test::Group(group) => entry.group == *group,
+        Test::Permissions(kind, mode) => {
+            let bits = entry.mode & 0o7777;
+            match kind {
+                PermissionsMatch::Exactly => bits == *mode,
+                PermissionsMatch::All => bits & mode == *mode,
+                PermissionsMatch::Any => *mode == 0 || bits & mode != 0,
+            }
+        }
+        Test::Empty => entry.empty,
+        Test::Constant(value) => *value,
+    }
+}
+
+fn matches_pattern(pattern: &str, name: &str, ignore_case: bool) -> bool {
+    if ignore_case {
+        glob_matches(&pattern.to_lowercase(), &name.to_lowercase())
+    } else {
+        glob_matches(pattern, name)
+    }
+}
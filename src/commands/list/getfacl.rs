@@ -0,0 +1,120 @@
+//! The getfacl command, displaying the access control lists of files.
+
+use std::path::Path;
+
+use crate::{
+    FileSystem,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder},
+    },
+    errors::ShellError,
+    fs::permissions::{Permission, acl::Acl},
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct GetFileAclCommand;
+
+impl ExecutableCommand for GetFileAclCommand {
+    fn name(&self) -> &'static str {
+        "getfacl"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new().into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        _: Flags,
+        args: Option<Argument>,
+        shell: &mut crate::shell::Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let fs = shell.fs.lock();
+        let paths = string_arguments(args)?;
+        if paths.is_empty() {
+            return Ok(CommandOutput(Some(
+                "getfacl: no files specified".to_string(),
+            )));
+        }
+
+        let mut outputs = Vec::new();
+        for path in paths {
+            let inode = match shell.current_session.lookup_inode(&fs, Path::new(&path)) {
+                Ok(inode) => inode,
+                Err(error) => {
+                    outputs.push(format!("getfacl: {path}: {}", error_message(error)?));
+                    continue;
+                }
+            };
+            let metadata = inode.metadata.clone();
+
+            let mut lines = vec![
+                format!("# file: {}", path.trim_start_matches('/')),
+                format!("# owner: {}", user_name(&fs, metadata.owner)),
+                format!("# group: {}", group_name(&fs, metadata.group)),
+            ];
+            lines.extend(format_acl(&fs, &metadata.access_acl(), ""));
+            if let Some(default_acl) = &metadata.default_acl {
+                lines.extend(format_acl(&fs, default_acl, "default:"));
+            }
+            outputs.push(lines.join("\n"));
+        }
+
+        Ok(CommandOutput(Some(outputs.join("\n\n"))))
+    }
+}
+
+/// Formats the entries of an ACL, one per line, in the order used by `getfacl`.
+fn format_acl(fs: &FileSystem, acl: &Acl, prefix: &str) -> Vec<String> {
+    let entry = |tag: &str, qualifier: &str, permission: &Permission, masked: bool| {
+        let mut line = format!(
+            "{prefix}{tag}:{qualifier}:{}",
+            format_permission(permission)
+        );
+        let effective = acl.effective(permission);
+        if masked && effective != *permission {
+            line.push_str(&format!("\t#effective:{}", format_permission(&effective)));
+        }
+        line
+    };
+
+    let mut lines = vec![entry("user", "", &acl.user, false)];
+    for (user_id, permission) in &acl.users {
+        lines.push(entry("user", &user_name(fs, *user_id), permission, true));
+    }
+    lines.push(entry("group", "", &acl.group, true));
+    for (group_id, permission) in &acl.groups {
+        lines.push(entry("group", &group_name(fs, *group_id), permission, true));
+    }
+    if let Some(mask) = &acl.mask {
+        lines.push(entry("mask", "", mask, false));
+    }
+    lines.push(entry("other", "", &acl.other, false));
+    lines
+}
+
+fn format_permission(permission: &Permission) -> String {
+    format!(
+        "{}{}{}",
+        if permission.read { 'r' } else { '-' },
+        if permission.write { 'w' } else { '-' },
+        if permission.execute { 'x' } else { '-' }
+    )
+}
+
+fn user_name(fs: &FileSystem, user_id: u32) -> String {
+    fs.get_user(user_id)
+        .map_or_else(|| user_id.to_string(), |user| user.name.clone())
+}
+
+fn group_name(fs: &FileSystem, group_id: u32) -> String {
+    fs.get_group(group_id)
+        .map_or_else(|| group_id.to_string(), |group| group.name.clone())
+}
@@ -0,0 +1,143 @@
+//! The gpasswd command, administering the members of groups.
+
+use crate::{
+    UserId,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_flag},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        require_superuser,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct GroupPasswordCommand;
+
+impl ExecutableCommand for GroupPasswordCommand {
+    fn name(&self) -> &'static str {
+        "gpasswd"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "add",
+                Some('a'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "delete",
+                Some('d'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "members",
+                Some('M'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        if let Some(output) = require_superuser(shell, self.name()) {
+            return Ok(output);
+        }
+        let mut fs = shell.fs.lock();
+        let name = match args {
+            Some(Argument::Basic(BasicArgument::String(name))) => name,
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => {
+                return Ok(CommandOutput(Some(
+                    "Usage: gpasswd [option] GROUP".to_string(),
+                )));
+            }
+        };
+        let Some(group_id) = fs.find_group_by_name(&name).map(|group| group.id) else {
+            return Ok(CommandOutput(Some(format!(
+                "gpasswd: group '{name}' does not exist in /etc/group"
+            ))));
+        };
+
+        let find_user = |username: &str| -> Result<UserId, CommandOutput> {
+            fs.find_user_by_name(username)
+                .map(|user| user.id)
+                .ok_or_else(|| {
+                    CommandOutput(Some(format!("gpasswd: user '{username}' does not exist")))
+                })
+        };
+
+        if let Some(username) = string_flag(&flags, "add")? {
+            let user_id = match find_user(username) {
+                Ok(user_id) => user_id,
+                Err(output) => return Ok(output),
+            };
+            fs.update_user(user_id, |user| user.add_group(group_id))?;
+            return Ok(CommandOutput(Some(format!(
+                "Adding user {username} to group {name}"
+            ))));
+        }
+
+        if let Some(username) = string_flag(&flags, "delete")? {
+            let user_id = match find_user(username) {
+                Ok(user_id) => user_id,
+                Err(output) => return Ok(output),
+            };
+            // Membership through the primary group is not listed in the group, so it stays.
+            let is_member = fs.update_user(user_id, |user| {
+                let is_member = user.supplementary_groups().contains(&group_id);
+                if is_member {
+                    user.remove_group(group_id);
+                }
+                is_member
+            })?;
+            if !is_member {
+                return Ok(CommandOutput(Some(format!(
+                    "gpasswd: user '{username}' is not a member of '{name}'"
+                ))));
+            }
+            return Ok(CommandOutput(Some(format!(
+                "Removing user {username} from group {name}"
+            ))));
+        }
+
+        if let Some(members) = string_flag(&flags, "members")? {
+            let mut member_ids = Vec::new();
+            for username in members.split(',').filter(|username| !username.is_empty()) {
+                match find_user(username) {
+                    Ok(user_id) => member_ids.push(user_id),
+                    Err(output) => return Ok(output),
+                }
+            }
+            let user_ids: Vec<UserId> = fs.users().map(|user| user.id).collect();
+            for user_id in user_ids {
+                let is_member = member_ids.contains(&user_id);
+                fs.update_user(user_id, |user| {
+                    if is_member {
+                        user.add_group(group_id);
+                    } else if user.primary_group() != Some(group_id) {
+                        user.remove_group(group_id);
+                    }
+                })?;
+            }
+            return Ok(CommandOutput(None));
+        }
+
+        Ok(CommandOutput(Some(
+            "Usage: gpasswd [option] GROUP".to_string(),
+        )))
+    }
+}
@@ -0,0 +1,883 @@
+//! The grep command, printing the lines matching a pattern.
+//!
+//! Patterns are POSIX basic regular expressions by default, with the GNU extensions (`\+`, `\?`,
+//! `\|`, `\<`, `\>`, `\w`...), or extended ones with `-E`. They are translated to the syntax of the
+//! `regex` crate, which doesn't support back-references.
+
+use std::path::Path;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    InodeContent,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::ShellError,
+    fs::inode::content::InodeType,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct GrepCommand;
+
+/// The exit status when a line was selected.
+const SELECTED: i32 = 0;
+
+/// The exit status when no line was selected.
+const NOT_SELECTED: i32 = 1;
+
+/// The exit status when an error occurred.
+const TROUBLE: i32 = 2;
+
+/// The name printed for the lines read from the standard input.
+const STDIN_NAME: &str = "(standard input)";
+
+impl ExecutableCommand for GrepCommand {
+    fn name(&self) -> &'static str {
+        "grep"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        let flags = [
+            ("extended-regexp", Some('E')),
+            ("fixed-strings", Some('F')),
+            ("basic-regexp", Some('G')),
+            ("ignore-case", Some('i')),
+            ("invert-match", Some('v')),
+            ("word-regexp", Some('w')),
+            ("line-regexp", Some('x')),
+            ("count", Some('c')),
+            ("files-with-matches", Some('l')),
+            ("files-without-match", Some('L')),
+            ("only-matching", Some('o')),
+            ("quiet", Some('q')),
+            ("no-messages", Some('s')),
+            ("line-number", Some('n')),
+            ("with-filename", Some('H')),
+            ("no-filename", Some('h')),
+            ("recursive", Some('r')),
+            ("dereference-recursive", Some('R')),
+        ];
+        let options = [
+            ("regexp", Some('e'), BasicArgumentKind::String),
+            ("after-context", Some('A'), BasicArgumentKind::Integer),
+            ("before-context", Some('B'), BasicArgumentKind::Integer),
+            ("context", Some('C'), BasicArgumentKind::Integer),
+            ("include", None, BasicArgumentKind::String),
+            ("exclude", None, BasicArgumentKind::String),
+        ];
+        let mut builder = FlagDefinitionBuilder::new();
+        for (name, short_hand) in flags {
+            builder = builder.with_flag(FlagSpecification::new(
+                name,
+                short_hand,
+                false,
+                ArgumentKind::Flag,
+            ));
+        }
+        for (name, short_hand, kind) in options {
+            builder = builder.with_flag(FlagSpecification::new(
+                name,
+                short_hand,
+                false,
+                ArgumentKind::Basic(kind),
+            ));
+        }
+        builder.into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Searches the files given, the directories below them with `-r`, or the standard input.
+    ///
+    /// The exit status is `0` if a line was selected, `1` if none was, and `2` if an error
+    /// occurred. With `-L`, a file being listed counts as a selection.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?;
+        // Every `-e` gives a pattern, the first operand being the pattern otherwise.
+        let mut patterns = flags
+            .flag_all("regexp")
+            .iter()
+            .filter_map(|pattern| match pattern {
+                Argument::Basic(BasicArgument::String(pattern)) => Some(pattern.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if patterns.is_empty() {
+            if operands.is_empty() {
+                shell.set_exit_status(TROUBLE);
+                return Ok(CommandOutput(Some(
+                    "Usage: grep [OPTION]... PATTERNS [FILE]...".to_string(),
+                )));
+            }
+            patterns.push(operands.remove(0));
+        }
+
+        let mut context = [None, None];
+        for (index, name) in ["after-context", "before-context"].into_iter().enumerate() {
+            for name in [name, "context"] {
+                if let Some(Argument::Basic(BasicArgument::Integer(lines))) = flags.flag(name) {
+                    let Ok(lines) = usize::try_from(*lines) else {
+                        shell.set_exit_status(TROUBLE);
+                        return Ok(CommandOutput(Some(format!(
+                            "grep: {lines}: invalid context length argument"
+                        ))));
+                    };
+                    context[index].get_or_insert(lines);
+                }
+            }
+        }
+        let [after_context, before_context] = context.map(Option::unwrap_or_default);
+
+        let syntax = if flags.flag("fixed-strings").is_some() {
+            Syntax::Fixed
+        } else if flags.flag("extended-regexp").is_some() {
+            Syntax::Extended
+        } else {
+            Syntax::Basic
+        };
+        let regex = match build_alternatives(
+            &patterns.iter().map(String::as_str).collect::<Vec<_>>(),
+            syntax,
+            flags.flag("ignore-case").is_some(),
+            flags.flag("word-regexp").is_some(),
+            flags.flag("line-regexp").is_some(),
+        ) {
+            Ok(regex) => regex,
+            Err(message) => {
+                shell.set_exit_status(TROUBLE);
+                return Ok(CommandOutput(Some(format!("grep: {message}"))));
+            }
+        };
+
+        let recursion = if flags.flag("dereference-recursive").is_some() {
+            Some(Recursion::FollowLinks)
+        } else if flags.flag("recursive").is_some() {
+            Some(Recursion::SkipLinks)
+        } else {
+            None
+        };
+        let globs = |name| {
+            flags
+                .flag_all(name)
+                .iter()
+                .filter_map(|glob| match glob {
+                    Argument::Basic(BasicArgument::String(glob)) => Some(glob.clone()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let with_filename = if flags.flag("no-filename").is_some() {
+            Some(false)
+        } else if flags.flag("with-filename").is_some() || operands.len() > 1 {
+            Some(true)
+        } else {
+            // A single operand is only prefixed if it is a directory searched recursively.
+            None
+        };
+
+        let mut grep = Grep {
+            regex,
+            invert: flags.flag("invert-match").is_some(),
+            output: if flags.flag("quiet").is_some() {
+                OutputMode::Quiet
+            } else if flags.flag("files-with-matches").is_some() {
+                OutputMode::FilesWithMatches
+            } else if flags.flag("files-without-match").is_some() {
+                OutputMode::FilesWithoutMatch
+            } else if flags.flag("count").is_some() {
+                OutputMode::Count
+            } else if flags.flag("only-matching").is_some() {
+                OutputMode::OnlyMatching
+            } else {
+                OutputMode::Lines
+            },
+            line_number: flags.flag("line-number").is_some(),
+            with_filename,
+            before_context,
+            after_context,
+            recursion,
+            include: globs("include"),
+            exclude: globs("exclude"),
+            messages: flags.flag("no-messages").is_none(),
+            lines: Vec::new(),
+            printed_group: false,
+            selected: false,
+            error: false,
+        };
+
+        if operands.is_empty() && recursion.is_some() {
+            // Searching the working directory recursively prints names relative to it.
+            grep.with_filename.get_or_insert(true);
+            grep.search_directory(shell, ".", "")?;
+        } else if operands.is_empty() {
+            let lines = shell.read_lines();
+            grep.search_lines(STDIN_NAME, &lines);
+        }
+        for operand in operands {
+            if operand == "-" {
+                let lines = shell.read_lines();
+                grep.search_lines(STDIN_NAME, &lines);
+            } else {
+                grep.search_path(shell, &operand, true)?;
+            }
+        }
+
+        // A match found quietly is a success even if some files couldn't be read.
+        let status = if grep.selected && (!grep.error || matches!(grep.output, OutputMode::Quiet)) {
+            SELECTED
+        } else if grep.error {
+            TROUBLE
+        } else {
+            NOT_SELECTED
+        };
+        shell.set_exit_status(status);
+        Ok(CommandOutput(
+            (!grep.lines.is_empty()).then(|| grep.lines.join("\n")),
+        ))
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum Syntax {
+    Basic,
+    Extended,
+    Fixed,
+}
+
+#[derive(Clone, Copy)]
+enum Recursion {
+    /// `-r`, skipping the symbolic links found in directories.
+    SkipLinks,
+    /// `-R`, following every symbolic link.
+    FollowLinks,
+}
+
+/// What is printed for the files searched.
+#[derive(Clone, Copy)]
+enum OutputMode {
+    Lines,
+    OnlyMatching,
+    Count,
+    FilesWithMatches,
+    FilesWithoutMatch,
+    Quiet,
+}
+
+struct Grep {
+    regex: Regex,
+    invert: bool,
+    output: OutputMode,
+    line_number: bool,
+    /// Whether lines are prefixed by the name of their file, `None` deciding for each operand.
+    with_filename: Option<bool>,
+    before_context: usize,
+    after_context: usize,
+    recursion: Option<Recursion>,
+    /// The globs of the names of the files searched, every file being searched if empty.
+    include: Vec<String>,
+    /// The globs of the names of the files skipped.
+    exclude: Vec<String>,
+    /// Whether the errors are printed, which `-s` disables.
+    messages: bool,
+    /// The lines of the output.
+    lines: Vec<String>,
+    /// Whether a group of lines was printed, to separate the next one with `--` when showing
+    /// context.
+    printed_group: bool,
+    selected: bool,
+    error: bool,
+}
+
+impl Grep {
+    /// Searches a file, or the entries of a directory when searching recursively.
+    ///
+    /// `operand` tells that the path was given on the command line, which decides how symbolic
+    /// links and file names are handled.
+    fn search_path(
+        &mut self,
+        shell: &mut Shell,
+        path: &str,
+        operand: bool,
+    ) -> Result<(), ShellError> {
+        let inode_type = {
+            let fs = shell.fs.lock();
+            shell
+                .current_session
+                .lookup_inode(&fs, Path::new(path))
+                .map(|inode| inode.inode_type())
+        };
+        match inode_type {
+            Ok(InodeType::Directory) if self.recursion.is_some() => {
+                if operand && self.with_filename.is_none() {
+                    self.with_filename = Some(true);
+                }
+                let prefix = if path.ends_with('/') {
+                    path.to_string()
+                } else {
+                    format!("{path}/")
+                };
+                self.search_directory(shell, path, &prefix)
+            }
+            Ok(InodeType::Directory) => {
+                self.report(format!("grep: {path}: Is a directory"));
+                Ok(())
+            }
+            Ok(InodeType::Link)
+                if !operand && matches!(self.recursion, Some(Recursion::SkipLinks)) =>
+            {
+                Ok(())
+            }
+            Ok(InodeType::Link) => {
+                let target = {
+                    let fs = shell.fs.lock();
+                    match &shell
+                        .current_session
+                        .lookup_inode(&fs, Path::new(path))?
+                        .content
+                    {
+                        InodeContent::Link(link) => link.target_path.clone(),
+                        _ => return Err(ShellError::Internal("Expected a link".to_string())),
+                    }
+                };
+                let parent = Path::new(path).parent().unwrap_or(Path::new(""));
+                let target = parent.join(target).display().to_string();
+                self.search_path_as(shell, &target, path)
+            }
+            Ok(InodeType::File) => self.search_path_as(shell, path, path),
+            Err(error) => {
+                let message = error_message(error)?;
+                self.report(format!("grep: {path}: {message}"));
+                Ok(())
+            }
+        }
+    }
+
+    /// Searches the file at `path`, naming it `name` in the output.
+    fn search_path_as(
+        &mut self,
+        shell: &mut Shell,
+        path: &str,
+        name: &str,
+    ) -> Result<(), ShellError> {
+        let file_name = name.rsplit('/').next().unwrap_or(name);
+        let included = self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|include| glob_matches(include, file_name));
+        if !included
+            || self
+                .exclude
+                .iter()
+                .any(|exclude| glob_matches(exclude, file_name))
+        {
+            return Ok(());
+        }
+        let content = shell
+            .current_session
+            .read_file(&mut shell.fs.lock(), Path::new(path));
+        match content {
+            Ok(content) => {
+                let content = content.strip_suffix('\n').unwrap_or(&content);
+                let lines = if content.is_empty() {
+                    Vec::new()
+                } else {
+                    content.split('\n').map(str::to_string).collect()
+                };
+                self.search_lines(name, &lines);
+            }
+            Err(error) => {
+                let message = error_message(error)?;
+                self.report(format!("grep: {name}: {message}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Searches the entries of a directory, naming them after `prefix`.
+    fn search_directory(
+        &mut self,
+        shell: &mut Shell,
+        path: &str,
+        prefix: &str,
+    ) -> Result<(), ShellError> {
+        let entries = {
+            let fs = shell.fs.lock();
+            shell.current_session.list_directory(&fs, Path::new(path))
+        };
+        match entries {
+            Ok(entries) => {
+                for entry in entries {
+                    self.search_path(shell, &format!("{prefix}{entry}"), false)?;
+                }
+            }
+            Err(error) => {
+                let message = error_message(error)?;
+                self.report(format!("grep: {path}: {message}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Searches the lines of a file, printing what the output mode asks for.
+    fn search_lines(&mut self, name: &str, lines: &[String]) {
+        let prefix = if self.with_filename.unwrap_or(false) {
+            Some(name)
+        } else {
+            None
+        };
+        let selected = lines
+            .iter()
+            .map(|line| self.regex.is_match(line) != self.invert)
+            .collect::<Vec<_>>();
+        let count = selected.iter().filter(|selected| **selected).count();
+        // `-L` succeeds when it lists a file, like GNU grep since 3.5.
+        self.selected |= match self.output {
+            OutputMode::FilesWithoutMatch => count == 0,
+            _ => count > 0,
+        };
+
+        match self.output {
+            OutputMode::Quiet => {}
+            OutputMode::FilesWithMatches if count > 0 => self.lines.push(name.to_string()),
+            OutputMode::FilesWithoutMatch if count == 0 => self.lines.push(name.to_string()),
+            OutputMode::FilesWithMatches | OutputMode::FilesWithoutMatch => {}
+            OutputMode::Count => self.lines.push(match prefix {
+                Some(prefix) => format!("{prefix}:{count}"),
+                None => count.to_string(),
+            }),
+            OutputMode::OnlyMatching => {
+                if self.invert {
+                    return;
+                }
+                for (index, line) in lines.iter().enumerate() {
+                    for found in self.regex.find_iter(line) {
+                        if !found.is_empty() {
+                            let line = self.format_line(prefix, index, ':', found.as_str());
+                            self.lines.push(line);
+                        }
+                    }
+                }
+            }
+            OutputMode::Lines => self.print_with_context(prefix, lines, &selected),
+        }
+    }
+
+    /// Prints the selected lines with the lines of context around them, separating the groups
+    /// of lines that are not contiguous with `--`.
+    fn print_with_context(&mut self, prefix: Option<&str>, lines: &[String], selected: &[bool]) {
+        let context = self.before_context > 0 || self.after_context > 0;
+        let mut next_unprinted = 0;
+        let mut after_left = 0;
+        for (index, line) in lines.iter().enumerate() {
+            if selected[index] {
+                let start = index
+                    .saturating_sub(self.before_context)
+                    .max(next_unprinted);
+                if context && self.printed_group && (start > next_unprinted || next_unprinted == 0)
+                {
+                    self.lines.push("--".to_string());
+                }
+                for (offset, line) in lines[start..index].iter().enumerate() {
+                    let line = self.format_line(prefix, start + offset, '-', line);
+                    self.lines.push(line);
+                }
+                let line = self.format_line(prefix, index, ':', line);
+                self.lines.push(line);
+                self.printed_group = true;
+                next_unprinted = index + 1;
+                after_left = self.after_context;
+            } else if after_left > 0 {
+                let line = self.format_line(prefix, index, '-', line);
+                self.lines.push(line);
+                next_unprinted = index + 1;
+                after_left -= 1;
+            }
+        }
+    }
+
+    /// Formats a line of output, `separator` being `:` for selected lines and `-` for context.
+    fn format_line(
+        &self,
+        prefix: Option<&str>,
+        index: usize,
+        separator: char,
+        line: &str,
+    ) -> String {
+        let mut output = String::new();
+        if let Some(prefix) = prefix {
+            output.push_str(prefix);
+            output.push(separator);
+        }
+        if self.line_number {
+            output.push_str(&(index + 1).to_string());
+            output.push(separator);
+        }
+        output.push_str(line);
+        output
+    }
+
+    fn report(&mut self, message: String) {
+        self.error = true;
+        if self.messages {
+            self.lines.push(message);
+        }
+    }
+}
+
+/// Compiles a pattern, returning the message to show if it is invalid.
+pub(crate) fn build_regex(
+    pattern: &str,
+    syntax: Syntax,
+    ignore_case: bool,
+    word: bool,
+    line: bool,
+) -> Result<Regex, String> {
+    build_alternatives(&[pattern], syntax, ignore_case, word, line)
+}
+
+/// Builds a regular expression matching any of the patterns, like the repeated `-e` of grep.
+fn build_alternatives(
+    patterns: &[&str],
+    syntax: Syntax,
+    ignore_case: bool,
+    word: bool,
+    line: bool,
+) -> Result<Regex, String> {
+    let mut alternatives = Vec::new();
+    for pattern in patterns {
+        let mut regex = match syntax {
+            Syntax::Fixed => regex::escape(pattern),
+            Syntax::Basic => translate(pattern, false)?,
+            Syntax::Extended => translate(pattern, true)?,
+        };
+        if line {
+            regex = format!("^(?:{regex})$");
+        } else if word {
+            regex = format!(r"\b{{start-half}}(?:{regex})\b{{end-half}}");
+        }
+        alternatives.push(regex);
+    }
+    let regex = match alternatives.as_slice() {
+        [regex] => regex.clone(),
+        _ => alternatives
+            .iter()
+            .map(|regex| format!("(?:{regex})"))
+            .collect::<Vec<_>>()
+            .join("|"),
+    };
+    RegexBuilder::new(&regex)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(|_| "Invalid regular expression".to_string())
+}
+
+/// Translates a POSIX regular expression to the syntax of the `regex` crate.
+///
+/// In basic expressions, `(`, `)`, `{`, `}`, `|`, `+` and `?` are literal unless escaped, while
+/// they are operators in extended ones. In both, `*` is literal at the start of an expression.
+fn translate(pattern: &str, extended: bool) -> Result<String, String> {
+    let chars = pattern.chars().collect::<Vec<_>>();
+    let mut output = String::new();
+    let mut index = 0;
+    // Whether the next character starts an expression, where `*` is literal and `^` an anchor.
+    let mut at_start = true;
+    while index < chars.len() {
+        let char = chars[index];
+        index += 1;
+        let starts_expression = match char {
+            '\\' => {
+                let Some(&escaped) = chars.get(index) else {
+                    return Err("Trailing backslash".to_string());
+                };
+                index += 1;
+                match escaped {
+                    '(' | '|' if !extended => {
+                        output.push(if escaped == '(' { '(' } else { '|' });
+                        true
+                    }
+                    ')' | '+' | '?' if !extended => {
+                        output.push(escaped);
+                        false
+                    }
+                    '{' if !extended => {
+                        index = translate_interval(&chars, index, extended, &mut output)?;
+                        false
+                    }
+                    '1'..='9' => return Err("back-references are not supported".to_string()),
+                    '<' | '>' | 'b' | 'B' | 'w' | 'W' | 's' | 'S' => {
+                        output.push('\\');
+                        output.push(escaped);
+                        false
+                    }
+                    escaped => {
+                        output.push_str(&regex::escape(&escaped.to_string()));
+                        false
+                    }
+                }
+            }
+            '[' => {
+                index = translate_bracket(&chars, index, &mut output)?;
+                false
+            }
+            '*' if at_start => {
+                output.push_str(r"\*");
+                false
+            }
+            '^' if extended || at_start => {
+                output.push('^');
+                at_start
+            }
+            '$' if extended || ends_expression(&chars, index) => {
+                output.push('$');
+                false
+            }
+            '(' | '|' if extended => {
+                output.push(char);
+                true
+            }
+            '{' if extended && is_interval(&chars, index) => {
+                index = translate_interval(&chars, index, extended, &mut output)?;
+                false
+            }
+            // What is left of these is literal, like a brace not starting an interval.
+            '(' | ')' | '{' | '}' | '|' | '+' | '?' | '^' | '$' if !extended || char == '{' => {
+                output.push('\\');
+                output.push(char);
+                false
+            }
+            char => {
+                output.push(char);
+                false
+            }
+        };
+        at_start = starts_expression;
+    }
+    Ok(output)
+}
+
+/// Checks if a `$` at `index - 1` of a basic expression ends it, making it an anchor.
+fn ends_expression(chars: &[char], index: usize) -> bool {
+    matches!(chars.get(index..), Some([] | ['\\', ')' | '|', ..]))
+}
+
+/// Checks if the `{` at `index - 1` of an extended expression starts an interval, like `{2,3}`,
+/// instead of being literal.
+fn is_interval(chars: &[char], index: usize) -> bool {
+    let bounds = chars[index..]
+        .iter()
+        .take_while(|char| **char != '}')
+        .collect::<String>();
+    index + bounds.len() < chars.len()
+        && !bounds.is_empty()
+        && bounds
+            .chars()
+            .all(|char| char.is_ascii_digit() || char == ',')
+        && bounds.matches(',').count() <= 1
+}
+
+/// Translates the bounds of an interval following its opening brace at `index`, returning the
+/// index following its closing brace.
+fn translate_interval(
+    chars: &[char],
+    mut index: usize,
+    extended: bool,
+    output: &mut String,
+) -> Result<usize, String> {
+    let mut bounds = String::new();
+    loop {
+        match (chars.get(index), chars.get(index + 1)) {
+            (Some('}'), _) if extended => {
+                index += 1;
+                break;
+            }
+            (Some('\\'), Some('}')) if !extended => {
+                index += 2;
+                break;
+            }
+            (Some(char @ ('0'..='9' | ',')), _) => {
+                bounds.push(*char);
+                index += 1;
+            }
+            (Some(_), _) => return Err("Invalid content of \\{\\}".to_string()),
+            (None, _) => return Err("Unmatched \\{".to_string()),
+        }
+    }
+    if bounds.starts_with(',') {
+        bounds.insert(0, '0');
+    }
+    output.push('{');
+    output.push_str(&bounds);
+    output.push('}');
+    Ok(index)
+}
+
+/// Translates a bracket expression following its opening bracket at `index`, returning the index
+/// following its closing bracket.
+///
+/// Backslashes are literal in POSIX bracket expressions, and the characters starting nested
+/// classes or set operations in the `regex` crate are escaped.
+fn translate_bracket(
+    chars: &[char],
+    mut index: usize,
+    output: &mut String,
+) -> Result<usize, String> {
+    const UNMATCHED: &str = "Unmatched [, [^, [:, [., or [=";
+    output.push('[');
+    if chars.get(index) == Some(&'^') {
+        output.push('^');
+        index += 1;
+    }
+    if chars.get(index) == Some(&']') {
+        output.push_str(r"\]");
+        index += 1;
+    }
+    loop {
+        let Some(&char) = chars.get(index) else {
+            return Err(UNMATCHED.to_string());
+        };
+        index += 1;
+        match char {
+            ']' => break,
+            '[' if matches!(chars.get(index), Some(':' | '=' | '.')) => {
+                let delimiter = chars[index];
+                let start = index + 1;
+                let end = chars[start..]
+                    .windows(2)
+                    .position(|window| window == [delimiter, ']'])
+                    .ok_or_else(|| UNMATCHED.to_string())?
+                    + start;
+                let name = chars[start..end].iter().collect::<String>();
+                if delimiter == ':' {
+                    output.push_str(&format!("[:{name}:]"));
+                } else {
+                    output.push_str(&regex::escape(&name));
+                }
+                index = end + 2;
+            }
+            '\\' | '[' | '&' | '~' => {
+                output.push('\\');
+                output.push(char);
+            }
+            char => output.push(char),
+        }
+    }
+    output.push(']');
+    Ok(index)
+}
+
+/// Checks if a name matches a shell wildcard pattern, with `*`, `?` and bracket expressions.
+pub(crate) fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    glob_matches_from(&pattern, &name)
+}
+
+fn glob_matches_from(pattern: &[char], name: &[char]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some(('*', rest)) => (0..=name.len()).any(|skip| glob_matches_from(rest, &name[skip..])),
+        Some(('?', rest)) => !name.is_empty() && glob_matches_from(rest, &name[1..]),
+        Some(('[', rest)) => match (name.split_first(), match_bracket(rest, name.first())) {
+            (Some((_, name)), Some((true, rest))) => glob_matches_from(rest, name),
+            (_, Some((false, _))) | (None, _) => false,
+            // An unterminated bracket is a literal `[`.
+            (Some((first, name)), None) => *first == '[' && glob_matches_from(rest, name),
+        },
+        Some(('\\', [escaped, rest @ ..])) | Some((escaped, rest)) => {
+            name.first() == Some(escaped) && glob_matches_from(rest, &name[1..])
+        }
+    }
+}
+
+/// Matches a character against the bracket expression following a `[`, returning whether it
+/// matched and the rest of the pattern, or `None` if the bracket is not closed.
+fn match_bracket<'a>(pattern: &'a [char], char: Option<&char>) -> Option<(bool, &'a [char])> {
+    let (negated, mut pattern) = match pattern.split_first() {
+        Some(('!' | '^', rest)) => (true, rest),
+        _ => (false, pattern),
+    };
+    let mut matched = false;
+    let mut first = true;
+    loop {
+        match pattern {
+            [']', rest @ ..] if !first => return Some((matched != negated, rest)),
+            [start, '-', end, rest @ ..] if *end != ']' => {
+                matched |= char.is_some_and(|char| (*start..=*end).contains(char));
+                pattern = rest;
+            }
+            [literal, rest @ ..] => {
+                matched |= char == Some(literal);
+                pattern = rest;
+            }
+            [] => return None,
+        }
+        first = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_basic() {
+        assert_eq!(translate(r"a\(b\|c\)*", false), Ok(r"a(b|c)*".to_string()));
+        assert_eq!(
+            translate("a(b|c)+?", false),
+            Ok(r"a\(b\|c\)\+\?".to_string())
+        );
+        assert_eq!(translate(r"*a\{2,\}", false), Ok(r"\*a{2,}".to_string()));
+        assert_eq!(translate(r"a^b$c$", false), Ok(r"a\^b\$c$".to_string()));
+        assert_eq!(translate(r"^*\{,3\}", false), Ok(r"^\*{0,3}".to_string()));
+        assert_eq!(
+            translate(r"a\1", false),
+            Err("back-references are not supported".to_string())
+        );
+        assert_eq!(
+            translate("a\\", false),
+            Err("Trailing backslash".to_string())
+        );
+    }
+
+    #[test]
+    fn test_translate_extended() {
+        assert_eq!(translate("(a|b)+c{2}", true), Ok("(a|b)+c{2}".to_string()));
+        assert_eq!(translate(r"\(a\)x{", true), Ok(r"\(a\)x\{".to_string()));
+    }
+
+    #[test]
+    fn test_translate_brackets() {
+        assert_eq!(translate(r"[]a\]", false), Ok(r"[\]a\\]".to_string()));
+        assert_eq!(
+            translate("[^[:digit:]x]", true),
+            Ok("[^[:digit:]x]".to_string())
+        );
+        assert_eq!(translate("[a&&b]", true), Ok(r"[a\&\&b]".to_string()));
+        assert!(translate("[abc", false).is_err());
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("*.log", "app.log"));
+        assert!(!glob_matches("*.log", "app.log.1"));
+        assert!(glob_matches("app-?.log", "app-1.log"));
+        assert!(glob_matches("[a-c]*", "beta"));
+        assert!(!glob_matches("[!a-c]*", "beta"));
+        assert!(glob_matches(r"\*", "*"));
+        assert!(glob_matches("[]x]", "]"));
+        assert!(glob_matches("[", "["));
+    }
+}
@@ -0,0 +1,76 @@
+//! The groupadd command, creating groups.
+
+use crate::{
+    AccountKind,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        is_valid_name, require_superuser,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct GroupAddCommand;
+
+impl ExecutableCommand for GroupAddCommand {
+    fn name(&self) -> &'static str {
+        "groupadd"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "system",
+                Some('r'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        if let Some(output) = require_superuser(shell, self.name()) {
+            return Ok(output);
+        }
+        let mut fs = shell.fs.lock();
+        let name = match args {
+            Some(Argument::Basic(BasicArgument::String(name))) => name,
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => {
+                return Ok(CommandOutput(Some(
+                    "Usage: groupadd [options] GROUP".to_string(),
+                )));
+            }
+        };
+        if !is_valid_name(&name) {
+            return Ok(CommandOutput(Some(format!(
+                "groupadd: '{name}' is not a valid group name"
+            ))));
+        }
+        if fs.find_group_by_name(&name).is_some() {
+            return Ok(CommandOutput(Some(format!(
+                "groupadd: group '{name}' already exists"
+            ))));
+        }
+
+        let kind = if flags.flag("system").is_some() {
+            AccountKind::System
+        } else {
+            AccountKind::Regular
+        };
+        fs.create_group(&name, kind)?;
+        Ok(CommandOutput(None))
+    }
+}
@@ -0,0 +1,67 @@
+//! The groupdel command, deleting groups.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        flags::{FlagDefinition, FlagDefinitionBuilder},
+        require_superuser,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct GroupDelCommand;
+
+impl ExecutableCommand for GroupDelCommand {
+    fn name(&self) -> &'static str {
+        "groupdel"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new().into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        _: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        if let Some(output) = require_superuser(shell, self.name()) {
+            return Ok(output);
+        }
+        let mut fs = shell.fs.lock();
+        let name = match args {
+            Some(Argument::Basic(BasicArgument::String(name))) => name,
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => {
+                return Ok(CommandOutput(Some(
+                    "Usage: groupdel [options] GROUP".to_string(),
+                )));
+            }
+        };
+        let Some(group_id) = fs.find_group_by_name(&name).map(|group| group.id) else {
+            return Ok(CommandOutput(Some(format!(
+                "groupdel: group '{name}' does not exist"
+            ))));
+        };
+        if let Some(user) = fs
+            .users()
+            .find(|user| user.primary_group() == Some(group_id))
+        {
+            return Ok(CommandOutput(Some(format!(
+                "groupdel: cannot remove the primary group of user '{}'",
+                user.name
+            ))));
+        }
+
+        fs.remove_group(group_id)?;
+        Ok(CommandOutput(None))
+    }
+}
@@ -0,0 +1,67 @@
+//! The groups command, printing the groups users are in.
+
+use crate::{
+    FileSystem, User,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        find_user,
+        flags::{FlagDefinition, FlagDefinitionBuilder},
+    },
+    errors::{SessionError, ShellError},
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct GroupsCommand;
+
+impl ExecutableCommand for GroupsCommand {
+    fn name(&self) -> &'static str {
+        "groups"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new().into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        _: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let fs = shell.fs.lock();
+        let usernames = string_arguments(args)?;
+        if usernames.is_empty() {
+            let user = fs
+                .get_user(shell.current_session.current_user())
+                .ok_or(ShellError::Session(SessionError::UserNotFound))?;
+            return Ok(CommandOutput(Some(group_names(&fs, user))));
+        }
+
+        // With operands, each line is prefixed by the name of the user.
+        let lines = usernames
+            .iter()
+            .map(|username| match find_user(&fs, username) {
+                Some(user) => format!("{username} : {}", group_names(&fs, user)),
+                None => format!("groups: '{username}': no such user"),
+            })
+            .collect::<Vec<_>>();
+        Ok(CommandOutput(Some(lines.join("\n"))))
+    }
+}
+
+fn group_names(fs: &FileSystem, user: &User) -> String {
+    user.groups
+        .iter()
+        .map(|group_id| {
+            fs.get_group(*group_id)
+                .map_or_else(|| group_id.to_string(), |group| group.name.clone())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
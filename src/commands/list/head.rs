@@ -0,0 +1,177 @@
+//! The head command, printing the first lines or bytes of files.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct HeadCommand;
+
+/// The number of lines printed by default.
+const DEFAULT_LINES: usize = 10;
+
+impl ExecutableCommand for HeadCommand {
+    fn name(&self) -> &'static str {
+        "head"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "lines",
+                Some('n'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "bytes",
+                Some('c'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "quiet",
+                Some('q'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "verbose",
+                Some('v'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the first 10 lines of the files given, or of the standard input.
+    ///
+    /// `-n -N` and `-c -N` print everything but the last `N` lines or bytes.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let (unit, count) = match (flags.flag("bytes"), flags.flag("lines")) {
+            (Some(Argument::Basic(BasicArgument::String(count))), _) => (Unit::Bytes, count),
+            (_, Some(Argument::Basic(BasicArgument::String(count)))) => (Unit::Lines, count),
+            _ => (Unit::Lines, &DEFAULT_LINES.to_string()),
+        };
+        let (all_but_last, number) = match count.strip_prefix('-') {
+            Some(number) => (true, number),
+            None => (false, count.as_str()),
+        };
+        let Ok(number) = number.parse::<usize>() else {
+            shell.set_exit_status(1);
+            return Ok(CommandOutput(Some(format!(
+                "head: invalid number of {unit}: '{count}'"
+            ))));
+        };
+
+        let mut paths = string_arguments(args)?;
+        if paths.is_empty() {
+            paths.push("-".to_string());
+        }
+        let headers =
+            flags.flag("verbose").is_some() || (paths.len() > 1 && flags.flag("quiet").is_none());
+        let mut output = String::new();
+        for (index, path) in paths.iter().enumerate() {
+            let content = match read_input(shell, path) {
+                Ok(content) => content,
+                Err(error) => {
+                    shell.set_exit_status(1);
+                    output.push_str(&format!(
+                        "head: cannot open '{path}' for reading: {}\n",
+                        error_message(error)?
+                    ));
+                    continue;
+                }
+            };
+            if headers {
+                output.push_str(&header(path, index == 0));
+            }
+            output.push_str(&head(&content, unit, number, all_but_last));
+        }
+
+        let output = output.strip_suffix('\n').unwrap_or(&output);
+        Ok(CommandOutput(
+            (!output.is_empty()).then(|| output.to_string()),
+        ))
+    }
+}
+
+/// What the counts of `head` and `tail` are in.
+#[derive(Clone, Copy)]
+pub(crate) enum Unit {
+    Lines,
+    Bytes,
+}
+
+impl std::fmt::Display for Unit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Unit::Lines => write!(f, "lines"),
+            Unit::Bytes => write!(f, "bytes"),
+        }
+    }
+}
+
+/// Returns the header naming a file when several are printed, like `==> notes <==`.
+pub(crate) fn header(path: &str, first: bool) -> String {
+    let name = if path == "-" { "standard input" } else { path };
+    let separator = if first { "" } else { "\n" };
+    format!("{separator}==> {name} <==\n")
+}
+
+/// Returns the first lines or bytes of a content, or all but the last ones.
+fn head(content: &str, unit: Unit, number: usize, all_but_last: bool) -> String {
+    match unit {
+        Unit::Lines => {
+            let lines = content.split_inclusive('\n').collect::<Vec<_>>();
+            let kept = if all_but_last {
+                lines.len().saturating_sub(number)
+            } else {
+                number
+            };
+            lines.into_iter().take(kept).collect()
+        }
+        Unit::Bytes => {
+            let bytes = content.as_bytes();
+            let kept = if all_but_last {
+                bytes.len().saturating_sub(number)
+            } else {
+                number.min(bytes.len())
+            };
+            String::from_utf8_lossy(&bytes[..kept]).into_owned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_head() {
+        let content = "1\n2\n3\n4\n";
+        assert_eq!(head(content, Unit::Lines, 2, false), "1\n2\n");
+        assert_eq!(head(content, Unit::Lines, 9, false), content);
+        assert_eq!(head(content, Unit::Lines, 1, true), "1\n2\n3\n");
+        assert_eq!(head(content, Unit::Bytes, 3, false), "1\n2");
+        assert_eq!(head(content, Unit::Bytes, 3, true), "1\n2\n3");
+        assert_eq!(head("", Unit::Lines, 3, false), "");
+    }
+}
@@ -0,0 +1,134 @@
+//! The id command, printing the user and group identities of a user.
+
+use crate::{
+    FileSystem, GroupId, User,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        find_user,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::{SessionError, ShellError},
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct IdCommand;
+
+impl ExecutableCommand for IdCommand {
+    fn name(&self) -> &'static str {
+        "id"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "user",
+                Some('u'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "group",
+                Some('g'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "groups",
+                Some('G'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "name",
+                Some('n'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let fs = shell.fs.lock();
+        let user = match args {
+            Some(Argument::Basic(BasicArgument::String(username))) => {
+                match find_user(&fs, &username) {
+                    Some(user) => user,
+                    None => {
+                        return Ok(CommandOutput(Some(format!(
+                            "id: '{username}': no such user"
+                        ))));
+                    }
+                }
+            }
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => fs
+                .get_user(shell.current_session.current_user())
+                .ok_or(ShellError::Session(SessionError::UserNotFound))?,
+        };
+
+        let names = flags.flag("name").is_some();
+        let selected = ["user", "group", "groups"]
+            .into_iter()
+            .filter(|flag| flags.flag(flag).is_some())
+            .collect::<Vec<_>>();
+        let output = match selected.as_slice() {
+            [] if names => "id: cannot print only names or real IDs in default format".to_string(),
+            [] => format_identity(&fs, user),
+            ["user"] if names => user.name.clone(),
+            ["user"] => user.id.to_string(),
+            ["group"] => {
+                let group_id = user.primary_group().unwrap_or_default();
+                if names {
+                    group_name(&fs, group_id)
+                } else {
+                    group_id.to_string()
+                }
+            }
+            ["groups"] => user
+                .groups
+                .iter()
+                .map(|group_id| {
+                    if names {
+                        group_name(&fs, *group_id)
+                    } else {
+                        group_id.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => "id: cannot print \"only\" of more than one choice".to_string(),
+        };
+        Ok(CommandOutput(Some(output)))
+    }
+}
+
+/// Formats the identity of a user like `uid=1000(alice) gid=1000(alice) groups=1000(alice)`.
+fn format_identity(fs: &FileSystem, user: &User) -> String {
+    let format_group = |group_id: &GroupId| match fs.get_group(*group_id) {
+        Some(group) => format!("{group_id}({})", group.name),
+        None => group_id.to_string(),
+    };
+    let mut identity = format!("uid={}({})", user.id, user.name);
+    if let Some(group_id) = user.primary_group() {
+        identity.push_str(&format!(" gid={}", format_group(&group_id)));
+        let groups = user.groups.iter().map(format_group).collect::<Vec<_>>();
+        identity.push_str(&format!(" groups={}", groups.join(",")));
+    }
+    identity
+}
+
+fn group_name(fs: &FileSystem, group_id: GroupId) -> String {
+    fs.get_group(group_id)
+        .map_or_else(|| group_id.to_string(), |group| group.name.clone())
+}
@@ -0,0 +1,157 @@
+//! The ln command, creating hard links between files.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    InodeContent,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::{FileSystemError, ShellError},
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct LinkCommand;
+
+impl ExecutableCommand for LinkCommand {
+    fn name(&self) -> &'static str {
+        "ln"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "force",
+                Some('f'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut crate::shell::Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut current_session = shell.current_session.clone();
+        let force = flags.flag("force").is_some();
+
+        let mut paths = match args {
+            Some(Argument::List(args)) => {
+                args.into_iter().try_fold(Vec::new(), |mut acc, arg| {
+                    match arg {
+                        BasicArgument::String(arg) => acc.push(PathBuf::from(arg)),
+                        _ => return Err(ShellError::Internal("Invalid argument".to_string())),
+                    }
+                    Ok(acc)
+                })?
+            }
+            _ => {
+                shell.set_exit_status(1);
+                return Ok(CommandOutput(Some("ln: missing file operand".to_string())));
+            }
+        };
+        let mut fs = shell.fs.lock();
+
+        // With a single operand, the link is created in the current directory.
+        let destination = if paths.len() == 1 {
+            PathBuf::from(".")
+        } else {
+            paths.pop().expect("There should be at least two operands")
+        };
+        let destination_is_directory = current_session
+            .find_inode(&fs, &destination)
+            .is_some_and(|inode| matches!(inode.content, InodeContent::Directory(_)));
+        if paths.len() > 1 && !destination_is_directory {
+            drop(fs);
+            shell.set_exit_status(1);
+            return Ok(CommandOutput(Some(format!(
+                "ln: target '{}': Not a directory",
+                destination.display()
+            ))));
+        }
+
+        let mut error_messages = Vec::new();
+        for target in paths {
+            let link = if destination_is_directory {
+                destination.join(target.file_name().unwrap_or(target.as_os_str()))
+            } else {
+                destination.clone()
+            };
+
+            match current_session.lookup_inode(&fs, &target) {
+                Ok(_) => {}
+                Err(ShellError::FileSystem(FileSystemError::PermissionDenied(_))) => {
+                    error_messages.push(format!(
+                        "ln: failed to access '{}': Permission denied",
+                        target.display()
+                    ));
+                    continue;
+                }
+                Err(_) => {
+                    error_messages.push(format!(
+                        "ln: failed to access '{}': No such file or directory",
+                        target.display()
+                    ));
+                    continue;
+                }
+            }
+            if force
+                && current_session.find_inode(&fs, &link).is_some()
+                && let Err(error) = current_session.remove_file(&mut fs, &link)
+            {
+                error_messages.push(link_error_message(&target, &link, error)?);
+                continue;
+            }
+
+            if let Err(error) = current_session.create_hard_link(&mut fs, &target, &link) {
+                error_messages.push(link_error_message(&target, &link, error)?);
+            }
+        }
+
+        drop(fs);
+        if error_messages.is_empty() {
+            Ok(CommandOutput(None))
+        } else {
+            shell.set_exit_status(1);
+            Ok(CommandOutput(Some(error_messages.join("\n"))))
+        }
+    }
+}
+
+fn link_error_message(target: &Path, link: &Path, error: ShellError) -> Result<String, ShellError> {
+    let message = match error {
+        ShellError::FileSystem(FileSystemError::HardLinkToDirectory(_)) => {
+            format!(
+                "ln: {}: hard link not allowed for directory",
+                target.display()
+            )
+        }
+        ShellError::FileSystem(FileSystemError::EntryAlreadyExists(_)) => format!(
+            "ln: failed to create hard link '{}': File exists",
+            link.display()
+        ),
+        ShellError::FileSystem(FileSystemError::EntryNotFound(_)) => format!(
+            "ln: failed to create hard link '{}': No such file or directory",
+            link.display()
+        ),
+        ShellError::FileSystem(FileSystemError::NotADirectory(_)) => format!(
+            "ln: failed to create hard link '{}': Not a directory",
+            link.display()
+        ),
+        ShellError::FileSystem(FileSystemError::PermissionDenied(_)) => format!(
+            "ln: failed to create hard link '{}': Permission denied",
+            link.display()
+        ),
+        error => return Err(error),
+    };
+    Ok(message)
+}
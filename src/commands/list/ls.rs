@@ -7,11 +7,11 @@ use chrono::{DateTime, Datelike, Timelike, Utc};
 
 use crate::commands::args::{ArgumentKind, BasicArgument, BasicArgumentKind};
 use crate::commands::flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification};
-use crate::commands::{Argument, CommandOutput, ExecutableCommand, Flags};
+use crate::commands::{Argument, CommandOutput, ExecutableCommand, Flags, error_message};
 use crate::errors::ShellError;
 use crate::fs::inode::content::InodeType;
 use crate::fs::inode::size::Size;
-use crate::fs::permissions::Permission;
+use crate::fs::permissions::{Access, Permission};
 use crate::{FilePermissions, FileSystem, Inode, InodeContent};
 
 #[derive(Clone, Default, Copy)]
@@ -49,6 +49,12 @@ impl ExecutableCommand for LsCommand {
                 false,
                 ArgumentKind::Flag,
             ))
+            .with_flag(FlagSpecification::new(
+                "inode",
+                Some('i'),
+                false,
+                ArgumentKind::Flag,
+            ))
             .into_flag_definition()
     }
 
@@ -62,6 +68,7 @@ impl ExecutableCommand for LsCommand {
         args: Option<Argument>,
         shell: &mut crate::shell::Shell,
     ) -> Result<CommandOutput, ShellError> {
+        let fs = shell.fs.lock();
         let args = match args {
             Some(Argument::List(args)) => {
                 let args = args
@@ -94,59 +101,81 @@ impl ExecutableCommand for LsCommand {
         } else {
             DisplayedEntriesOptions::HideDotFiles
         };
+        let show_inode = flags.flag("inode").is_some();
+        let now = fs.now();
 
         let mut output = String::new();
 
         match args {
             ListArgKind::Single(dir) => {
-                match shell.current_session.find_inode(&shell.fs, Path::new(&dir)) {
-                    Some(inode) => {
-                        let inode = inode.lock().expect("Failed to lock inode");
+                match shell.current_session.lookup_inode(&fs, Path::new(&dir)) {
+                    Ok(inode) => {
                         let inode_type = inode.inode_type();
-                        if inode_type == InodeType::Directory {
+                        if inode_type == InodeType::Directory
+                            && !shell.current_session.can_access(&fs, inode, Access::Read)
+                        {
+                            output.push_str(&open_directory_error(&dir));
+                        } else if inode_type == InodeType::Directory {
                             let contents = get_dir_contents(
-                                &shell.fs,
-                                &inode,
+                                &fs,
+                                &dir,
+                                inode,
                                 &display_mode,
-                                &LongEntryFormatOptions::new(),
+                                &LongEntryFormatOptions::new(now),
                                 &displayed_entries,
+                                show_inode,
                             )?;
                             output.push_str(&contents);
                         } else {
-                            output.push_str(&inode.name);
+                            output.push_str(&format_dir_entry(
+                                &fs,
+                                &dir,
+                                inode,
+                                &display_mode,
+                                &LongEntryFormatOptions::new(now),
+                                show_inode,
+                            ));
                         }
                     }
-                    None => {
-                        output.push_str(&format!(
-                            "ls: cannot access '{dir}': No such file or directory"
-                        ));
-                    }
+                    Err(error) => output.push_str(&access_error(&dir, error)?),
                 };
             }
             ListArgKind::Multiple(items) => {
-                let entry_inodes = items.iter().fold(DirEntries::new(), |mut acc, item| {
-                    match shell.current_session.find_inode(&shell.fs, Path::new(item)) {
-                        Some(inode) => {
-                            let inode = inode.lock().expect("Failed to lock inode");
-                            let clone = inode.clone();
-                            acc.add_entry(DirEntry { inode: clone });
-                            drop(inode);
-                        }
-                        None => output.push_str(&format!(
-                            "ls: cannot access '{item}': No such file or directory"
-                        )),
-                    };
-                    acc
-                });
+                let entry_inodes =
+                    items
+                        .iter()
+                        .try_fold(DirEntries::new(now), |mut acc, item| {
+                            match shell.current_session.lookup_inode(&fs, Path::new(item)) {
+                                Ok(inode) => acc.add_entry(DirEntry {
+                                    name: item.clone(),
+                                    inode,
+                                }),
+                                Err(error) => output.push_str(&access_error(item, error)?),
+                            };
+                            Ok::<_, ShellError>(acc)
+                        })?;
 
                 let mut entry_inodes_iter = entry_inodes.entries.iter().peekable();
                 while let Some(inode) = entry_inodes_iter.next() {
+                    if inode.inode.inode_type() == InodeType::Directory
+                        && !shell
+                            .current_session
+                            .can_access(&fs, inode.inode, Access::Read)
+                    {
+                        output.push_str(&open_directory_error(&inode.name));
+                        if entry_inodes_iter.peek().is_some() {
+                            output.push_str(entry_separator(&display_mode));
+                        }
+                        continue;
+                    }
                     let contents = get_dir_contents(
-                        &shell.fs,
-                        &inode.inode,
+                        &fs,
+                        &inode.name,
+                        inode.inode,
                         &display_mode,
                         &entry_inodes.options,
                         &displayed_entries,
+                        show_inode,
                     )?;
                     output.push_str(&contents);
                     if entry_inodes_iter.peek().is_some() {
@@ -164,35 +193,50 @@ impl ExecutableCommand for LsCommand {
     }
 }
 
-struct DirEntry {
-    inode: Inode,
+/// Returns the message of an inode that couldn't be reached.
+fn access_error(path: &str, error: ShellError) -> Result<String, ShellError> {
+    Ok(format!(
+        "ls: cannot access '{path}': {}",
+        error_message(error)?
+    ))
+}
+
+fn open_directory_error(path: &str) -> String {
+    format!("ls: cannot open directory '{path}': Permission denied")
 }
 
-struct DirEntries {
-    entries: Vec<DirEntry>,
+struct DirEntry<'a> {
+    name: String,
+    inode: &'a Inode,
+}
+
+struct DirEntries<'a> {
+    entries: Vec<DirEntry<'a>>,
     options: LongEntryFormatOptions,
 }
 
-impl DirEntries {
-    fn new() -> Self {
+impl<'a> DirEntries<'a> {
+    fn new(now: DateTime<Utc>) -> Self {
         Self {
             entries: Vec::new(),
-            options: LongEntryFormatOptions::new(),
+            options: LongEntryFormatOptions::new(now),
         }
     }
 
-    fn add_entry(&mut self, entry: DirEntry) {
-        self.options.update(&entry.inode);
+    fn add_entry(&mut self, entry: DirEntry<'a>) {
+        self.options.update(&entry.name, entry.inode);
         self.entries.push(entry);
     }
 }
 
 fn get_dir_contents(
     fs: &FileSystem,
+    name: &str,
     entry: &Inode,
     display_mode: &ListDisplayMode,
     entry_format: &LongEntryFormatOptions,
     displayed_entries: &DisplayedEntriesOptions,
+    show_inode: bool,
 ) -> Result<String, ShellError> {
     let mut content = String::new();
 
@@ -200,10 +244,11 @@ fn get_dir_contents(
         InodeContent::File(_) => {
             content.push_str(&format_dir_entry(
                 fs,
-                &entry.name,
+                name,
                 entry,
                 display_mode,
                 entry_format,
+                show_inode,
             ));
         }
         InodeContent::Directory(dir) => {
@@ -217,35 +262,34 @@ fn get_dir_contents(
                     entry,
                     display_mode,
                     entry_format,
+                    show_inode,
                 ));
                 content.push_str(entry_separator(display_mode));
                 has_entries = true;
 
-                let parent = entry.parent.as_ref().expect("Parent inode should exist");
-                let parent = parent.upgrade().expect("Parent inode should exist");
-                let parent = parent.lock().expect("Failed to lock parent inode");
+                let parent = fs
+                    .inode(dir.parent)
+                    .ok_or_else(|| ShellError::Internal("Parent inode should exist".to_string()))?;
                 content.push_str(&format_dir_entry(
                     fs,
                     "..",
-                    &parent,
+                    parent,
                     display_mode,
                     entry_format,
+                    show_inode,
                 ));
             }
 
-            // Sort entries and filter out hidden entries (if needed)
-            let mut entries = dir
-                .children
-                .iter()
-                .filter(|(name, _)| {
-                    !(name.starts_with('.')
-                        && displayed_entries == &DisplayedEntriesOptions::HideDotFiles)
-                })
-                .collect::<Vec<_>>();
-            entries.sort_by(|a, b| a.0.cmp(b.0));
+            // Entries are sorted by name, hidden entries being filtered out (if needed)
+            let entries = dir.children.iter().filter(|(name, _)| {
+                !(name.starts_with('.')
+                    && displayed_entries == &DisplayedEntriesOptions::HideDotFiles)
+            });
 
-            for (name, inode) in entries {
-                let inode = inode.lock().expect("Failed to lock inode");
+            for (name, &inode) in entries {
+                let inode = fs.inode(inode).ok_or_else(|| {
+                    ShellError::Internal(format!("Inode {inode} of {name} should exist"))
+                })?;
 
                 if !(name.starts_with('.')
                     && displayed_entries == &DisplayedEntriesOptions::HideDotFiles)
@@ -258,10 +302,11 @@ fn get_dir_contents(
 
                     content.push_str(&format_dir_entry(
                         fs,
-                        &inode.name,
-                        &inode,
+                        name,
+                        inode,
                         display_mode,
                         entry_format,
+                        show_inode,
                     ));
                 }
             }
@@ -278,11 +323,17 @@ fn format_dir_entry(
     entry: &Inode,
     display_mode: &ListDisplayMode,
     options: &LongEntryFormatOptions,
+    show_inode: bool,
 ) -> String {
-    match display_mode {
+    let entry_line = match display_mode {
         ListDisplayMode::Long => {
             let dir = format_is_dir(entry.inode_type() == InodeType::Directory);
             let permissions = format_permissions(&entry.metadata.permissions);
+            let acl = if entry.metadata.has_extended_acl() {
+                "+"
+            } else {
+                ""
+            };
 
             let hard_links = entry.hard_link_count();
 
@@ -300,15 +351,19 @@ fn format_dir_entry(
 
             let size = entry.size();
 
-            let date = format_date(entry.metadata.created_at, options.has_dates_from_this_year);
+            let date = format_date(entry.metadata.modified_at, options);
 
-            format!("{dir}{permissions} {hard_links} {user} {group} {size} {date} {name}")
+            format!("{dir}{permissions}{acl} {hard_links} {user} {group} {size} {date} {name}")
         }
         ListDisplayMode::Short => name.to_string(),
+    };
+    if show_inode {
+        format!("{} {entry_line}", entry.number)
+    } else {
+        entry_line
     }
 }
 
-#[derive(Default)]
 struct LongEntryFormatOptions {
     name_length: usize,
     hard_link_length: usize,
@@ -316,10 +371,12 @@ struct LongEntryFormatOptions {
     group_length: usize,
     size_length: usize,
     has_dates_from_this_year: bool,
+    /// The time of the listing, from the clock of the file system.
+    now: DateTime<Utc>,
 }
 
 impl LongEntryFormatOptions {
-    fn new() -> Self {
+    fn new(now: DateTime<Utc>) -> Self {
         Self {
             name_length: 0,
             hard_link_length: 0,
@@ -327,11 +384,12 @@ impl LongEntryFormatOptions {
             group_length: 0,
             size_length: 0,
             has_dates_from_this_year: false,
+            now,
         }
     }
 
-    fn update(&mut self, entry: &Inode) {
-        self.name_length = self.name_length.max(entry.name.len());
+    fn update(&mut self, name: &str, entry: &Inode) {
+        self.name_length = self.name_length.max(name.len());
         self.hard_link_length = self
             .hard_link_length
             .max(entry.hard_link_count().to_string().len());
@@ -340,15 +398,14 @@ impl LongEntryFormatOptions {
             .group_length
             .max(entry.metadata.group.to_string().len());
         self.size_length = self.size_length.max(entry.size().to_string().len());
-        if !self.has_dates_from_this_year && entry.metadata.created_at.year() == Utc::now().year() {
+        if !self.has_dates_from_this_year && entry.metadata.modified_at.year() == self.now.year() {
             self.has_dates_from_this_year = true;
         }
     }
 }
 
-fn format_date(date: DateTime<Utc>, has_dates_from_this_year: bool) -> String {
-    let now = Utc::now();
-    if date.year() == now.year() {
+fn format_date(date: DateTime<Utc>, options: &LongEntryFormatOptions) -> String {
+    if date.year() == options.now.year() {
         format!(
             "{} {} {:02}:{:02}",
             format_month(date.month()),
@@ -361,7 +418,11 @@ fn format_date(date: DateTime<Utc>, has_dates_from_this_year: bool) -> String {
             "{} {} {}{}",
             format_month(date.month()),
             date.day(),
-            if has_dates_from_this_year { " " } else { "" },
+            if options.has_dates_from_this_year {
+                " "
+            } else {
+                ""
+            },
             date.year(),
         )
     }
@@ -385,25 +446,32 @@ fn format_month(month: u32) -> &'static str {
     }
 }
 
-fn format_is_dir(is_dir: bool) -> char {
+pub(crate) fn format_is_dir(is_dir: bool) -> char {
     if is_dir { 'd' } else { '-' }
 }
 
-fn format_permissions(permissions: &FilePermissions) -> String {
+pub(crate) fn format_permissions(permissions: &FilePermissions) -> String {
     format!(
         "{}{}{}",
-        format_permission(&permissions.user()),
-        format_permission(&permissions.group()),
-        format_permission(&permissions.other())
+        format_permission(&permissions.user(), permissions.setuid(), 's'),
+        format_permission(&permissions.group(), permissions.setgid(), 's'),
+        format_permission(&permissions.other(), permissions.sticky(), 't')
     )
 }
 
-fn format_permission(permission: &Permission) -> String {
+/// Formats the permission of a class, the special bit of the class replacing the execute
+/// letter: lowercase if the execute bit is also set, uppercase otherwise.
+fn format_permission(permission: &Permission, special: bool, special_letter: char) -> String {
+    let execute = match (special, permission.execute) {
+        (true, true) => special_letter,
+        (true, false) => special_letter.to_ascii_uppercase(),
+        (false, true) => 'x',
+        (false, false) => '-',
+    };
     format!(
-        "{}{}{}",
+        "{}{}{execute}",
         if permission.read { 'r' } else { '-' },
         if permission.write { 'w' } else { '-' },
-        if permission.execute { 'x' } else { '-' }
     )
 }
 
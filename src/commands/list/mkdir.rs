@@ -7,9 +7,9 @@ use crate::{
     commands::{
         Argument, CommandOutput, ExecutableCommand, Flags,
         args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        error_message,
         flags::{FlagDefinition, FlagDefinitionBuilder},
     },
-    errors::FileSystemError,
 };
 
 #[derive(Default, Clone, Copy)]
@@ -39,7 +39,7 @@ impl ExecutableCommand for MakeDirectoryCommand {
         };
 
         let mut current_session = shell.current_session.clone();
-        let mut fs = shell.fs.clone();
+        let mut fs = shell.fs.lock();
         let mut error_messages = Vec::new();
 
         match args {
@@ -51,18 +51,7 @@ impl ExecutableCommand for MakeDirectoryCommand {
                     };
                     let path = PathBuf::from(&arg);
                     if let Err(error) = current_session.create_directory(&mut fs, &path) {
-                        match error {
-                            ShellError::FileSystem(FileSystemError::EntryAlreadyExists(_)) => {
-                                error_messages.push(error_builder(&arg, "File exists"));
-                            }
-                            ShellError::FileSystem(FileSystemError::EntryNotFound(_)) => {
-                                error_messages
-                                    .push(error_builder(&arg, "No such file or directory"));
-                            }
-                            _ => {
-                                return Err(error);
-                            }
-                        }
+                        error_messages.push(error_builder(&arg, error_message(error)?));
                     }
                 }
             }
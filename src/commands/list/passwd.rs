@@ -0,0 +1,186 @@
+//! The passwd command, changing the password of users.
+
+use chrono::DateTime;
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::{SessionError, ShellError},
+    fs::users::PasswordStatus,
+    shell::Shell,
+    stdin::prompt_unlocked,
+};
+
+/// The message of a password change that did not go through.
+const UNCHANGED: &str =
+    "passwd: Authentication token manipulation error\npasswd: password unchanged";
+
+#[derive(Default, Clone, Copy)]
+pub struct PasswordCommand;
+
+impl ExecutableCommand for PasswordCommand {
+    fn name(&self) -> &'static str {
+        "passwd"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "delete",
+                Some('d'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "lock",
+                Some('l'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "unlock",
+                Some('u'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "status",
+                Some('S'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut fs = shell.fs.lock();
+        let user = fs
+            .get_user(shell.current_session.current_user())
+            .ok_or(ShellError::Session(SessionError::UserNotFound))?;
+        let is_root = user.is_root();
+        let target = match args {
+            Some(Argument::Basic(BasicArgument::String(username))) => {
+                match fs.find_user_by_name(&username) {
+                    Some(target) => target.clone(),
+                    None => {
+                        return Ok(CommandOutput(Some(format!(
+                            "passwd: user '{username}' does not exist"
+                        ))));
+                    }
+                }
+            }
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => user.clone(),
+        };
+        if !is_root && target.id != user.id {
+            return Ok(CommandOutput(Some(format!(
+                "passwd: You may not view or modify password information for {}.",
+                target.name
+            ))));
+        }
+        let target_id = target.id;
+
+        if flags.flag("status").is_some() {
+            let status = match target.password_status() {
+                PasswordStatus::Usable => "P",
+                PasswordStatus::Locked => "L",
+                PasswordStatus::Empty => "NP",
+            };
+            let last_change = target
+                .password_changed
+                .and_then(|day| DateTime::from_timestamp(day * 24 * 60 * 60, 0))
+                .map_or_else(
+                    || "never".to_string(),
+                    |date| date.format("%Y-%m-%d").to_string(),
+                );
+            return Ok(CommandOutput(Some(format!(
+                "{} {status} {last_change} 0 99999 7 -1",
+                target.name
+            ))));
+        }
+
+        let administration = ["delete", "lock", "unlock"]
+            .into_iter()
+            .find(|flag| flags.flag(flag).is_some());
+        if let Some(action) = administration {
+            if !is_root {
+                return Ok(CommandOutput(Some(
+                    "passwd: Permission denied.".to_string(),
+                )));
+            }
+            let now = fs.now();
+            let unlocked = fs.update_user(target_id, |target| match action {
+                "delete" => {
+                    target.delete_password(now);
+                    true
+                }
+                "lock" => {
+                    target.lock_password();
+                    true
+                }
+                _ => target.unlock_password(),
+            })?;
+            if !unlocked {
+                return Ok(CommandOutput(Some(
+                    "passwd: unlocking the password would result in a passwordless account.\n\
+                     You should set a password with usermod -p to unlock the password of this account."
+                        .to_string(),
+                )));
+            }
+            let message = match action {
+                "delete" => "passwd: password changed.",
+                _ => "passwd: password expiry information changed.",
+            };
+            return Ok(CommandOutput(Some(message.to_string())));
+        }
+
+        let password = prompt_unlocked(fs, || {
+            // Regular users must prove they know their current password.
+            if !is_root && target.password_status() != PasswordStatus::Empty {
+                let authenticated = shell
+                    .read_line("Current password: ")
+                    .is_some_and(|password| target.verify_password(&password));
+                if !authenticated {
+                    return Err(UNCHANGED.to_string());
+                }
+            }
+
+            let password = shell
+                .read_line("New password: ")
+                .ok_or_else(|| UNCHANGED.to_string())?;
+            if password.is_empty() {
+                return Err(format!("No password has been supplied.\n{UNCHANGED}"));
+            }
+            let confirmation = shell
+                .read_line("Retype new password: ")
+                .ok_or_else(|| UNCHANGED.to_string())?;
+            if confirmation != password {
+                return Err(format!("Sorry, passwords do not match.\n{UNCHANGED}"));
+            }
+            Ok(password)
+        });
+        let password = match password {
+            Ok(password) => password,
+            Err(message) => return Ok(CommandOutput(Some(message))),
+        };
+
+        let mut fs = shell.fs.lock();
+        let now = fs.now();
+        fs.update_user(target_id, |target| target.set_password(&password, now))?;
+        Ok(CommandOutput(Some(
+            "passwd: password updated successfully".to_string(),
+        )))
+    }
+}
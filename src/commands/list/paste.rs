@@ -0,0 +1,181 @@
+//! The paste command, merging the lines of files.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct PasteCommand;
+
+impl ExecutableCommand for PasteCommand {
+    fn name(&self) -> &'static str {
+        "paste"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "delimiters",
+                Some('d'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "serial",
+                Some('s'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the lines of the files given side by side, separated by tabs or by the delimiters
+    /// given, used in turn.
+    ///
+    /// With `-s`, the lines of each file are printed on a single line instead.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let delimiters = match flags.flag("delimiters") {
+            Some(Argument::Basic(BasicArgument::String(list))) => parse_delimiters(list),
+            _ => vec![Some('\t')],
+        };
+        let mut paths = string_arguments(args)?;
+        if paths.is_empty() {
+            paths.push("-".to_string());
+        }
+
+        // The standard input is read once, its lines being shared by each `-` operand in turn.
+        let mut files = Vec::new();
+        let mut stdin = None;
+        for path in &paths {
+            let content = if path == "-" {
+                if stdin.is_none() {
+                    stdin = Some(read_input(shell, path)?);
+                }
+                None
+            } else {
+                match read_input(shell, path) {
+                    Ok(content) => Some(content),
+                    Err(error) => {
+                        shell.set_exit_status(1);
+                        return Ok(CommandOutput(Some(format!(
+                            "paste: {path}: {}",
+                            error_message(error)?
+                        ))));
+                    }
+                }
+            };
+            files.push(content);
+        }
+        let stdin = stdin.unwrap_or_default();
+        let mut stdin_lines = stdin.split_terminator('\n');
+        let columns = if flags.flag("serial").is_some() {
+            files
+                .iter()
+                .map(|content| match content {
+                    Some(content) => content.split_terminator('\n').collect(),
+                    None => stdin_lines.by_ref().collect(),
+                })
+                .collect::<Vec<Vec<_>>>()
+        } else {
+            let mut columns = files
+                .iter()
+                .map(|content| {
+                    content
+                        .as_ref()
+                        .map(|content| content.split_terminator('\n').collect::<Vec<_>>())
+                })
+                .collect::<Vec<_>>();
+            let mut rows = Vec::new();
+            loop {
+                let mut row = Vec::new();
+                let mut any = false;
+                for column in &mut columns {
+                    let line = match column {
+                        Some(lines) if !lines.is_empty() => Some(lines.remove(0)),
+                        Some(_) => None,
+                        None => stdin_lines.next(),
+                    };
+                    any |= line.is_some();
+                    row.push(line.unwrap_or(""));
+                }
+                if !any {
+                    break;
+                }
+                rows.push(row);
+            }
+            rows
+        };
+
+        let lines = columns
+            .iter()
+            .map(|fields| join(fields, &delimiters))
+            .collect::<Vec<_>>();
+        Ok(CommandOutput((!lines.is_empty()).then(|| lines.join("\n"))))
+    }
+}
+
+/// Parses a delimiter list, `\0` standing for no delimiter.
+fn parse_delimiters(list: &str) -> Vec<Option<char>> {
+    let mut delimiters = Vec::new();
+    let mut chars = list.chars();
+    while let Some(char) = chars.next() {
+        delimiters.push(match char {
+            '\\' => match chars.next() {
+                Some('n') => Some('\n'),
+                Some('t') => Some('\t'),
+                Some('0') => None,
+                Some(char) => Some(char),
+                None => Some('\\'),
+            },
+            char => Some(char),
+        });
+    }
+    if delimiters.is_empty() {
+        delimiters.push(None);
+    }
+    delimiters
+}
+
+/// Joins fields, using the delimiters in turn.
+fn join(fields: &[&str], delimiters: &[Option<char>]) -> String {
+    let mut line = String::new();
+    for (index, field) in fields.iter().enumerate() {
+        if index > 0
+            && let Some(delimiter) = delimiters[(index - 1) % delimiters.len()]
+        {
+            line.push(delimiter);
+        }
+        line.push_str(field);
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join() {
+        let delimiters = parse_delimiters(r",\0;");
+        assert_eq!(delimiters, vec![Some(','), None, Some(';')]);
+        assert_eq!(join(&["a", "b", "c", "d", "e"], &delimiters), "a,bc;d,e");
+        assert_eq!(join(&["a", "b"], &parse_delimiters("")), "ab");
+    }
+}
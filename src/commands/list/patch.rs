@@ -0,0 +1,470 @@
+//! The patch command, applying the unified diffs made by `diff -u` to files.
+
+use std::path::Path;
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct PatchCommand;
+
+impl ExecutableCommand for PatchCommand {
+    fn name(&self) -> &'static str {
+        "patch"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "strip",
+                Some('p'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "input",
+                Some('i'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "reverse",
+                Some('R'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Applies a patch, read from `-i`, the second operand, or the standard input.
+    ///
+    /// The files patched are named by the headers of the patch, with `-p N` removing their first
+    /// `N` components, or only keeping their last one without it. A first operand patches that
+    /// file instead. Hunks which can't be applied are saved to a `.rej` file next to it.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?.into_iter();
+        let target = operands.next();
+        let input = match flags.flag("input") {
+            Some(Argument::Basic(BasicArgument::String(input))) => Some(input.clone()),
+            _ => operands.next(),
+        };
+        if let Some(extra) = operands.next() {
+            shell.set_exit_status(2);
+            return Ok(CommandOutput(Some(format!(
+                "patch: **** extra operand '{extra}'"
+            ))));
+        }
+        let strip = match flags.flag("strip") {
+            Some(Argument::Basic(BasicArgument::String(strip))) => match strip.parse() {
+                Ok(strip) => Some(strip),
+                Err(_) => {
+                    shell.set_exit_status(2);
+                    return Ok(CommandOutput(Some(format!(
+                        "patch: **** strip count {strip} is not a number"
+                    ))));
+                }
+            },
+            _ => None,
+        };
+        let input = input.unwrap_or_else(|| "-".to_string());
+        let text = match read_input(shell, &input) {
+            Ok(text) => text,
+            Err(error) => {
+                shell.set_exit_status(2);
+                return Ok(CommandOutput(Some(format!(
+                    "patch: **** Can't open patch file {input} : {}",
+                    error_message(error)?
+                ))));
+            }
+        };
+        let mut patches = parse(&text);
+        if patches.is_empty() {
+            shell.set_exit_status(2);
+            return Ok(CommandOutput(Some(
+                "patch: **** Only garbage was found in the patch input.".to_string(),
+            )));
+        }
+        if flags.flag("reverse").is_some() {
+            patches.iter_mut().for_each(FilePatch::reverse);
+        }
+
+        let mut output = Vec::new();
+        let mut status = 0;
+        for patch in &patches {
+            let path = match &target {
+                Some(target) => Some(target.clone()),
+                None => patch.target(shell, strip),
+            };
+            // Only a patch creating a file can be applied to a file which doesn't exist.
+            let path = path.filter(|path| {
+                let fs = shell.fs.lock();
+                patch.old_path == "/dev/null"
+                    || shell
+                        .current_session
+                        .find_inode(&fs, Path::new(path))
+                        .is_some()
+            });
+            let Some(path) = path else {
+                output.push(format!(
+                    "can't find file to patch at input line {}",
+                    patch.line
+                ));
+                output.push("No file to patch.  Skipping patch.".to_string());
+                output.push(format!(
+                    "{} out of {} {} ignored",
+                    patch.hunks.len(),
+                    patch.hunks.len(),
+                    plural("hunk", patch.hunks.len())
+                ));
+                status = 1;
+                continue;
+            };
+            output.push(format!("patching file {path}"));
+            let exists = {
+                let fs = shell.fs.lock();
+                shell
+                    .current_session
+                    .find_inode(&fs, Path::new(&path))
+                    .is_some()
+            };
+            let original = if exists {
+                match read_input(shell, &path) {
+                    Ok(original) => original,
+                    Err(error) => {
+                        shell.set_exit_status(2);
+                        output.push(format!(
+                            "patch: **** Can't open file {path} : {}",
+                            error_message(error)?
+                        ));
+                        return Ok(CommandOutput(Some(output.join("\n"))));
+                    }
+                }
+            } else {
+                String::new()
+            };
+
+            let (patched, rejected) = patch.apply(&original, &mut output);
+            let mut written = {
+                let mut fs = shell.fs.lock();
+                if rejected.len() == patch.hunks.len() {
+                    // Nothing changed, so the file is left as it was.
+                    Ok(())
+                } else if patch.new_path == "/dev/null" && patched.is_empty() {
+                    shell.current_session.remove_file(&mut fs, Path::new(&path))
+                } else {
+                    shell
+                        .current_session
+                        .write_file(&mut fs, Path::new(&path), &patched)
+                }
+            };
+            if !rejected.is_empty() {
+                let hunks = patch.hunks.len();
+                let rejects = format!("{path}.rej");
+                output.push(format!(
+                    "{} out of {hunks} {} FAILED -- saving rejects to file {rejects}",
+                    rejected.len(),
+                    plural("hunk", hunks)
+                ));
+                let mut content = format!("--- {}\n+++ {}\n", patch.old_path, patch.new_path);
+                for hunk in rejected {
+                    content.push_str(&hunk.to_string());
+                }
+                if written.is_ok() {
+                    let mut fs = shell.fs.lock();
+                    written =
+                        shell
+                            .current_session
+                            .write_file(&mut fs, Path::new(&rejects), &content);
+                }
+                status = 1;
+            }
+            if let Err(error) = written {
+                shell.set_exit_status(2);
+                output.push(format!(
+                    "patch: **** Can't create file {path} : {}",
+                    error_message(error)?
+                ));
+                return Ok(CommandOutput(Some(output.join("\n"))));
+            }
+        }
+        shell.set_exit_status(status);
+        Ok(CommandOutput(Some(output.join("\n"))))
+    }
+}
+
+/// The changes of a patch to a file.
+#[derive(Debug)]
+struct FilePatch {
+    old_path: String,
+    new_path: String,
+    /// The line of the patch the headers are on.
+    line: usize,
+    hunks: Vec<Hunk>,
+}
+
+impl FilePatch {
+    /// Returns the file to patch, the one named by the headers which exists, or the new one if
+    /// the file is created.
+    fn target(&self, shell: &Shell, strip: Option<usize>) -> Option<String> {
+        let candidates = [&self.old_path, &self.new_path]
+            .into_iter()
+            .filter(|path| *path != "/dev/null")
+            .filter_map(|path| strip_path(path, strip))
+            .collect::<Vec<_>>();
+        let fs = shell.fs.lock();
+        let existing = candidates.iter().find(|path| {
+            shell
+                .current_session
+                .find_inode(&fs, Path::new(path))
+                .is_some()
+        });
+        match existing {
+            Some(path) => Some(path.clone()),
+            None if self.old_path == "/dev/null" => candidates.into_iter().next(),
+            None => None,
+        }
+    }
+
+    fn reverse(&mut self) {
+        std::mem::swap(&mut self.old_path, &mut self.new_path);
+        for hunk in &mut self.hunks {
+            std::mem::swap(&mut hunk.old_start, &mut hunk.new_start);
+            for (kind, _) in &mut hunk.lines {
+                *kind = match *kind {
+                    '-' => '+',
+                    '+' => '-',
+                    kind => kind,
+                };
+            }
+        }
+    }
+
+    /// Applies the hunks to the content of a file, returning the patched content and the hunks
+    /// which couldn't be applied.
+    ///
+    /// A hunk whose lines aren't where it expects them is applied where they are found the
+    /// nearest, and the following hunks are moved by as many lines.
+    fn apply<'a>(&'a self, original: &str, output: &mut Vec<String>) -> (String, Vec<&'a Hunk>) {
+        let lines = original.split_inclusive('\n').collect::<Vec<_>>();
+        let mut patched = String::new();
+        let mut rejected = Vec::new();
+        let mut consumed = 0;
+        let mut offset = 0isize;
+        for (number, hunk) in self.hunks.iter().enumerate() {
+            let expected = hunk.lines_of(&['-', ' ']);
+            // A hunk adding lines to an empty range starts after the line it names.
+            let start = if expected.is_empty() {
+                hunk.old_start
+            } else {
+                hunk.old_start.saturating_sub(1)
+            };
+            let wanted = (start as isize + offset).max(0) as usize;
+            let matches = |position: usize| {
+                position >= consumed
+                    && position + expected.len() <= lines.len()
+                    && lines[position..position + expected.len()] == expected[..]
+            };
+            let found = (0..=lines.len()).find_map(|distance| {
+                [wanted.checked_sub(distance), Some(wanted + distance)]
+                    .into_iter()
+                    .flatten()
+                    .find(|position| matches(*position))
+            });
+            let Some(position) = found else {
+                output.push(format!("Hunk #{} FAILED at {}.", number + 1, start + 1));
+                rejected.push(hunk);
+                continue;
+            };
+            lines[consumed..position]
+                .iter()
+                .for_each(|line| patched.push_str(line));
+            patched.extend(hunk.lines_of(&['+', ' ']));
+            consumed = position + expected.len();
+            let moved = position as isize - start as isize;
+            offset = moved;
+            if moved != 0 {
+                output.push(format!(
+                    "Hunk #{} succeeded at {} (offset {moved} {}).",
+                    number + 1,
+                    position + 1,
+                    plural("line", moved.unsigned_abs())
+                ));
+            }
+        }
+        lines[consumed..]
+            .iter()
+            .for_each(|line| patched.push_str(line));
+        (patched, rejected)
+    }
+}
+
+/// A group of changed lines with their context, like `@@ -1,3 +1,4 @@`.
+#[derive(Debug)]
+struct Hunk {
+    old_start: usize,
+    new_start: usize,
+    /// The lines, with their newline if they have one, and their kind: ` `, `-` or `+`.
+    lines: Vec<(char, String)>,
+}
+
+impl Hunk {
+    fn lines_of(&self, kinds: &[char]) -> Vec<&str> {
+        self.lines
+            .iter()
+            .filter(|(kind, _)| kinds.contains(kind))
+            .map(|(_, line)| line.as_str())
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Hunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let range = |start: usize, count: usize| match count {
+            1 => start.to_string(),
+            _ => format!("{start},{count}"),
+        };
+        writeln!(
+            f,
+            "@@ -{} +{} @@",
+            range(self.old_start, self.lines_of(&['-', ' ']).len()),
+            range(self.new_start, self.lines_of(&['+', ' ']).len())
+        )?;
+        for (kind, line) in &self.lines {
+            match line.strip_suffix('\n') {
+                Some(line) => writeln!(f, "{kind}{line}")?,
+                None => writeln!(f, "{kind}{line}\n\\ No newline at end of file")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads the file patches of a unified diff, ignoring the lines around them.
+fn parse(text: &str) -> Vec<FilePatch> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let path = |header: &str| {
+        header
+            .split('\t')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string()
+    };
+    let mut patches: Vec<FilePatch> = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        if let (Some(old), Some(new)) = (
+            line.strip_prefix("--- "),
+            lines
+                .get(index + 1)
+                .and_then(|line| line.strip_prefix("+++ ")),
+        ) {
+            patches.push(FilePatch {
+                old_path: path(old),
+                new_path: path(new),
+                line: index + 1,
+                hunks: Vec::new(),
+            });
+            index += 2;
+            continue;
+        }
+        index += 1;
+        let (Some(patch), Some((old_start, old_count, new_start, new_count))) =
+            (patches.last_mut(), parse_hunk_header(line))
+        else {
+            continue;
+        };
+        let mut hunk = Hunk {
+            old_start,
+            new_start,
+            lines: Vec::new(),
+        };
+        let (mut old_left, mut new_left) = (old_count, new_count);
+        while index < lines.len() {
+            let line = lines[index];
+            // A marker follows a line without a newline, which may not be the last of the hunk.
+            if line.starts_with('\\') {
+                if let Some((_, line)) = hunk.lines.last_mut() {
+                    line.pop();
+                }
+                index += 1;
+                continue;
+            }
+            if old_left + new_left == 0 {
+                break;
+            }
+            let (kind, content) = match line.chars().next() {
+                Some(kind @ (' ' | '-' | '+')) => (kind, &line[1..]),
+                // Empty context lines may have lost their space.
+                None => (' ', ""),
+                Some(_) => break,
+            };
+            match kind {
+                ' ' if old_left > 0 && new_left > 0 => {
+                    (old_left, new_left) = (old_left - 1, new_left - 1)
+                }
+                '-' if old_left > 0 => old_left -= 1,
+                '+' if new_left > 0 => new_left -= 1,
+                _ => break,
+            }
+            hunk.lines.push((kind, format!("{content}\n")));
+            index += 1;
+        }
+        patch.hunks.push(hunk);
+    }
+    patches.retain(|patch| !patch.hunks.is_empty());
+    patches
+}
+
+/// Reads a hunk header like `@@ -1,3 +1,4 @@`, returning its starts and counts.
+fn parse_hunk_header(line: &str) -> Option<(usize, usize, usize, usize)> {
+    let ranges = line.strip_prefix("@@ -")?;
+    let (ranges, _) = ranges.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let range = |range: &str| -> Option<(usize, usize)> {
+        match range.split_once(',') {
+            Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+            None => Some((range.parse().ok()?, 1)),
+        }
+    };
+    let (old_start, old_count) = range(old)?;
+    let (new_start, new_count) = range(new)?;
+    Some((old_start, old_count, new_start, new_count))
+}
+
+/// Removes the first components of a path, keeping only its file name without a count.
+fn strip_path(path: &str, strip: Option<usize>) -> Option<String> {
+    let components = path.split('/').collect::<Vec<_>>();
+    match strip {
+        None => components.last().map(|name| name.to_string()),
+        Some(count) if count < components.len() => Some(components[count..].join("/")),
+        Some(_) => None,
+    }
+}
+
+fn plural(word: &str, count: usize) -> String {
+    if count == 1 {
+        word.to_string()
+    } else {
+        format!("{word}s")
+    }
+}
@@ -7,8 +7,10 @@ use crate::{
     commands::{
         Argument, CommandOutput, ExecutableCommand, Flags,
         args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        error_message,
         flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
     },
+    errors::FileSystemError,
 };
 
 #[derive(Default, Clone, Copy)]
@@ -47,7 +49,7 @@ impl ExecutableCommand for RemoveCommand {
         shell: &mut crate::shell::Shell,
     ) -> Result<CommandOutput, ShellError> {
         let mut current_session = shell.current_session.clone();
-        let mut fs = shell.fs.clone();
+        let mut fs = shell.fs.lock();
 
         let force = flags.flag("force").is_some();
         let recursive = flags.flag("recursive").is_some();
@@ -66,39 +68,40 @@ impl ExecutableCommand for RemoveCommand {
             };
 
             let path = PathBuf::from(path);
-            match current_session.find_inode(&fs, &path) {
-                Some(inode) => {
-                    let inode = inode.lock().expect("Failed to lock inode");
+            match current_session.lookup_inode(&fs, &path) {
+                Ok(inode) => {
                     if let InodeContent::Directory(_) = &inode.content
                         && !recursive
-                        && !force
                     {
                         errors.push(format!(
                             "rm: cannot remove '{}': Is a directory",
                             path.display()
                         ));
-                    } else {
-                        drop(inode); // Drop the lock to avoid deadlocks
-                        if let Err(error) = current_session.remove_file(&mut fs, &path) {
-                            if !force {
-                                errors.push(format!(
-                                    "rm: cannot remove '{}': {error}",
-                                    path.display()
-                                ));
-                            }
-                        }
+                        continue;
+                    }
+                    if let Err(error) = current_session.remove_file(&mut fs, &path) {
+                        errors.push(format!(
+                            "rm: cannot remove '{}': {}",
+                            path.display(),
+                            error_message(error)?
+                        ));
                     }
                 }
-                None if !force => {
+                Err(ShellError::FileSystem(FileSystemError::EntryNotFound(_))) if force => {}
+                Err(error) => {
                     errors.push(format!(
-                        "rm: cannot remove '{}': No such file or directory",
-                        path.display()
+                        "rm: cannot remove '{}': {}",
+                        path.display(),
+                        error_message(error)?
                     ));
                 }
-                None => {}
             }
         }
 
-        Ok(CommandOutput(None))
+        if errors.is_empty() {
+            Ok(CommandOutput(None))
+        } else {
+            Ok(CommandOutput(Some(errors.join("\n"))))
+        }
     }
 }
@@ -0,0 +1,805 @@
+//! The sed command, editing streams of lines with a script.
+//!
+//! Scripts support the `s`, `y`, `d`, `p`, `a`, `i`, `c`, `q` and `=` commands, `{...}` blocks,
+//! and addresses made of line numbers, `$`, regular expressions and ranges, possibly negated with
+//! `!`. Regular expressions are translated like those of grep.
+
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::grep::{Syntax, build_regex},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct SedCommand;
+
+impl ExecutableCommand for SedCommand {
+    fn name(&self) -> &'static str {
+        "sed"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "quiet",
+                Some('n'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "in-place",
+                Some('i'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "regexp-extended",
+                Some('E'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "expression",
+                Some('e'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Runs a script, given with `-e` or as the first operand, on the lines of the files given,
+    /// or of the standard input.
+    ///
+    /// Files are edited as a single stream, unless they are edited in place with `-i`.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?;
+        let mut expressions = flags
+            .flag_all("expression")
+            .iter()
+            .filter_map(|expression| match expression {
+                Argument::Basic(BasicArgument::String(expression)) => Some(expression.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if expressions.is_empty() {
+            if operands.is_empty() {
+                shell.set_exit_status(1);
+                return Ok(CommandOutput(Some(
+                    "Usage: sed [OPTION]... {script-only-if-no-other-script} [input-file]..."
+                        .to_string(),
+                )));
+            }
+            expressions.push(operands.remove(0));
+        }
+        let syntax = if flags.flag("regexp-extended").is_some() {
+            Syntax::Extended
+        } else {
+            Syntax::Basic
+        };
+        let mut commands = Vec::new();
+        for (number, expression) in expressions.iter().enumerate() {
+            let mut parser = Parser::new(expression, syntax, commands.len());
+            match parser.parse() {
+                Ok(parsed) => commands.extend(parsed),
+                Err(message) => {
+                    shell.set_exit_status(1);
+                    return Ok(CommandOutput(Some(format!(
+                        "sed: -e expression #{}, char {}: {message}",
+                        number + 1,
+                        parser.index
+                    ))));
+                }
+            }
+        }
+        let quiet = flags.flag("quiet").is_some();
+
+        let mut errors = Vec::new();
+        if flags.flag("in-place").is_some() {
+            if operands.is_empty() {
+                shell.set_exit_status(1);
+                return Ok(CommandOutput(Some("sed: no input files".to_string())));
+            }
+            for path in operands {
+                let content = match read_input(shell, &path) {
+                    Ok(content) => content,
+                    Err(error) => {
+                        errors.push(format!("sed: can't read {path}: {}", error_message(error)?));
+                        continue;
+                    }
+                };
+                let lines = content.split_terminator('\n').collect::<Vec<_>>();
+                let mut output = Sed::new(&commands, quiet).run(&lines).join("\n");
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                let written = shell.current_session.write_file(
+                    &mut shell.fs.lock(),
+                    Path::new(&path),
+                    &output,
+                );
+                if let Err(error) = written {
+                    errors.push(format!(
+                        "sed: couldn't edit {path}: {}",
+                        error_message(error)?
+                    ));
+                }
+            }
+            if !errors.is_empty() {
+                shell.set_exit_status(4);
+            }
+            return Ok(CommandOutput(
+                (!errors.is_empty()).then(|| errors.join("\n")),
+            ));
+        }
+
+        if operands.is_empty() {
+            operands.push("-".to_string());
+        }
+        let mut content = String::new();
+        for path in operands {
+            match read_input(shell, &path) {
+                Ok(input) => {
+                    content.push_str(&input);
+                    if !content.is_empty() && !content.ends_with('\n') {
+                        content.push('\n');
+                    }
+                }
+                Err(error) => {
+                    errors.push(format!("sed: can't read {path}: {}", error_message(error)?));
+                }
+            }
+        }
+        if !errors.is_empty() {
+            shell.set_exit_status(2);
+        }
+        let lines = content.split_terminator('\n').collect::<Vec<_>>();
+        let mut output = Sed::new(&commands, quiet).run(&lines);
+        output.extend(errors);
+        Ok(CommandOutput(
+            (!output.is_empty()).then(|| output.join("\n")),
+        ))
+    }
+}
+
+/// A command of a script, with the lines it applies to.
+#[derive(Debug)]
+struct Command {
+    start: Option<Address>,
+    end: Option<Address>,
+    negated: bool,
+    action: Action,
+}
+
+#[derive(Debug)]
+enum Address {
+    Line(usize),
+    Last,
+    Pattern(Regex),
+}
+
+impl Address {
+    fn matches(&self, line_number: usize, last: bool, pattern_space: &str) -> bool {
+        match self {
+            Address::Line(number) => *number == line_number,
+            Address::Last => last,
+            Address::Pattern(regex) => regex.is_match(pattern_space),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Action {
+    /// The start of a block, with the index of the command ending it.
+    Block(usize),
+    EndBlock,
+    Substitute(Substitution),
+    Transliterate(Vec<(char, char)>),
+    Delete,
+    Print,
+    Insert(String),
+    Append(String),
+    Change(String),
+    Quit,
+    LineNumber,
+}
+
+#[derive(Debug)]
+struct Substitution {
+    regex: Regex,
+    replacement: Vec<Replacement>,
+    global: bool,
+    /// The match replaced first, counted from 1.
+    occurrence: usize,
+    print: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Replacement {
+    Literal(String),
+    /// `&`, the whole match.
+    Match,
+    /// `\N`, a group of the match.
+    Group(usize),
+}
+
+impl Substitution {
+    /// Returns the text with the matches replaced, if there was one.
+    fn apply(&self, text: &str) -> Option<String> {
+        let mut result = String::new();
+        let mut last = 0;
+        let mut replaced = false;
+        for (index, captures) in self.regex.captures_iter(text).enumerate() {
+            if index + 1 < self.occurrence {
+                continue;
+            }
+            let Some(matched) = captures.get(0) else {
+                continue;
+            };
+            result.push_str(&text[last..matched.start()]);
+            for part in &self.replacement {
+                match part {
+                    Replacement::Literal(literal) => result.push_str(literal),
+                    Replacement::Match => result.push_str(matched.as_str()),
+                    Replacement::Group(group) => {
+                        result.push_str(captures.get(*group).map_or("", |group| group.as_str()))
+                    }
+                }
+            }
+            last = matched.end();
+            replaced = true;
+            if !self.global {
+                break;
+            }
+        }
+        replaced.then(|| result + &text[last..])
+    }
+}
+
+/// Parses an expression of a script into commands.
+struct Parser {
+    chars: Vec<char>,
+    /// The number of characters read, which error messages refer to.
+    index: usize,
+    syntax: Syntax,
+    /// The index of the first command parsed in the whole script, blocks referring to commands
+    /// by index.
+    offset: usize,
+}
+
+impl Parser {
+    fn new(expression: &str, syntax: Syntax, offset: usize) -> Self {
+        Self {
+            chars: expression.chars().collect(),
+            index: 0,
+            syntax,
+            offset,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let char = self.peek()?;
+        self.index += 1;
+        Some(char)
+    }
+
+    fn skip_blanks(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t')) {
+            self.index += 1;
+        }
+    }
+
+    fn parse(&mut self) -> Result<Vec<Command>, String> {
+        let mut commands: Vec<Command> = Vec::new();
+        let mut blocks = Vec::new();
+        loop {
+            while matches!(self.peek(), Some(' ' | '\t' | '\n' | ';')) {
+                self.index += 1;
+            }
+            if self.peek().is_none() {
+                break;
+            }
+            let start = self.parse_address()?;
+            let end = if start.is_some() && self.peek() == Some(',') {
+                self.index += 1;
+                self.skip_blanks();
+                Some(self.parse_address()?.ok_or("unexpected `,'".to_string())?)
+            } else {
+                None
+            };
+            self.skip_blanks();
+            let negated = self.peek() == Some('!');
+            if negated {
+                self.index += 1;
+                self.skip_blanks();
+            }
+            let Some(letter) = self.next() else {
+                return Err("missing command".to_string());
+            };
+            let action = match letter {
+                '{' => {
+                    blocks.push(commands.len());
+                    Action::Block(0)
+                }
+                '}' => {
+                    if start.is_some() {
+                        return Err("} doesn't want any addresses".to_string());
+                    }
+                    let Some(block) = blocks.pop() else {
+                        return Err("unexpected `}'".to_string());
+                    };
+                    let end = self.offset + commands.len();
+                    commands[block].action = Action::Block(end);
+                    Action::EndBlock
+                }
+                's' => Action::Substitute(self.parse_substitution()?),
+                'y' => Action::Transliterate(self.parse_transliteration()?),
+                'd' => Action::Delete,
+                'p' => Action::Print,
+                'q' => Action::Quit,
+                '=' => Action::LineNumber,
+                'a' => Action::Append(self.parse_text()?),
+                'i' => Action::Insert(self.parse_text()?),
+                'c' => Action::Change(self.parse_text()?),
+                '#' if start.is_none() => {
+                    while !matches!(self.next(), Some('\n') | None) {}
+                    continue;
+                }
+                letter => return Err(format!("unknown command: `{letter}'")),
+            };
+            // A block is directly followed by its commands.
+            let block = matches!(action, Action::Block(_));
+            commands.push(Command {
+                start,
+                end,
+                negated,
+                action,
+            });
+            self.skip_blanks();
+            if block {
+                continue;
+            }
+            match self.peek() {
+                None | Some(';' | '\n' | '}' | '#') => {}
+                Some(_) => return Err("extra characters after command".to_string()),
+            }
+        }
+        if !blocks.is_empty() {
+            return Err("unmatched `{'".to_string());
+        }
+        Ok(commands)
+    }
+
+    fn parse_address(&mut self) -> Result<Option<Address>, String> {
+        match self.peek() {
+            Some('0'..='9') => {
+                let mut number = String::new();
+                while let Some(digit) = self.peek().filter(char::is_ascii_digit) {
+                    number.push(digit);
+                    self.index += 1;
+                }
+                match number.parse() {
+                    Ok(0) => Err("invalid usage of line address 0".to_string()),
+                    Ok(number) => Ok(Some(Address::Line(number))),
+                    Err(_) => Err("invalid line number".to_string()),
+                }
+            }
+            Some('$') => {
+                self.index += 1;
+                Ok(Some(Address::Last))
+            }
+            Some('/' | '\\') => {
+                let mut delimiter = self.next();
+                if delimiter == Some('\\') {
+                    delimiter = self.next();
+                }
+                let delimiter = delimiter.ok_or("unexpected end of expression".to_string())?;
+                let pattern = self
+                    .read_delimited(delimiter)
+                    .ok_or("unterminated address regex".to_string())?;
+                let ignore_case = self.peek() == Some('I');
+                if ignore_case {
+                    self.index += 1;
+                }
+                Ok(Some(Address::Pattern(self.compile(&pattern, ignore_case)?)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn compile(&self, pattern: &str, ignore_case: bool) -> Result<Regex, String> {
+        if pattern.is_empty() {
+            return Err("no previous regular expression".to_string());
+        }
+        build_regex(pattern, self.syntax, ignore_case, false, false)
+    }
+
+    /// Reads up to an unescaped delimiter, which is consumed, unescaping the delimiter and
+    /// newlines.
+    fn read_delimited(&mut self, delimiter: char) -> Option<String> {
+        let mut text = String::new();
+        loop {
+            match self.next()? {
+                char if char == delimiter => return Some(text),
+                '\n' => return None,
+                '\\' => match self.next()? {
+                    char if char == delimiter => text.push(char),
+                    'n' => text.push('\n'),
+                    char => {
+                        text.push('\\');
+                        text.push(char);
+                    }
+                },
+                char => text.push(char),
+            }
+        }
+    }
+
+    fn parse_substitution(&mut self) -> Result<Substitution, String> {
+        let unterminated = || "unterminated `s' command".to_string();
+        let delimiter = match self.next() {
+            Some('\n' | '\\') | None => return Err(unterminated()),
+            Some(delimiter) => delimiter,
+        };
+        let pattern = self.read_delimited(delimiter).ok_or_else(unterminated)?;
+        let replacement = self.read_delimited(delimiter).ok_or_else(unterminated)?;
+
+        let mut global = false;
+        let mut print = false;
+        let mut ignore_case = false;
+        let mut occurrence = None;
+        while let Some(flag) = self.peek() {
+            match flag {
+                'g' => global = true,
+                'p' => print = true,
+                'i' | 'I' => ignore_case = true,
+                '0'..='9' => {
+                    let mut number = String::new();
+                    while let Some(digit) = self.peek().filter(char::is_ascii_digit) {
+                        number.push(digit);
+                        self.index += 1;
+                    }
+                    match number.parse() {
+                        Ok(0) | Err(_) => {
+                            return Err("number option to `s' command may not be zero".to_string());
+                        }
+                        Ok(number) => occurrence = Some(number),
+                    }
+                    continue;
+                }
+                ';' | '\n' | '}' | '#' | ' ' | '\t' => break,
+                _ => return Err("unknown option to `s'".to_string()),
+            }
+            self.index += 1;
+        }
+
+        let regex = self.compile(&pattern, ignore_case)?;
+        let replacement = parse_replacement(&replacement);
+        for part in &replacement {
+            if let Replacement::Group(group) = part
+                && *group >= regex.captures_len()
+            {
+                return Err(format!("invalid reference \\{group} on `s' command's RHS"));
+            }
+        }
+        Ok(Substitution {
+            regex,
+            replacement,
+            global,
+            occurrence: occurrence.unwrap_or(1),
+            print,
+        })
+    }
+
+    fn parse_transliteration(&mut self) -> Result<Vec<(char, char)>, String> {
+        let unterminated = || "unterminated `y' command".to_string();
+        let delimiter = match self.next() {
+            Some('\n' | '\\') | None => return Err(unterminated()),
+            Some(delimiter) => delimiter,
+        };
+        let mut sets = Vec::new();
+        for _ in 0..2 {
+            let set = self.read_delimited(delimiter).ok_or_else(unterminated)?;
+            let mut chars = Vec::new();
+            let mut iter = set.chars();
+            while let Some(char) = iter.next() {
+                chars.push(match char {
+                    '\\' => match iter.next() {
+                        Some('\\') => '\\',
+                        Some('t') => '\t',
+                        _ => return Err("unknown option to `y'".to_string()),
+                    },
+                    char => char,
+                });
+            }
+            sets.push(chars);
+        }
+        if sets[0].len() != sets[1].len() {
+            return Err("strings for `y' command are different lengths".to_string());
+        }
+        Ok(sets[0]
+            .iter()
+            .copied()
+            .zip(sets[1].iter().copied())
+            .collect())
+    }
+
+    /// Parses the text of `a`, `i` and `c`, given as `a\`, a newline and the text, or as `a text`
+    /// on a single line.
+    fn parse_text(&mut self) -> Result<String, String> {
+        self.skip_blanks();
+        if self.peek() == Some('\\') {
+            self.index += 1;
+            if self.peek() == Some('\n') {
+                self.index += 1;
+            } else {
+                self.skip_blanks();
+            }
+        }
+        if self.peek().is_none() {
+            return Err("expected \\ after `a', `c' or `i'".to_string());
+        }
+        let mut text = String::new();
+        while let Some(char) = self.peek().filter(|char| *char != '\n') {
+            self.index += 1;
+            match char {
+                '\\' => match self.next() {
+                    Some('t') => text.push('\t'),
+                    Some(char) => text.push(char),
+                    None => break,
+                },
+                char => text.push(char),
+            }
+        }
+        Ok(text)
+    }
+}
+
+/// Parses the replacement of `s`, where `&` is the match, `\N` a group and `\n` a newline.
+fn parse_replacement(replacement: &str) -> Vec<Replacement> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = replacement.chars();
+    while let Some(char) = chars.next() {
+        let part = match char {
+            '&' => Replacement::Match,
+            '\\' => match chars.next() {
+                Some(digit @ '0'..='9') => {
+                    let group = digit.to_digit(10).unwrap_or_default() as usize;
+                    if group == 0 {
+                        Replacement::Match
+                    } else {
+                        Replacement::Group(group)
+                    }
+                }
+                Some('t') => {
+                    literal.push('\t');
+                    continue;
+                }
+                Some(char) => {
+                    literal.push(char);
+                    continue;
+                }
+                None => continue,
+            },
+            char => {
+                literal.push(char);
+                continue;
+            }
+        };
+        if !literal.is_empty() {
+            parts.push(Replacement::Literal(std::mem::take(&mut literal)));
+        }
+        parts.push(part);
+    }
+    if !literal.is_empty() {
+        parts.push(Replacement::Literal(literal));
+    }
+    parts
+}
+
+/// Runs commands on lines.
+struct Sed<'a> {
+    commands: &'a [Command],
+    quiet: bool,
+    /// Whether each command with a range is inside it.
+    in_range: Vec<bool>,
+}
+
+impl<'a> Sed<'a> {
+    fn new(commands: &'a [Command], quiet: bool) -> Self {
+        Self {
+            commands,
+            quiet,
+            in_range: vec![false; commands.len()],
+        }
+    }
+
+    /// Checks if a command applies to the current line, updating the state of its range.
+    fn selects(
+        &mut self,
+        index: usize,
+        line_number: usize,
+        last: bool,
+        pattern_space: &str,
+    ) -> bool {
+        let command = &self.commands[index];
+        let selected = match (&command.start, &command.end) {
+            (None, _) => true,
+            (Some(start), None) => start.matches(line_number, last, pattern_space),
+            (Some(start), Some(end)) => {
+                if self.in_range[index] {
+                    self.in_range[index] = !match end {
+                        Address::Line(number) => line_number >= *number,
+                        end => end.matches(line_number, last, pattern_space),
+                    };
+                    true
+                } else if start.matches(line_number, last, pattern_space) {
+                    // A regular expression ending a range is only checked from the next line.
+                    self.in_range[index] = match end {
+                        Address::Line(number) => line_number < *number,
+                        Address::Last => !last,
+                        Address::Pattern(_) => true,
+                    };
+                    true
+                } else {
+                    false
+                }
+            }
+        };
+        selected != command.negated
+    }
+
+    /// Returns the lines printed by the script.
+    fn run(mut self, lines: &[&str]) -> Vec<String> {
+        let mut output = Vec::new();
+        for (index, line) in lines.iter().enumerate() {
+            let line_number = index + 1;
+            let last = line_number == lines.len();
+            let mut pattern_space = line.to_string();
+            let mut appended = Vec::new();
+            let mut deleted = false;
+            let mut quit = false;
+            let mut command_index = 0;
+            while command_index < self.commands.len() {
+                let selected = self.selects(command_index, line_number, last, &pattern_space);
+                let action = &self.commands[command_index].action;
+                command_index += 1;
+                if !selected {
+                    if let Action::Block(end) = action {
+                        command_index = end + 1;
+                    }
+                    continue;
+                }
+                match action {
+                    Action::Block(_) | Action::EndBlock => {}
+                    Action::Substitute(substitution) => {
+                        if let Some(result) = substitution.apply(&pattern_space) {
+                            pattern_space = result;
+                            if substitution.print {
+                                output.push(pattern_space.clone());
+                            }
+                        }
+                    }
+                    Action::Transliterate(pairs) => {
+                        pattern_space = pattern_space
+                            .chars()
+                            .map(|char| {
+                                pairs
+                                    .iter()
+                                    .find(|(from, _)| *from == char)
+                                    .map_or(char, |(_, to)| *to)
+                            })
+                            .collect();
+                    }
+                    Action::Delete => {
+                        deleted = true;
+                        break;
+                    }
+                    Action::Print => output.push(pattern_space.clone()),
+                    Action::Insert(text) => output.push(text.clone()),
+                    Action::Append(text) => appended.push(text.clone()),
+                    Action::Change(text) => {
+                        // The text replaces a whole range, so it is printed at its end.
+                        if !self.in_range[command_index - 1] {
+                            output.push(text.clone());
+                        }
+                        deleted = true;
+                        break;
+                    }
+                    Action::Quit => {
+                        quit = true;
+                        break;
+                    }
+                    Action::LineNumber => output.push(line_number.to_string()),
+                }
+            }
+            if !deleted && !self.quiet {
+                output.push(pattern_space);
+            }
+            output.extend(appended);
+            if quit {
+                break;
+            }
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sed(script: &str, quiet: bool, lines: &[&str]) -> Vec<String> {
+        let commands = Parser::new(script, Syntax::Basic, 0)
+            .parse()
+            .expect("Invalid script");
+        Sed::new(&commands, quiet).run(lines)
+    }
+
+    #[test]
+    fn test_parse_replacement() {
+        assert_eq!(
+            parse_replacement(r"<&>\1\&\\"),
+            vec![
+                Replacement::Literal("<".to_string()),
+                Replacement::Match,
+                Replacement::Literal(">".to_string()),
+                Replacement::Group(1),
+                Replacement::Literal("&\\".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |script| Parser::new(script, Syntax::Basic, 0).parse().err();
+        assert_eq!(error("s/a/b"), Some("unterminated `s' command".to_string()));
+        assert_eq!(error("s/a/b/x"), Some("unknown option to `s'".to_string()));
+        assert_eq!(error("k"), Some("unknown command: `k'".to_string()));
+        assert_eq!(error("{p"), Some("unmatched `{'".to_string()));
+        assert_eq!(
+            error("pq"),
+            Some("extra characters after command".to_string())
+        );
+        assert_eq!(
+            error("s/a/\\1/"),
+            Some("invalid reference \\1 on `s' command's RHS".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run() {
+        let lines = ["one", "two", "three", "four"];
+        assert_eq!(sed("2,3d", false, &lines), vec!["one", "four"]);
+        assert_eq!(sed("/t/,/t/!d", false, &lines), vec!["two", "three"]);
+        assert_eq!(sed("$p", true, &lines), vec!["four"]);
+        assert_eq!(sed("/^t/{s/o/0/g;p}", true, &lines), vec!["tw0", "three"]);
+        assert_eq!(sed("2q", false, &lines), vec!["one", "two"]);
+        assert_eq!(sed("s/e/E/2", false, &["eee"]), vec!["eEe"]);
+        assert_eq!(sed("s/e/E/2g", false, &["eee"]), vec!["eEE"]);
+    }
+}
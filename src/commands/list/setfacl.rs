@@ -0,0 +1,367 @@
+//! The setfacl command, modifying the access control lists of files.
+
+use std::path::Path;
+
+use crate::{
+    FileSystem, InodeContent,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        resolve_group, resolve_user,
+    },
+    errors::ShellError,
+    fs::permissions::{Permission, acl::Acl},
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct SetFileAclCommand;
+
+/// The entry of an ACL targeted by a `setfacl` specification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryTag {
+    User(Option<u32>),
+    Group(Option<u32>),
+    Mask,
+    Other,
+}
+
+/// A parsed ACL entry specification, like `default:user:bob:rw-`.
+#[derive(Debug, Clone, Copy)]
+struct EntrySpec {
+    default: bool,
+    tag: EntryTag,
+    /// The permission, or `None` for the specifications of `-x`.
+    permission: Option<PermissionSpec>,
+}
+
+/// The permission of an entry specification, where `X` grants execute only to directories.
+#[derive(Debug, Clone, Copy)]
+struct PermissionSpec {
+    permission: Permission,
+    conditional_execute: bool,
+}
+
+impl PermissionSpec {
+    fn resolve(&self, is_directory: bool) -> Permission {
+        let mut permission = self.permission;
+        permission.execute |= self.conditional_execute && is_directory;
+        permission
+    }
+}
+
+impl ExecutableCommand for SetFileAclCommand {
+    fn name(&self) -> &'static str {
+        "setfacl"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "modify",
+                Some('m'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "remove",
+                Some('x'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "remove-all",
+                Some('b'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "remove-default",
+                Some('k'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "default",
+                Some('d'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut crate::shell::Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut fs = shell.fs.lock();
+        let paths = string_arguments(args)?;
+        let default = flags.flag("default").is_some();
+
+        let mut modifications = Vec::new();
+        let mut removals = Vec::new();
+        for (name, option, specs, with_permission) in [
+            ("modify", 'm', &mut modifications, true),
+            ("remove", 'x', &mut removals, false),
+        ] {
+            match flags.flag(name) {
+                Some(Argument::Basic(BasicArgument::String(value))) => {
+                    match parse_specs(&fs, value, with_permission, default) {
+                        Ok(parsed) => *specs = parsed,
+                        Err(position) => {
+                            return Ok(CommandOutput(Some(format!(
+                                "setfacl: Option -{option}: Invalid argument near character {position}"
+                            ))));
+                        }
+                    }
+                }
+                Some(_) => return Err(ShellError::Internal(format!("Invalid {name} flag"))),
+                None => {}
+            }
+        }
+        let remove_all = flags.flag("remove-all").is_some();
+        let remove_default = remove_all || flags.flag("remove-default").is_some();
+
+        if modifications.is_empty() && removals.is_empty() && !remove_all && !remove_default {
+            return Ok(CommandOutput(Some(
+                "setfacl: no ACL modification specified".to_string(),
+            )));
+        }
+        if paths.is_empty() {
+            return Ok(CommandOutput(Some(
+                "setfacl: no files specified".to_string(),
+            )));
+        }
+
+        let mut errors = Vec::new();
+        for path in paths {
+            let inode = match shell.current_session.lookup_inode(&fs, Path::new(&path)) {
+                Ok(inode) => inode,
+                Err(error) => {
+                    errors.push(format!("setfacl: {path}: {}", error_message(error)?));
+                    continue;
+                }
+            };
+            let metadata = inode.metadata.clone();
+            let is_directory = matches!(inode.content, InodeContent::Directory(_));
+
+            let mut access_acl = metadata.access_acl();
+            let mut default_acl = metadata.default_acl.clone();
+            if remove_all {
+                // The owning group keeps only what the mask granted it.
+                access_acl.group = access_acl.effective(&access_acl.group);
+                access_acl.mask = None;
+                access_acl.users.clear();
+                access_acl.groups.clear();
+            }
+            if remove_default {
+                default_acl = None;
+            }
+
+            let touches_default = removals
+                .iter()
+                .chain(&modifications)
+                .any(|spec| spec.default);
+            if touches_default && !is_directory {
+                errors.push(format!(
+                    "setfacl: {path}: Only directories can have default ACLs"
+                ));
+                continue;
+            }
+
+            for spec in &removals {
+                let acl = if spec.default {
+                    match default_acl.as_mut() {
+                        Some(acl) => acl,
+                        None => continue,
+                    }
+                } else {
+                    &mut access_acl
+                };
+                remove_entry(acl, spec.tag);
+            }
+
+            let mut explicit_mask = [false, false];
+            for spec in &modifications {
+                let acl = if spec.default {
+                    // A new default ACL starts as a copy of the access ACL.
+                    default_acl.get_or_insert_with(|| access_acl.clone())
+                } else {
+                    &mut access_acl
+                };
+                let permission = spec
+                    .permission
+                    .expect("Modifications have a permission")
+                    .resolve(is_directory);
+                match spec.tag {
+                    EntryTag::User(None) => acl.user = permission,
+                    EntryTag::User(Some(user_id)) => {
+                        acl.users.insert(user_id, permission);
+                    }
+                    EntryTag::Group(None) => acl.group = permission,
+                    EntryTag::Group(Some(group_id)) => {
+                        acl.groups.insert(group_id, permission);
+                    }
+                    EntryTag::Mask => {
+                        acl.mask = Some(permission);
+                        explicit_mask[usize::from(spec.default)] = true;
+                    }
+                    EntryTag::Other => acl.other = permission,
+                }
+            }
+
+            // Like setfacl, the mask is recalculated unless it was given explicitly.
+            if !removals.is_empty() || !modifications.is_empty() {
+                for (acl, explicit_mask) in [Some(&mut access_acl), default_acl.as_mut()]
+                    .into_iter()
+                    .flatten()
+                    .zip(explicit_mask)
+                {
+                    if !explicit_mask && (acl.mask.is_some() || acl.has_named_entries()) {
+                        acl.recalculate_mask();
+                    }
+                }
+            }
+
+            if let Err(error) =
+                shell
+                    .current_session
+                    .set_acl(&mut fs, Path::new(&path), access_acl, default_acl)
+            {
+                errors.push(format!("setfacl: {path}: {}", error_message(error)?));
+            }
+        }
+
+        Ok(CommandOutput(
+            (!errors.is_empty()).then(|| errors.join("\n")),
+        ))
+    }
+}
+
+/// Removes an entry from an ACL. The mask is kept as long as named entries need it.
+fn remove_entry(acl: &mut Acl, tag: EntryTag) {
+    match tag {
+        EntryTag::User(Some(user_id)) => {
+            acl.users.remove(&user_id);
+        }
+        EntryTag::Group(Some(group_id)) => {
+            acl.groups.remove(&group_id);
+        }
+        EntryTag::Mask if !acl.has_named_entries() => acl.mask = None,
+        _ => {}
+    }
+    if !acl.has_named_entries() && acl.mask.is_some() {
+        // Without named entries, the ACL is minimal again and the group class is the group entry.
+        acl.mask = None;
+    }
+}
+
+/// Parses a comma-separated list of entry specifications.
+///
+/// On failure, the 1-based position of the invalid entry is returned, like setfacl reports it.
+fn parse_specs(
+    fs: &FileSystem,
+    specs: &str,
+    with_permission: bool,
+    default: bool,
+) -> Result<Vec<EntrySpec>, usize> {
+    let mut entries = Vec::new();
+    let mut position = 1;
+    for spec in specs.split(',') {
+        let entry = parse_spec(fs, spec, with_permission).ok_or(position)?;
+        entries.push(EntrySpec {
+            default: default || entry.default,
+            ..entry
+        });
+        position += spec.chars().count() + 1;
+    }
+    Ok(entries)
+}
+
+fn parse_spec(fs: &FileSystem, spec: &str, with_permission: bool) -> Option<EntrySpec> {
+    let mut parts: Vec<&str> = spec.split(':').collect();
+    let default = matches!(parts.first(), Some(&"d" | &"default"));
+    if default {
+        parts.remove(0);
+    }
+
+    let (tag, rest) = parts.split_first()?;
+    let (tag, rest) = match *tag {
+        "u" | "user" => {
+            let (qualifier, rest) = rest.split_first()?;
+            let user = match *qualifier {
+                "" => None,
+                name => Some(resolve_user(fs, name)?),
+            };
+            (EntryTag::User(user), rest)
+        }
+        "g" | "group" => {
+            let (qualifier, rest) = rest.split_first()?;
+            let group = match *qualifier {
+                "" => None,
+                name => Some(resolve_group(fs, name)?),
+            };
+            (EntryTag::Group(group), rest)
+        }
+        "m" | "mask" => (EntryTag::Mask, rest.strip_prefix(&[""][..]).unwrap_or(rest)),
+        "o" | "other" => (
+            EntryTag::Other,
+            rest.strip_prefix(&[""][..]).unwrap_or(rest),
+        ),
+        _ => return None,
+    };
+
+    let permission = match (rest, with_permission) {
+        ([permission], true) => Some(parse_permission(permission)?),
+        ([], false) => None,
+        _ => return None,
+    };
+    // Only the named entries and the mask can be removed.
+    if !with_permission
+        && matches!(
+            tag,
+            EntryTag::User(None) | EntryTag::Group(None) | EntryTag::Other
+        )
+    {
+        return None;
+    }
+
+    Some(EntrySpec {
+        default,
+        tag,
+        permission,
+    })
+}
+
+/// Parses permissions written as `rwx` letters, with `-` placeholders, or as an octal digit.
+fn parse_permission(permission: &str) -> Option<PermissionSpec> {
+    if let Ok(bits @ 0..=7) = permission.parse::<u32>() {
+        return Some(PermissionSpec {
+            permission: Permission::from_bits(bits),
+            conditional_execute: false,
+        });
+    }
+
+    let mut spec = PermissionSpec {
+        permission: Permission::default(),
+        conditional_execute: false,
+    };
+    for letter in permission.chars() {
+        match letter {
+            'r' => spec.permission.read = true,
+            'w' => spec.permission.write = true,
+            'x' => spec.permission.execute = true,
+            'X' => spec.conditional_execute = true,
+            '-' => {}
+            _ => return None,
+        }
+    }
+    Some(spec)
+}
@@ -0,0 +1,440 @@
+//! The sort command, sorting the lines of files.
+//!
+//! Lines are compared byte by byte, like GNU sort in the C locale. When the keys of two lines
+//! compare equal, the whole lines are compared as a last resort, unless `-u` is given.
+
+use std::cmp::Ordering;
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct SortCommand;
+
+impl ExecutableCommand for SortCommand {
+    fn name(&self) -> &'static str {
+        "sort"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        let flags = [
+            ("numeric-sort", 'n'),
+            ("human-numeric-sort", 'h'),
+            ("reverse", 'r'),
+            ("ignore-case", 'f'),
+            ("ignore-leading-blanks", 'b'),
+            ("unique", 'u'),
+        ];
+        let mut builder = FlagDefinitionBuilder::new();
+        for (name, short_hand) in flags {
+            builder = builder.with_flag(FlagSpecification::new(
+                name,
+                Some(short_hand),
+                false,
+                ArgumentKind::Flag,
+            ));
+        }
+        builder
+            .with_flag(FlagSpecification::new(
+                "key",
+                Some('k'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "field-separator",
+                Some('t'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Sorts the lines of the files given, or of the standard input, by the keys given with
+    /// `-k`, or by the whole line.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let options = KeyOptions {
+            numeric: flags.flag("numeric-sort").is_some(),
+            human: flags.flag("human-numeric-sort").is_some(),
+            reverse: flags.flag("reverse").is_some(),
+            fold_case: flags.flag("ignore-case").is_some(),
+            skip_blanks: flags.flag("ignore-leading-blanks").is_some(),
+        };
+        let separator = match flags.flag("field-separator") {
+            Some(Argument::Basic(BasicArgument::String(separator))) => {
+                let mut chars = separator.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(separator), None) => Some(separator),
+                    _ => {
+                        shell.set_exit_status(2);
+                        return Ok(CommandOutput(Some(format!(
+                            "sort: multi-character tab '{separator}'"
+                        ))));
+                    }
+                }
+            }
+            _ => None,
+        };
+        let mut keys = Vec::new();
+        for key in flags.flag_all("key") {
+            if let Argument::Basic(BasicArgument::String(key)) = key {
+                match Key::parse(key, options) {
+                    Some(key) => keys.push(key),
+                    None => {
+                        shell.set_exit_status(2);
+                        return Ok(CommandOutput(Some(format!("sort: invalid key '{key}'"))));
+                    }
+                }
+            }
+        }
+        if keys.is_empty() {
+            keys.push(Key::whole_line(options));
+        }
+        let sorter = Sorter {
+            keys,
+            separator,
+            reverse: options.reverse,
+        };
+
+        let mut paths = string_arguments(args)?;
+        if paths.is_empty() {
+            paths.push("-".to_string());
+        }
+        let mut content = String::new();
+        for path in paths {
+            match read_input(shell, &path) {
+                Ok(input) => {
+                    content.push_str(&input);
+                    if !content.is_empty() && !content.ends_with('\n') {
+                        content.push('\n');
+                    }
+                }
+                Err(error) => {
+                    shell.set_exit_status(2);
+                    return Ok(CommandOutput(Some(format!(
+                        "sort: cannot read: {path}: {}",
+                        error_message(error)?
+                    ))));
+                }
+            }
+        }
+
+        let mut lines = content.split_terminator('\n').collect::<Vec<_>>();
+        // Without the last resort comparison, the sort is stable, so `-u` keeps the first line.
+        let unique = flags.flag("unique").is_some();
+        lines.sort_by(|a, b| sorter.compare(a, b, !unique));
+        if unique {
+            lines.dedup_by(|b, a| sorter.compare(a, b, false) == Ordering::Equal);
+        }
+        Ok(CommandOutput((!lines.is_empty()).then(|| lines.join("\n"))))
+    }
+}
+
+/// How the keys are compared, given globally or for each key like in `-k2,2nr`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct KeyOptions {
+    numeric: bool,
+    human: bool,
+    reverse: bool,
+    fold_case: bool,
+    skip_blanks: bool,
+}
+
+/// A sort key, `-k START[,END]`, the positions being `FIELD[.CHARACTER]` counted from 1.
+#[derive(Debug, PartialEq)]
+struct Key {
+    start_field: usize,
+    /// The first character of the key in its field, `0` meaning the start of the field.
+    start_char: usize,
+    /// The last field of the key, `None` meaning the end of the line.
+    end_field: Option<usize>,
+    /// The last character of the key in its last field, `0` meaning the end of the field.
+    end_char: usize,
+    options: KeyOptions,
+}
+
+impl Key {
+    fn whole_line(options: KeyOptions) -> Self {
+        Self {
+            start_field: 1,
+            start_char: 0,
+            end_field: None,
+            end_char: 0,
+            options,
+        }
+    }
+
+    /// Parses a key definition, the key inheriting the global options unless it has its own.
+    fn parse(definition: &str, global: KeyOptions) -> Option<Self> {
+        let (start, end) = match definition.split_once(',') {
+            Some((start, end)) => (start, Some(end)),
+            None => (definition, None),
+        };
+        let mut options = KeyOptions::default();
+        let (start_field, start_char) = parse_position(start, &mut options)?;
+        if start_field == 0 {
+            return None;
+        }
+        let (end_field, end_char) = match end {
+            Some(end) => {
+                let (field, char) = parse_position(end, &mut options)?;
+                if field == 0 {
+                    return None;
+                }
+                (Some(field), char)
+            }
+            None => (None, 0),
+        };
+        Some(Self {
+            start_field,
+            start_char,
+            end_field,
+            end_char,
+            options: if options == KeyOptions::default() {
+                global
+            } else {
+                options
+            },
+        })
+    }
+
+    /// Returns the part of a line the key covers.
+    fn extract<'a>(&self, line: &'a str, separator: Option<char>) -> &'a str {
+        let fields = fields(line, separator);
+        let Some(&(field_start, field_end)) = fields.get(self.start_field - 1) else {
+            return "";
+        };
+        let mut start = field_start;
+        if self.options.skip_blanks {
+            start += blank_prefix(&line[start..field_end]);
+        }
+        if self.start_char > 0 {
+            start = advance(line, start, self.start_char - 1).min(field_end);
+        }
+        let end = match self.end_field {
+            None => line.len(),
+            Some(end_field) => match fields.get(end_field - 1) {
+                None => line.len(),
+                Some(&(_, end)) if self.end_char == 0 => end,
+                Some(&(mut start, end)) => {
+                    if self.options.skip_blanks {
+                        start += blank_prefix(&line[start..end]);
+                    }
+                    advance(line, start, self.end_char).min(end)
+                }
+            },
+        };
+        if start >= end { "" } else { &line[start..end] }
+    }
+}
+
+/// Parses `FIELD[.CHARACTER][OPTIONS]`, adding the options of the position to the key.
+fn parse_position(position: &str, options: &mut KeyOptions) -> Option<(usize, usize)> {
+    let end = position
+        .find(|char: char| !char.is_ascii_digit() && char != '.')
+        .unwrap_or(position.len());
+    let (numbers, letters) = position.split_at(end);
+    for letter in letters.chars() {
+        match letter {
+            'n' => options.numeric = true,
+            'h' => options.human = true,
+            'r' => options.reverse = true,
+            'f' => options.fold_case = true,
+            'b' => options.skip_blanks = true,
+            _ => return None,
+        }
+    }
+    let (field, char) = match numbers.split_once('.') {
+        Some((field, char)) => (field.parse().ok()?, char.parse().ok()?),
+        None => (numbers.parse().ok()?, 0),
+    };
+    Some((field, char))
+}
+
+/// Returns the byte ranges of the fields of a line.
+///
+/// Without separator, fields are separated by the empty string between a non-blank and a blank
+/// character, so a field includes the blanks preceding it.
+fn fields(line: &str, separator: Option<char>) -> Vec<(usize, usize)> {
+    let mut fields = Vec::new();
+    match separator {
+        Some(separator) => {
+            let mut start = 0;
+            for (index, char) in line.char_indices() {
+                if char == separator {
+                    fields.push((start, index));
+                    start = index + char.len_utf8();
+                }
+            }
+            fields.push((start, line.len()));
+        }
+        None => {
+            let mut start = 0;
+            let mut in_word = false;
+            for (index, char) in line.char_indices() {
+                let blank = char == ' ' || char == '\t';
+                if blank && in_word {
+                    fields.push((start, index));
+                    start = index;
+                }
+                in_word = !blank;
+            }
+            fields.push((start, line.len()));
+        }
+    }
+    fields
+}
+
+fn blank_prefix(text: &str) -> usize {
+    text.len() - text.trim_start_matches([' ', '\t']).len()
+}
+
+/// Returns the byte index `count` characters after `start`.
+fn advance(line: &str, start: usize, count: usize) -> usize {
+    line[start..]
+        .char_indices()
+        .nth(count)
+        .map_or(line.len(), |(offset, _)| start + offset)
+}
+
+struct Sorter {
+    keys: Vec<Key>,
+    separator: Option<char>,
+    reverse: bool,
+}
+
+impl Sorter {
+    /// Compares two lines by their keys, then by their bytes if `last_resort` is set.
+    fn compare(&self, a: &str, b: &str, last_resort: bool) -> Ordering {
+        for key in &self.keys {
+            let ordering = compare_keys(
+                key.extract(a, self.separator),
+                key.extract(b, self.separator),
+                key.options,
+            );
+            let ordering = if key.options.reverse {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        if !last_resort {
+            return Ordering::Equal;
+        }
+        let ordering = a.cmp(b);
+        if self.reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    }
+}
+
+fn compare_keys(a: &str, b: &str, options: KeyOptions) -> Ordering {
+    if options.numeric || options.human {
+        let value = |key: &str| numeric_value(key, options.human);
+        return value(a).total_cmp(&value(b));
+    }
+    let (a, b) = if options.skip_blanks {
+        (
+            a.trim_start_matches([' ', '\t']),
+            b.trim_start_matches([' ', '\t']),
+        )
+    } else {
+        (a, b)
+    };
+    if options.fold_case {
+        a.bytes()
+            .map(|byte| byte.to_ascii_uppercase())
+            .cmp(b.bytes().map(|byte| byte.to_ascii_uppercase()))
+    } else {
+        a.cmp(b)
+    }
+}
+
+/// Returns the number at the start of a key, `0` if there is none.
+///
+/// Human numbers can have a suffix, like `2K` or `1.5G`, each being 1024 times the previous one.
+fn numeric_value(key: &str, human: bool) -> f64 {
+    let key = key.trim_start_matches([' ', '\t']);
+    let mut end = usize::from(key.starts_with('-'));
+    let mut seen_point = false;
+    for char in key[end..].chars() {
+        match char {
+            '0'..='9' => {}
+            '.' if !seen_point => seen_point = true,
+            _ => break,
+        }
+        end += 1;
+    }
+    let value = key[..end].parse::<f64>().unwrap_or(0.0);
+    if !human {
+        return value;
+    }
+    let exponent = match key[end..].chars().next() {
+        Some('k' | 'K') => 1,
+        Some('M') => 2,
+        Some('G') => 3,
+        Some('T') => 4,
+        Some('P') => 5,
+        Some('E') => 6,
+        _ => 0,
+    };
+    value * 1024_f64.powi(exponent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        let key = Key::parse("2,3n", KeyOptions::default()).expect("Invalid key");
+        assert_eq!(key.start_field, 2);
+        assert_eq!(key.end_field, Some(3));
+        assert!(key.options.numeric);
+        assert!(Key::parse("0", KeyOptions::default()).is_none());
+        assert!(Key::parse("1x", KeyOptions::default()).is_none());
+        assert!(Key::parse("a", KeyOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_extract_key() {
+        let key = |definition| Key::parse(definition, KeyOptions::default()).expect("Invalid key");
+        assert_eq!(key("2").extract("a  b c", None), "  b c");
+        assert_eq!(key("2,2").extract("a  b c", None), "  b");
+        assert_eq!(key("2b,2").extract("a  b c", None), "b");
+        assert_eq!(key("2,2").extract("a:b:c", Some(':')), "b");
+        assert_eq!(key("1.2,1.3").extract("hello", None), "el");
+        assert_eq!(key("4").extract("a b", None), "");
+    }
+
+    #[test]
+    fn test_numeric_value() {
+        assert_eq!(numeric_value(" -1.5x", false), -1.5);
+        assert_eq!(numeric_value("abc", false), 0.0);
+        assert_eq!(numeric_value("2K", true), 2048.0);
+        assert_eq!(numeric_value("1.5M", true), 1.5 * 1024.0 * 1024.0);
+    }
+}
@@ -0,0 +1,237 @@
+//! The stat command, displaying the status of files.
+
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    FileSystem, Inode,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::ls::{format_is_dir, format_permissions},
+    },
+    errors::ShellError,
+    fs::inode::{
+        content::InodeType,
+        size::{self, Size},
+    },
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct StatCommand;
+
+/// The format of `stat` without `-c`, as printed by GNU coreutils.
+const DEFAULT_FORMAT: &str = "  File: %N
+  Size: %-10s\tBlocks: %-10b IO Block: %-6o %F
+Device: %Hd,%Ld\tInode: %-11i Links: %h
+Access: (%04a/%10.10A)  Uid: (%5u/%8U)   Gid: (%5g/%8G)
+Access: %x
+Modify: %y
+Change: %z
+ Birth: %w";
+
+/// The size of the blocks counted by `%b`.
+const BLOCK_SIZE: u64 = 512;
+
+/// The major and minor numbers of the device holding the file system.
+const DEVICE: (u64, u64) = (8, 1);
+
+impl ExecutableCommand for StatCommand {
+    fn name(&self) -> &'static str {
+        "stat"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "format",
+                Some('c'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let paths = string_arguments(args)?;
+        if paths.is_empty() {
+            shell.set_exit_status(1);
+            return Ok(CommandOutput(Some("stat: missing operand".to_string())));
+        }
+        let format = match flags.flag("format") {
+            Some(Argument::Basic(BasicArgument::String(format))) => format.as_str(),
+            _ => DEFAULT_FORMAT,
+        };
+
+        let fs = shell.fs.lock();
+        let mut outputs = Vec::new();
+        let mut failed = false;
+        for path in paths {
+            match shell.current_session.lookup_inode(&fs, Path::new(&path)) {
+                Ok(inode) => outputs.push(format_status(&fs, &path, inode, format)),
+                Err(error) => {
+                    failed = true;
+                    outputs.push(format!(
+                        "stat: cannot statx '{path}': {}",
+                        error_message(error)?
+                    ));
+                }
+            }
+        }
+        drop(fs);
+        if failed {
+            shell.set_exit_status(1);
+        }
+        Ok(CommandOutput(Some(outputs.join("\n"))))
+    }
+}
+
+/// Expands the directives of a format, like `%s` for the size, for a file.
+///
+/// Directives accept the flags `-` (left alignment) and `0` (zero padding), a width and a
+/// precision truncating the value, like in `printf`.
+fn format_status(fs: &FileSystem, name: &str, inode: &Inode, format: &str) -> String {
+    let mut output = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(char) = chars.next() {
+        if char != '%' {
+            output.push(char);
+            continue;
+        }
+
+        let mut left_aligned = false;
+        let mut zero_padded = false;
+        while let Some(flag @ ('-' | '0')) = chars.peek().copied() {
+            left_aligned |= flag == '-';
+            zero_padded |= flag == '0';
+            chars.next();
+        }
+        let mut width = 0;
+        while let Some(digit) = chars.peek().and_then(|char| char.to_digit(10)) {
+            width = width * 10 + digit as usize;
+            chars.next();
+        }
+        let mut precision = None;
+        if chars.next_if_eq(&'.').is_some() {
+            let mut digits = 0;
+            while let Some(digit) = chars.peek().and_then(|char| char.to_digit(10)) {
+                digits = digits * 10 + digit as usize;
+                chars.next();
+            }
+            precision = Some(digits);
+        }
+
+        let Some(directive) = chars.next() else {
+            output.push('%');
+            break;
+        };
+        let value = match directive {
+            // The device numbers are the only directives of two letters.
+            'H' | 'L' if chars.next_if_eq(&'d').is_some() => {
+                let (major, minor) = DEVICE;
+                Some(if directive == 'H' { major } else { minor }.to_string())
+            }
+            directive => directive_value(fs, name, inode, directive),
+        };
+        let Some(mut value) = value else {
+            output.push('%');
+            output.push(directive);
+            continue;
+        };
+
+        if let Some(precision) = precision {
+            value = value.chars().take(precision).collect();
+        }
+        let padding = width.saturating_sub(value.chars().count());
+        if left_aligned {
+            output.push_str(&value);
+            output.extend(std::iter::repeat_n(' ', padding));
+        } else {
+            let fill = if zero_padded { '0' } else { ' ' };
+            output.extend(std::iter::repeat_n(fill, padding));
+            output.push_str(&value);
+        }
+    }
+    output
+}
+
+/// Returns the value of a directive of one letter, or `None` if it is unknown.
+fn directive_value(fs: &FileSystem, name: &str, inode: &Inode, directive: char) -> Option<String> {
+    let metadata = &inode.metadata;
+    let value = match directive {
+        '%' => "%".to_string(),
+        'a' => format!("{:o}", metadata.permissions.mode()),
+        'A' => format!(
+            "{}{}",
+            format_is_dir(inode.inode_type() == InodeType::Directory),
+            format_permissions(&metadata.permissions)
+        ),
+        'b' => (inode.allocated_size() / BLOCK_SIZE).to_string(),
+        'B' => BLOCK_SIZE.to_string(),
+        'd' => (DEVICE.0 << 8 | DEVICE.1).to_string(),
+        'D' => format!("{:x}", DEVICE.0 << 8 | DEVICE.1),
+        'f' => format!("{:x}", file_type_bits(inode) | metadata.permissions.mode()),
+        'F' => file_type(inode).to_string(),
+        'g' => metadata.group.to_string(),
+        'G' => fs
+            .get_group(metadata.group)
+            .map_or_else(|| "UNKNOWN".to_string(), |group| group.name.clone()),
+        'h' => inode.hard_link_count().to_string(),
+        'i' => inode.number.to_string(),
+        'n' => name.to_string(),
+        'N' => shlex::try_quote(name).map_or_else(|_| name.to_string(), |name| name.to_string()),
+        // The blocks allocated are also the optimal size of transfers.
+        'o' => size::BLOCK_SIZE.to_string(),
+        's' => inode.size().to_string(),
+        'u' => metadata.owner.to_string(),
+        'U' => fs
+            .get_user(metadata.owner)
+            .map_or_else(|| "UNKNOWN".to_string(), |user| user.name.clone()),
+        'w' => format_time(metadata.created_at),
+        'W' => metadata.created_at.timestamp().to_string(),
+        'x' => format_time(metadata.accessed_at),
+        'X' => metadata.accessed_at.timestamp().to_string(),
+        'y' => format_time(metadata.modified_at),
+        'Y' => metadata.modified_at.timestamp().to_string(),
+        'z' => format_time(metadata.changed_at),
+        'Z' => metadata.changed_at.timestamp().to_string(),
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Returns the description of the type of a file.
+fn file_type(inode: &Inode) -> &'static str {
+    match inode.inode_type() {
+        InodeType::File if inode.size() == 0 => "regular empty file",
+        InodeType::File => "regular file",
+        InodeType::Directory => "directory",
+        InodeType::Link => "symbolic link",
+    }
+}
+
+/// Returns the bits of the type of a file in a raw mode, like `S_IFREG`.
+fn file_type_bits(inode: &Inode) -> u32 {
+    match inode.inode_type() {
+        InodeType::File => 0o100_000,
+        InodeType::Directory => 0o040_000,
+        InodeType::Link => 0o120_000,
+    }
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y-%m-%d %H:%M:%S%.9f %z").to_string()
+}
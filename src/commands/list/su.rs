@@ -0,0 +1,100 @@
+//! The su command, switching to another user.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments, string_flag},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::sudo::run_as,
+    },
+    errors::ShellError,
+    fs::users::PasswordStatus,
+    shell::Shell,
+    stdin::prompt_unlocked,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct SwitchUserCommand;
+
+impl ExecutableCommand for SwitchUserCommand {
+    fn name(&self) -> &'static str {
+        "su"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "login",
+                Some('l'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "command",
+                Some('c'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut login = flags.flag("login").is_some();
+        let mut args = string_arguments(args)?.into_iter().peekable();
+        // A lone dash is a shorthand for `--login`.
+        if args.next_if(|arg| arg == "-").is_some() {
+            login = true;
+        }
+        let username = args.next().unwrap_or_else(|| "root".to_string());
+        let fs = shell.fs.lock();
+        let Some(target) = fs.find_user_by_name(&username).cloned() else {
+            return Ok(CommandOutput(Some(format!(
+                "su: user {username} does not exist or the user entry does not contain all the required fields"
+            ))));
+        };
+        let user_id = target.id;
+
+        // Only root can become another user without knowing their password.
+        let is_root = fs
+            .get_user(shell.current_session.current_user())
+            .is_some_and(|user| user.is_root());
+        let authenticated = prompt_unlocked(fs, || {
+            is_root
+                || target.password_status() == PasswordStatus::Empty
+                || shell
+                    .read_line("Password: ")
+                    .is_some_and(|password| target.verify_password(&password))
+        });
+        if !authenticated {
+            return Ok(CommandOutput(Some(
+                "su: Authentication failure".to_string(),
+            )));
+        }
+
+        if let Some(command) = string_flag(&flags, "command")? {
+            let Some(command) = shlex::split(command) else {
+                return Err(ShellError::Internal("Failed to parse command".to_string()));
+            };
+            if command.is_empty() {
+                return Ok(CommandOutput(None));
+            }
+            return run_as(shell, user_id, login, command);
+        }
+
+        let warning = shell
+            .current_session
+            .switch_user(&shell.fs.lock(), user_id, login)?;
+        Ok(CommandOutput(
+            warning.map(|warning| format!("su: {warning}")),
+        ))
+    }
+}
@@ -0,0 +1,175 @@
+//! The sudo command, running a command as another user according to the sudoers policy.
+
+use crate::{
+    UserId,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments, string_flag},
+        find_user,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::{SessionError, ShellError},
+    host::HOSTNAME,
+    shell::Shell,
+    stdin::prompt_unlocked,
+    sudoers::Sudoers,
+};
+
+/// The number of times the password is asked before giving up.
+const PASSWORD_ATTEMPTS: usize = 3;
+
+#[derive(Default, Clone, Copy)]
+pub struct SudoCommand;
+
+impl ExecutableCommand for SudoCommand {
+    fn name(&self) -> &'static str {
+        "sudo"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "user",
+                Some('u'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "login",
+                Some('i'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// The options following the command belong to it.
+    fn permute_arguments(&self) -> bool {
+        false
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let command = string_arguments(args)?;
+        let login = flags.flag("login").is_some();
+        if command.is_empty() && !login {
+            return Ok(CommandOutput(Some(
+                "usage: sudo [-i] [-u user] [command [arg ...]]".to_string(),
+            )));
+        }
+
+        let target_name = string_flag(&flags, "user")?.unwrap_or("root");
+        let fs = shell.fs.lock();
+        let Some(target) = find_user(&fs, target_name).cloned() else {
+            return Ok(CommandOutput(Some(format!(
+                "sudo: unknown user {target_name}"
+            ))));
+        };
+        let user = fs
+            .get_user(shell.current_session.current_user())
+            .ok_or(ShellError::Session(SessionError::UserNotFound))?
+            .clone();
+
+        // The messages of failed authentication attempts, shown before the output.
+        let mut messages = Vec::new();
+        if !user.is_root() {
+            let sudoers = Sudoers::load(&fs);
+            if !sudoers.is_listed(&fs, &user) {
+                return Ok(CommandOutput(Some(format!(
+                    "{} is not in the sudoers file.  This incident will be reported.",
+                    user.name
+                ))));
+            }
+            let program = command.first().map(String::as_str);
+            if !sudoers.allows(&fs, &user, &target, program) {
+                let command_line = if command.is_empty() {
+                    target.shell.clone()
+                } else {
+                    command.join(" ")
+                };
+                return Ok(CommandOutput(Some(format!(
+                    "Sorry, user {} is not allowed to execute '{command_line}' as {} on {HOSTNAME}.",
+                    user.name, target.name
+                ))));
+            }
+            let requires_password = sudoers.requires_password(&fs, &user, &target, program);
+            let authenticated = prompt_unlocked(fs, || {
+                if !requires_password {
+                    return true;
+                }
+                let prompt = format!("[sudo] password for {}: ", user.name);
+                for attempt in 1..=PASSWORD_ATTEMPTS {
+                    let Some(password) = shell.read_line(&prompt) else {
+                        messages.push("sudo: no password was provided".to_string());
+                        return false;
+                    };
+                    if user.verify_password(&password) {
+                        return true;
+                    }
+                    if attempt < PASSWORD_ATTEMPTS {
+                        messages.push("Sorry, try again.".to_string());
+                    }
+                }
+                messages.push(format!(
+                    "sudo: {PASSWORD_ATTEMPTS} incorrect password attempts"
+                ));
+                false
+            });
+            if !authenticated {
+                return Ok(CommandOutput(Some(messages.join("\n"))));
+            }
+        } else {
+            drop(fs);
+        }
+
+        let target = target.id;
+        let output = if command.is_empty() {
+            // An interactive login shell, left with `exit`.
+            let warning = shell
+                .current_session
+                .switch_user(&shell.fs.lock(), target, true)?;
+            CommandOutput(warning.map(|warning| format!("sudo: {warning}")))
+        } else {
+            run_as(shell, target, login, command)?
+        };
+        messages.extend(output.0);
+        Ok(CommandOutput(
+            (!messages.is_empty()).then(|| messages.join("\n")),
+        ))
+    }
+}
+
+/// Runs a command as another user, then switches back to the current identity.
+///
+/// If the command opened a shell as yet another user, like `sudo su -`, that shell is kept, and
+/// exiting it returns directly to the current identity.
+pub fn run_as(
+    shell: &mut Shell,
+    user: UserId,
+    login: bool,
+    command: Vec<String>,
+) -> Result<CommandOutput, ShellError> {
+    let depth = shell.current_session.identity_depth();
+    shell
+        .current_session
+        .switch_user(&shell.fs.lock(), user, login)?;
+    let output = shell.execute_tokens(command);
+
+    let session = &mut shell.current_session;
+    match session.identity_depth() {
+        current if current == depth + 1 => {
+            session.restore_user();
+        }
+        current if current > depth + 1 => session.discard_identity(depth + 1),
+        _ => {}
+    }
+    output
+}
@@ -0,0 +1,156 @@
+//! The tail command, printing the last lines or bytes of files.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::head::{Unit, header},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct TailCommand;
+
+/// The number of lines printed by default.
+const DEFAULT_LINES: usize = 10;
+
+impl ExecutableCommand for TailCommand {
+    fn name(&self) -> &'static str {
+        "tail"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "lines",
+                Some('n'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "bytes",
+                Some('c'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "quiet",
+                Some('q'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "verbose",
+                Some('v'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the last 10 lines of the files given, or of the standard input.
+    ///
+    /// `-n +N` and `-c +N` print everything from the `N`th line or byte.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let (unit, count) = match (flags.flag("bytes"), flags.flag("lines")) {
+            (Some(Argument::Basic(BasicArgument::String(count))), _) => (Unit::Bytes, count),
+            (_, Some(Argument::Basic(BasicArgument::String(count)))) => (Unit::Lines, count),
+            _ => (Unit::Lines, &DEFAULT_LINES.to_string()),
+        };
+        let (from_start, number) = match count.strip_prefix('+') {
+            Some(number) => (true, number),
+            None => (false, count.strip_prefix('-').unwrap_or(count)),
+        };
+        let Ok(number) = number.parse::<usize>() else {
+            shell.set_exit_status(1);
+            return Ok(CommandOutput(Some(format!(
+                "tail: invalid number of {unit}: '{count}'"
+            ))));
+        };
+
+        let mut paths = string_arguments(args)?;
+        if paths.is_empty() {
+            paths.push("-".to_string());
+        }
+        let headers =
+            flags.flag("verbose").is_some() || (paths.len() > 1 && flags.flag("quiet").is_none());
+        let mut output = String::new();
+        for (index, path) in paths.iter().enumerate() {
+            let content = match read_input(shell, path) {
+                Ok(content) => content,
+                Err(error) => {
+                    shell.set_exit_status(1);
+                    output.push_str(&format!(
+                        "tail: cannot open '{path}' for reading: {}\n",
+                        error_message(error)?
+                    ));
+                    continue;
+                }
+            };
+            if headers {
+                output.push_str(&header(path, index == 0));
+            }
+            output.push_str(&tail(&content, unit, number, from_start));
+        }
+
+        let output = output.strip_suffix('\n').unwrap_or(&output);
+        Ok(CommandOutput(
+            (!output.is_empty()).then(|| output.to_string()),
+        ))
+    }
+}
+
+/// Returns the last lines or bytes of a content, or the ones from the `number`th.
+fn tail(content: &str, unit: Unit, number: usize, from_start: bool) -> String {
+    match unit {
+        Unit::Lines => {
+            let lines = content.split_inclusive('\n').collect::<Vec<_>>();
+            let skipped = if from_start {
+                number.saturating_sub(1)
+            } else {
+                lines.len().saturating_sub(number)
+            };
+            lines.into_iter().skip(skipped).collect()
+        }
+        Unit::Bytes => {
+            let bytes = content.as_bytes();
+            let skipped = if from_start {
+                number.saturating_sub(1).min(bytes.len())
+            } else {
+                bytes.len().saturating_sub(number)
+            };
+            String::from_utf8_lossy(&bytes[skipped..]).into_owned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tail() {
+        let content = "1\n2\n3\n4\n";
+        assert_eq!(tail(content, Unit::Lines, 2, false), "3\n4\n");
+        assert_eq!(tail(content, Unit::Lines, 9, false), content);
+        assert_eq!(tail(content, Unit::Lines, 3, true), "3\n4\n");
+        assert_eq!(tail(content, Unit::Lines, 0, true), content);
+        assert_eq!(tail(content, Unit::Bytes, 3, false), "\n4\n");
+        assert_eq!(tail(content, Unit::Bytes, 7, true), "4\n");
+        assert_eq!(tail("1\n2", Unit::Lines, 1, false), "2");
+    }
+}
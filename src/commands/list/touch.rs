@@ -1,16 +1,20 @@
 //! The touch command.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use chrono::Utc;
+use chrono::{
+    DateTime, Datelike, Days, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
 
 use crate::{
     commands::{
         Argument, CommandOutput, ExecutableCommand, Flags,
         args::{ArgumentKind, BasicArgument, BasicArgumentKind},
-        flags::{FlagDefinition, FlagDefinitionBuilder},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
     },
     errors::{FileSystemError, ShellError},
+    fs::permissions::Access,
 };
 
 #[derive(Default, Clone, Copy)]
@@ -22,16 +26,55 @@ impl ExecutableCommand for TouchCommand {
     }
 
     fn flags(&self) -> FlagDefinition {
-        FlagDefinitionBuilder::new().into_flag_definition()
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "a",
+                Some('a'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "m",
+                Some('m'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "no-create",
+                Some('c'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "date",
+                Some('d'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "t",
+                Some('t'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "reference",
+                Some('r'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
     }
 
     fn args(&self) -> Option<ArgumentKind> {
         Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
     }
 
+    /// Sets the access and modification times of files to the current time, or to the time given
+    /// by `-d`, `-t` or `-r`, creating the files that don't exist unless `-c` is given.
     fn execute(
         &self,
-        _: Flags,
+        flags: Flags,
         args: Option<Argument>,
         shell: &mut crate::shell::Shell,
     ) -> Result<CommandOutput, ShellError> {
@@ -39,7 +82,7 @@ impl ExecutableCommand for TouchCommand {
             |path: &str, message: &str| format!("touch: cannot touch '{path}': {message}");
 
         let mut current_session = shell.current_session.clone();
-        let mut fs = shell.fs.clone();
+        let mut fs = shell.fs.lock();
         let mut error_messages = Vec::new();
 
         let paths = match args {
@@ -53,38 +96,118 @@ impl ExecutableCommand for TouchCommand {
                     Ok(acc)
                 })?
             }
-            _ => return Err(ShellError::Internal("Invalid arguments".to_string())),
+            _ => {
+                return Ok(CommandOutput(Some(
+                    "touch: missing file operand".to_string(),
+                )));
+            }
         };
 
-        for path in paths {
-            let inode = match current_session.find_inode(&fs, &path) {
-                Some(inode) => inode,
-                None => match current_session.create_file(&mut fs, &path) {
-                    Ok(inode) => inode,
+        let now = fs.now();
+        let string_flag = |name: &str| match flags.flag(name) {
+            Some(Argument::Basic(BasicArgument::String(value))) => Some(value.as_str()),
+            _ => None,
+        };
+        let sources = [
+            string_flag("date"),
+            string_flag("t"),
+            string_flag("reference"),
+        ];
+        if sources.iter().flatten().count() > 1 {
+            return Ok(CommandOutput(Some(
+                "touch: cannot specify times from more than one source".to_string(),
+            )));
+        }
+        // The access and modification times given explicitly, the current time being used
+        // otherwise.
+        let times = match sources {
+            [Some(date), _, _] => match parse_date(date, now) {
+                Some(time) => Some((time, time)),
+                None => {
+                    return Ok(CommandOutput(Some(format!(
+                        "touch: invalid date format '{date}'"
+                    ))));
+                }
+            },
+            [_, Some(stamp), _] => match parse_stamp(stamp, now) {
+                Some(time) => Some((time, time)),
+                None => {
+                    return Ok(CommandOutput(Some(format!(
+                        "touch: invalid date format '{stamp}'"
+                    ))));
+                }
+            },
+            [_, _, Some(reference)] => {
+                match current_session.lookup_inode(&fs, Path::new(reference)) {
+                    Ok(inode) => Some((inode.metadata.accessed_at, inode.metadata.modified_at)),
                     Err(error) => {
-                        match error {
-                            ShellError::FileSystem(FileSystemError::NotADirectory(_)) => {
-                                error_messages.push(error_builder(
-                                    &path.display().to_string(),
-                                    "Not a directory",
-                                ));
-                            }
-                            ShellError::FileSystem(FileSystemError::EntryNotFound(_)) => {
-                                error_messages.push(error_builder(
-                                    &path.display().to_string(),
-                                    "No such file or directory",
-                                ));
-                            }
-                            _ => {
-                                return Err(error);
-                            }
-                        }
+                        return Ok(CommandOutput(Some(format!(
+                            "touch: failed to get attributes of '{reference}': {}",
+                            error_message(error)?
+                        ))));
+                    }
+                }
+            }
+            _ => None,
+        };
+        // With neither -a nor -m, both times are set.
+        let only_access = flags.flag("a").is_some() && flags.flag("m").is_none();
+        let only_modification = flags.flag("m").is_some() && flags.flag("a").is_none();
+        let is_root = fs
+            .get_user(current_session.current_user())
+            .is_some_and(|user| user.is_root());
+
+        for path in paths {
+            let display_path = path.display().to_string();
+            let inode = match current_session.lookup_inode(&fs, &path) {
+                Ok(inode) => {
+                    let is_owner = inode.metadata.owner == current_session.current_user();
+                    // Setting the timestamps to given times requires owning the file, while
+                    // setting them to the current time only requires write permission.
+                    if times.is_some() && !is_owner && !is_root {
+                        error_messages.push(format!(
+                            "touch: setting times of '{display_path}': Operation not permitted"
+                        ));
                         continue;
                     }
-                },
+                    if !is_owner && !current_session.can_access(&fs, inode, Access::Write) {
+                        error_messages.push(error_builder(&display_path, "Permission denied"));
+                        continue;
+                    }
+                    inode.number
+                }
+                Err(ShellError::FileSystem(FileSystemError::EntryNotFound(_)))
+                    if flags.flag("no-create").is_some() =>
+                {
+                    continue;
+                }
+                Err(ShellError::FileSystem(FileSystemError::EntryNotFound(_))) => {
+                    match current_session.create_file(&mut fs, &path) {
+                        Ok(inode) => inode,
+                        Err(error) => {
+                            error_messages
+                                .push(error_builder(&display_path, error_message(error)?));
+                            continue;
+                        }
+                    }
+                }
+                Err(error) => {
+                    error_messages.push(error_builder(&display_path, error_message(error)?));
+                    continue;
+                }
             };
-            let mut unlocked_inode = inode.lock().expect("Failed to lock inode");
-            unlocked_inode.metadata.updated_at = Utc::now();
+            // Taken after the creation of the file, so that it isn't changed before its birth.
+            let changed_at = fs.now();
+            let (accessed_at, modified_at) = times.unwrap_or((changed_at, changed_at));
+            if let Some(inode) = fs.inode_mut(inode) {
+                if !only_modification {
+                    inode.metadata.accessed_at = accessed_at;
+                }
+                if !only_access {
+                    inode.metadata.modified_at = modified_at;
+                }
+                inode.metadata.mark_changed(changed_at);
+            }
         }
 
         if error_messages.is_empty() {
@@ -94,3 +217,186 @@ impl ExecutableCommand for TouchCommand {
         }
     }
 }
+
+/// Parses a date given to `-d`, in UTC.
+///
+/// The accepted forms are `@SECONDS` since the epoch, `YYYY-MM-DD` optionally followed by a
+/// time `HH:MM[:SS[.NNN]]` (separated by a space or `T`), RFC 3339 dates, `now`, `today`,
+/// `yesterday`, `tomorrow`, and relative dates like `2 days ago` or `+3 hours`.
+pub(crate) fn parse_date(date: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let date = date.trim();
+    if let Some(seconds) = date.strip_prefix('@') {
+        return DateTime::from_timestamp(seconds.parse().ok()?, 0);
+    }
+    match date.to_lowercase().as_str() {
+        "now" | "today" => return Some(now),
+        "yesterday" => return now.checked_sub_days(Days::new(1)),
+        "tomorrow" => return now.checked_add_days(Days::new(1)),
+        _ => {}
+    }
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.with_timezone(&Utc));
+    }
+    let date = date
+        .strip_suffix(" UTC")
+        .or_else(|| date.strip_suffix('Z'))
+        .unwrap_or(date);
+    for format in [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ] {
+        if let Ok(date) = NaiveDateTime::parse_from_str(date, format) {
+            return Some(date.and_utc());
+        }
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        return Some(date.and_time(NaiveTime::MIN).and_utc());
+    }
+    parse_relative_date(date, now)
+}
+
+/// Parses a date relative to now, like `1 year ago`, `3 hours` or `-2 weeks`.
+fn parse_relative_date(date: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let words = date.split_whitespace().collect::<Vec<_>>();
+    let (amount, unit, ago) = match words.as_slice() {
+        [amount, unit] => (*amount, *unit, false),
+        [amount, unit, "ago"] => (*amount, *unit, true),
+        _ => return None,
+    };
+    let amount = amount
+        .strip_prefix('+')
+        .unwrap_or(amount)
+        .parse::<i64>()
+        .ok()?;
+    let amount = if ago { -amount } else { amount };
+    let unit = unit.strip_suffix('s').unwrap_or(unit);
+    let months = match unit {
+        "month" => amount,
+        "year" => amount.checked_mul(12)?,
+        _ => {
+            let seconds = match unit {
+                "second" | "sec" => 1,
+                "minute" | "min" => 60,
+                "hour" => 3600,
+                "day" => 86_400,
+                "week" => 604_800,
+                _ => return None,
+            };
+            return now.checked_add_signed(Duration::try_seconds(amount.checked_mul(seconds)?)?);
+        }
+    };
+    let shift = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months < 0 {
+        now.checked_sub_months(shift)
+    } else {
+        now.checked_add_months(shift)
+    }
+}
+
+/// Parses a stamp given to `-t`, `[[CC]YY]MMDDhhmm[.ss]` in UTC.
+///
+/// Without a year, the current one is used. A two-digit year from 69 is in the 20th century,
+/// below in the 21st.
+fn parse_stamp(stamp: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let (stamp, seconds) = match stamp.split_once('.') {
+        Some((stamp, seconds)) if seconds.len() == 2 => (stamp, seconds.parse().ok()?),
+        Some(_) => return None,
+        None => (stamp, 0),
+    };
+    if !stamp.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    let field = |start: usize| stamp[start..start + 2].parse::<u32>().ok();
+    let (year, rest) = match stamp.len() {
+        8 => (now.year(), 0),
+        10 => {
+            let year = field(0)? as i32;
+            (if year >= 69 { 1900 + year } else { 2000 + year }, 2)
+        }
+        12 => (stamp[..4].parse().ok()?, 4),
+        _ => return None,
+    };
+    Utc.with_ymd_and_hms(
+        year,
+        field(rest)?,
+        field(rest + 2)?,
+        field(rest + 4)?,
+        field(rest + 6)?,
+        seconds,
+    )
+    .single()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 17, 10, 30, 0)
+            .single()
+            .expect("Invalid date")
+    }
+
+    fn date(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
+            .single()
+            .expect("Invalid date")
+    }
+
+    #[test]
+    fn test_parse_absolute_dates() {
+        assert_eq!(
+            parse_date("2020-02-29", now()),
+            Some(date(2020, 2, 29, 0, 0, 0))
+        );
+        assert_eq!(
+            parse_date("2020-02-29 13:45", now()),
+            Some(date(2020, 2, 29, 13, 45, 0))
+        );
+        assert_eq!(
+            parse_date("2020-02-29T13:45:12Z", now()),
+            Some(date(2020, 2, 29, 13, 45, 12))
+        );
+        assert_eq!(parse_date("@86400", now()), Some(date(1970, 1, 2, 0, 0, 0)));
+        assert_eq!(parse_date("2020-02-30", now()), None);
+        assert_eq!(parse_date("soon", now()), None);
+    }
+
+    #[test]
+    fn test_parse_relative_dates() {
+        assert_eq!(parse_date("now", now()), Some(now()));
+        assert_eq!(
+            parse_date("yesterday", now()),
+            Some(date(2024, 5, 16, 10, 30, 0))
+        );
+        assert_eq!(
+            parse_date("1 year ago", now()),
+            Some(date(2023, 5, 17, 10, 30, 0))
+        );
+        assert_eq!(
+            parse_date("+3 hours", now()),
+            Some(date(2024, 5, 17, 13, 30, 0))
+        );
+        assert_eq!(parse_date("2 fortnights ago", now()), None);
+    }
+
+    #[test]
+    fn test_parse_stamps() {
+        assert_eq!(
+            parse_stamp("01021530", now()),
+            Some(date(2024, 1, 2, 15, 30, 0))
+        );
+        assert_eq!(
+            parse_stamp("9901021530.45", now()),
+            Some(date(1999, 1, 2, 15, 30, 45))
+        );
+        assert_eq!(
+            parse_stamp("202301021530", now()),
+            Some(date(2023, 1, 2, 15, 30, 0))
+        );
+        assert_eq!(parse_stamp("13021530", now()), None);
+        assert_eq!(parse_stamp("0102153", now()), None);
+    }
+}
@@ -0,0 +1,296 @@
+//! The tr command, translating, squeezing or deleting characters of the standard input.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct TrCommand;
+
+impl ExecutableCommand for TrCommand {
+    fn name(&self) -> &'static str {
+        "tr"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "delete",
+                Some('d'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "squeeze-repeats",
+                Some('s'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "complement",
+                Some('c'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Translates the characters of the standard input in `SET1` to the matching ones in `SET2`.
+    ///
+    /// Sets support ranges like `a-z`, classes like `[:digit:]` and escapes like `\n`.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let delete = flags.flag("delete").is_some();
+        let squeeze = flags.flag("squeeze-repeats").is_some();
+        let operands = string_arguments(args)?;
+        let needed = if delete == squeeze { 2 } else { 1 };
+        let maximum = if delete && !squeeze { 1 } else { 2 };
+        if operands.len() < needed {
+            shell.set_exit_status(1);
+            return Ok(CommandOutput(Some(match operands.first() {
+                None => "tr: missing operand".to_string(),
+                Some(set) => {
+                    let reason = if squeeze {
+                        "Two strings must be given when both deleting and squeezing repeats."
+                    } else {
+                        "Two strings must be given when translating."
+                    };
+                    format!("tr: missing operand after '{set}'\n{reason}")
+                }
+            })));
+        }
+        if let Some(extra) = operands.get(maximum) {
+            shell.set_exit_status(1);
+            return Ok(CommandOutput(Some(format!("tr: extra operand '{extra}'"))));
+        }
+        let mut sets = Vec::new();
+        for operand in &operands {
+            match parse_set(operand) {
+                Ok(set) => sets.push(set),
+                Err(message) => {
+                    shell.set_exit_status(1);
+                    return Ok(CommandOutput(Some(format!("tr: {message}"))));
+                }
+            }
+        }
+        let translation = Translation {
+            set1: sets.remove(0),
+            set2: sets.pop(),
+            complement: flags.flag("complement").is_some(),
+            delete,
+            squeeze,
+        };
+
+        let input = shell
+            .read_lines()
+            .into_iter()
+            .map(|line| line + "\n")
+            .collect::<String>();
+        let output = translation.apply(&input);
+        let output = output.strip_suffix('\n').unwrap_or(&output);
+        Ok(CommandOutput(
+            (!output.is_empty()).then(|| output.to_string()),
+        ))
+    }
+}
+
+struct Translation {
+    set1: Vec<char>,
+    set2: Option<Vec<char>>,
+    complement: bool,
+    delete: bool,
+    squeeze: bool,
+}
+
+impl Translation {
+    fn in_set1(&self, char: char) -> bool {
+        self.set1.contains(&char) != self.complement
+    }
+
+    /// Returns the character replacing one of `SET1`, `SET2` being padded with its last character.
+    fn translate(&self, char: char) -> char {
+        let Some(set2) = self.set2.as_ref().filter(|set2| !set2.is_empty()) else {
+            return char;
+        };
+        if self.complement {
+            return if self.in_set1(char) {
+                set2[set2.len() - 1]
+            } else {
+                char
+            };
+        }
+        match self.set1.iter().rposition(|candidate| *candidate == char) {
+            Some(index) => set2[index.min(set2.len() - 1)],
+            None => char,
+        }
+    }
+
+    fn apply(&self, input: &str) -> String {
+        // Repeats are squeezed in the last set given.
+        let squeezed = |char: char| match (&self.set2, self.delete) {
+            (Some(set2), _) => set2.contains(&char),
+            (None, false) => self.in_set1(char),
+            (None, true) => false,
+        };
+        let mut output = String::new();
+        let mut last = None;
+        for char in input.chars() {
+            let char = if self.delete {
+                if self.in_set1(char) {
+                    continue;
+                }
+                char
+            } else {
+                self.translate(char)
+            };
+            if self.squeeze && last == Some(char) && squeezed(char) {
+                continue;
+            }
+            output.push(char);
+            last = Some(char);
+        }
+        output
+    }
+}
+
+/// Expands a set into its characters.
+fn parse_set(set: &str) -> Result<Vec<char>, String> {
+    let chars = set.chars().collect::<Vec<_>>();
+    let mut expanded = Vec::new();
+    let mut index = 0;
+    while index < chars.len() {
+        if chars[index] == '['
+            && chars.get(index + 1) == Some(&':')
+            && let Some(end) = set_class_end(&chars, index + 2)
+        {
+            let name = chars[index + 2..end].iter().collect::<String>();
+            expanded.extend(class(&name).ok_or(format!("invalid character class '{name}'"))?);
+            index = end + 2;
+            continue;
+        }
+        let (start, next) = unescape(&chars, index);
+        if chars.get(next) == Some(&'-') && next + 1 < chars.len() {
+            let (end, after) = unescape(&chars, next + 1);
+            if end < start {
+                return Err(format!(
+                    "range-endpoints of '{start}-{end}' are in reverse collating sequence order"
+                ));
+            }
+            expanded.extend(start..=end);
+            index = after;
+        } else {
+            expanded.push(start);
+            index = next;
+        }
+    }
+    Ok(expanded)
+}
+
+/// Returns the index of the `:]` closing a class name starting at `start`.
+fn set_class_end(chars: &[char], start: usize) -> Option<usize> {
+    (start..chars.len().saturating_sub(1))
+        .find(|index| chars[*index] == ':' && chars[index + 1] == ']')
+}
+
+/// Returns the character at an index of a set, with its escape sequence if any, and the index
+/// following it.
+fn unescape(chars: &[char], index: usize) -> (char, usize) {
+    if chars[index] != '\\' || index + 1 == chars.len() {
+        return (chars[index], index + 1);
+    }
+    let char = match chars[index + 1] {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        'a' => '\x07',
+        'b' => '\x08',
+        'f' => '\x0c',
+        'v' => '\x0b',
+        '0'..='7' => {
+            let digits = chars[index + 1..]
+                .iter()
+                .take(3)
+                .take_while(|char| ('0'..='7').contains(*char))
+                .collect::<String>();
+            let value = u32::from_str_radix(&digits, 8).unwrap_or(0);
+            return (
+                char::from_u32(value).unwrap_or('\0'),
+                index + 1 + digits.len(),
+            );
+        }
+        char => char,
+    };
+    (char, index + 2)
+}
+
+fn class(name: &str) -> Option<Vec<char>> {
+    let ascii = (0..=127u8).map(char::from);
+    let chars = match name {
+        "alpha" => ascii.filter(char::is_ascii_alphabetic).collect(),
+        "digit" => ascii.filter(char::is_ascii_digit).collect(),
+        "alnum" => ascii.filter(char::is_ascii_alphanumeric).collect(),
+        "upper" => ascii.filter(char::is_ascii_uppercase).collect(),
+        "lower" => ascii.filter(char::is_ascii_lowercase).collect(),
+        "space" => ascii
+            .filter(|char| char.is_ascii_whitespace() || *char == '\x0b')
+            .collect(),
+        "blank" => vec![' ', '\t'],
+        "punct" => ascii.filter(char::is_ascii_punctuation).collect(),
+        "cntrl" => ascii.filter(char::is_ascii_control).collect(),
+        "xdigit" => ascii.filter(char::is_ascii_hexdigit).collect(),
+        _ => return None,
+    };
+    Some(chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn translation(set1: &str, set2: Option<&str>) -> Translation {
+        Translation {
+            set1: parse_set(set1).expect("Invalid set"),
+            set2: set2.map(|set2| parse_set(set2).expect("Invalid set")),
+            complement: false,
+            delete: false,
+            squeeze: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_set() {
+        assert_eq!(parse_set("a-e"), Ok(vec!['a', 'b', 'c', 'd', 'e']));
+        assert_eq!(parse_set(r"\n\t\\-"), Ok(vec!['\n', '\t', '\\', '-']));
+        assert_eq!(parse_set("[:digit:]x").map(|set| set.len()), Ok(11));
+        assert_eq!(parse_set(r"\101"), Ok(vec!['A']));
+        assert!(parse_set("z-a").is_err());
+        assert!(parse_set("[:nope:]").is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(translation("a-z", Some("A-Z")).apply("hi!"), "HI!");
+        assert_eq!(translation("abc", Some("x")).apply("abcd"), "xxxd");
+        let mut squeeze = translation(" ", None);
+        squeeze.squeeze = true;
+        assert_eq!(squeeze.apply("a   b  c"), "a b c");
+        let mut delete = translation("[:digit:]", None);
+        delete.delete = true;
+        delete.complement = true;
+        assert_eq!(delete.apply("a1b2\n"), "12");
+    }
+}
@@ -0,0 +1,217 @@
+//! The tree command, drawing the hierarchy of the entries below directories.
+
+use std::path::Path;
+
+use crate::{
+    InodeContent,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_arguments},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::ShellError,
+    fs::inode::content::InodeType,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct TreeCommand;
+
+impl ExecutableCommand for TreeCommand {
+    fn name(&self) -> &'static str {
+        "tree"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "all",
+                Some('a'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "directories",
+                Some('d'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "full-path",
+                Some('f'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "level",
+                Some('L'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Draws the entries below the directories given, or the current directory, followed by the
+    /// number of directories and files drawn.
+    ///
+    /// Hidden entries are skipped unless `-a` is given, and symbolic links are shown with their
+    /// target without being followed.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut operands = string_arguments(args)?;
+        if operands.is_empty() {
+            operands.push(".".to_string());
+        }
+        let max_depth = match flags.flag("level") {
+            Some(Argument::Basic(BasicArgument::String(level))) => match level.parse() {
+                Ok(level) if level > 0 => Some(level),
+                _ => {
+                    shell.set_exit_status(1);
+                    return Ok(CommandOutput(Some(
+                        "tree: Invalid level, must be greater than 0.".to_string(),
+                    )));
+                }
+            },
+            _ => None,
+        };
+        let mut tree = Tree {
+            all: flags.flag("all").is_some(),
+            directories_only: flags.flag("directories").is_some(),
+            full_path: flags.flag("full-path").is_some(),
+            max_depth,
+            output: Vec::new(),
+            directories: 0,
+            files: 0,
+        };
+        for operand in &operands {
+            match tree.children(shell, operand) {
+                Some(children) => {
+                    tree.output.push(operand.clone());
+                    tree.draw(shell, operand, children, "", 1);
+                }
+                None => {
+                    shell.set_exit_status(1);
+                    tree.output.push(format!("{operand} [error opening dir]"));
+                }
+            }
+        }
+
+        let directories = plural(tree.directories, "directory", "directories");
+        tree.output.push(String::new());
+        tree.output.push(if tree.directories_only {
+            directories
+        } else {
+            format!("{directories}, {}", plural(tree.files, "file", "files"))
+        });
+        Ok(CommandOutput(Some(tree.output.join("\n"))))
+    }
+}
+
+/// An entry of a directory drawn in the tree.
+struct Entry {
+    name: String,
+    inode_type: InodeType,
+    /// The target of a symbolic link.
+    target: Option<String>,
+}
+
+struct Tree {
+    all: bool,
+    directories_only: bool,
+    full_path: bool,
+    max_depth: Option<usize>,
+    output: Vec<String>,
+    directories: usize,
+    files: usize,
+}
+
+impl Tree {
+    /// Returns the entries of a directory which are drawn, or `None` if it can't be read.
+    fn children(&self, shell: &Shell, path: &str) -> Option<Vec<Entry>> {
+        let fs = shell.fs.lock();
+        let names = shell
+            .current_session
+            .list_directory(&fs, Path::new(path))
+            .ok()?;
+        let entries = names
+            .into_iter()
+            .filter(|name| self.all || !name.starts_with('.'))
+            .filter_map(|name| {
+                let inode = shell
+                    .current_session
+                    .find_inode(&fs, Path::new(&join(path, &name)))?;
+                let target = match &inode.content {
+                    InodeContent::Link(link) => Some(link.target_path.clone()),
+                    _ => None,
+                };
+                Some(Entry {
+                    name,
+                    inode_type: inode.inode_type(),
+                    target,
+                })
+            })
+            .filter(|entry| !self.directories_only || entry.inode_type == InodeType::Directory)
+            .collect();
+        Some(entries)
+    }
+
+    /// Draws the entries of a directory at a depth, each line starting with a prefix showing
+    /// the directories they are in.
+    fn draw(&mut self, shell: &Shell, path: &str, entries: Vec<Entry>, prefix: &str, depth: usize) {
+        let count = entries.len();
+        for (index, entry) in entries.into_iter().enumerate() {
+            let last = index + 1 == count;
+            let entry_path = join(path, &entry.name);
+            let mut line = format!(
+                "{prefix}{}{}",
+                if last { "└── " } else { "├── " },
+                if self.full_path {
+                    &entry_path
+                } else {
+                    &entry.name
+                }
+            );
+            if let Some(target) = &entry.target {
+                line.push_str(&format!(" -> {target}"));
+            }
+            if entry.inode_type != InodeType::Directory {
+                self.files += 1;
+                self.output.push(line);
+                continue;
+            }
+            self.directories += 1;
+            if self.max_depth.is_some_and(|max| depth >= max) {
+                self.output.push(line);
+                continue;
+            }
+            match self.children(shell, &entry_path) {
+                Some(children) => {
+                    self.output.push(line);
+                    let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+                    self.draw(shell, &entry_path, children, &prefix, depth + 1);
+                }
+                None => self.output.push(format!("{line} [error opening dir]")),
+            }
+        }
+    }
+}
+
+fn join(directory: &str, name: &str) -> String {
+    if directory.ends_with('/') {
+        format!("{directory}{name}")
+    } else {
+        format!("{directory}/{name}")
+    }
+}
+
+fn plural(count: usize, singular: &str, plural: &str) -> String {
+    format!("{count} {}", if count == 1 { singular } else { plural })
+}
@@ -0,0 +1,97 @@
+//! The umask builtin, displaying or setting the file mode creation mask of the session.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::ShellError,
+    fs::permissions::mode::ModeChange,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct UmaskCommand;
+
+impl ExecutableCommand for UmaskCommand {
+    fn name(&self) -> &'static str {
+        "umask"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "S",
+                Some('S'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut crate::shell::Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mode = match args {
+            Some(Argument::Basic(BasicArgument::String(mode))) => mode,
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => {
+                let umask = shell.current_session.umask();
+                let output = if flags.flag("S").is_some() {
+                    format_symbolic(umask)
+                } else {
+                    format!("{umask:04o}")
+                };
+                return Ok(CommandOutput(Some(output)));
+            }
+        };
+
+        let umask = match ModeChange::parse(&mode) {
+            Some(ModeChange::Octal { mode: umask, .. }) if umask <= 0o777 => umask,
+            // A symbolic umask describes the permissions that are kept, not the masked ones.
+            Some(change @ ModeChange::Symbolic(_)) => {
+                let kept = change.apply(!shell.current_session.umask() & 0o777, false, 0);
+                !kept & 0o777
+            }
+            // Like bash, a mode starting with a digit can only be an octal number.
+            _ if mode.starts_with(|c: char| c.is_ascii_digit()) => {
+                return Ok(CommandOutput(Some(format!(
+                    "umask: {mode}: octal number out of range"
+                ))));
+            }
+            _ => {
+                return Ok(CommandOutput(Some(format!(
+                    "umask: {mode}: invalid symbolic mode"
+                ))));
+            }
+        };
+        shell.current_session.set_umask(umask);
+
+        Ok(CommandOutput(None))
+    }
+}
+
+/// Formats the permissions kept by a umask, like `u=rwx,g=rx,o=rx`.
+fn format_symbolic(umask: u32) -> String {
+    let kept = !umask & 0o777;
+    [('u', 6), ('g', 3), ('o', 0)]
+        .iter()
+        .map(|(class, shift)| {
+            let bits = (kept >> shift) & 0o7;
+            let letters = [(0o4, 'r'), (0o2, 'w'), (0o1, 'x')]
+                .iter()
+                .filter(|(bit, _)| bits & bit != 0)
+                .map(|(_, letter)| *letter)
+                .collect::<String>();
+            format!("{class}={letters}")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
@@ -0,0 +1,132 @@
+//! The uniq command, filtering adjacent repeated lines.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct UniqCommand;
+
+impl ExecutableCommand for UniqCommand {
+    fn name(&self) -> &'static str {
+        "uniq"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "count",
+                Some('c'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "repeated",
+                Some('d'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "unique",
+                Some('u'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "ignore-case",
+                Some('i'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the lines of a file, or of the standard input, merging adjacent identical lines.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut paths = string_arguments(args)?;
+        if paths.len() > 1 {
+            // The second operand would be an output file, which this shell does not write to.
+            shell.set_exit_status(1);
+            return Ok(CommandOutput(Some(format!(
+                "uniq: extra operand '{}'",
+                paths[1]
+            ))));
+        }
+        let path = paths.pop().unwrap_or_else(|| "-".to_string());
+        let content = match read_input(shell, &path) {
+            Ok(content) => content,
+            Err(error) => {
+                shell.set_exit_status(1);
+                return Ok(CommandOutput(Some(format!(
+                    "uniq: {path}: {}",
+                    error_message(error)?
+                ))));
+            }
+        };
+
+        let groups = groups(&content, flags.flag("ignore-case").is_some());
+        let repeated = flags.flag("repeated").is_some();
+        let unique = flags.flag("unique").is_some();
+        let lines = groups
+            .into_iter()
+            .filter(|(count, _)| (!repeated || *count > 1) && (!unique || *count == 1))
+            .map(|(count, line)| {
+                if flags.flag("count").is_some() {
+                    format!("{count:>7} {line}")
+                } else {
+                    line.to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        Ok(CommandOutput((!lines.is_empty()).then(|| lines.join("\n"))))
+    }
+}
+
+/// Returns the runs of adjacent identical lines of a content, with their lengths.
+fn groups(content: &str, ignore_case: bool) -> Vec<(usize, &str)> {
+    let mut groups: Vec<(usize, &str)> = Vec::new();
+    for line in content.split_terminator('\n') {
+        match groups.last_mut() {
+            Some((count, previous))
+                if *previous == line || (ignore_case && previous.eq_ignore_ascii_case(line)) =>
+            {
+                *count += 1;
+            }
+            _ => groups.push((1, line)),
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups() {
+        assert_eq!(
+            groups("a\na\nb\na\n", false),
+            vec![(2, "a"), (1, "b"), (1, "a")]
+        );
+        assert_eq!(groups("a\nA\n", false), vec![(1, "a"), (1, "A")]);
+        assert_eq!(groups("a\nA\n", true), vec![(2, "a")]);
+        assert_eq!(groups("", false), vec![]);
+    }
+}
@@ -0,0 +1,172 @@
+//! The useradd command, creating user accounts.
+
+use crate::{
+    AccountKind,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_flag},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        is_valid_name, require_superuser, resolve_group, resolve_groups,
+    },
+    errors::{FileSystemError, ShellError},
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct UserAddCommand;
+
+impl ExecutableCommand for UserAddCommand {
+    fn name(&self) -> &'static str {
+        "useradd"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "create-home",
+                Some('m'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "gid",
+                Some('g'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "groups",
+                Some('G'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "shell",
+                Some('s'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "home-dir",
+                Some('d'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "system",
+                Some('r'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        if let Some(output) = require_superuser(shell, self.name()) {
+            return Ok(output);
+        }
+        let mut fs = shell.fs.lock();
+        let username = match args {
+            Some(Argument::Basic(BasicArgument::String(username))) => username,
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => {
+                return Ok(CommandOutput(Some(
+                    "Usage: useradd [options] LOGIN".to_string(),
+                )));
+            }
+        };
+        if !is_valid_name(&username) {
+            return Ok(CommandOutput(Some(format!(
+                "useradd: invalid user name '{username}'"
+            ))));
+        }
+        if fs.find_user_by_name(&username).is_some() {
+            return Ok(CommandOutput(Some(format!(
+                "useradd: user '{username}' already exists"
+            ))));
+        }
+
+        let primary_group = match string_flag(&flags, "gid")? {
+            Some(group) => match resolve_group(&fs, group) {
+                Some(group_id) if fs.get_group(group_id).is_some() => Some(group_id),
+                _ => {
+                    return Ok(CommandOutput(Some(format!(
+                        "useradd: group '{group}' does not exist"
+                    ))));
+                }
+            },
+            None => {
+                if fs.find_group_by_name(&username).is_some() {
+                    return Ok(CommandOutput(Some(format!(
+                        "useradd: group {username} exists - if you want to add this user to that group, use -g."
+                    ))));
+                }
+                None
+            }
+        };
+        let supplementary_groups = match string_flag(&flags, "groups")? {
+            Some(groups) => match resolve_groups(&fs, groups) {
+                Ok(groups) => groups,
+                Err(group) => {
+                    return Ok(CommandOutput(Some(format!(
+                        "useradd: group '{group}' does not exist"
+                    ))));
+                }
+            },
+            None => Vec::new(),
+        };
+
+        let kind = if flags.flag("system").is_some() {
+            AccountKind::System
+        } else {
+            AccountKind::Regular
+        };
+        // Without an explicit primary group, each user gets a group of its own.
+        let primary_group = match primary_group {
+            Some(group_id) => group_id,
+            None => fs.create_group(&username, kind)?,
+        };
+        let user_id = fs.create_user(&username, primary_group, kind)?;
+        let login_shell = string_flag(&flags, "shell")?;
+        let home = string_flag(&flags, "home-dir")?;
+        let home = fs.update_user(user_id, |user| {
+            user.set_supplementary_groups(&supplementary_groups);
+            if let Some(login_shell) = login_shell {
+                user.shell = login_shell.to_string();
+            }
+            if let Some(home) = home {
+                user.home = home.to_string();
+            }
+            user.home.clone()
+        })?;
+
+        if flags.flag("create-home").is_some() {
+            match fs.create_home(user_id) {
+                Ok(()) => {}
+                Err(ShellError::FileSystem(FileSystemError::EntryAlreadyExists(_))) => {
+                    return Ok(CommandOutput(Some(format!(
+                        "useradd: warning: the home directory {home} already exists.\n\
+                         useradd: Not copying any file from skel directory into it."
+                    ))));
+                }
+                Err(ShellError::FileSystem(_)) => {
+                    return Ok(CommandOutput(Some(format!(
+                        "useradd: cannot create directory {home}"
+                    ))));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(CommandOutput(None))
+    }
+}
@@ -0,0 +1,102 @@
+//! The userdel command, deleting user accounts.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        require_superuser,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct UserDelCommand;
+
+impl ExecutableCommand for UserDelCommand {
+    fn name(&self) -> &'static str {
+        "userdel"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "remove",
+                Some('r'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        if let Some(output) = require_superuser(shell, self.name()) {
+            return Ok(output);
+        }
+        let mut fs = shell.fs.lock();
+        let username = match args {
+            Some(Argument::Basic(BasicArgument::String(username))) => username,
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => {
+                return Ok(CommandOutput(Some(
+                    "Usage: userdel [options] LOGIN".to_string(),
+                )));
+            }
+        };
+        let Some(user) = fs.find_user_by_name(&username) else {
+            return Ok(CommandOutput(Some(format!(
+                "userdel: user '{username}' does not exist"
+            ))));
+        };
+        let logged_in = shell
+            .host()
+            .logins()
+            .iter()
+            .any(|login| login.user == user.id);
+        if user.id == shell.current_session.current_user() || logged_in {
+            return Ok(CommandOutput(Some(format!(
+                "userdel: user {username} is currently used by process 1"
+            ))));
+        }
+
+        let user_id = user.id;
+        let user = fs.remove_user(user_id)?;
+        // The group created along with the user goes away with it, unless it is still the
+        // primary group of another user.
+        if let Some(group) = user
+            .primary_group()
+            .and_then(|group_id| fs.get_group(group_id))
+            .filter(|group| group.name == user.name)
+        {
+            let group_id = group.id;
+            let in_use = fs
+                .users()
+                .any(|other| other.primary_group() == Some(group_id));
+            if !in_use {
+                fs.remove_group(group_id)?;
+            }
+        }
+
+        if flags.flag("remove").is_some() {
+            if fs.find_absolute_inode(&user.home).is_none() {
+                return Ok(CommandOutput(Some(format!(
+                    "userdel: {username} home directory ({}) not found",
+                    user.home
+                ))));
+            }
+            fs.remove_inode(&user.home)?;
+        }
+
+        Ok(CommandOutput(None))
+    }
+}
@@ -0,0 +1,167 @@
+//! The usermod command, modifying user accounts.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgument, BasicArgumentKind, string_flag},
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        is_valid_name, require_superuser, resolve_group, resolve_groups,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct UserModCommand;
+
+impl ExecutableCommand for UserModCommand {
+    fn name(&self) -> &'static str {
+        "usermod"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "append",
+                Some('a'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "gid",
+                Some('g'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "groups",
+                Some('G'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "shell",
+                Some('s'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "home",
+                Some('d'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .with_flag(FlagSpecification::new(
+                "login",
+                Some('l'),
+                false,
+                ArgumentKind::Basic(BasicArgumentKind::String),
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Basic(BasicArgumentKind::String))
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        if let Some(output) = require_superuser(shell, self.name()) {
+            return Ok(output);
+        }
+        let mut fs = shell.fs.lock();
+        let username = match args {
+            Some(Argument::Basic(BasicArgument::String(username))) => username,
+            Some(_) => return Err(ShellError::Internal("Invalid argument".to_string())),
+            None => {
+                return Ok(CommandOutput(Some(
+                    "Usage: usermod [options] LOGIN".to_string(),
+                )));
+            }
+        };
+        let Some(user_id) = fs.find_user_by_name(&username).map(|user| user.id) else {
+            return Ok(CommandOutput(Some(format!(
+                "usermod: user '{username}' does not exist"
+            ))));
+        };
+        if flags.flags().is_empty() {
+            return Ok(CommandOutput(Some("usermod: no changes".to_string())));
+        }
+        let append = flags.flag("append").is_some();
+
+        let primary_group = match string_flag(&flags, "gid")? {
+            Some(group) => match resolve_group(&fs, group)
+                .filter(|group_id| fs.get_group(*group_id).is_some())
+            {
+                Some(group_id) => Some(group_id),
+                None => {
+                    return Ok(CommandOutput(Some(format!(
+                        "usermod: group '{group}' does not exist"
+                    ))));
+                }
+            },
+            None => None,
+        };
+        let supplementary_groups = match string_flag(&flags, "groups")? {
+            Some(groups) => match resolve_groups(&fs, groups) {
+                Ok(groups) => Some(groups),
+                Err(group) => {
+                    return Ok(CommandOutput(Some(format!(
+                        "usermod: group '{group}' does not exist"
+                    ))));
+                }
+            },
+            None if append => {
+                return Ok(CommandOutput(Some(
+                    "usermod: -a flag is ONLY allowed with the -G flag".to_string(),
+                )));
+            }
+            None => None,
+        };
+        let login = string_flag(&flags, "login")?;
+        if let Some(login) = login {
+            if !is_valid_name(login) {
+                return Ok(CommandOutput(Some(format!(
+                    "usermod: invalid user name '{login}'"
+                ))));
+            }
+            if login != username && fs.find_user_by_name(login).is_some() {
+                return Ok(CommandOutput(Some(format!(
+                    "usermod: user '{login}' already exists"
+                ))));
+            }
+        }
+
+        let login_shell = string_flag(&flags, "shell")?;
+        let home = string_flag(&flags, "home")?;
+        fs.update_user(user_id, |user| {
+            if let Some(group_id) = primary_group {
+                user.set_primary_group(group_id);
+            }
+            if let Some(groups) = supplementary_groups {
+                if append {
+                    for group_id in groups {
+                        user.add_group(group_id);
+                    }
+                } else {
+                    user.set_supplementary_groups(&groups);
+                }
+            }
+            if let Some(login_shell) = login_shell {
+                user.shell = login_shell.to_string();
+            }
+            if let Some(home) = home {
+                user.home = home.to_string();
+            }
+            if let Some(login) = login {
+                user.name = login.to_string();
+            }
+        })?;
+
+        Ok(CommandOutput(None))
+    }
+}
@@ -0,0 +1,74 @@
+//! The w command, showing who is logged in and what they are doing.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::ArgumentKind,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        list::who::user_name,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct WCommand;
+
+impl ExecutableCommand for WCommand {
+    fn name(&self) -> &'static str {
+        "w"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "no-header",
+                Some('h'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        None
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        _: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let logins = shell.host().logins();
+        let fs = shell.fs.lock();
+        let now = fs.now();
+
+        let mut lines = Vec::new();
+        if flags.flag("no-header").is_none() {
+            let boot_time = shell.host().boot_time();
+            let users = logins.len();
+            lines.push(format!(
+                " {} up {:>2} min,  {users} user{},  load average: 0.00, 0.00, 0.00",
+                now.format("%H:%M:%S"),
+                (now - boot_time).num_minutes(),
+                if users == 1 { "" } else { "s" }
+            ));
+            lines.push(format!(
+                "{:<8} {:<8} {:<8} {:<6} {}",
+                "USER", "TTY", "LOGIN@", "IDLE", "WHAT"
+            ));
+        }
+        for login in logins {
+            lines.push(format!(
+                "{:<8} {:<8} {:<8} {:<6} {}",
+                user_name(&fs, login.user),
+                login.terminal,
+                login.login_time.format("%H:%M"),
+                "0.00s",
+                login.what
+            ));
+        }
+        Ok(CommandOutput(Some(lines.join("\n"))))
+    }
+}
@@ -0,0 +1,189 @@
+//! The wc command, counting the lines, words, characters and bytes of files.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        error_message,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+        read_input,
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct WcCommand;
+
+/// The smallest width of the columns when the standard input is counted, as its size is unknown.
+const STDIN_WIDTH: usize = 7;
+
+impl ExecutableCommand for WcCommand {
+    fn name(&self) -> &'static str {
+        "wc"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "lines",
+                Some('l'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "words",
+                Some('w'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "chars",
+                Some('m'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "bytes",
+                Some('c'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Prints the counts of the files given, or of the standard input, followed by their total
+    /// when there are several files.
+    ///
+    /// Without options, the lines, words and bytes are counted.
+    fn execute(
+        &self,
+        flags: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let mut columns =
+            ["lines", "words", "chars", "bytes"].map(|name| flags.flag(name).is_some());
+        if columns == [false; 4] {
+            columns = [true, true, false, true];
+        }
+
+        let paths = string_arguments(args)?;
+        let names = if paths.is_empty() {
+            vec![None]
+        } else {
+            paths.iter().map(|path| Some(path.as_str())).collect()
+        };
+        let mut rows = Vec::new();
+        let mut errors = Vec::new();
+        let mut total = Counts::default();
+        for name in &names {
+            match read_input(shell, name.unwrap_or("-")) {
+                Ok(content) => {
+                    let counts = Counts::of(&content);
+                    total.add(&counts);
+                    rows.push((counts, *name));
+                }
+                Err(error) => {
+                    let name = name.unwrap_or("-");
+                    errors.push((rows.len(), format!("wc: {name}: {}", error_message(error)?)));
+                }
+            }
+        }
+        if names.len() > 1 {
+            rows.push((total.clone(), Some("total")));
+        }
+        if !errors.is_empty() {
+            shell.set_exit_status(1);
+        }
+
+        // Like GNU wc, the columns are as wide as the total size of the files, so that they line
+        // up, unless a single number is printed.
+        let width = if names.len() == 1 && columns.iter().filter(|column| **column).count() == 1 {
+            1
+        } else if names.iter().any(|name| matches!(name, None | Some("-"))) {
+            STDIN_WIDTH.max(total.bytes.to_string().len())
+        } else {
+            total.bytes.to_string().len()
+        };
+        let mut lines = rows
+            .into_iter()
+            .map(|(counts, name)| {
+                let mut line = counts
+                    .values()
+                    .into_iter()
+                    .zip(columns)
+                    .filter(|(_, shown)| *shown)
+                    .map(|(value, _)| format!("{value:>width$}"))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                if let Some(name) = name {
+                    line.push(' ');
+                    line.push_str(name);
+                }
+                line
+            })
+            .collect::<Vec<_>>();
+        // The errors are printed in the order of the files.
+        for (index, error) in errors.into_iter().rev() {
+            lines.insert(index, error);
+        }
+
+        Ok(CommandOutput(Some(lines.join("\n"))))
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+struct Counts {
+    lines: usize,
+    words: usize,
+    chars: usize,
+    bytes: usize,
+}
+
+impl Counts {
+    /// Counts a content, the lines being the number of line terminators.
+    fn of(content: &str) -> Self {
+        Self {
+            lines: content.matches('\n').count(),
+            words: content.split_whitespace().count(),
+            chars: content.chars().count(),
+            bytes: content.len(),
+        }
+    }
+
+    fn add(&mut self, other: &Counts) {
+        self.lines += other.lines;
+        self.words += other.words;
+        self.chars += other.chars;
+        self.bytes += other.bytes;
+    }
+
+    /// Returns the counts in the order of the columns.
+    fn values(&self) -> [usize; 4] {
+        [self.lines, self.words, self.chars, self.bytes]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counts() {
+        assert_eq!(
+            Counts::of("héllo world\n  two  words\nno newline"),
+            Counts {
+                lines: 2,
+                words: 6,
+                chars: 35,
+                bytes: 36,
+            }
+        );
+        assert_eq!(Counts::of(""), Counts::default());
+    }
+}
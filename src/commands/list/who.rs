@@ -0,0 +1,84 @@
+//! The who command, listing the users logged in.
+
+use crate::{
+    FileSystem, UserId,
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::ArgumentKind,
+        flags::{FlagDefinition, FlagDefinitionBuilder, FlagSpecification},
+    },
+    errors::ShellError,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct WhoCommand;
+
+impl ExecutableCommand for WhoCommand {
+    fn name(&self) -> &'static str {
+        "who"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new()
+            .with_flag(FlagSpecification::new(
+                "heading",
+                Some('H'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .with_flag(FlagSpecification::new(
+                "count",
+                Some('q'),
+                false,
+                ArgumentKind::Flag,
+            ))
+            .into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        None
+    }
+
+    fn execute(
+        &self,
+        flags: Flags,
+        _: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let logins = shell.host().logins();
+        let fs = shell.fs.lock();
+
+        if flags.flag("count").is_some() {
+            let names = logins
+                .iter()
+                .map(|login| user_name(&fs, login.user))
+                .collect::<Vec<_>>();
+            return Ok(CommandOutput(Some(format!(
+                "{}\n# users={}",
+                names.join(" "),
+                names.len()
+            ))));
+        }
+
+        let mut lines = Vec::new();
+        if flags.flag("heading").is_some() {
+            lines.push(format!("{:<8} {:<12} {}", "NAME", "LINE", "TIME"));
+        }
+        for login in logins {
+            lines.push(format!(
+                "{:<8} {:<12} {}",
+                user_name(&fs, login.user),
+                login.terminal,
+                login.login_time.format("%Y-%m-%d %H:%M")
+            ));
+        }
+        Ok(CommandOutput(Some(lines.join("\n"))))
+    }
+}
+
+/// Returns the name of a user, or its id if the user no longer exists.
+pub fn user_name(fs: &FileSystem, user_id: UserId) -> String {
+    fs.get_user(user_id)
+        .map_or_else(|| user_id.to_string(), |user| user.name.clone())
+}
@@ -0,0 +1,40 @@
+//! The whoami command, printing the name of the current user.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::ArgumentKind,
+        flags::{FlagDefinition, FlagDefinitionBuilder},
+    },
+    errors::{SessionError, ShellError},
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct WhoAmICommand;
+
+impl ExecutableCommand for WhoAmICommand {
+    fn name(&self) -> &'static str {
+        "whoami"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new().into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        None
+    }
+
+    fn execute(
+        &self,
+        _: Flags,
+        _: Option<Argument>,
+        shell: &mut crate::shell::Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let fs = shell.fs.lock();
+        let user = fs
+            .get_user(shell.current_session.current_user())
+            .ok_or(ShellError::Session(SessionError::UserNotFound))?;
+        Ok(CommandOutput(Some(user.name.clone())))
+    }
+}
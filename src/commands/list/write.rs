@@ -0,0 +1,108 @@
+//! The write command, sending a message to the terminal of another user.
+
+use crate::{
+    commands::{
+        Argument, CommandOutput, ExecutableCommand, Flags,
+        args::{ArgumentKind, BasicArgumentKind, string_arguments},
+        flags::{FlagDefinition, FlagDefinitionBuilder},
+        list::who::user_name,
+    },
+    errors::ShellError,
+    host::HOSTNAME,
+    shell::Shell,
+};
+
+#[derive(Default, Clone, Copy)]
+pub struct WriteCommand;
+
+impl ExecutableCommand for WriteCommand {
+    fn name(&self) -> &'static str {
+        "write"
+    }
+
+    fn flags(&self) -> FlagDefinition {
+        FlagDefinitionBuilder::new().into_flag_definition()
+    }
+
+    fn args(&self) -> Option<ArgumentKind> {
+        Some(ArgumentKind::Enumeration(BasicArgumentKind::String))
+    }
+
+    /// Sends the lines read from the standard input, until its end, to a terminal of the user.
+    fn execute(
+        &self,
+        _: Flags,
+        args: Option<Argument>,
+        shell: &mut Shell,
+    ) -> Result<CommandOutput, ShellError> {
+        let args = string_arguments(args)?;
+        let (username, terminal) = match args.as_slice() {
+            [username] => (username, None),
+            [username, terminal] => (username, Some(terminal)),
+            _ => return Ok(CommandOutput(Some("usage: write user [tty]".to_string()))),
+        };
+
+        let logins = shell.host().logins();
+        let fs = shell.fs.lock();
+        let recipient = fs.find_user_by_name(username).map(|user| user.id);
+        let terminals = logins
+            .iter()
+            .filter(|login| Some(login.user) == recipient)
+            .map(|login| login.terminal.as_str())
+            .collect::<Vec<_>>();
+        let mut warning = None;
+        let recipient_terminal = match (terminal, terminals.as_slice()) {
+            (_, []) => {
+                return Ok(CommandOutput(Some(format!(
+                    "write: {username} is not logged in"
+                ))));
+            }
+            (Some(terminal), terminals) => {
+                if !terminals.contains(&terminal.as_str()) {
+                    return Ok(CommandOutput(Some(format!(
+                        "write: {username} is not logged in on {terminal}"
+                    ))));
+                }
+                terminal.clone()
+            }
+            (None, [terminal]) => (*terminal).to_string(),
+            (None, [terminal, ..]) => {
+                warning = Some(format!(
+                    "write: {username} is logged in more than once; writing to {terminal}"
+                ));
+                (*terminal).to_string()
+            }
+        };
+
+        // The message is signed with the name the sender logged in with, not the one `su`
+        // switched to.
+        let own_terminal = shell.current_session.terminal();
+        let sender = logins
+            .iter()
+            .find(|login| login.terminal == own_terminal)
+            .map_or(shell.current_session.current_user(), |login| login.user);
+        let sender = user_name(&fs, sender);
+        let now = fs.now();
+        drop(fs);
+
+        let mut message = vec![format!(
+            "Message from {sender}@{HOSTNAME} on {own_terminal} at {} ...",
+            now.format("%H:%M")
+        )];
+        while let Some(line) = shell.read_line("") {
+            message.push(line);
+        }
+        message.push("EOF".to_string());
+
+        if !shell
+            .host()
+            .send_message(&recipient_terminal, message.join("\n"))
+        {
+            // The recipient logged out while the message was typed.
+            return Ok(CommandOutput(Some(format!(
+                "write: {username} is not logged in on {recipient_terminal}"
+            ))));
+        }
+        Ok(CommandOutput(warning))
+    }
+}
@@ -0,0 +1,329 @@
+//! Line-based differences between texts, shown by `diff` and applied by `patch`.
+//!
+//! A [`Diff`] finds a shortest edit script with the Myers algorithm. It can be shown in the normal
+//! or unified formats of `diff`, so that a grader can show how a file differs from its expected
+//! content the way a learner would see it in the shell.
+
+/// A line of a difference, which keeps its newline so that a missing final newline is a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edit<'a> {
+    /// A line of both texts.
+    Equal(&'a str),
+    /// A line of the old text only.
+    Delete(&'a str),
+    /// A line of the new text only.
+    Insert(&'a str),
+}
+
+/// The differences between two texts.
+#[derive(Debug, Clone)]
+pub struct Diff<'a> {
+    edits: Vec<Edit<'a>>,
+}
+
+impl<'a> Diff<'a> {
+    /// Compares two texts line by line.
+    ///
+    /// In every group of changed lines, the deleted lines come before the inserted ones.
+    #[must_use]
+    pub fn new(old: &'a str, new: &'a str) -> Self {
+        let old = old.split_inclusive('\n').collect::<Vec<_>>();
+        let new = new.split_inclusive('\n').collect::<Vec<_>>();
+        let mut edits = Vec::new();
+        let mut deleted = Vec::new();
+        let mut inserted = Vec::new();
+        for edit in shortest_edit(&old, &new) {
+            match edit {
+                Edit::Delete(_) => deleted.push(edit),
+                Edit::Insert(_) => inserted.push(edit),
+                Edit::Equal(_) => {
+                    edits.append(&mut deleted);
+                    edits.append(&mut inserted);
+                    edits.push(edit);
+                }
+            }
+        }
+        edits.append(&mut deleted);
+        edits.append(&mut inserted);
+        Self { edits }
+    }
+
+    #[must_use]
+    pub fn edits(&self) -> &[Edit<'a>] {
+        &self.edits
+    }
+
+    /// Checks if the texts are the same.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.edits.iter().all(|edit| matches!(edit, Edit::Equal(_)))
+    }
+
+    /// Returns the lines of the normal format of `diff`, like `2,3c2` followed by the lines
+    /// changed.
+    #[must_use]
+    pub fn normal(&self) -> Vec<String> {
+        let mut output = Vec::new();
+        let (mut old_line, mut new_line) = (0, 0);
+        let mut index = 0;
+        while index < self.edits.len() {
+            if let Edit::Equal(_) = self.edits[index] {
+                old_line += 1;
+                new_line += 1;
+                index += 1;
+                continue;
+            }
+            let end = self.edits[index..]
+                .iter()
+                .position(|edit| matches!(edit, Edit::Equal(_)))
+                .map_or(self.edits.len(), |length| index + length);
+            let group = &self.edits[index..end];
+            let deleted = group
+                .iter()
+                .filter(|edit| matches!(edit, Edit::Delete(_)))
+                .count();
+            let inserted = group.len() - deleted;
+            let old_range = range(old_line + 1, old_line + deleted);
+            let new_range = range(new_line + 1, new_line + inserted);
+            output.push(match (deleted, inserted) {
+                (0, _) => format!("{old_line}a{new_range}"),
+                (_, 0) => format!("{old_range}d{new_line}"),
+                _ => format!("{old_range}c{new_range}"),
+            });
+            for (position, edit) in group.iter().enumerate() {
+                if position == deleted && deleted > 0 {
+                    output.push("---".to_string());
+                }
+                match edit {
+                    Edit::Delete(line) => push_line(&mut output, "< ", line),
+                    Edit::Insert(line) => push_line(&mut output, "> ", line),
+                    Edit::Equal(_) => {}
+                }
+            }
+            old_line += deleted;
+            new_line += inserted;
+            index = end;
+        }
+        output
+    }
+
+    /// Returns the hunks of the unified format of `diff`, with as many lines of context around
+    /// the changes, without the `---` and `+++` headers naming the files.
+    #[must_use]
+    pub fn unified(&self, context: usize) -> Vec<String> {
+        let changes = self
+            .edits
+            .iter()
+            .enumerate()
+            .filter(|(_, edit)| !matches!(edit, Edit::Equal(_)))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        // Changes separated by at most twice the context are shown in the same hunk.
+        let mut hunks: Vec<(usize, usize)> = Vec::new();
+        for index in changes {
+            let start = index.saturating_sub(context);
+            let end = (index + context + 1).min(self.edits.len());
+            match hunks.last_mut() {
+                Some((_, last_end)) if start <= *last_end => *last_end = end,
+                _ => hunks.push((start, end)),
+            }
+        }
+
+        let mut output = Vec::new();
+        for (start, end) in hunks {
+            let counts = |edits: &[Edit]| {
+                let old = edits
+                    .iter()
+                    .filter(|edit| !matches!(edit, Edit::Insert(_)))
+                    .count();
+                let new = edits
+                    .iter()
+                    .filter(|edit| !matches!(edit, Edit::Delete(_)))
+                    .count();
+                (old, new)
+            };
+            let (old_start, new_start) = counts(&self.edits[..start]);
+            let (old_count, new_count) = counts(&self.edits[start..end]);
+            output.push(format!(
+                "@@ -{} +{} @@",
+                hunk_range(old_start, old_count),
+                hunk_range(new_start, new_count)
+            ));
+            for edit in &self.edits[start..end] {
+                match edit {
+                    Edit::Equal(line) => push_line(&mut output, " ", line),
+                    Edit::Delete(line) => push_line(&mut output, "-", line),
+                    Edit::Insert(line) => push_line(&mut output, "+", line),
+                }
+            }
+        }
+        output
+    }
+}
+
+/// Finds a shortest edit script turning the old lines into the new ones, with the Myers
+/// algorithm.
+fn shortest_edit<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let max = n + m;
+    // The furthest old line reached on each diagonal `k = x - y`, offset by `max`.
+    let mut furthest = vec![0; 2 * max as usize + 2];
+    let at = |k: isize| (k + max + 1) as usize;
+    let mut trace = Vec::new();
+    'search: for depth in 0..=max {
+        trace.push(furthest.clone());
+        for k in (-depth..=depth).step_by(2) {
+            let mut x = if k == -depth || (k != depth && furthest[at(k - 1)] < furthest[at(k + 1)])
+            {
+                furthest[at(k + 1)]
+            } else {
+                furthest[at(k - 1)] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            furthest[at(k)] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (depth, furthest) in trace.iter().enumerate().rev() {
+        let depth = depth as isize;
+        let k = x - y;
+        let previous_k = if k == -depth || (k != depth && furthest[at(k - 1)] < furthest[at(k + 1)])
+        {
+            k + 1
+        } else {
+            k - 1
+        };
+        let previous_x = furthest[at(previous_k)];
+        let previous_y = previous_x - previous_k;
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(old[x as usize]));
+        }
+        if depth > 0 {
+            if x == previous_x {
+                edits.push(Edit::Insert(new[previous_y as usize]));
+            } else {
+                edits.push(Edit::Delete(old[previous_x as usize]));
+            }
+        }
+        (x, y) = (previous_x, previous_y);
+    }
+    edits.reverse();
+    edits
+}
+
+/// Formats a range of lines of the normal format, like `2,4`.
+fn range(start: usize, end: usize) -> String {
+    if end <= start {
+        start.to_string()
+    } else {
+        format!("{start},{end}")
+    }
+}
+
+/// Formats a range of lines of a unified hunk from the number of lines before it and its length,
+/// like `3,2`.
+fn hunk_range(before: usize, count: usize) -> String {
+    match count {
+        0 => format!("{before},0"),
+        1 => (before + 1).to_string(),
+        _ => format!("{},{count}", before + 1),
+    }
+}
+
+/// Adds a line to an output, marking it if it has no newline.
+fn push_line(output: &mut Vec<String>, prefix: &str, line: &str) {
+    match line.strip_suffix('\n') {
+        Some(line) => output.push(format!("{prefix}{line}")),
+        None => {
+            output.push(format!("{prefix}{line}"));
+            output.push("\\ No newline at end of file".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shortest_edit() {
+        let diff = Diff::new("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(
+            diff.edits(),
+            [
+                Edit::Equal("a\n"),
+                Edit::Delete("b\n"),
+                Edit::Equal("c\n"),
+                Edit::Insert("d\n"),
+            ]
+        );
+        assert!(Diff::new("same\n", "same\n").is_empty());
+        assert!(!Diff::new("end", "end\n").is_empty());
+        assert_eq!(Diff::new("", "a\n").edits(), [Edit::Insert("a\n")]);
+    }
+
+    #[test]
+    fn test_normal() {
+        assert_eq!(
+            Diff::new("a\nb\nc\nd\n", "a\nx\nc\nd\ne\n").normal(),
+            ["2c2", "< b", "---", "> x", "4a5", "> e"]
+        );
+        assert_eq!(
+            Diff::new("a\nb\nc\n", "c\n").normal(),
+            ["1,2d0", "< a", "< b"]
+        );
+        assert_eq!(
+            Diff::new("a\n", "a").normal(),
+            ["1c1", "< a", "---", "> a", "\\ No newline at end of file"]
+        );
+    }
+
+    #[test]
+    fn test_unified() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\n9\n10\n11\n";
+        assert_eq!(
+            Diff::new(old, new).unified(3),
+            [
+                "@@ -1,6 +1,6 @@",
+                " 1",
+                " 2",
+                "-3",
+                "+three",
+                " 4",
+                " 5",
+                " 6",
+                "@@ -8,3 +8,4 @@",
+                " 8",
+                " 9",
+                " 10",
+                "+11",
+            ]
+        );
+        assert_eq!(
+            Diff::new(old, new).unified(1),
+            [
+                "@@ -2,3 +2,3 @@",
+                " 2",
+                "-3",
+                "+three",
+                " 4",
+                "@@ -10 +10,2 @@",
+                " 10",
+                "+11"
+            ]
+        );
+        assert_eq!(Diff::new("", "a\n").unified(3), ["@@ -0,0 +1 @@", "+a"]);
+    }
+}
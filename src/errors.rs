@@ -22,8 +22,16 @@ pub enum FileSystemError {
     EntryAlreadyExists(String),
     #[error("'{0}': Not a directory")]
     NotADirectory(String),
+    #[error("{0}: Is a directory")]
+    IsADirectory(String),
     #[error("User already exists: {0}")]
     UserAlreadyExists(String),
+    #[error("User not found: {0}")]
+    UserNotFound(String),
+    #[error("Group already exists: {0}")]
+    GroupAlreadyExists(String),
+    #[error("Group not found: {0}")]
+    GroupNotFound(String),
     #[error("Directory not found")]
     DirectoryNotFound(String),
     #[error("Incorrect path")]
@@ -32,6 +40,16 @@ pub enum FileSystemError {
     EntryNotFound(String),
     #[error("Failed to get parent directory")]
     FailedToGetParent,
+    #[error("{0}: Permission denied")]
+    PermissionDenied(String),
+    #[error("{0}: Operation not permitted")]
+    OperationNotPermitted(String),
+    #[error("'{0}': hard link not allowed for directory")]
+    HardLinkToDirectory(String),
+    #[error("{0}: No space left on device")]
+    NoSpaceLeft(String),
+    #[error("{0}: Disk quota exceeded")]
+    QuotaExceeded(String),
 }
 
 #[derive(Error, Debug, PartialEq)]
@@ -1,66 +1,234 @@
 //! The virtual file system used by the shell.
 
-use std::{
-    path::PathBuf,
-    sync::{Arc, Mutex},
-};
+use std::{collections::HashMap, sync::Arc};
 
-use inode::{Inode, content::Directory};
-use users::{GroupStore, UserStore};
+use chrono::{DateTime, Utc};
+use inode::{
+    Inode,
+    content::{Directory, InodeType},
+    size::Size,
+    table::{InodeNumber, InodeTable, ROOT_INODE},
+};
+use users::{AccountKind, GroupStore, ROOT_GROUP_ID, ROOT_USER_ID, SUDO_GROUP_ID, UserStore};
 
 use crate::{
     FilePermissions, Group, GroupId, InodeContent, InodeMetadata, User, UserId,
+    clock::{Clock, SystemClock},
     errors::{FileSystemError, ShellError},
-    fs::inode::content::File,
+    fs::{inode::content::File, permissions::Access},
+    sudoers::{DEFAULT_SUDOERS, SUDOERS_PATH},
 };
+use accounts::{GROUP_PATH, PASSWD_PATH, SHADOW_PATH};
+use quota::Quota;
 
+pub mod accounts;
 pub mod inode;
 pub mod permissions;
+pub mod quota;
 pub mod resolver;
 pub mod users;
 
+/// The directories of a new file system, all owned by root, following the Filesystem
+/// Hierarchy Standard. Parents come before their children.
+const SKELETON: [(&str, u32); 11] = [
+    ("/bin", 0o755),
+    ("/dev", 0o755),
+    ("/etc", 0o755),
+    ("/home", 0o755),
+    ("/proc", 0o555),
+    ("/root", 0o700),
+    ("/tmp", 0o1777),
+    ("/usr", 0o755),
+    ("/usr/bin", 0o755),
+    ("/var", 0o755),
+    ("/var/log", 0o755),
+];
+
+/// The size of the disk of a new file system, 20 GiB.
+const DEFAULT_CAPACITY: u64 = 20 * 1024 * 1024 * 1024;
+
 /// The file system
+///
+/// Cloning a file system takes a snapshot of it: the copy and the original don't share any inode,
+/// only their clock.
 #[derive(Debug, Clone)]
 pub struct FileSystem {
-    pub root: Arc<Mutex<Inode>>,
+    inodes: InodeTable,
     users: UserStore,
     groups: GroupStore,
+    clock: Arc<dyn Clock>,
+    /// The size of the disk holding the file system, in bytes.
+    capacity: u64,
+    /// The limits of the users having a quota.
+    quotas: HashMap<UserId, Quota>,
 }
 
 impl FileSystem {
     #[must_use]
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock))
+    }
+
+    /// Creates a file system reading the time from the given clock.
+    #[must_use]
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
         let mut groups = GroupStore::new();
         let mut users = UserStore::new();
 
-        // Create the root user and group
-        let root_group_id = groups.add_group("root".to_string());
-        let root_user_id = users.add_user("root".to_string());
+        // Create the root user and group, and the group of the users allowed to use sudo
+        let root_group_id = groups.add_group_with_id(ROOT_GROUP_ID, "root".to_string());
+        let root_user_id = users.add_user_with_id(ROOT_USER_ID, "root".to_string());
         let user = users.user_mut(root_user_id).expect("User not found");
         user.add_group(root_group_id);
+        groups.add_group_with_id(SUDO_GROUP_ID, "sudo".to_string());
+
+        // Create the root directory, which is its own parent
+        let mut inodes = InodeTable::new();
+        let mut metadata = InodeMetadata::new(
+            FilePermissions::from_mode(0o755),
+            root_user_id,
+            root_group_id,
+        );
+        metadata.mark_created(clock.now());
+        let root = inodes.insert(
+            InodeContent::Directory(Directory::new(ROOT_INODE)),
+            metadata,
+        );
+        debug_assert_eq!(root, ROOT_INODE);
 
-        // Create the root directory
-        let root = Arc::new(Mutex::new(
-            Inode::new(
-                String::new(),
-                InodeContent::Directory(Directory::new()),
+        let mut fs = Self {
+            inodes,
+            users,
+            groups,
+            clock,
+            capacity: DEFAULT_CAPACITY,
+            quotas: HashMap::new(),
+        };
+        for (path, mode) in SKELETON {
+            fs.create_directory(
+                path,
                 InodeMetadata::new(
-                    FilePermissions::from_mode(0o755),
+                    FilePermissions::from_mode(mode),
                     root_user_id,
                     root_group_id,
                 ),
-                None,
             )
-            .expect("Failed to create root inode"),
-        ));
+            .unwrap_or_else(|_| panic!("Failed to create {path}"));
+        }
+        let sudoers = fs
+            .create_file(
+                SUDOERS_PATH,
+                InodeMetadata::new(
+                    FilePermissions::from_mode(0o440),
+                    root_user_id,
+                    root_group_id,
+                ),
+            )
+            .expect("Failed to create the sudoers file");
+        if let Some(InodeContent::File(file)) =
+            fs.inode_mut(sudoers).map(|inode| &mut inode.content)
+        {
+            file.content = DEFAULT_SUDOERS.to_string();
+        }
+        fs.sync_account_files()
+            .expect("Failed to create the account files");
+        fs
+    }
 
-        Self {
-            root,
-            users,
-            groups,
+    /// Returns the current time, according to the clock of the file system.
+    #[must_use]
+    pub fn now(&self) -> DateTime<Utc> {
+        self.clock.now()
+    }
+
+    /// Replaces the clock of the file system. The times already recorded are kept.
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    /// Returns the size of the disk holding the file system, in bytes.
+    #[must_use]
+    pub fn capacity(&self) -> u64 {
+        self.capacity
+    }
+
+    /// Changes the size of the disk, in bytes. Writes which would allocate more space than it
+    /// holds fail, while the inodes already allocated are kept.
+    pub fn set_capacity(&mut self, capacity: u64) {
+        self.capacity = capacity;
+    }
+
+    /// Returns the space allocated to every inode, in bytes.
+    #[must_use]
+    pub fn used_space(&self) -> u64 {
+        self.inodes.iter().map(Size::allocated_size).sum()
+    }
+
+    /// Returns the limits of a user, which aren't set by default.
+    #[must_use]
+    pub fn quota(&self, user_id: UserId) -> Quota {
+        self.quotas.get(&user_id).copied().unwrap_or_default()
+    }
+
+    /// Changes the limits of a user. Like lowering the capacity, the inodes already owned by the
+    /// user are kept even if they go over the new limits.
+    pub fn set_quota(&mut self, user_id: UserId, quota: Quota) {
+        if quota == Quota::default() {
+            self.quotas.remove(&user_id);
+        } else {
+            self.quotas.insert(user_id, quota);
         }
     }
 
+    /// Returns the space allocated to the inodes owned by a user, in bytes, and their number.
+    #[must_use]
+    pub fn usage(&self, user_id: UserId) -> (u64, usize) {
+        self.inodes
+            .iter()
+            .filter(|inode| inode.metadata.owner == user_id)
+            .fold((0, 0), |(space, inodes), inode| {
+                (space + inode.allocated_size(), inodes + 1)
+            })
+    }
+
+    /// Checks that more space and inodes can be given to a user, first within their quota, then
+    /// on the disk. Giving nothing always succeeds, so that a full disk can still be cleaned up.
+    ///
+    /// # Errors
+    ///
+    /// The following errors can be returned:
+    /// - `FileSystemError::QuotaExceeded` if the user would go over one of their limits
+    /// - `FileSystemError::NoSpaceLeft` if the disk would be over its capacity
+    fn check_allocation(
+        &self,
+        path: &str,
+        owner: UserId,
+        space: u64,
+        inodes: usize,
+    ) -> Result<(), ShellError> {
+        let quota = self.quota(owner);
+        if quota != Quota::default() {
+            let (used_space, used_inodes) = self.usage(owner);
+            let over_space =
+                space > 0 && quota.space.is_some_and(|limit| used_space + space > limit);
+            let over_inodes = inodes > 0
+                && quota
+                    .inodes
+                    .is_some_and(|limit| used_inodes + inodes > limit);
+            if over_space || over_inodes {
+                return Err(ShellError::FileSystem(FileSystemError::QuotaExceeded(
+                    path.to_string(),
+                )));
+            }
+        }
+        if space > 0 && self.used_space() + space > self.capacity {
+            return Err(ShellError::FileSystem(FileSystemError::NoSpaceLeft(
+                path.to_string(),
+            )));
+        }
+        Ok(())
+    }
+
     pub fn get_user(&self, user_id: UserId) -> Option<&User> {
         self.users.user(user_id)
     }
@@ -69,88 +237,228 @@ impl FileSystem {
         self.groups.group(group_id)
     }
 
-    pub fn create_file(&mut self, path: &str) -> Result<Arc<Mutex<Inode>>, ShellError> {
+    pub fn find_user_by_name(&self, username: &str) -> Option<&User> {
+        self.users
+            .find_by_username(username)
+            .and_then(|user_id| self.users.user(user_id))
+    }
+
+    pub fn find_group_by_name(&self, name: &str) -> Option<&Group> {
+        self.groups
+            .find_by_name(name)
+            .and_then(|group_id| self.groups.group(group_id))
+    }
+
+    /// Returns the inode with the given number, if it is in use.
+    #[must_use]
+    pub fn inode(&self, number: InodeNumber) -> Option<&Inode> {
+        self.inodes.get(number)
+    }
+
+    pub fn inode_mut(&mut self, number: InodeNumber) -> Option<&mut Inode> {
+        self.inodes.get_mut(number)
+    }
+
+    /// Returns the number of inodes in use.
+    #[must_use]
+    pub fn inode_count(&self) -> usize {
+        self.inodes.len()
+    }
+
+    pub fn create_file(
+        &mut self,
+        path: &str,
+        metadata: InodeMetadata,
+    ) -> Result<InodeNumber, ShellError> {
         let (components, last_component) = path.split_at(path.rfind('/').unwrap_or(0) + 1);
-        let current_inode = self.path_from_components(components)?;
-        current_inode
-            .lock()
-            .expect("Failed to lock inode")
-            .add_child(
-                last_component,
-                InodeContent::File(File::new()),
-                Arc::downgrade(&current_inode),
-            )
+        let parent = self.path_from_components(components)?;
+        self.add_child(
+            parent,
+            last_component,
+            InodeContent::File(File::new()),
+            metadata,
+        )
     }
 
-    pub fn create_directory(&mut self, path: &str) -> Result<Arc<Mutex<Inode>>, ShellError> {
+    pub fn create_directory(
+        &mut self,
+        path: &str,
+        metadata: InodeMetadata,
+    ) -> Result<InodeNumber, ShellError> {
         let (components, last_component) = path.split_at(path.rfind('/').unwrap_or(0) + 1);
-        let current_inode = self.path_from_components(components)?;
-        current_inode
-            .lock()
-            .expect("Failed to lock inode")
-            .add_child(
-                last_component,
-                InodeContent::Directory(Directory::new()),
-                Arc::downgrade(&current_inode),
-            )
+        let parent = self.path_from_components(components)?;
+        self.add_child(
+            parent,
+            last_component,
+            InodeContent::Directory(Directory::new(parent)),
+            metadata,
+        )
+    }
+
+    /// Replaces the content of the file at an absolute path, the space it grows by being given to
+    /// its owner.
+    ///
+    /// # Errors
+    ///
+    /// The following errors can be returned:
+    /// - `FileSystemError::EntryNotFound` if there is no inode at the path
+    /// - `FileSystemError::IsADirectory` if the inode is not a file
+    /// - `FileSystemError::QuotaExceeded` or `FileSystemError::NoSpaceLeft` if the new content
+    ///   doesn't fit
+    pub fn write_file(&mut self, path: &str, content: String) -> Result<(), ShellError> {
+        self.replace_file_content(path, content, true)
+    }
+
+    /// Replaces the content of the file at an absolute path, checking that the space it grows by
+    /// can be allocated only if `limited`.
+    fn replace_file_content(
+        &mut self,
+        path: &str,
+        content: String,
+        limited: bool,
+    ) -> Result<(), ShellError> {
+        let number = self.find_absolute_inode(path).ok_or_else(|| {
+            ShellError::FileSystem(FileSystemError::EntryNotFound(path.to_string()))
+        })?;
+        let inode = self
+            .inodes
+            .get(number)
+            .ok_or_else(|| ShellError::Internal(format!("Inode {number} should exist")))?;
+        if !matches!(inode.content, InodeContent::File(_)) {
+            return Err(ShellError::FileSystem(FileSystemError::IsADirectory(
+                path.to_string(),
+            )));
+        }
+        let file = File { content };
+        if limited {
+            let growth = file.allocated_size().saturating_sub(inode.allocated_size());
+            self.check_allocation(path, inode.metadata.owner, growth, 0)?;
+        }
+
+        let now = self.now();
+        if let Some(inode) = self.inodes.get_mut(number) {
+            inode.content = InodeContent::File(file);
+            inode.metadata.mark_modified(now);
+        }
+        Ok(())
+    }
+
+    /// Creates a new entry at `link_path` pointing to the inode at `target_path`.
+    pub fn create_hard_link(
+        &mut self,
+        target_path: &str,
+        link_path: &str,
+    ) -> Result<(), ShellError> {
+        let target = self.find_absolute_inode(target_path).ok_or_else(|| {
+            ShellError::FileSystem(FileSystemError::EntryNotFound(target_path.to_string()))
+        })?;
+        if self.inodes.get(target).map(Inode::inode_type) == Some(InodeType::Directory) {
+            return Err(ShellError::FileSystem(
+                FileSystemError::HardLinkToDirectory(target_path.to_string()),
+            ));
+        }
+        let (components, last_component) =
+            link_path.split_at(link_path.rfind('/').unwrap_or(0) + 1);
+        let parent = self.path_from_components(components)?;
+        self.directory_mut(parent)?
+            .add_child(last_component, target)?;
+        let now = self.now();
+        if let Some(target) = self.inodes.get_mut(target) {
+            target.hard_link_count += 1;
+            target.metadata.mark_changed(now);
+        }
+        self.mark_modified(parent);
+        Ok(())
+    }
+
+    /// Creates a new inode and adds an entry pointing to it in the directory `parent`.
+    ///
+    /// # Errors
+    ///
+    /// The following errors can be returned:
+    /// - `ShellError::Internal` if the name is empty, or the parent is not a directory
+    /// - `FileSystemError::EntryAlreadyExists` if the directory already has an entry of that name
+    /// - `FileSystemError::QuotaExceeded` or `FileSystemError::NoSpaceLeft` if the owner of the
+    ///   inode can't be given it
+    fn add_child(
+        &mut self,
+        parent: InodeNumber,
+        name: &str,
+        content: InodeContent,
+        metadata: InodeMetadata,
+    ) -> Result<InodeNumber, ShellError> {
+        if name.is_empty() {
+            return Err(ShellError::Internal(
+                "Cannot create an inode with an empty name".to_string(),
+            ));
+        }
+        if self.directory_mut(parent)?.contains(name) {
+            return Err(ShellError::FileSystem(FileSystemError::EntryAlreadyExists(
+                name.to_string(),
+            )));
+        }
+        self.check_allocation(name, metadata.owner, content.allocated_size(), 1)?;
+
+        let is_directory = matches!(content, InodeContent::Directory(_));
+        let mut metadata = metadata;
+        metadata.mark_created(self.now());
+        let number = self.inodes.insert(content, metadata);
+        self.directory_mut(parent)?.add_child(name, number)?;
+        if is_directory {
+            // The `..` entry of the new directory links to its parent.
+            if let Some(parent) = self.inodes.get_mut(parent) {
+                parent.hard_link_count += 1;
+            }
+        }
+        self.mark_modified(parent);
+        Ok(number)
+    }
+
+    /// Records a write of the content of an inode, like a change of the entries of a directory.
+    fn mark_modified(&mut self, number: InodeNumber) {
+        let now = self.now();
+        if let Some(inode) = self.inodes.get_mut(number) {
+            inode.metadata.mark_modified(now);
+        }
+    }
+
+    fn directory_mut(&mut self, number: InodeNumber) -> Result<&mut Directory, ShellError> {
+        match self.inodes.get_mut(number).map(|inode| &mut inode.content) {
+            Some(InodeContent::Directory(directory)) => Ok(directory),
+            _ => Err(ShellError::Internal(
+                "Tried to change the entries of an inode that is not a directory".to_string(),
+            )),
+        }
     }
 
-    fn path_from_components(&self, path: &str) -> Result<Arc<Mutex<Inode>>, ShellError> {
-        let mut current_inode = Some(self.root.clone());
+    fn path_from_components(&self, path: &str) -> Result<InodeNumber, ShellError> {
+        let mut current_inode = ROOT_INODE;
         // We separate the path into components (which should be directories) and the last component (which is the path)
         let (components, _) = path.split_at(path.rfind('/').unwrap_or(0) + 1);
         let mut components_iter = components.split('/').peekable();
         while let Some(component) = components_iter.next() {
-            let inode = current_inode.take().expect("Inode is empty");
-            let inode_copy = inode.clone();
-            let inner_inode = inode.lock().expect("Failed to lock inner inode");
-            match component {
-                "" | "." => {
-                    current_inode = Some(inode_copy);
-                }
-                ".." => match inner_inode.parent() {
-                    Some(parent) => {
-                        let parent_arc = parent.upgrade().expect("Failed to upgrade parent");
-                        current_inode = Some(parent_arc);
-                    }
-                    None => {
-                        let path = inner_inode.path()?;
-                        if path == PathBuf::from("/") {
-                            current_inode = Some(inode_copy);
+            let inode = self.inodes.get(current_inode).ok_or_else(|| {
+                ShellError::Internal(format!("Inode {current_inode} should exist"))
+            })?;
+            match (component, &inode.content) {
+                ("" | ".", _) => {}
+                ("..", InodeContent::Directory(directory)) => current_inode = directory.parent,
+                (path, _) => match inode.find_child(path) {
+                    Some(child) => {
+                        if self.inodes.get(child).map(Inode::inode_type)
+                            == Some(InodeType::Directory)
+                        {
+                            current_inode = child;
+                        } else if components_iter.peek().is_some() {
+                            return Err(ShellError::FileSystem(FileSystemError::NotADirectory(
+                                path.to_string(),
+                            )));
                         } else {
                             return Err(ShellError::FileSystem(
-                                FileSystemError::DirectoryNotFound(format!(
-                                    "Failed to get parent of {}",
-                                    path.display()
-                                )),
+                                FileSystemError::EntryAlreadyExists(path.to_string()),
                             ));
                         }
                     }
-                },
-                path => match inner_inode.find_child(path) {
-                    Some(child_inode) => {
-                        let child_content = child_inode
-                            .lock()
-                            .expect("Failed to lock child inode")
-                            .content
-                            .clone();
-                        match child_content {
-                            InodeContent::Directory(_) => {
-                                current_inode = Some(child_inode);
-                            }
-                            _ => {
-                                if components_iter.peek().is_some() {
-                                    return Err(ShellError::FileSystem(
-                                        FileSystemError::NotADirectory(path.to_string()),
-                                    ));
-                                } else {
-                                    return Err(ShellError::FileSystem(
-                                        FileSystemError::EntryAlreadyExists(path.to_string()),
-                                    ));
-                                }
-                            }
-                        }
-                    }
                     None => {
                         return Err(ShellError::FileSystem(FileSystemError::EntryNotFound(
                             path.to_string(),
@@ -160,55 +468,307 @@ impl FileSystem {
             }
         }
 
-        Ok(current_inode.expect("Inode is empty"))
+        Ok(current_inode)
     }
 
+    /// Adds a user with a group of the same name and a home directory, like `useradd -m` does.
     pub fn add_user(&mut self, username: &str) -> Result<UserId, ShellError> {
-        if self.find_absolute_inode("/home").is_none() {
-            self.create_directory("/home")?;
+        if self.users.find_by_username(username).is_some() {
+            return Err(ShellError::FileSystem(FileSystemError::UserAlreadyExists(
+                username.to_string(),
+            )));
         }
 
+        let user_group_id = self.create_group(username, AccountKind::Regular)?;
+        let user_id = self.create_user(username, user_group_id, AccountKind::Regular)?;
+        self.create_home(user_id)?;
+        Ok(user_id)
+    }
+
+    /// Adds a user with the given primary group, without creating its home directory.
+    ///
+    /// The id is taken from the range of the kind of account.
+    ///
+    /// # Errors
+    ///
+    /// The following errors can be returned:
+    /// - `FileSystemError::UserAlreadyExists` if a user has the same name
+    /// - `FileSystemError::GroupNotFound` if the primary group does not exist
+    pub fn create_user(
+        &mut self,
+        username: &str,
+        primary_group: GroupId,
+        kind: AccountKind,
+    ) -> Result<UserId, ShellError> {
         if self.users.find_by_username(username).is_some() {
-            return Err(ShellError::FileSystem(FileSystemError::EntryAlreadyExists(
+            return Err(ShellError::FileSystem(FileSystemError::UserAlreadyExists(
                 username.to_string(),
             )));
         }
+        if self.groups.group(primary_group).is_none() {
+            return Err(ShellError::FileSystem(FileSystemError::GroupNotFound(
+                primary_group.to_string(),
+            )));
+        }
 
-        let user_id = self.users.add_user(username.to_string());
-        let user_group_id = self.groups.add_group(username.to_string());
+        let user_id = self.users.add_user(username.to_string(), kind);
         let user = self.users.user_mut(user_id).expect("User not found");
-        user.add_group(user_group_id);
-        let user_home_dir = self.create_directory(&format!("/home/{username}"))?;
-        let mut user_home_dir_inode = user_home_dir
-            .lock()
-            .expect("Failed to lock user home directory");
-        user_home_dir_inode.metadata.group = user_group_id;
-        user_home_dir_inode.metadata.owner = user_id;
+        user.add_group(primary_group);
+        self.sync_account_files()?;
         Ok(user_id)
     }
 
+    /// Creates the home directory of a user, owned by the user and its primary group.
+    ///
+    /// The `/home` directory is created beforehand if needed.
+    pub fn create_home(&mut self, user_id: UserId) -> Result<(), ShellError> {
+        let user = self.users.user(user_id).ok_or_else(|| {
+            ShellError::FileSystem(FileSystemError::UserNotFound(user_id.to_string()))
+        })?;
+        let home = user.home.clone();
+        let group_id = user.primary_group().unwrap_or(ROOT_GROUP_ID);
+
+        if home.starts_with("/home/") && self.find_absolute_inode("/home").is_none() {
+            self.create_directory(
+                "/home",
+                InodeMetadata::new(
+                    FilePermissions::from_mode(0o755),
+                    ROOT_USER_ID,
+                    ROOT_GROUP_ID,
+                ),
+            )?;
+        }
+        self.create_directory(
+            &home,
+            InodeMetadata::new(FilePermissions::from_mode(0o755), user_id, group_id),
+        )?;
+        Ok(())
+    }
+
+    /// Removes a user. Its files are left in place, owned by its now unknown id.
+    pub fn remove_user(&mut self, user_id: UserId) -> Result<User, ShellError> {
+        let user = self.users.remove_user(user_id).ok_or_else(|| {
+            ShellError::FileSystem(FileSystemError::UserNotFound(user_id.to_string()))
+        })?;
+        self.sync_account_files()?;
+        Ok(user)
+    }
+
+    /// Modifies a user, then updates the account files accordingly.
+    pub fn update_user<R>(
+        &mut self,
+        user_id: UserId,
+        update: impl FnOnce(&mut User) -> R,
+    ) -> Result<R, ShellError> {
+        let user = self.users.user_mut(user_id).ok_or_else(|| {
+            ShellError::FileSystem(FileSystemError::UserNotFound(user_id.to_string()))
+        })?;
+        let result = update(user);
+        self.sync_account_files()?;
+        Ok(result)
+    }
+
+    pub fn users(&self) -> impl Iterator<Item = &User> {
+        self.users.users()
+    }
+
+    /// Adds a group without members, with an id from the range of the kind of account.
+    ///
+    /// # Errors
+    ///
+    /// `FileSystemError::GroupAlreadyExists` is returned if a group has the same name.
+    pub fn create_group(&mut self, name: &str, kind: AccountKind) -> Result<GroupId, ShellError> {
+        if self.groups.find_by_name(name).is_some() {
+            return Err(ShellError::FileSystem(FileSystemError::GroupAlreadyExists(
+                name.to_string(),
+            )));
+        }
+        let group_id = self.groups.add_group(name.to_string(), kind);
+        self.sync_account_files()?;
+        Ok(group_id)
+    }
+
+    /// Removes a group, and the membership of every user in it.
+    pub fn remove_group(&mut self, group_id: GroupId) -> Result<Group, ShellError> {
+        let group = self.groups.remove_group(group_id).ok_or_else(|| {
+            ShellError::FileSystem(FileSystemError::GroupNotFound(group_id.to_string()))
+        })?;
+        for user in self.users.users_mut() {
+            user.remove_group(group_id);
+        }
+        self.sync_account_files()?;
+        Ok(group)
+    }
+
+    /// Rewrites `/etc/passwd`, `/etc/group` and `/etc/shadow` from the user and group stores.
+    ///
+    /// The shadow file, holding the password hashes, is only readable by root.
+    pub fn sync_account_files(&mut self) -> Result<(), ShellError> {
+        let files = [
+            (PASSWD_PATH, 0o644, accounts::passwd(&self.users)),
+            (
+                GROUP_PATH,
+                0o644,
+                accounts::group(&self.users, &self.groups),
+            ),
+            (SHADOW_PATH, 0o600, accounts::shadow(&self.users)),
+        ];
+        for (path, mode, content) in files {
+            self.write_system_file(path, mode, content)?;
+        }
+        Ok(())
+    }
+
+    /// Replaces the content of a file owned by root, creating it and its parent if needed.
+    ///
+    /// Like with the blocks ext4 reserves for root, the content is written even on a full disk,
+    /// so that the account files never disagree with the stores they are written from.
+    fn write_system_file(
+        &mut self,
+        path: &str,
+        mode: u32,
+        content: String,
+    ) -> Result<(), ShellError> {
+        let metadata = || {
+            InodeMetadata::new(
+                FilePermissions::from_mode(mode),
+                ROOT_USER_ID,
+                ROOT_GROUP_ID,
+            )
+        };
+        if self.find_absolute_inode(path).is_none() {
+            let parent = &path[..path.rfind('/').unwrap_or(0)];
+            if !parent.is_empty() && self.find_absolute_inode(parent).is_none() {
+                self.create_directory(
+                    parent,
+                    InodeMetadata::new(
+                        FilePermissions::from_mode(0o755),
+                        ROOT_USER_ID,
+                        ROOT_GROUP_ID,
+                    ),
+                )?;
+            }
+            self.create_file(path, metadata())?;
+        }
+        self.replace_file_content(path, content, false)
+    }
+
+    pub fn groups(&self) -> impl Iterator<Item = &Group> {
+        self.groups.groups.values()
+    }
+
+    #[must_use]
+    pub fn find_absolute_inode(&self, path: &str) -> Option<InodeNumber> {
+        self.find_relative_inode(ROOT_INODE, path)
+    }
+
+    /// Finds the inode at a path relative to the directory `base`.
     #[must_use]
-    pub fn find_absolute_inode(&self, path: &str) -> Option<Arc<Mutex<Inode>>> {
-        find_relative_inode(self.root.clone(), path)
+    pub fn find_relative_inode(
+        &self,
+        base: InodeNumber,
+        relative_path: &str,
+    ) -> Option<InodeNumber> {
+        let mut current_inode = base;
+        for component in relative_path.split('/') {
+            let inode = self.inodes.get(current_inode)?;
+            current_inode = match (component, &inode.content) {
+                ("" | ".", _) => current_inode,
+                ("..", InodeContent::Directory(directory)) => directory.parent,
+                (name, _) => inode.find_child(name)?,
+            };
+        }
+        Some(current_inode)
+    }
+
+    /// Finds the inode at an absolute path on behalf of a user, checking that the user can search
+    /// every directory traversed along the way.
+    ///
+    /// # Errors
+    ///
+    /// The following errors can be returned:
+    /// - `FileSystemError::NotADirectory` if a traversed component is not a directory
+    /// - `FileSystemError::PermissionDenied` if a traversed directory can't be searched
+    /// - `FileSystemError::EntryNotFound` if a component does not exist
+    pub fn find_inode_as(&self, user: &User, path: &str) -> Result<InodeNumber, ShellError> {
+        let mut current_inode = ROOT_INODE;
+        for component in path.split('/') {
+            if component.is_empty() || component == "." {
+                continue;
+            }
+            let inode = self.inodes.get(current_inode).ok_or_else(|| {
+                ShellError::Internal(format!("Inode {current_inode} should exist"))
+            })?;
+            let InodeContent::Directory(directory) = &inode.content else {
+                return Err(ShellError::FileSystem(FileSystemError::NotADirectory(
+                    path.to_string(),
+                )));
+            };
+            if !inode.metadata.can_access(user, Access::Execute) {
+                return Err(ShellError::FileSystem(FileSystemError::PermissionDenied(
+                    path.to_string(),
+                )));
+            }
+            current_inode = if component == ".." {
+                directory.parent
+            } else {
+                directory.find_child(component).ok_or_else(|| {
+                    ShellError::FileSystem(FileSystemError::EntryNotFound(path.to_string()))
+                })?
+            };
+        }
+        Ok(current_inode)
     }
 
+    /// Removes an entry, decreasing the link count of the inode it pointed to.
+    ///
+    /// Removing a directory entry releases every entry below it, so that other links to the same
+    /// inodes keep an accurate count. An inode is freed once nothing points to it.
     pub fn remove_inode(&mut self, path: &str) -> Result<(), ShellError> {
         let (parent, child) = path.split_at(path.rfind('/').unwrap_or(0) + 1);
 
-        if let Some(parent_inode) = self.find_absolute_inode(parent) {
-            let mut parent_inode = parent_inode.lock().expect("Failed to lock parent inode");
-            if let InodeContent::Directory(parent_dir) = &mut parent_inode.content {
-                println!("Removing child: {child:?} from {parent:?}");
-                parent_dir.remove_child(child);
-            }
-        } else {
-            return Err(ShellError::FileSystem(FileSystemError::DirectoryNotFound(
+        let parent = self.find_absolute_inode(parent).ok_or_else(|| {
+            ShellError::FileSystem(FileSystemError::DirectoryNotFound(
                 "Parent directory does not exist".to_string(),
-            )));
+            ))
+        })?;
+        let number = self
+            .directory_mut(parent)?
+            .remove_child(child)
+            .ok_or_else(|| {
+                ShellError::FileSystem(FileSystemError::EntryNotFound(child.to_string()))
+            })?;
+        if self.inodes.get(number).map(Inode::inode_type) == Some(InodeType::Directory)
+            && let Some(parent) = self.inodes.get_mut(parent)
+        {
+            parent.hard_link_count -= 1;
         }
-
+        self.mark_modified(parent);
+        self.release_link(number);
         Ok(())
     }
+
+    /// Drops a link to an inode, recursively for directories, freeing the inode once it isn't
+    /// linked anymore.
+    fn release_link(&mut self, number: InodeNumber) {
+        let now = self.now();
+        let Some(inode) = self.inodes.get_mut(number) else {
+            return;
+        };
+        if let InodeContent::Directory(directory) = &inode.content {
+            let children = directory.children.values().copied().collect::<Vec<_>>();
+            for child in children {
+                self.release_link(child);
+            }
+            self.inodes.remove(number);
+        } else {
+            inode.hard_link_count = inode.hard_link_count.saturating_sub(1);
+            inode.metadata.mark_changed(now);
+            if inode.hard_link_count == 0 {
+                self.inodes.remove(number);
+            }
+        }
+    }
 }
 
 impl Default for FileSystem {
@@ -217,23 +777,175 @@ impl Default for FileSystem {
     }
 }
 
-pub fn find_relative_inode(
-    base: Arc<Mutex<Inode>>,
-    relative_path: &str,
-) -> Option<Arc<Mutex<Inode>>> {
-    let mut current_inode = base;
-    for component in relative_path.split('/') {
-        if component.is_empty() {
-            continue;
-        }
-        let child_inode = current_inode
-            .lock()
-            .expect("Failed to lock inode")
-            .find_child(component);
-        match child_inode {
-            Some(inode) => current_inode = inode,
-            None => return None,
-        }
-    }
-    Some(current_inode)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(mode: u32) -> InodeMetadata {
+        InodeMetadata::new(
+            FilePermissions::from_mode(mode),
+            ROOT_USER_ID,
+            ROOT_GROUP_ID,
+        )
+    }
+
+    fn link_count(fs: &FileSystem, path: &str) -> u64 {
+        fs.find_absolute_inode(path)
+            .and_then(|number| fs.inode(number))
+            .map(Inode::hard_link_count)
+            .unwrap_or_else(|| panic!("Missing {path}"))
+    }
+
+    #[test]
+    fn test_find_inode_by_path() {
+        let mut fs = FileSystem::new();
+        let dir = fs
+            .create_directory("/tmp/dir", metadata(0o755))
+            .expect("Failed to create directory");
+        let file = fs
+            .create_file("/tmp/dir/file", metadata(0o644))
+            .expect("Failed to create file");
+
+        assert_eq!(fs.find_absolute_inode("/"), Some(ROOT_INODE));
+        assert_eq!(fs.find_absolute_inode("/.."), Some(ROOT_INODE));
+        assert_eq!(fs.find_absolute_inode("/tmp/dir"), Some(dir));
+        assert_eq!(fs.find_absolute_inode("/tmp/dir/./file"), Some(file));
+        assert_eq!(fs.find_absolute_inode("/tmp/dir/../dir/file"), Some(file));
+        assert_eq!(fs.find_relative_inode(dir, "file"), Some(file));
+        assert_eq!(fs.find_absolute_inode("/tmp/dir/file/.."), None);
+        assert!(fs.create_file("/tmp/dir/", metadata(0o644)).is_err());
+    }
+
+    #[test]
+    fn test_hard_link_count() {
+        let mut fs = FileSystem::new();
+        fs.create_directory("/tmp/dir", metadata(0o755))
+            .expect("Failed to create directory");
+        fs.create_directory("/tmp/dir/sub", metadata(0o755))
+            .expect("Failed to create directory");
+        fs.create_file("/tmp/file", metadata(0o644))
+            .expect("Failed to create file");
+        assert_eq!(link_count(&fs, "/tmp/dir"), 3);
+        assert_eq!(link_count(&fs, "/tmp/dir/sub"), 2);
+
+        fs.create_hard_link("/tmp/file", "/tmp/dir/link")
+            .expect("Failed to link file");
+        assert_eq!(link_count(&fs, "/tmp/file"), 2);
+        assert!(fs.create_hard_link("/tmp/dir", "/tmp/link").is_err());
+
+        fs.remove_inode("/tmp/file").expect("Failed to remove file");
+        assert_eq!(link_count(&fs, "/tmp/dir/link"), 1);
+        fs.remove_inode("/tmp/dir/sub")
+            .expect("Failed to remove directory");
+        assert_eq!(link_count(&fs, "/tmp/dir"), 2);
+    }
+
+    #[test]
+    fn test_removed_inodes_are_freed() {
+        let mut fs = FileSystem::new();
+        let inode_count = fs.inode_count();
+        fs.create_directory("/tmp/dir", metadata(0o755))
+            .expect("Failed to create directory");
+        let file = fs
+            .create_file("/tmp/dir/file", metadata(0o644))
+            .expect("Failed to create file");
+        fs.create_hard_link("/tmp/dir/file", "/tmp/link")
+            .expect("Failed to link file");
+
+        fs.remove_inode("/tmp/dir")
+            .expect("Failed to remove directory");
+        assert_eq!(fs.inode_count(), inode_count + 1);
+        assert_eq!(fs.find_absolute_inode("/tmp/link"), Some(file));
+        assert_eq!(link_count(&fs, "/tmp/link"), 1);
+
+        fs.remove_inode("/tmp/link").expect("Failed to remove link");
+        assert_eq!(fs.inode_count(), inode_count);
+        assert!(fs.inode(file).is_none());
+    }
+
+    #[test]
+    fn test_snapshot_is_independent() {
+        let mut fs = FileSystem::new();
+        fs.create_file("/tmp/file", metadata(0o644))
+            .expect("Failed to create file");
+        let snapshot = fs.clone();
+
+        fs.remove_inode("/tmp/file").expect("Failed to remove file");
+        fs.create_directory("/tmp/dir", metadata(0o755))
+            .expect("Failed to create directory");
+        assert!(snapshot.find_absolute_inode("/tmp/file").is_some());
+        assert!(snapshot.find_absolute_inode("/tmp/dir").is_none());
+    }
+
+    #[test]
+    fn test_capacity_limits_allocations() {
+        let mut fs = FileSystem::new();
+        fs.create_file("/tmp/file", metadata(0o644))
+            .expect("Failed to create file");
+        fs.set_capacity(fs.used_space() + 2 * inode::size::BLOCK_SIZE);
+
+        fs.write_file("/tmp/file", "x".repeat(8192))
+            .expect("The content should fit on the disk");
+        assert_eq!(
+            fs.write_file("/tmp/file", "x".repeat(8193)),
+            Err(ShellError::FileSystem(FileSystemError::NoSpaceLeft(
+                "/tmp/file".to_string()
+            )))
+        );
+        assert_eq!(
+            fs.create_directory("/tmp/dir", metadata(0o755)),
+            Err(ShellError::FileSystem(FileSystemError::NoSpaceLeft(
+                "dir".to_string()
+            )))
+        );
+        // Empty files take no space, and shrinking a file frees some.
+        fs.create_file("/tmp/empty", metadata(0o644))
+            .expect("An empty file should fit on a full disk");
+        fs.write_file("/tmp/file", String::new())
+            .expect("Failed to empty file");
+        fs.create_directory("/tmp/dir", metadata(0o755))
+            .expect("Failed to create directory");
+    }
+
+    #[test]
+    fn test_quota_limits_owner() {
+        let mut fs = FileSystem::new();
+        let user = fs.add_user("alice").expect("Failed to add user");
+        let owned = || InodeMetadata::new(FilePermissions::from_mode(0o644), user, ROOT_GROUP_ID);
+        assert_eq!(fs.usage(user), (4096, 1));
+
+        let quota = Quota {
+            space: Some(12288),
+            inodes: Some(3),
+        };
+        fs.set_quota(user, quota);
+        assert_eq!(fs.quota(user), quota);
+        fs.create_file("/home/alice/a", owned())
+            .expect("Failed to create file");
+        fs.write_file("/home/alice/a", "x".repeat(8192))
+            .expect("The content should fit in the quota");
+        assert_eq!(
+            fs.write_file("/home/alice/a", "x".repeat(8193)),
+            Err(ShellError::FileSystem(FileSystemError::QuotaExceeded(
+                "/home/alice/a".to_string()
+            )))
+        );
+        fs.create_file("/home/alice/b", owned())
+            .expect("Failed to create file");
+        assert_eq!(
+            fs.create_file("/home/alice/c", owned()),
+            Err(ShellError::FileSystem(FileSystemError::QuotaExceeded(
+                "c".to_string()
+            )))
+        );
+        assert_eq!(fs.usage(user), (12288, 3));
+        // Only the inodes of the user are counted.
+        fs.create_file("/home/alice/c", metadata(0o644))
+            .expect("The quota of the user should not apply to root");
+
+        fs.set_quota(user, Quota::default());
+        assert_eq!(fs.quota(user), Quota::default());
+        fs.create_file("/home/alice/d", owned())
+            .expect("Failed to create file");
+    }
 }
@@ -0,0 +1,70 @@
+//! The account databases of `/etc`, generated from the user and group stores.
+//!
+//! The stores are the source of truth: the files are rewritten whenever an account changes, and
+//! editing them has no effect on the accounts.
+
+use crate::fs::users::{Group, GroupStore, User, UserStore};
+
+pub const PASSWD_PATH: &str = "/etc/passwd";
+pub const GROUP_PATH: &str = "/etc/group";
+pub const SHADOW_PATH: &str = "/etc/shadow";
+
+/// Formats `/etc/passwd`, one `name:x:uid:gid:gecos:home:shell` line per user.
+pub fn passwd(users: &UserStore) -> String {
+    sorted_users(users)
+        .iter()
+        .map(|user| {
+            format!(
+                "{}:x:{}:{}::{}:{}\n",
+                user.name,
+                user.id,
+                user.primary_group().unwrap_or_default(),
+                user.home,
+                user.shell
+            )
+        })
+        .collect()
+}
+
+/// Formats `/etc/group`, one `name:x:gid:members` line per group, where the members are the
+/// users having the group as a supplementary group.
+pub fn group(users: &UserStore, groups: &GroupStore) -> String {
+    let users = sorted_users(users);
+    let mut groups: Vec<&Group> = groups.groups.values().collect();
+    groups.sort_by_key(|group| group.id);
+    groups
+        .iter()
+        .map(|group| {
+            let members = users
+                .iter()
+                .filter(|user| user.supplementary_groups().contains(&group.id))
+                .map(|user| user.name.as_str())
+                .collect::<Vec<_>>();
+            format!("{}:x:{}:{}\n", group.name, group.id, members.join(","))
+        })
+        .collect()
+}
+
+/// Formats `/etc/shadow`, one `name:hash:lastchg:min:max:warn:inactive:expire:` line per user.
+pub fn shadow(users: &UserStore) -> String {
+    sorted_users(users)
+        .iter()
+        .map(|user| {
+            let last_change = user
+                .password_changed
+                .map(|day| day.to_string())
+                .unwrap_or_default();
+            format!(
+                "{}:{}:{last_change}:0:99999:7:::\n",
+                user.name,
+                user.password.as_deref().unwrap_or("!")
+            )
+        })
+        .collect()
+}
+
+fn sorted_users(users: &UserStore) -> Vec<&User> {
+    let mut users: Vec<&User> = users.users().collect();
+    users.sort_by_key(|user| user.id);
+    users
+}
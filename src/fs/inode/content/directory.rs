@@ -1,25 +1,28 @@
 //! The content of a directory inode.
+//!
+//! A directory is a set of entries, each entry mapping a name to an inode number. Several entries
+//! (in the same directory or not) can point to the same inode, which is what a hard link is.
 
-use std::{
-    collections::{HashMap, hash_map::Entry},
-    sync::{Arc, Mutex},
-};
+use std::collections::{BTreeMap, btree_map::Entry};
 
 use crate::{
-    Inode,
     errors::{FileSystemError, ShellError},
-    fs::inode::size::Size,
+    fs::inode::{size::Size, table::InodeNumber},
 };
 
 #[derive(Debug, Clone)]
 pub struct Directory {
-    pub children: HashMap<String, Arc<Mutex<Inode>>>,
+    /// The entries of the directory, sorted by name.
+    pub children: BTreeMap<String, InodeNumber>,
+    /// The directory the `..` entry points to, which is the directory itself for the root.
+    pub parent: InodeNumber,
 }
 
 impl Directory {
-    pub fn new() -> Self {
+    pub fn new(parent: InodeNumber) -> Self {
         Self {
-            children: HashMap::new(),
+            children: BTreeMap::new(),
+            parent,
         }
     }
 
@@ -27,15 +30,15 @@ impl Directory {
         self.children.is_empty()
     }
 
-    pub fn add_child(&mut self, child: &Arc<Mutex<Inode>>) -> Result<(), ShellError> {
-        let name = child.lock().expect("Failed to lock inode").name.clone();
-        match self.children.entry(name.clone()) {
+    /// Adds an entry named `name` pointing to the inode `child`.
+    pub fn add_child(&mut self, name: &str, child: InodeNumber) -> Result<(), ShellError> {
+        match self.children.entry(name.to_string()) {
             Entry::Vacant(entry) => {
-                entry.insert(child.clone());
+                entry.insert(child);
                 Ok(())
             }
             Entry::Occupied(_) => Err(ShellError::FileSystem(FileSystemError::EntryAlreadyExists(
-                name,
+                name.to_string(),
             ))),
         }
     }
@@ -44,12 +47,13 @@ impl Directory {
         self.children.contains_key(name)
     }
 
-    pub fn remove_child(&mut self, child_name: &str) {
-        self.children.remove(child_name);
+    /// Removes the entry named `child_name`, returning the inode it pointed to.
+    pub fn remove_child(&mut self, child_name: &str) -> Option<InodeNumber> {
+        self.children.remove(child_name)
     }
 
-    pub fn find_child(&self, name: &str) -> Option<Arc<Mutex<Inode>>> {
-        self.children.get(name).cloned()
+    pub fn find_child(&self, name: &str) -> Option<InodeNumber> {
+        self.children.get(name).copied()
     }
 }
 
@@ -60,9 +64,3 @@ impl Size for Directory {
         DIR_ENTRY_SIZE
     }
 }
-
-impl Default for Directory {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -1,13 +1,10 @@
 //! The content of a link inode.
 
-use std::sync::{Mutex, Weak};
-
-use crate::{Inode, fs::inode::size::Size};
+use crate::fs::inode::size::Size;
 
 #[derive(Debug, Clone)]
 pub struct Link {
     pub target_path: String,
-    pub target_inode: Weak<Mutex<Inode>>,
 }
 
 impl Size for Link {
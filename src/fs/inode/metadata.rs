@@ -3,8 +3,11 @@
 use chrono::{DateTime, Utc};
 
 use crate::fs::{
-    permissions::FilePermissions,
-    users::{GroupId, UserId},
+    permissions::{
+        Access, FilePermissions, Permission,
+        acl::{Acl, ExtendedAcl},
+    },
+    users::{GroupId, User, UserId},
 };
 
 #[derive(Debug, Clone)]
@@ -12,19 +15,153 @@ pub struct InodeMetadata {
     pub permissions: FilePermissions,
     pub owner: UserId,
     pub group: GroupId,
+    /// The last time the content was read (atime).
+    pub accessed_at: DateTime<Utc>,
+    /// The last time the content was written (mtime).
+    pub modified_at: DateTime<Utc>,
+    /// The last time the content or the metadata changed (ctime).
+    pub changed_at: DateTime<Utc>,
+    /// The time the inode was created (birth time).
     pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
+    /// The entries of the access ACL beyond the permission bits, if any.
+    pub acl: Option<ExtendedAcl>,
+    /// The ACL given to the entries created in a directory.
+    pub default_acl: Option<Acl>,
 }
 
 impl InodeMetadata {
+    /// Creates the metadata of a new inode, its times being set by the file system when the
+    /// inode is created.
     #[must_use]
     pub fn new(permissions: FilePermissions, owner: UserId, group: GroupId) -> Self {
         Self {
             permissions,
             owner,
             group,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            accessed_at: DateTime::UNIX_EPOCH,
+            modified_at: DateTime::UNIX_EPOCH,
+            changed_at: DateTime::UNIX_EPOCH,
+            created_at: DateTime::UNIX_EPOCH,
+            acl: None,
+            default_acl: None,
         }
     }
+
+    /// Records the creation of the inode, setting every time.
+    pub fn mark_created(&mut self, now: DateTime<Utc>) {
+        self.accessed_at = now;
+        self.modified_at = now;
+        self.changed_at = now;
+        self.created_at = now;
+    }
+
+    /// Records a read of the content.
+    pub fn mark_accessed(&mut self, now: DateTime<Utc>) {
+        self.accessed_at = now;
+    }
+
+    /// Records a write of the content, which also changes the inode.
+    pub fn mark_modified(&mut self, now: DateTime<Utc>) {
+        self.modified_at = now;
+        self.changed_at = now;
+    }
+
+    /// Records a change of the metadata, like the mode, the owner or the link count.
+    pub fn mark_changed(&mut self, now: DateTime<Utc>) {
+        self.changed_at = now;
+    }
+
+    /// Returns the complete access ACL of the inode, derived from the mode for minimal ACLs.
+    #[must_use]
+    pub fn access_acl(&self) -> Acl {
+        Acl::from_parts(&self.permissions, self.acl.as_ref())
+    }
+
+    /// Replaces the access ACL of the inode, updating the rwx bits of the mode accordingly.
+    pub fn set_access_acl(&mut self, acl: Acl) {
+        let (mode, extended) = acl.into_parts();
+        let special_bits = self.permissions.mode() & 0o7000;
+        self.permissions.set_mode(special_bits | mode);
+        self.acl = extended;
+    }
+
+    /// Checks if the inode has an ACL that is not equivalent to its mode, marked by a `+` in
+    /// `ls -l`.
+    #[must_use]
+    pub fn has_extended_acl(&self) -> bool {
+        self.acl.is_some() || self.default_acl.is_some()
+    }
+
+    /// Returns the permission class that applies to the user: the owner bits, the group bits if
+    /// the user is a member of the owning group, or the other bits.
+    #[must_use]
+    pub fn permission_for(&self, user: &User) -> Permission {
+        if user.id == self.owner {
+            self.permissions.user()
+        } else if user.groups.contains(&self.group) {
+            self.permissions.group()
+        } else {
+            self.permissions.other()
+        }
+    }
+
+    /// Checks if the user is granted the access, root bypassing every check.
+    #[must_use]
+    pub fn can_access(&self, user: &User, access: Access) -> bool {
+        if user.is_root() {
+            return true;
+        }
+        match &self.acl {
+            Some(_) => self
+                .access_acl()
+                .allows(user, self.owner, self.group, access),
+            None => self.permission_for(user).allows(access),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: UserId, groups: Vec<GroupId>) -> User {
+        let mut user = User::new(id, format!("user{id}"));
+        for group in groups {
+            user.add_group(group);
+        }
+        user
+    }
+
+    #[test]
+    fn test_permission_classes() {
+        let metadata = InodeMetadata::new(FilePermissions::from_mode(0o640), 1, 1);
+
+        let owner = user(1, vec![1]);
+        assert!(metadata.can_access(&owner, Access::Read));
+        assert!(metadata.can_access(&owner, Access::Write));
+        assert!(!metadata.can_access(&owner, Access::Execute));
+
+        let member = user(2, vec![2, 1]);
+        assert!(metadata.can_access(&member, Access::Read));
+        assert!(!metadata.can_access(&member, Access::Write));
+
+        let other = user(3, vec![3]);
+        assert!(!metadata.can_access(&other, Access::Read));
+    }
+
+    #[test]
+    fn test_owner_class_takes_precedence() {
+        // The owner bits apply even if the group or other bits are more permissive.
+        let metadata = InodeMetadata::new(FilePermissions::from_mode(0o077), 1, 1);
+        assert!(!metadata.can_access(&user(1, vec![1]), Access::Read));
+        assert!(metadata.can_access(&user(2, vec![1]), Access::Read));
+    }
+
+    #[test]
+    fn test_root_bypasses_checks() {
+        let metadata = InodeMetadata::new(FilePermissions::from_mode(0o000), 1, 1);
+        let root = user(0, vec![0]);
+        assert!(metadata.can_access(&root, Access::Read));
+        assert!(metadata.can_access(&root, Access::Write));
+    }
 }
@@ -1,6 +1,15 @@
 //! Size utilities for inodes.
 
+/// The size of the blocks allocated to inodes, like on ext4.
+pub const BLOCK_SIZE: u64 = 4096;
+
 pub trait Size {
     /// Returns the size of the inode in bytes.
     fn size(&self) -> u64;
+
+    /// Returns the space allocated to the inode, in whole blocks, which is what `du` and `df`
+    /// count.
+    fn allocated_size(&self) -> u64 {
+        self.size().div_ceil(BLOCK_SIZE) * BLOCK_SIZE
+    }
 }
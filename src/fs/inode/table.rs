@@ -0,0 +1,121 @@
+//! The table holding every inode of a file system, indexed by inode number.
+//!
+//! Directory entries refer to inodes by number, so an inode can be reached from several entries
+//! (hard links) without being shared through pointers. The numbers of freed inodes are given to
+//! the next inodes created, like ext4 does.
+
+use crate::{Inode, InodeContent, InodeMetadata};
+
+/// The number identifying an inode in its file system, shown by `ls -i`.
+pub type InodeNumber = u64;
+
+/// The inode number of the root directory, the first one available on ext4 since `0` means no
+/// inode and `1` is reserved for bad blocks.
+pub const ROOT_INODE: InodeNumber = 2;
+
+#[derive(Debug, Clone)]
+pub struct InodeTable {
+    /// The inodes, the slot of an inode being its number. The slots below [`ROOT_INODE`] are
+    /// reserved and always empty.
+    inodes: Vec<Option<Inode>>,
+    /// The numbers of the freed inodes, to be reused.
+    free: Vec<InodeNumber>,
+}
+
+impl InodeTable {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            inodes: vec![None; ROOT_INODE as usize],
+            free: Vec::new(),
+        }
+    }
+
+    /// Stores a new inode, returning the number it was given.
+    pub fn insert(&mut self, content: InodeContent, metadata: InodeMetadata) -> InodeNumber {
+        match self.free.pop() {
+            Some(number) => {
+                self.inodes[number as usize] = Some(Inode::new(number, content, metadata));
+                number
+            }
+            None => {
+                let number = self.inodes.len() as InodeNumber;
+                self.inodes
+                    .push(Some(Inode::new(number, content, metadata)));
+                number
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, number: InodeNumber) -> Option<&Inode> {
+        self.inodes.get(number as usize).and_then(Option::as_ref)
+    }
+
+    pub fn get_mut(&mut self, number: InodeNumber) -> Option<&mut Inode> {
+        self.inodes
+            .get_mut(number as usize)
+            .and_then(Option::as_mut)
+    }
+
+    /// Frees an inode, its number becoming available for the next inode created.
+    pub fn remove(&mut self, number: InodeNumber) -> Option<Inode> {
+        let inode = self.inodes.get_mut(number as usize)?.take()?;
+        self.free.push(number);
+        Some(inode)
+    }
+
+    /// Iterates over the inodes in use, by number.
+    pub fn iter(&self) -> impl Iterator<Item = &Inode> {
+        self.inodes.iter().flatten()
+    }
+
+    /// Returns the number of inodes in use.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+impl Default for InodeTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FilePermissions, fs::inode::content::File};
+
+    use super::*;
+
+    fn insert_file(table: &mut InodeTable) -> InodeNumber {
+        table.insert(
+            InodeContent::File(File::new()),
+            InodeMetadata::new(FilePermissions::from_mode(0o644), 0, 0),
+        )
+    }
+
+    #[test]
+    fn test_numbers_start_at_root() {
+        let mut table = InodeTable::new();
+        assert_eq!(insert_file(&mut table), ROOT_INODE);
+        assert_eq!(insert_file(&mut table), ROOT_INODE + 1);
+        assert!(table.get(0).is_none());
+        assert_eq!(table.len(), 2);
+    }
+
+    #[test]
+    fn test_freed_numbers_are_reused() {
+        let mut table = InodeTable::new();
+        insert_file(&mut table);
+        let freed = insert_file(&mut table);
+        let kept = insert_file(&mut table);
+
+        assert!(table.remove(freed).is_some());
+        assert!(table.get(freed).is_none());
+        assert!(table.remove(freed).is_none());
+        assert_eq!(insert_file(&mut table), freed);
+        assert_eq!(table.get(kept).map(|inode| inode.number), Some(kept));
+    }
+}
@@ -1,5 +1,16 @@
 //! Permissions are a set of flags that control the access to a file system object.
 
+pub mod acl;
+pub mod mode;
+
+/// The set-user-ID bit, running an executable as its owner.
+pub const SETUID: u32 = 0o4000;
+/// The set-group-ID bit, running an executable as its group, or giving the group of a directory
+/// to the entries created in it.
+pub const SETGID: u32 = 0o2000;
+/// The sticky bit, only letting the owners of entries of a directory remove or rename them.
+pub const STICKY: u32 = 0o1000;
+
 #[derive(Debug, Clone)]
 pub struct FilePermissions {
     pub mode: u32,
@@ -35,6 +46,21 @@ impl FilePermissions {
         self.mode
     }
 
+    #[must_use]
+    pub fn setuid(&self) -> bool {
+        (self.mode & SETUID) != 0
+    }
+
+    #[must_use]
+    pub fn setgid(&self) -> bool {
+        (self.mode & SETGID) != 0
+    }
+
+    #[must_use]
+    pub fn sticky(&self) -> bool {
+        (self.mode & STICKY) != 0
+    }
+
     #[must_use]
     pub fn user(&self) -> Permission {
         Permission {
@@ -63,6 +89,18 @@ impl FilePermissions {
     }
 }
 
+/// The kind of access requested on a file system object.
+///
+/// For directories, `Read` lists the entries, `Write` creates or removes entries, and `Execute`
+/// searches the directory (traverses it or accesses an entry by name).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Permission {
     pub read: bool,
     pub write: bool,
@@ -77,6 +115,32 @@ impl Permission {
             execute,
         }
     }
+
+    /// Creates a permission from the 3 bits of a class, like `0o5` for `r-x`.
+    #[must_use]
+    pub fn from_bits(bits: u32) -> Self {
+        Self::new(bits & 0o4 != 0, bits & 0o2 != 0, bits & 0o1 != 0)
+    }
+
+    #[must_use]
+    pub fn bits(&self) -> u32 {
+        (u32::from(self.read) << 2) | (u32::from(self.write) << 1) | u32::from(self.execute)
+    }
+
+    /// Returns the permission granted by both permissions, like an entry restricted by a mask.
+    #[must_use]
+    pub fn intersect(&self, other: &Permission) -> Self {
+        Self::from_bits(self.bits() & other.bits())
+    }
+
+    #[must_use]
+    pub fn allows(&self, access: Access) -> bool {
+        match access {
+            Access::Read => self.read,
+            Access::Write => self.write,
+            Access::Execute => self.execute,
+        }
+    }
 }
 
 pub struct FilePermissionBuilder {
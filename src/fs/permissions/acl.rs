@@ -0,0 +1,186 @@
+//! POSIX access control lists, extending the permissions of the mode bits with entries for
+//! named users and groups.
+//!
+//! Like on Linux, the entries of the owner, the owning group and the others of an access ACL are
+//! backed by the mode bits when the ACL is minimal. Once it has a mask, the group class bits of
+//! the mode become the mask, and the permissions of the owning group are stored apart.
+
+use std::collections::BTreeMap;
+
+use crate::fs::{
+    permissions::{Access, FilePermissions, Permission},
+    users::{GroupId, User, UserId},
+};
+
+/// A complete ACL, as displayed by `getfacl` and edited by `setfacl`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Acl {
+    pub user: Permission,
+    pub group: Permission,
+    pub other: Permission,
+    /// The upper bound of the permissions granted to named users and to groups.
+    pub mask: Option<Permission>,
+    pub users: BTreeMap<UserId, Permission>,
+    pub groups: BTreeMap<GroupId, Permission>,
+}
+
+/// The entries of an access ACL that can't be represented by the mode bits.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtendedAcl {
+    /// The permissions of the owning group, the group class bits of the mode being the mask.
+    pub group: Permission,
+    pub users: BTreeMap<UserId, Permission>,
+    pub groups: BTreeMap<GroupId, Permission>,
+}
+
+impl Acl {
+    /// Creates the minimal ACL equivalent to the permission bits.
+    #[must_use]
+    pub fn from_permissions(permissions: &FilePermissions) -> Self {
+        Self {
+            user: permissions.user(),
+            group: permissions.group(),
+            other: permissions.other(),
+            ..Self::default()
+        }
+    }
+
+    /// Creates the access ACL of an inode from its mode and extended entries.
+    #[must_use]
+    pub fn from_parts(permissions: &FilePermissions, extended: Option<&ExtendedAcl>) -> Self {
+        let mut acl = Self::from_permissions(permissions);
+        if let Some(extended) = extended {
+            acl.mask = Some(permissions.group());
+            acl.group = extended.group;
+            acl.users = extended.users.clone();
+            acl.groups = extended.groups.clone();
+        }
+        acl
+    }
+
+    /// Splits the ACL into the rwx bits of the mode and its extended entries.
+    #[must_use]
+    pub fn into_parts(mut self) -> (u32, Option<ExtendedAcl>) {
+        if self.mask.is_none() && self.has_named_entries() {
+            self.recalculate_mask();
+        }
+        let group_class = self.mask.unwrap_or(self.group);
+        let mode = (self.user.bits() << 6) | (group_class.bits() << 3) | self.other.bits();
+        let extended = self.mask.map(|_| ExtendedAcl {
+            group: self.group,
+            users: self.users,
+            groups: self.groups,
+        });
+        (mode, extended)
+    }
+
+    #[must_use]
+    pub fn has_named_entries(&self) -> bool {
+        !self.users.is_empty() || !self.groups.is_empty()
+    }
+
+    /// Sets the mask to the union of the permissions of the group class entries, like
+    /// `setfacl` does after each modification.
+    pub fn recalculate_mask(&mut self) {
+        let bits = self
+            .users
+            .values()
+            .chain(self.groups.values())
+            .fold(self.group.bits(), |bits, permission| {
+                bits | permission.bits()
+            });
+        self.mask = Some(Permission::from_bits(bits));
+    }
+
+    /// Returns the permission effectively granted by an entry of the group class.
+    #[must_use]
+    pub fn effective(&self, permission: &Permission) -> Permission {
+        match &self.mask {
+            Some(mask) => permission.intersect(mask),
+            None => *permission,
+        }
+    }
+
+    /// Checks if the user is granted the access by the ACL, following the POSIX algorithm: the
+    /// owner entry, then the named user entries, then the group entries, then the other entry.
+    #[must_use]
+    pub fn allows(&self, user: &User, owner: UserId, group: GroupId, access: Access) -> bool {
+        if user.id == owner {
+            return self.user.allows(access);
+        }
+        if let Some(permission) = self.users.get(&user.id) {
+            return self.effective(permission).allows(access);
+        }
+
+        let mut matched_group = false;
+        let group_entries = std::iter::once((&group, &self.group)).chain(self.groups.iter());
+        for (group_id, permission) in group_entries {
+            if user.groups.contains(group_id) {
+                if self.effective(permission).allows(access) {
+                    return true;
+                }
+                matched_group = true;
+            }
+        }
+        if matched_group {
+            return false;
+        }
+
+        self.other.allows(access)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(id: UserId, groups: Vec<GroupId>) -> User {
+        let mut user = User::new(id, format!("user{id}"));
+        for group in groups {
+            user.add_group(group);
+        }
+        user
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let permissions = FilePermissions::from_mode(0o640);
+        let mut acl = Acl::from_parts(&permissions, None);
+        assert_eq!(acl.clone().into_parts(), (0o640, None));
+
+        acl.users.insert(2, Permission::from_bits(0o6));
+        let (mode, extended) = acl.into_parts();
+        // The mask is the union of the group class entries.
+        assert_eq!(mode, 0o660);
+        let extended = extended.expect("Missing extended entries");
+        assert_eq!(extended.group, Permission::from_bits(0o4));
+
+        let acl = Acl::from_parts(&FilePermissions::from_mode(mode), Some(&extended));
+        assert_eq!(acl.mask, Some(Permission::from_bits(0o6)));
+        assert_eq!(acl.users.get(&2), Some(&Permission::from_bits(0o6)));
+    }
+
+    #[test]
+    fn test_named_entries_are_masked() {
+        let mut acl = Acl::from_permissions(&FilePermissions::from_mode(0o600));
+        acl.users.insert(2, Permission::from_bits(0o7));
+        acl.groups.insert(3, Permission::from_bits(0o6));
+        acl.mask = Some(Permission::from_bits(0o4));
+
+        assert!(acl.allows(&user(2, vec![2]), 1, 1, Access::Read));
+        assert!(!acl.allows(&user(2, vec![2]), 1, 1, Access::Write));
+        assert!(acl.allows(&user(4, vec![3]), 1, 1, Access::Read));
+        assert!(!acl.allows(&user(4, vec![3]), 1, 1, Access::Write));
+    }
+
+    #[test]
+    fn test_matching_group_without_access_denies() {
+        let mut acl = Acl::from_permissions(&FilePermissions::from_mode(0o604));
+        acl.groups.insert(3, Permission::from_bits(0o0));
+        acl.recalculate_mask();
+
+        // A user of a matching group doesn't fall back to the other entry.
+        assert!(!acl.allows(&user(4, vec![3]), 1, 1, Access::Read));
+        assert!(acl.allows(&user(5, vec![5]), 1, 1, Access::Read));
+    }
+}
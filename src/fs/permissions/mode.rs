@@ -0,0 +1,300 @@
+//! Mode changes as accepted by `chmod`, either octal (`755`) or symbolic (`u+x,g-w,o=`).
+//!
+//! The symbolic grammar follows GNU chmod: a comma separated list of clauses, each made of an
+//! optional set of classes (`ugoa`) and one or more operations (`+`, `-` or `=`) followed by
+//! either permission letters (`rwxXst`) or a single class to copy the permissions from (`ugo`).
+
+use crate::fs::permissions::{FilePermissions, Permission, SETGID, SETUID, STICKY};
+
+/// A parsed mode change, applied to the current mode of an inode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModeChange {
+    /// An octal mode, replacing the current one. Unless `exact`, the set-user-ID and set-group-ID
+    /// bits of directories are kept when the mode doesn't set them, like GNU chmod does for modes
+    /// of less than five digits.
+    Octal {
+        mode: u32,
+        exact: bool,
+    },
+    Symbolic(Vec<SymbolicClause>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolicClause {
+    /// The mask of the classes affected by the clause, `None` when no class was given.
+    classes: Option<u32>,
+    operations: Vec<SymbolicOperation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SymbolicOperation {
+    operator: Operator,
+    value: SymbolicValue,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Remove,
+    Set,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SymbolicValue {
+    /// Permission letters, with `X` kept apart as it depends on the inode.
+    Bits {
+        bits: u32,
+        conditional_execute: bool,
+    },
+    /// The permissions of a class, copied to the affected classes.
+    Copy(Class),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    User,
+    Group,
+    Other,
+}
+
+impl Class {
+    /// The mask of the rwx bits and special bit belonging to the class.
+    const fn mask(self) -> u32 {
+        match self {
+            Class::User => 0o700 | SETUID,
+            Class::Group => 0o070 | SETGID,
+            Class::Other => 0o007 | STICKY,
+        }
+    }
+
+    fn permission(self, permissions: &FilePermissions) -> Permission {
+        match self {
+            Class::User => permissions.user(),
+            Class::Group => permissions.group(),
+            Class::Other => permissions.other(),
+        }
+    }
+}
+
+impl ModeChange {
+    /// Parses a mode, returning `None` if it is invalid.
+    #[must_use]
+    pub fn parse(mode: &str) -> Option<Self> {
+        if !mode.is_empty() && mode.chars().all(|c| c.is_digit(8)) {
+            return u32::from_str_radix(mode, 8)
+                .ok()
+                .filter(|mode| *mode <= 0o7777)
+                .map(|octal| ModeChange::Octal {
+                    mode: octal,
+                    exact: mode.len() >= 5,
+                });
+        }
+        mode.split(',')
+            .map(SymbolicClause::parse)
+            .collect::<Option<Vec<_>>>()
+            .map(ModeChange::Symbolic)
+    }
+
+    /// Computes the new mode of an inode from its current mode.
+    ///
+    /// The `umask` restricts the bits changed by clauses that don't name any class, like `+x`.
+    #[must_use]
+    pub fn apply(&self, mode: u32, is_directory: bool, umask: u32) -> u32 {
+        match self {
+            ModeChange::Octal {
+                mode: new_mode,
+                exact,
+            } => {
+                if is_directory && !exact {
+                    new_mode | (mode & (SETUID | SETGID))
+                } else {
+                    *new_mode
+                }
+            }
+            ModeChange::Symbolic(clauses) => clauses
+                .iter()
+                .fold(mode, |mode, clause| clause.apply(mode, is_directory, umask)),
+        }
+    }
+}
+
+impl SymbolicClause {
+    fn parse(clause: &str) -> Option<Self> {
+        let mut chars = clause.chars().peekable();
+
+        let mut classes = None;
+        while let Some(class) = chars.peek().and_then(|c| class_mask(*c)) {
+            classes = Some(classes.unwrap_or(0) | class);
+            chars.next();
+        }
+
+        let mut operations = Vec::new();
+        while let Some(operator) = chars.next() {
+            let operator = match operator {
+                '+' => Operator::Add,
+                '-' => Operator::Remove,
+                '=' => Operator::Set,
+                _ => return None,
+            };
+            let value = match chars.peek() {
+                Some('u') | Some('g') | Some('o') => {
+                    let class = match chars.next() {
+                        Some('u') => Class::User,
+                        Some('g') => Class::Group,
+                        _ => Class::Other,
+                    };
+                    SymbolicValue::Copy(class)
+                }
+                _ => {
+                    let mut bits = 0;
+                    let mut conditional_execute = false;
+                    while let Some(letter) = chars.peek() {
+                        match letter {
+                            'r' => bits |= 0o444,
+                            'w' => bits |= 0o222,
+                            'x' => bits |= 0o111,
+                            'X' => conditional_execute = true,
+                            's' => bits |= SETUID | SETGID,
+                            't' => bits |= STICKY,
+                            _ => break,
+                        }
+                        chars.next();
+                    }
+                    SymbolicValue::Bits {
+                        bits,
+                        conditional_execute,
+                    }
+                }
+            };
+            operations.push(SymbolicOperation { operator, value });
+        }
+
+        if operations.is_empty() {
+            return None;
+        }
+        Some(Self {
+            classes,
+            operations,
+        })
+    }
+
+    fn apply(&self, mut mode: u32, is_directory: bool, umask: u32) -> u32 {
+        // Without an explicit class, every class is affected except the bits set in the umask.
+        let (classes, restriction) = match self.classes {
+            Some(classes) => (classes, 0o7777),
+            None => (0o7777, !umask & 0o7777),
+        };
+
+        for operation in &self.operations {
+            let value = match &operation.value {
+                SymbolicValue::Bits {
+                    bits,
+                    conditional_execute,
+                } => {
+                    let executable = is_directory || mode & 0o111 != 0;
+                    if *conditional_execute && executable {
+                        bits | 0o111
+                    } else {
+                        *bits
+                    }
+                }
+                SymbolicValue::Copy(class) => {
+                    let permission = class.permission(&FilePermissions::from_mode(mode));
+                    let bits =
+                        FilePermissions::from_permissions(&permission, &permission, &permission);
+                    bits.mode()
+                }
+            };
+            let value = value & classes & restriction;
+
+            mode = match operation.operator {
+                Operator::Add => mode | value,
+                Operator::Remove => mode & !value,
+                Operator::Set => {
+                    // Like GNU chmod, `=` keeps the set-user-ID and set-group-ID bits of
+                    // directories unless they are explicitly given.
+                    let mut cleared = classes;
+                    if is_directory {
+                        cleared &= !(SETUID | SETGID);
+                    }
+                    (mode & !cleared) | value
+                }
+            };
+        }
+
+        mode
+    }
+}
+
+/// Returns the mask of the bits of a class letter.
+fn class_mask(class: char) -> Option<u32> {
+    match class {
+        'u' => Some(Class::User.mask()),
+        'g' => Some(Class::Group.mask()),
+        'o' => Some(Class::Other.mask()),
+        'a' => Some(0o7777),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(mode: &str, current: u32, is_directory: bool) -> u32 {
+        ModeChange::parse(mode)
+            .expect("Failed to parse mode")
+            .apply(current, is_directory, 0o022)
+    }
+
+    #[test]
+    fn test_octal_modes() {
+        assert_eq!(apply("755", 0o644, false), 0o755);
+        assert_eq!(apply("0640", 0o777, false), 0o640);
+        assert_eq!(apply("4755", 0o644, false), 0o4755);
+        assert!(ModeChange::parse("17777").is_none());
+        assert_eq!(apply("755", 0o2775, true), 0o2755);
+        assert_eq!(apply("4755", 0o2775, true), 0o6755);
+        assert_eq!(apply("00755", 0o2775, true), 0o755);
+        assert_eq!(apply("755", 0o2775, false), 0o755);
+        assert!(ModeChange::parse("8").is_none());
+    }
+
+    #[test]
+    fn test_symbolic_modes() {
+        assert_eq!(apply("u+x", 0o644, false), 0o744);
+        assert_eq!(apply("u+x,g-w,o=", 0o664, false), 0o740);
+        assert_eq!(apply("a-w", 0o666, false), 0o444);
+        assert_eq!(apply("go=r", 0o600, false), 0o644);
+        assert_eq!(apply("u=rwx,g=rx,o=", 0o000, false), 0o750);
+        assert_eq!(apply("g=u", 0o640, false), 0o660);
+        assert_eq!(apply("o+t", 0o777, true), 0o1777);
+        assert_eq!(apply("u+s", 0o755, false), 0o4755);
+        assert_eq!(apply("u+r-w", 0o200, false), 0o400);
+    }
+
+    #[test]
+    fn test_symbolic_without_class_respects_umask() {
+        assert_eq!(apply("+w", 0o444, false), 0o644);
+        assert_eq!(apply("=rw", 0o777, false), 0o644);
+        assert_eq!(apply("a+w", 0o444, false), 0o666);
+    }
+
+    #[test]
+    fn test_conditional_execute() {
+        assert_eq!(apply("a+rX", 0o600, false), 0o644);
+        assert_eq!(apply("a+rX", 0o700, false), 0o755);
+        assert_eq!(apply("a+rX", 0o700, true), 0o755);
+        assert_eq!(apply("a+rX", 0o600, true), 0o755);
+    }
+
+    #[test]
+    fn test_invalid_modes() {
+        for mode in ["", "u", "u+z", "z+x", "u+x,", "+x,,g-w"] {
+            assert!(
+                ModeChange::parse(mode).is_none(),
+                "{mode} should be invalid"
+            );
+        }
+    }
+}
@@ -0,0 +1,11 @@
+//! Limits on the space and the inodes a user can own, like the disk quotas of Linux.
+
+/// The limits of a user, counted over the inodes they own. A limit of `None` means the user isn't
+/// limited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Quota {
+    /// The space allocated to the inodes of the user, in bytes.
+    pub space: Option<u64>,
+    /// The number of inodes of the user.
+    pub inodes: Option<usize>,
+}
@@ -2,37 +2,80 @@
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
+
 pub type UserId = u32;
 pub type GroupId = u32;
 
+/// The id of the superuser, which bypasses permission checks.
+pub const ROOT_USER_ID: UserId = 0;
+/// The id of the group of the superuser.
+pub const ROOT_GROUP_ID: GroupId = 0;
+/// The id of the group granted every privilege by the default sudoers policy, as on Debian.
+pub const SUDO_GROUP_ID: GroupId = 27;
+/// The highest id of system accounts, like `SYS_UID_MAX` in `/etc/login.defs`.
+pub const SYSTEM_ID_MAX: u32 = 999;
+/// The first id of regular accounts, like `UID_MIN` in `/etc/login.defs`.
+pub const REGULAR_ID_MIN: u32 = 1000;
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// The login shell given to users created without one.
+pub const DEFAULT_SHELL: &str = "/bin/sh";
+
+/// The kind of an account, deciding the range its id is allocated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountKind {
+    /// Accounts of services, with ids counting down from `SYSTEM_ID_MAX`.
+    System,
+    /// Accounts of people, with ids counting up from `REGULAR_ID_MIN`.
+    Regular,
+}
+
+impl AccountKind {
+    /// Allocates an id the way the shadow utilities do: regular ids follow the highest one in
+    /// use, and system ids take the highest free one, so that both ranges stay contiguous.
+    fn allocate_id(self, used: impl Iterator<Item = u32> + Clone) -> u32 {
+        match self {
+            Self::Regular => used
+                .filter(|id| *id >= REGULAR_ID_MIN)
+                .max()
+                .map_or(REGULAR_ID_MIN, |id| id + 1),
+            Self::System => (1..=SYSTEM_ID_MAX)
+                .rev()
+                .find(|id| !used.clone().any(|used| used == *id))
+                .expect("No system id left"),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UserStore {
     users: HashMap<UserId, User>,
-    next_user_id: UserId,
 }
 
 impl UserStore {
     pub fn new() -> Self {
         Self {
             users: HashMap::new(),
-            next_user_id: 0,
         }
     }
 
-    pub fn add_user(&mut self, name: String) -> UserId {
-        let id = self.next_user_id;
-        self.next_user_id += 1;
-        self.users.insert(
-            id,
-            User {
-                id,
-                name,
-                groups: vec![],
-            },
-        );
+    /// Adds a user with the next free id of its kind.
+    pub fn add_user(&mut self, name: String, kind: AccountKind) -> UserId {
+        let id = kind.allocate_id(self.users.keys().copied());
+        self.add_user_with_id(id, name)
+    }
+
+    /// Adds a user with a fixed id, like root.
+    pub fn add_user_with_id(&mut self, id: UserId, name: String) -> UserId {
+        self.users.insert(id, User::new(id, name));
         id
     }
 
+    pub fn remove_user(&mut self, id: UserId) -> Option<User> {
+        self.users.remove(&id)
+    }
+
     pub fn find_by_username(&self, username: &str) -> Option<UserId> {
         self.users
             .iter()
@@ -47,6 +90,14 @@ impl UserStore {
     pub fn user_mut(&mut self, id: UserId) -> Option<&mut User> {
         self.users.get_mut(&id)
     }
+
+    pub fn users(&self) -> impl Iterator<Item = &User> {
+        self.users.values()
+    }
+
+    pub fn users_mut(&mut self) -> impl Iterator<Item = &mut User> {
+        self.users.values_mut()
+    }
 }
 
 impl Default for UserStore {
@@ -59,21 +110,148 @@ impl Default for UserStore {
 pub struct User {
     pub id: UserId,
     pub name: String,
+    /// The groups of the user, starting with its primary group.
     pub groups: Vec<GroupId>,
+    pub home: String,
+    pub shell: String,
+    /// The salted hash of the password, in the `crypt` format of the shadow file.
+    ///
+    /// A missing password locks the account, an empty one lets anyone log in, and a hash
+    /// prefixed with `!` is locked until `passwd -u` removes the prefix.
+    pub password: Option<String>,
+    /// The day of the last password change, counted from the Unix epoch.
+    pub password_changed: Option<i64>,
+}
+
+/// The state of a password, as reported by `passwd -S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasswordStatus {
+    Usable,
+    Locked,
+    Empty,
 }
 
 impl User {
     #[must_use]
     pub fn new(id: UserId, name: String) -> Self {
+        let home = if id == ROOT_USER_ID {
+            "/root".to_string()
+        } else {
+            format!("/home/{name}")
+        };
         Self {
             id,
             name,
             groups: vec![],
+            home,
+            shell: DEFAULT_SHELL.to_string(),
+            password: None,
+            password_changed: None,
+        }
+    }
+
+    /// Returns the primary group of the user, the first group it was added to.
+    #[must_use]
+    pub fn primary_group(&self) -> Option<GroupId> {
+        self.groups.first().copied()
+    }
+
+    /// Hashes the password with a random salt, using SHA-512 like modern Linux distributions.
+    ///
+    /// The day of the change is recorded from the given time.
+    pub fn set_password(&mut self, password: &str, now: DateTime<Utc>) {
+        let hash = pwhash::sha512_crypt::hash(password).expect("Failed to hash password");
+        self.password = Some(hash);
+        self.password_changed = Some(now.timestamp().div_euclid(SECONDS_PER_DAY));
+    }
+
+    /// Removes the password, letting anyone log in as the user.
+    pub fn delete_password(&mut self, now: DateTime<Utc>) {
+        self.password = Some(String::new());
+        self.password_changed = Some(now.timestamp().div_euclid(SECONDS_PER_DAY));
+    }
+
+    /// Locks the password by prefixing its hash with `!`.
+    pub fn lock_password(&mut self) {
+        match &mut self.password {
+            Some(hash) if !hash.starts_with('!') => hash.insert(0, '!'),
+            Some(_) => {}
+            None => self.password = Some("!".to_string()),
+        }
+    }
+
+    /// Unlocks the password. A password that was never set stays locked, like `passwd -u`
+    /// refuses to create an empty password.
+    ///
+    /// Returns `false` if the password can't be unlocked.
+    pub fn unlock_password(&mut self) -> bool {
+        match &mut self.password {
+            Some(hash) if hash.starts_with('!') && hash.len() > 1 => {
+                hash.remove(0);
+                true
+            }
+            Some(hash) => !hash.is_empty() && !hash.starts_with('!'),
+            None => false,
+        }
+    }
+
+    #[must_use]
+    pub fn password_status(&self) -> PasswordStatus {
+        match self.password.as_deref() {
+            Some("") => PasswordStatus::Empty,
+            Some(hash) if !hash.starts_with('!') => PasswordStatus::Usable,
+            _ => PasswordStatus::Locked,
+        }
+    }
+
+    /// Checks a password against the stored hash. A locked password never matches.
+    #[must_use]
+    pub fn verify_password(&self, password: &str) -> bool {
+        match self.password_status() {
+            PasswordStatus::Usable => self
+                .password
+                .as_deref()
+                .is_some_and(|hash| pwhash::unix::verify(password, hash)),
+            PasswordStatus::Empty => password.is_empty(),
+            PasswordStatus::Locked => false,
         }
     }
 
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.id == ROOT_USER_ID
+    }
+
+    /// Returns the groups of the user besides its primary group.
+    #[must_use]
+    pub fn supplementary_groups(&self) -> &[GroupId] {
+        self.groups.get(1..).unwrap_or_default()
+    }
+
     pub fn add_group(&mut self, group_id: GroupId) {
-        self.groups.push(group_id);
+        if !self.groups.contains(&group_id) {
+            self.groups.push(group_id);
+        }
+    }
+
+    /// Replaces the primary group of the user, keeping its supplementary groups.
+    pub fn set_primary_group(&mut self, group_id: GroupId) {
+        if self.primary_group() == Some(group_id) {
+            return;
+        }
+        self.groups.retain(|g| *g != group_id);
+        match self.groups.first_mut() {
+            Some(primary_group) => *primary_group = group_id,
+            None => self.groups.push(group_id),
+        }
+    }
+
+    /// Replaces the supplementary groups of the user, keeping its primary group.
+    pub fn set_supplementary_groups(&mut self, group_ids: &[GroupId]) {
+        self.groups.truncate(1);
+        for group_id in group_ids {
+            self.add_group(*group_id);
+        }
     }
 
     pub fn remove_group(&mut self, group_id: GroupId) {
@@ -84,27 +262,41 @@ impl User {
 #[derive(Debug, Clone)]
 pub struct GroupStore {
     pub groups: HashMap<GroupId, Group>,
-    pub next_group_id: GroupId,
 }
 
 impl GroupStore {
     pub fn new() -> Self {
         Self {
             groups: HashMap::new(),
-            next_group_id: 0,
         }
     }
 
-    pub fn add_group(&mut self, name: String) -> GroupId {
-        let id = self.next_group_id;
-        self.next_group_id += 1;
+    /// Adds a group with the next free id of its kind.
+    pub fn add_group(&mut self, name: String, kind: AccountKind) -> GroupId {
+        let id = kind.allocate_id(self.groups.keys().copied());
+        self.add_group_with_id(id, name)
+    }
+
+    /// Adds a group with a fixed id, like the group of root.
+    pub fn add_group_with_id(&mut self, id: GroupId, name: String) -> GroupId {
         self.groups.insert(id, Group { id, name });
         id
     }
 
+    pub fn remove_group(&mut self, id: GroupId) -> Option<Group> {
+        self.groups.remove(&id)
+    }
+
     pub fn group(&self, id: GroupId) -> Option<&Group> {
         self.groups.get(&id)
     }
+
+    pub fn find_by_name(&self, name: &str) -> Option<GroupId> {
+        self.groups
+            .iter()
+            .find(|(_, group)| group.name == name)
+            .map(|(id, _)| *id)
+    }
 }
 
 impl Default for GroupStore {
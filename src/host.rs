@@ -0,0 +1,176 @@
+//! The machine the shells run on.
+//!
+//! A host owns the file system and the table of logged in users. Each login opens a [`Shell`] on
+//! a terminal of its own, with its own user, working directory and history, while the files and
+//! the accounts are shared with every other terminal of the host.
+//!
+//! Hosts and shells are `Send` and `Sync`: a server can clone the host into each of its threads or
+//! tasks, and give every connection a shell of its own. The locking rules are described in the
+//! `locks` module.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    FileSystem, ShellError, UserId, errors::SessionError, locks::MutexExt, sessions::Session,
+    shell::Shell,
+};
+
+/// The name of the machine, as shown by `sudo` and `write`.
+pub const HOSTNAME: &str = "localhost";
+
+/// A file system shared by the terminals of a host.
+#[derive(Debug, Clone, Default)]
+pub struct SharedFileSystem(Arc<Mutex<FileSystem>>);
+
+impl SharedFileSystem {
+    #[must_use]
+    pub fn new(fs: FileSystem) -> Self {
+        Self(Arc::new(Mutex::new(fs)))
+    }
+
+    /// Locks the file system, waiting for the commands of other terminals to finish with it.
+    pub fn lock(&self) -> MutexGuard<'_, FileSystem> {
+        self.0.lock_unpoisoned()
+    }
+}
+
+/// A user logged in on a terminal, like the records of `/var/run/utmp`.
+#[derive(Debug, Clone)]
+pub struct Login {
+    /// The user who logged in, which switching users with `su` doesn't change.
+    pub user: UserId,
+    pub terminal: String,
+    pub login_time: DateTime<Utc>,
+    /// The last command run on the terminal.
+    pub what: String,
+    /// The messages written to the terminal and not read yet.
+    messages: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Host {
+    pub fs: SharedFileSystem,
+    logins: Arc<Mutex<Vec<Login>>>,
+    boot_time: DateTime<Utc>,
+}
+
+impl Host {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_file_system(FileSystem::new())
+    }
+
+    #[must_use]
+    pub fn with_file_system(fs: FileSystem) -> Self {
+        let boot_time = fs.now();
+        Self {
+            fs: SharedFileSystem::new(fs),
+            logins: Arc::new(Mutex::new(Vec::new())),
+            boot_time,
+        }
+    }
+
+    /// Logs a user in on the first free terminal, opening a shell in its home directory.
+    pub fn login(&self, username: &str) -> Result<Shell, ShellError> {
+        let fs = self.fs.lock();
+        let user_id = fs
+            .find_user_by_name(username)
+            .map(|user| user.id)
+            .ok_or(ShellError::Session(SessionError::UserNotFound))?;
+
+        let mut logins = self.lock_logins();
+        let terminal = (0..)
+            .map(|number| format!("pts/{number}"))
+            .find(|terminal| !logins.iter().any(|login| login.terminal == *terminal))
+            .expect("No terminal left");
+        let session = Session::login(&fs, user_id, &terminal)?;
+        logins.push(Login {
+            user: user_id,
+            terminal,
+            login_time: session.login_time(),
+            what: String::new(),
+            messages: Vec::new(),
+        });
+        drop(logins);
+        drop(fs);
+        Ok(Shell::new(self.clone(), session))
+    }
+
+    /// Returns the users logged in, ordered by terminal.
+    #[must_use]
+    pub fn logins(&self) -> Vec<Login> {
+        let mut logins = self.lock_logins().clone();
+        logins.sort_by_key(|login| terminal_number(&login.terminal));
+        logins
+    }
+
+    /// Returns the time the host was started.
+    #[must_use]
+    pub fn boot_time(&self) -> DateTime<Utc> {
+        self.boot_time
+    }
+
+    /// Removes the login of a terminal, dropping its unread messages.
+    pub(crate) fn logout(&self, terminal: &str) {
+        self.lock_logins()
+            .retain(|login| login.terminal != terminal);
+    }
+
+    /// Records the command last run on a terminal, shown by `w`.
+    pub(crate) fn set_activity(&self, terminal: &str, what: &str) {
+        if let Some(login) = self
+            .lock_logins()
+            .iter_mut()
+            .find(|login| login.terminal == terminal)
+        {
+            login.what = what.to_string();
+        }
+    }
+
+    /// Delivers a message to a terminal.
+    ///
+    /// Returns `false` if nobody is logged in on the terminal.
+    pub(crate) fn send_message(&self, terminal: &str, message: String) -> bool {
+        match self
+            .lock_logins()
+            .iter_mut()
+            .find(|login| login.terminal == terminal)
+        {
+            Some(login) => {
+                login.messages.push(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Takes the messages written to a terminal, oldest first.
+    pub(crate) fn take_messages(&self, terminal: &str) -> Vec<String> {
+        self.lock_logins()
+            .iter_mut()
+            .find(|login| login.terminal == terminal)
+            .map(|login| std::mem::take(&mut login.messages))
+            .unwrap_or_default()
+    }
+
+    fn lock_logins(&self) -> MutexGuard<'_, Vec<Login>> {
+        self.logins.lock_unpoisoned()
+    }
+}
+
+impl Default for Host {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the number of a terminal like `pts/3`, so that `pts/10` comes after `pts/9`.
+fn terminal_number(terminal: &str) -> u32 {
+    terminal
+        .rsplit('/')
+        .next()
+        .and_then(|number| number.parse().ok())
+        .unwrap_or_default()
+}
@@ -1,18 +1,34 @@
 #![doc = include_str!("../README.md")]
 
 // Crate modules
+mod clock;
 mod commands;
+mod diff;
 mod errors;
 mod fs;
+mod host;
+mod locks;
 mod sessions;
 mod shell;
+mod stdin;
+mod sudoers;
 
 // Re-export
+pub use clock::{Clock, FixedClock, ManualClock, SystemClock};
+pub use diff::{Diff, Edit};
 pub use errors::ShellError;
 pub use fs::{
     FileSystem,
-    inode::{Inode, content::InodeContent, metadata::InodeMetadata},
+    inode::{
+        Inode,
+        content::InodeContent,
+        metadata::InodeMetadata,
+        table::{InodeNumber, ROOT_INODE},
+    },
     permissions::FilePermissions,
-    users::{Group, GroupId, User, UserId},
+    quota::Quota,
+    users::{AccountKind, Group, GroupId, User, UserId},
 };
+pub use host::{Host, Login, SharedFileSystem};
 pub use shell::Shell;
+pub use stdin::{QueuedInput, StdinProvider};
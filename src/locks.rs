@@ -0,0 +1,27 @@
+//! Locking of the state shared between the shells of a host.
+//!
+//! Every lock is taken in the same order, so that terminals used from several threads can't
+//! deadlock:
+//!
+//! 1. the file system of the host ([`crate::SharedFileSystem`]), inodes included,
+//! 2. the table of logins of the host,
+//! 3. the standard input of a shell, which is never read while the file system is locked, so that
+//!    a user typing a password doesn't block the other terminals.
+//!
+//! The time of a [`crate::ManualClock`] is locked last, only long enough to read or change it.
+//!
+//! A command panicking while holding a lock poisons it. The shells of the other users carry on
+//! with the state left behind instead of panicking in turn.
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+pub(crate) trait MutexExt<T: ?Sized> {
+    /// Locks the mutex, recovering the guard if another thread panicked while holding it.
+    fn lock_unpoisoned(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T: ?Sized> MutexExt<T> for Mutex<T> {
+    fn lock_unpoisoned(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
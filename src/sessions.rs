@@ -1,17 +1,28 @@
 //! Representation of a shell session.
 //!
 //! A session represents a shell instance, a single file system can have multiple sessions.
-//! It contains a reference to the file system, and information like the current user and directory.
+//! It contains information like the current user and directory, the file system being given to
+//! each operation.
+//!
+//! Every operation on the file system goes through the permission checks for the session's user.
 
 use std::{
+    collections::BTreeMap,
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
 };
 
+use chrono::{DateTime, Utc};
+
 use crate::{
-    FileSystem, Inode, ShellError, UserId,
+    FilePermissions, FileSystem, GroupId, Inode, InodeContent, InodeMetadata, InodeNumber,
+    ShellError, User, UserId,
     errors::{FileSystemError, SessionError},
-    fs::resolver::resolve_path,
+    fs::{
+        inode::content::InodeType,
+        permissions::{Access, SETGID, SETUID, acl::Acl},
+        resolver::resolve_path,
+        users::ROOT_GROUP_ID,
+    },
 };
 
 #[derive(Debug, Clone)]
@@ -20,61 +31,303 @@ pub struct Session {
     previous_working_directory: Option<PathBuf>,
     current_user: UserId,
     executed_commands: Vec<String>,
+    /// The permission bits removed from the mode of created files and directories.
+    umask: u32,
+    /// The name of the terminal line the session is attached to, like `pts/0`.
+    terminal: String,
+    login_time: DateTime<Utc>,
+    environment: BTreeMap<String, String>,
+    /// The identities the session had before switching users with `su` or `sudo`, the last one
+    /// being restored by `exit`.
+    previous_identities: Vec<Identity>,
+}
+
+/// The state of a session tied to the user it runs as.
+#[derive(Debug, Clone)]
+struct Identity {
+    user: UserId,
+    current_working_directory: PathBuf,
+    previous_working_directory: Option<PathBuf>,
+    environment: BTreeMap<String, String>,
 }
 
+/// The search path of login shells.
+pub const DEFAULT_PATH: &str = "/usr/local/bin:/usr/bin:/bin";
+
+/// The umask of a new session, as commonly set by Linux distributions.
+pub const DEFAULT_UMASK: u32 = 0o022;
+
 impl Session {
-    pub fn new(current_working_directory: PathBuf, current_user: UserId) -> Self {
+    pub fn new(
+        current_working_directory: PathBuf,
+        current_user: UserId,
+        login_time: DateTime<Utc>,
+    ) -> Self {
         Self {
             current_working_directory,
             previous_working_directory: None,
             current_user,
             executed_commands: Vec::new(),
+            umask: DEFAULT_UMASK,
+            terminal: "pts/0".to_string(),
+            login_time,
+            environment: BTreeMap::new(),
+            previous_identities: Vec::new(),
         }
     }
 
+    /// Creates a session for a user logging in on a terminal, starting in its home directory with
+    /// a login environment.
+    pub fn login(fs: &FileSystem, user_id: UserId, terminal: &str) -> Result<Self, ShellError> {
+        let user = fs
+            .get_user(user_id)
+            .ok_or(ShellError::Session(SessionError::UserNotFound))?;
+        let mut session = Self::new(PathBuf::from(&user.home), user_id, fs.now());
+        session.environment = login_environment(user);
+        session.terminal = terminal.to_string();
+        Ok(session)
+    }
+
     pub fn create_file(
         &mut self,
         fs: &mut FileSystem,
         path: &Path,
-    ) -> Result<Arc<Mutex<Inode>>, ShellError> {
-        let resolved_path = resolve_path(
-            path,
-            &self.get_user_home_directory(fs),
-            &self.current_working_directory,
-        );
-        fs.create_file(&resolved_path.display().to_string())
+    ) -> Result<InodeNumber, ShellError> {
+        let resolved_path = self.resolve(fs, path);
+        let parent = self.check_can_create(fs, &resolved_path)?;
+        let metadata = self.new_inode_metadata(fs, &parent, 0o666, false)?;
+        fs.create_file(&resolved_path.display().to_string(), metadata)
     }
 
     pub fn create_directory(
         &mut self,
         fs: &mut FileSystem,
         path: &Path,
-    ) -> Result<Arc<Mutex<Inode>>, ShellError> {
-        let resolved_path = resolve_path(
-            path,
-            &self.get_user_home_directory(fs),
-            &self.current_working_directory,
-        );
-        fs.create_directory(&resolved_path.display().to_string())
+    ) -> Result<InodeNumber, ShellError> {
+        let resolved_path = self.resolve(fs, path);
+        let parent = self.check_can_create(fs, &resolved_path)?;
+        let metadata = self.new_inode_metadata(fs, &parent, 0o777, true)?;
+        fs.create_directory(&resolved_path.display().to_string(), metadata)
+    }
+
+    pub fn create_hard_link(
+        &mut self,
+        fs: &mut FileSystem,
+        target: &Path,
+        link: &Path,
+    ) -> Result<(), ShellError> {
+        let resolved_target = self.resolve(fs, target);
+        let resolved_link = self.resolve(fs, link);
+        self.lookup_inode(fs, &resolved_target)?;
+        self.check_can_create(fs, &resolved_link)?;
+        fs.create_hard_link(
+            &resolved_target.display().to_string(),
+            &resolved_link.display().to_string(),
+        )
     }
 
+    /// Removes an entry, recursively if it is a directory.
+    ///
+    /// Removing an entry requires write and search permissions on its parent directory, and
+    /// emptying a directory requires read, write and search permissions on it. In a directory
+    /// with the sticky bit, only the owner of the entry or of the directory can remove it.
     pub fn remove_file(&mut self, fs: &mut FileSystem, path: &Path) -> Result<(), ShellError> {
-        let resolved_path = resolve_path(
-            path,
-            &self.get_user_home_directory(fs),
-            &self.current_working_directory,
-        );
+        let resolved_path = self.resolve(fs, path);
+        let inode = self.lookup_inode(fs, &resolved_path)?;
+        let parent = self.check_parent_writable(fs, &resolved_path)?;
+        self.check_sticky(fs, &parent, &inode.metadata, path)?;
+        self.check_can_empty(fs, inode, path)?;
         fs.remove_inode(&resolved_path.display().to_string())?;
         Ok(())
     }
 
-    pub fn find_inode(&self, fs: &FileSystem, path: &Path) -> Option<Arc<Mutex<Inode>>> {
-        let resolved_path = resolve_path(
-            path,
-            &self.get_user_home_directory(fs),
-            &self.current_working_directory,
-        );
-        fs.find_absolute_inode(&resolved_path.display().to_string())
+    /// Finds an inode, returning `None` if it does not exist or can't be reached by the user.
+    pub fn find_inode<'a>(&self, fs: &'a FileSystem, path: &Path) -> Option<&'a Inode> {
+        self.lookup_inode(fs, path).ok()
+    }
+
+    /// Finds an inode, checking that the user can search every directory leading to it.
+    pub fn lookup_inode<'a>(
+        &self,
+        fs: &'a FileSystem,
+        path: &Path,
+    ) -> Result<&'a Inode, ShellError> {
+        let resolved_path = self.resolve(fs, path);
+        let number = fs.find_inode_as(self.user(fs)?, &resolved_path.display().to_string())?;
+        fs.inode(number)
+            .ok_or_else(|| ShellError::Internal(format!("Inode {number} should exist")))
+    }
+
+    /// Finds an inode to modify it, checking that the user can search every directory leading to
+    /// it.
+    pub fn lookup_inode_mut<'a>(
+        &self,
+        fs: &'a mut FileSystem,
+        path: &Path,
+    ) -> Result<&'a mut Inode, ShellError> {
+        let number = self.lookup_inode(fs, path)?.number;
+        fs.inode_mut(number)
+            .ok_or_else(|| ShellError::Internal(format!("Inode {number} should exist")))
+    }
+
+    /// Returns the content of a file, which requires read permission.
+    pub fn read_file(&self, fs: &mut FileSystem, path: &Path) -> Result<String, ShellError> {
+        let inode = self.lookup_inode(fs, path)?;
+        let InodeContent::File(file) = &inode.content else {
+            return Err(ShellError::FileSystem(FileSystemError::IsADirectory(
+                path.display().to_string(),
+            )));
+        };
+        if !self.can_access(fs, inode, Access::Read) {
+            return Err(permission_denied(path));
+        }
+        let content = file.content.clone();
+        let now = fs.now();
+        self.lookup_inode_mut(fs, path)?.metadata.mark_accessed(now);
+        Ok(content)
+    }
+
+    /// Replaces the content of a file, creating it if it doesn't exist, which requires write
+    /// permission and enough space for its owner.
+    pub fn write_file(
+        &mut self,
+        fs: &mut FileSystem,
+        path: &Path,
+        content: &str,
+    ) -> Result<(), ShellError> {
+        if self.find_inode(fs, path).is_none() {
+            self.create_file(fs, path)?;
+        }
+        let inode = self.lookup_inode(fs, path)?;
+        if !matches!(inode.content, InodeContent::File(_)) {
+            return Err(ShellError::FileSystem(FileSystemError::IsADirectory(
+                path.display().to_string(),
+            )));
+        }
+        if !self.can_access(fs, inode, Access::Write) {
+            return Err(permission_denied(path));
+        }
+        let resolved_path = self.resolve(fs, path);
+        fs.write_file(&resolved_path.display().to_string(), content.to_string())
+    }
+
+    /// Returns the names of the entries of a directory, sorted, which requires read permission.
+    pub fn list_directory(&self, fs: &FileSystem, path: &Path) -> Result<Vec<String>, ShellError> {
+        let inode = self.lookup_inode(fs, path)?;
+        let InodeContent::Directory(directory) = &inode.content else {
+            return Err(ShellError::FileSystem(FileSystemError::NotADirectory(
+                path.display().to_string(),
+            )));
+        };
+        if !self.can_access(fs, inode, Access::Read) {
+            return Err(permission_denied(path));
+        }
+        Ok(directory.children.keys().cloned().collect())
+    }
+
+    /// Changes the mode of an inode, which only its owner or root can do.
+    pub fn change_mode(
+        &mut self,
+        fs: &mut FileSystem,
+        path: &Path,
+        mode: u32,
+    ) -> Result<(), ShellError> {
+        let user = self.user(fs)?.clone();
+        let now = fs.now();
+        let inode = self.lookup_inode_mut(fs, path)?;
+        if !user.is_root() && inode.metadata.owner != user.id {
+            return Err(ShellError::FileSystem(
+                FileSystemError::OperationNotPermitted(path.display().to_string()),
+            ));
+        }
+        // Like Linux, the set-group-ID bit is silently cleared if the user isn't a member of the
+        // group of the inode.
+        let mode = if user.is_root() || user.groups.contains(&inode.metadata.group) {
+            mode
+        } else {
+            mode & !SETGID
+        };
+        inode.metadata.permissions.set_mode(mode);
+        inode.metadata.mark_changed(now);
+        Ok(())
+    }
+
+    /// Replaces the access and default ACLs of an inode, which only its owner or root can do.
+    pub fn set_acl(
+        &mut self,
+        fs: &mut FileSystem,
+        path: &Path,
+        access_acl: Acl,
+        default_acl: Option<Acl>,
+    ) -> Result<(), ShellError> {
+        let user = self.user(fs)?.clone();
+        let now = fs.now();
+        let inode = self.lookup_inode_mut(fs, path)?;
+        if !user.is_root() && inode.metadata.owner != user.id {
+            return Err(ShellError::FileSystem(
+                FileSystemError::OperationNotPermitted(path.display().to_string()),
+            ));
+        }
+        if default_acl.is_some() && inode.inode_type() != InodeType::Directory {
+            return Err(ShellError::FileSystem(FileSystemError::NotADirectory(
+                path.display().to_string(),
+            )));
+        }
+        inode.metadata.set_access_acl(access_acl);
+        inode.metadata.default_acl = default_acl;
+        inode.metadata.mark_changed(now);
+        Ok(())
+    }
+
+    /// Changes the owner and/or the group of an inode.
+    ///
+    /// Only root can give an inode to another user, while the owner of an inode can change its
+    /// group to one they are a member of.
+    pub fn change_owner(
+        &mut self,
+        fs: &mut FileSystem,
+        path: &Path,
+        owner: Option<UserId>,
+        group: Option<GroupId>,
+    ) -> Result<(), ShellError> {
+        let user = self.user(fs)?.clone();
+        let now = fs.now();
+        let inode = self.lookup_inode_mut(fs, path)?;
+        if !user.is_root() {
+            let changes_owner = owner.is_some_and(|owner| owner != inode.metadata.owner);
+            let changes_to_foreign_group = group.is_some_and(|group| {
+                group != inode.metadata.group && !user.groups.contains(&group)
+            });
+            if changes_owner || inode.metadata.owner != user.id || changes_to_foreign_group {
+                return Err(ShellError::FileSystem(
+                    FileSystemError::OperationNotPermitted(path.display().to_string()),
+                ));
+            }
+        }
+        let changes_ownership = owner.is_some_and(|owner| owner != inode.metadata.owner)
+            || group.is_some_and(|group| group != inode.metadata.group);
+        if changes_ownership && inode.inode_type() != InodeType::Directory {
+            // Changing the ownership of an executable drops its privileges.
+            let mut mode = inode.metadata.permissions.mode() & !SETUID;
+            if inode.metadata.permissions.group().execute {
+                mode &= !SETGID;
+            }
+            inode.metadata.permissions.set_mode(mode);
+        }
+        if let Some(owner) = owner {
+            inode.metadata.owner = owner;
+        }
+        if let Some(group) = group {
+            inode.metadata.group = group;
+        }
+        inode.metadata.mark_changed(now);
+        Ok(())
+    }
+
+    /// Checks if the user of the session is granted the access to the inode.
+    pub fn can_access(&self, fs: &FileSystem, inode: &Inode, access: Access) -> bool {
+        self.user(fs)
+            .is_ok_and(|user| inode.metadata.can_access(user, access))
     }
 
     pub fn change_directory(&mut self, fs: &FileSystem, path: &Path) -> Result<(), ShellError> {
@@ -87,13 +340,14 @@ impl Session {
                     .ok_or(ShellError::Session(
                         SessionError::NoPreviousWorkingDirectory,
                     ))?;
-            let previous_inode = fs.find_absolute_inode(&previous_path.display().to_string());
-            if previous_inode.is_some() {
-                self.current_working_directory = previous_path;
-            } else {
-                return Err(ShellError::Session(
-                    SessionError::PreviousWorkingDirectoryDoesNotExist,
-                ));
+            match self.check_can_enter(fs, &previous_path) {
+                Ok(()) => self.current_working_directory = previous_path,
+                Err(ShellError::FileSystem(FileSystemError::EntryNotFound(_))) => {
+                    return Err(ShellError::Session(
+                        SessionError::PreviousWorkingDirectoryDoesNotExist,
+                    ));
+                }
+                Err(error) => return Err(error),
             }
         } else if path != prev_working_directory {
             let resolved_path = resolve_path(
@@ -101,13 +355,14 @@ impl Session {
                 &self.get_user_home_directory(fs),
                 &prev_working_directory,
             );
-            let inode = fs.find_absolute_inode(&resolved_path.display().to_string());
-            if inode.is_some() {
-                self.current_working_directory = resolved_path;
-            } else {
-                return Err(ShellError::FileSystem(FileSystemError::DirectoryNotFound(
-                    resolved_path.display().to_string(),
-                )));
+            match self.check_can_enter(fs, &resolved_path) {
+                Ok(()) => self.current_working_directory = resolved_path,
+                Err(ShellError::FileSystem(FileSystemError::EntryNotFound(_))) => {
+                    return Err(ShellError::FileSystem(FileSystemError::DirectoryNotFound(
+                        resolved_path.display().to_string(),
+                    )));
+                }
+                Err(error) => return Err(error),
             }
         }
         self.previous_working_directory = Some(prev_working_directory);
@@ -116,7 +371,263 @@ impl Session {
 
     fn get_user_home_directory(&self, fs: &FileSystem) -> PathBuf {
         let user = fs.get_user(self.current_user).expect("User not found");
-        PathBuf::from(format!("/home/{}", user.name))
+        PathBuf::from(&user.home)
+    }
+
+    fn resolve(&self, fs: &FileSystem, path: &Path) -> PathBuf {
+        resolve_path(
+            path,
+            &self.get_user_home_directory(fs),
+            &self.current_working_directory,
+        )
+    }
+
+    /// Returns the metadata of an inode created by the user, the umask being applied to the
+    /// requested mode.
+    ///
+    /// In a directory with the set-group-ID bit, the inode belongs to the group of the directory
+    /// instead of the primary group of the user, and subdirectories inherit the bit.
+    ///
+    /// In a directory with a default ACL, the inode gets it as its access ACL instead of applying
+    /// the umask, and subdirectories inherit it as their default ACL.
+    fn new_inode_metadata(
+        &self,
+        fs: &FileSystem,
+        parent: &InodeMetadata,
+        mode: u32,
+        is_directory: bool,
+    ) -> Result<InodeMetadata, ShellError> {
+        let user = self.user(fs)?;
+        let requested_mode = mode;
+        let mut mode = mode & !self.umask;
+        let group = if parent.permissions.setgid() {
+            if is_directory {
+                mode |= SETGID;
+            }
+            parent.group
+        } else {
+            user.primary_group().unwrap_or(ROOT_GROUP_ID)
+        };
+        let mut metadata = InodeMetadata::new(FilePermissions::from_mode(mode), user.id, group);
+        if let Some(default_acl) = &parent.default_acl {
+            let requested = FilePermissions::from_mode(requested_mode);
+            let mut acl = default_acl.clone();
+            acl.user = acl.user.intersect(&requested.user());
+            acl.other = acl.other.intersect(&requested.other());
+            match &mut acl.mask {
+                Some(mask) => *mask = mask.intersect(&requested.group()),
+                None => acl.group = acl.group.intersect(&requested.group()),
+            }
+            metadata.set_access_acl(acl);
+            if is_directory {
+                metadata.default_acl = Some(default_acl.clone());
+            }
+        }
+        Ok(metadata)
+    }
+
+    fn user<'a>(&self, fs: &'a FileSystem) -> Result<&'a User, ShellError> {
+        fs.get_user(self.current_user)
+            .ok_or(ShellError::Session(SessionError::UserNotFound))
+    }
+
+    /// Checks that the directory exists, and that the user can search it.
+    fn check_can_enter(&self, fs: &FileSystem, path: &Path) -> Result<(), ShellError> {
+        let inode = self.lookup_inode(fs, path)?;
+        if !matches!(inode.content, InodeContent::Directory(_)) {
+            return Err(ShellError::FileSystem(FileSystemError::NotADirectory(
+                path.display().to_string(),
+            )));
+        }
+        if !self.can_access(fs, inode, Access::Execute) {
+            return Err(permission_denied(path));
+        }
+        Ok(())
+    }
+
+    /// Checks that an entry can be added at the given path, which must not exist yet, returning
+    /// the metadata of the parent directory.
+    fn check_can_create(&self, fs: &FileSystem, path: &Path) -> Result<InodeMetadata, ShellError> {
+        if self.lookup_inode(fs, path).is_ok() {
+            return Err(ShellError::FileSystem(FileSystemError::EntryAlreadyExists(
+                path.display().to_string(),
+            )));
+        }
+        self.check_parent_writable(fs, path)
+    }
+
+    /// Checks that the user can add or remove entries in the parent directory of the path,
+    /// returning the metadata of the parent directory.
+    fn check_parent_writable(
+        &self,
+        fs: &FileSystem,
+        path: &Path,
+    ) -> Result<InodeMetadata, ShellError> {
+        let parent_path = path.parent().unwrap_or(Path::new("/"));
+        let parent = self.lookup_inode(fs, parent_path)?;
+        if !matches!(parent.content, InodeContent::Directory(_)) {
+            return Err(ShellError::FileSystem(FileSystemError::NotADirectory(
+                parent_path.display().to_string(),
+            )));
+        }
+        if !self.can_access(fs, parent, Access::Write)
+            || !self.can_access(fs, parent, Access::Execute)
+        {
+            return Err(permission_denied(path));
+        }
+        Ok(parent.metadata.clone())
+    }
+
+    /// Checks that the user can remove an entry of a directory with the sticky bit, which only
+    /// the owners of the entry and of the directory can do.
+    fn check_sticky(
+        &self,
+        fs: &FileSystem,
+        parent: &InodeMetadata,
+        entry: &InodeMetadata,
+        path: &Path,
+    ) -> Result<(), ShellError> {
+        let user = self.user(fs)?;
+        if parent.permissions.sticky()
+            && !user.is_root()
+            && user.id != parent.owner
+            && user.id != entry.owner
+        {
+            return Err(ShellError::FileSystem(
+                FileSystemError::OperationNotPermitted(path.display().to_string()),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the user can remove every entry below a directory.
+    fn check_can_empty(
+        &self,
+        fs: &FileSystem,
+        inode: &Inode,
+        path: &Path,
+    ) -> Result<(), ShellError> {
+        let InodeContent::Directory(directory) = &inode.content else {
+            return Ok(());
+        };
+        if directory.is_empty() {
+            return Ok(());
+        }
+        if ![Access::Read, Access::Write, Access::Execute]
+            .into_iter()
+            .all(|access| self.can_access(fs, inode, access))
+        {
+            return Err(permission_denied(path));
+        }
+        for (name, &child) in &directory.children {
+            let Some(child) = fs.inode(child) else {
+                continue;
+            };
+            let child_path = path.join(name);
+            self.check_sticky(fs, &inode.metadata, &child.metadata, &child_path)?;
+            self.check_can_empty(fs, child, &child_path)?;
+        }
+        Ok(())
+    }
+
+    /// Switches to another user, keeping the current identity to be restored by
+    /// [`Session::restore_user`].
+    ///
+    /// A login switch, like `su -`, starts over from the home directory of the user with a fresh
+    /// environment. Otherwise the working directory is kept, and only the variables describing
+    /// the user are updated.
+    ///
+    /// If the home directory can't be entered, the session starts from the root directory and a
+    /// warning is returned.
+    pub fn switch_user(
+        &mut self,
+        fs: &FileSystem,
+        user_id: UserId,
+        login: bool,
+    ) -> Result<Option<String>, ShellError> {
+        let user = fs
+            .get_user(user_id)
+            .ok_or(ShellError::Session(SessionError::UserNotFound))?;
+        let identity = Identity {
+            user: self.current_user,
+            current_working_directory: self.current_working_directory.clone(),
+            previous_working_directory: self.previous_working_directory.clone(),
+            environment: self.environment.clone(),
+        };
+
+        let mut warning = None;
+        if login {
+            self.environment = login_environment(user);
+            self.previous_working_directory = None;
+            self.current_user = user_id;
+            let home = PathBuf::from(&user.home);
+            match self.check_can_enter(fs, &home) {
+                Ok(()) => self.current_working_directory = home,
+                Err(error) => {
+                    warning = Some(format!(
+                        "warning: cannot change directory to {}: {}",
+                        home.display(),
+                        crate::commands::error_message(error)?
+                    ));
+                    self.current_working_directory = PathBuf::from("/");
+                }
+            }
+        } else {
+            self.environment
+                .insert("HOME".to_string(), user.home.clone());
+            self.environment
+                .insert("SHELL".to_string(), user.shell.clone());
+            // Like su, the user variables are kept when becoming root.
+            if !user.is_root() {
+                self.environment
+                    .insert("USER".to_string(), user.name.clone());
+                self.environment
+                    .insert("LOGNAME".to_string(), user.name.clone());
+            }
+            self.current_user = user_id;
+        }
+        self.previous_identities.push(identity);
+        Ok(warning)
+    }
+
+    /// Restores the identity the session had before the last user switch.
+    ///
+    /// Returns `false` if the session runs as the user it was opened for.
+    pub fn restore_user(&mut self) -> bool {
+        match self.previous_identities.pop() {
+            Some(identity) => {
+                self.current_user = identity.user;
+                self.current_working_directory = identity.current_working_directory;
+                self.previous_working_directory = identity.previous_working_directory;
+                self.environment = identity.environment;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of user switches that `exit` can undo.
+    pub fn identity_depth(&self) -> usize {
+        self.previous_identities.len()
+    }
+
+    /// Forgets a saved identity, so that `exit` skips it.
+    pub fn discard_identity(&mut self, depth: usize) {
+        if depth < self.previous_identities.len() {
+            self.previous_identities.remove(depth);
+        }
+    }
+
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        self.environment.get(name).map(String::as_str)
+    }
+
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        self.environment.insert(name.to_string(), value.to_string());
+    }
+
+    pub fn environment(&self) -> &BTreeMap<String, String> {
+        &self.environment
     }
 
     pub fn change_user(&mut self, fs: &FileSystem, user_id: UserId) -> Result<(), ShellError> {
@@ -127,6 +638,26 @@ impl Session {
         Ok(())
     }
 
+    pub fn current_user(&self) -> UserId {
+        self.current_user
+    }
+
+    pub fn umask(&self) -> u32 {
+        self.umask
+    }
+
+    pub fn set_umask(&mut self, umask: u32) {
+        self.umask = umask & 0o777;
+    }
+
+    pub fn terminal(&self) -> &str {
+        &self.terminal
+    }
+
+    pub fn login_time(&self) -> DateTime<Utc> {
+        self.login_time
+    }
+
     pub fn add_to_history(&mut self, command: &str) {
         self.executed_commands.push(command.to_string());
     }
@@ -143,3 +674,20 @@ impl Session {
         self.previous_working_directory.clone()
     }
 }
+
+/// Returns the environment of a login shell of the user.
+fn login_environment(user: &User) -> BTreeMap<String, String> {
+    BTreeMap::from([
+        ("HOME".to_string(), user.home.clone()),
+        ("LOGNAME".to_string(), user.name.clone()),
+        ("PATH".to_string(), DEFAULT_PATH.to_string()),
+        ("SHELL".to_string(), user.shell.clone()),
+        ("USER".to_string(), user.name.clone()),
+    ])
+}
+
+fn permission_denied(path: &Path) -> ShellError {
+    ShellError::FileSystem(FileSystemError::PermissionDenied(
+        path.display().to_string(),
+    ))
+}
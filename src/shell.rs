@@ -1,37 +1,104 @@
 //! The shell structure, the main unit of the shell environment.
 
-use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 use crate::{
     commands::{self, Command, CommandOutput, ExecutableCommand as CommandTrait},
     errors::ShellError,
-    fs::FileSystem,
+    host::{Host, SharedFileSystem},
+    locks::MutexExt,
     sessions::Session,
+    stdin::{QueuedInput, StdinProvider},
 };
 
-#[derive(Debug, Clone)]
+/// A shell open on a terminal of a [`Host`].
+///
+/// The terminal is released when the shell exits or is dropped.
+#[derive(Debug)]
 pub struct Shell {
-    pub fs: FileSystem,
+    pub fs: SharedFileSystem,
     pub current_session: Session,
     pub active: bool,
+    host: Host,
+    stdin: Arc<Mutex<dyn StdinProvider>>,
+    /// The exit status of the last command, `0` meaning success.
+    exit_status: i32,
 }
 
 impl Shell {
+    /// Creates a host with a single user, logged in on its first terminal.
     #[must_use]
     pub fn new_with_user(username: &str) -> Self {
-        let mut fs = FileSystem::new();
-        let user_id = fs.add_user(username).expect("Failed to add user");
-        let current_session = Session::new(PathBuf::from(format!("/home/{username}")), user_id);
+        let host = Host::new();
+        host.fs
+            .lock()
+            .add_user(username)
+            .expect("Failed to add user");
+        host.login(username).expect("Failed to open session")
+    }
 
+    pub(crate) fn new(host: Host, current_session: Session) -> Self {
         Self {
-            fs,
+            fs: host.fs.clone(),
             current_session,
             active: true,
+            host,
+            stdin: Arc::new(Mutex::new(QueuedInput::default())),
+            exit_status: 0,
         }
     }
+
+    /// Returns the host the shell runs on, which can log other users in.
+    #[must_use]
+    pub fn host(&self) -> &Host {
+        &self.host
+    }
+
+    /// Takes the messages written to the terminal of the shell by `write`, oldest first.
+    #[must_use]
+    pub fn take_messages(&self) -> Vec<String> {
+        self.host.take_messages(self.current_session.terminal())
+    }
+
+    /// Closes the shell, releasing its terminal.
+    pub(crate) fn logout(&mut self) {
+        self.active = false;
+        self.host.logout(self.current_session.terminal());
+    }
+
+    /// Replaces the provider of the lines read by commands prompting the user, like passwords.
+    pub fn set_stdin(&mut self, stdin: impl StdinProvider + 'static) {
+        self.stdin = Arc::new(Mutex::new(stdin));
+    }
+
+    /// Reads a line from the standard input after showing the prompt.
+    pub fn read_line(&self, prompt: &str) -> Option<String> {
+        self.stdin.lock_unpoisoned().read_line(prompt)
+    }
+
+    /// Reads every line left on the standard input, like a command reading a pipe.
+    pub fn read_lines(&self) -> Vec<String> {
+        let mut stdin = self.stdin.lock_unpoisoned();
+        std::iter::from_fn(|| stdin.read_line("")).collect()
+    }
+
+    /// Returns the exit status of the last command run, like `$?`.
+    #[must_use]
+    pub fn exit_status(&self) -> i32 {
+        self.exit_status
+    }
+
+    /// Sets the exit status of the running command, which succeeds unless told otherwise.
+    pub(crate) fn set_exit_status(&mut self, status: i32) {
+        self.exit_status = status;
+    }
 }
 
 impl Shell {
+    /// Executes a command line, which can be a pipeline of commands separated by `|`.
+    ///
+    /// The output of each command of a pipeline is the standard input of the next one, and the
+    /// exit status is the one of the last command.
     pub fn execute(&mut self, command: &str) -> Result<CommandOutput, ShellError> {
         if !self.active {
             return Err(ShellError::ShellNotActive);
@@ -39,9 +106,48 @@ impl Shell {
         if command.is_empty() {
             return Err(ShellError::Internal("Empty command provided".to_string()));
         }
-        let tokens = shlex::split(command)
-            .ok_or_else(|| ShellError::Internal("Failed to parse command".to_string()))?;
+        let mut stages = Vec::new();
+        for stage in split_pipeline(command) {
+            if stage.trim().is_empty() {
+                return Err(ShellError::Internal(
+                    "syntax error near unexpected token `|'".to_string(),
+                ));
+            }
+            stages.push(
+                shlex::split(stage)
+                    .ok_or_else(|| ShellError::Internal("Failed to parse command".to_string()))?,
+            );
+        }
 
+        let last = stages.pop().unwrap_or_default();
+        let stdin = self.stdin.clone();
+        let mut result = Ok(CommandOutput(None));
+        for tokens in stages {
+            result = self.run(tokens, true);
+            let Ok(CommandOutput(output)) = &result else {
+                break;
+            };
+            let lines = output.as_deref().map(|output| output.split('\n'));
+            self.stdin = Arc::new(Mutex::new(QueuedInput::new(lines.into_iter().flatten())));
+        }
+        if result.is_ok() {
+            result = self.run(last, true);
+        }
+        self.stdin = stdin;
+        result
+    }
+
+    /// Executes a command that was already split into words, like the commands run by `sudo`.
+    ///
+    /// Unlike [`Shell::execute`], the command is not added to the history.
+    pub(crate) fn execute_tokens(
+        &mut self,
+        tokens: Vec<String>,
+    ) -> Result<CommandOutput, ShellError> {
+        self.run(tokens, false)
+    }
+
+    fn run(&mut self, tokens: Vec<String>, record: bool) -> Result<CommandOutput, ShellError> {
         let Command {
             command,
             flags,
@@ -49,7 +155,13 @@ impl Shell {
         } = commands::Command::from_tokens(tokens)
             .map_err(|e| ShellError::Internal(e.to_string()))?;
 
-        self.current_session.add_to_history(command.name());
+        if record {
+            self.current_session.add_to_history(command.name());
+            self.host
+                .set_activity(self.current_session.terminal(), command.name());
+        }
+
+        self.exit_status = 0;
 
         match command.execute(flags, args, self) {
             Ok(output) => Ok(output),
@@ -57,3 +169,36 @@ impl Shell {
         }
     }
 }
+
+/// Splits a command line on the `|` that are not quoted or escaped.
+fn split_pipeline(command: &str) -> Vec<&str> {
+    let mut stages = Vec::new();
+    let mut start = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, char) in command.char_indices() {
+        match (quote, char) {
+            _ if escaped => escaped = false,
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), _) => {}
+            (_, '\\') => escaped = true,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(char),
+            (None, '|') => {
+                stages.push(&command[start..index]);
+                start = index + 1;
+            }
+            (None, _) => {}
+        }
+    }
+    stages.push(&command[start..]);
+    stages
+}
+
+impl Drop for Shell {
+    fn drop(&mut self) {
+        if self.active {
+            self.logout();
+        }
+    }
+}
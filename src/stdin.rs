@@ -0,0 +1,52 @@
+//! The standard input of the shell, read by the commands prompting the user.
+//!
+//! The shell doesn't own a terminal, so the input is supplied by a [`StdinProvider`], which an
+//! interactive front end can implement by reading from the real terminal.
+
+use std::{collections::VecDeque, fmt::Debug, sync::MutexGuard};
+
+use crate::FileSystem;
+
+pub trait StdinProvider: Debug + Send {
+    /// Reads a line typed by the user after showing the prompt, without its line terminator.
+    ///
+    /// Returns `None` at the end of the input.
+    fn read_line(&mut self, prompt: &str) -> Option<String>;
+}
+
+/// Input given in advance, one line per prompt.
+#[derive(Debug, Clone, Default)]
+pub struct QueuedInput {
+    lines: VecDeque<String>,
+}
+
+impl QueuedInput {
+    #[must_use]
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            lines: lines.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    pub fn push_line(&mut self, line: impl Into<String>) {
+        self.lines.push_back(line.into());
+    }
+}
+
+impl StdinProvider for QueuedInput {
+    fn read_line(&mut self, _: &str) -> Option<String> {
+        self.lines.pop_front()
+    }
+}
+
+/// Runs `prompt`, reading what the user types, after releasing the lock of the file system.
+///
+/// Other terminals can use the file system while the user types, which can take a while.
+pub(crate) fn prompt_unlocked<T>(fs: MutexGuard<'_, FileSystem>, prompt: impl FnOnce() -> T) -> T {
+    drop(fs);
+    prompt()
+}
@@ -0,0 +1,244 @@
+//! The sudoers policy, deciding which users may run commands as other users.
+//!
+//! The policy is read from `/etc/sudoers`, with a subset of its syntax: one rule per line made
+//! of a user (or `%group`), a host, an optional run-as list in parentheses and a list of
+//! commands.
+//!
+//! ```text
+//! root    ALL=(ALL:ALL) ALL
+//! %sudo   ALL=(ALL:ALL) ALL
+//! bob     ALL=(root) /bin/ls, /bin/cat
+//! ```
+
+use crate::{FileSystem, InodeContent, User};
+
+/// The location of the policy in the file system.
+pub const SUDOERS_PATH: &str = "/etc/sudoers";
+
+/// The policy installed with the file system, granting every privilege to the `sudo` group.
+pub const DEFAULT_SUDOERS: &str = "\
+# User privilege specification
+root\tALL=(ALL:ALL) ALL
+
+# Members of the group 'sudo' may execute any command
+%sudo\tALL=(ALL:ALL) ALL
+";
+
+#[derive(Debug, Clone, Default)]
+pub struct Sudoers {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    principal: Principal,
+    /// The users commands can be run as, `None` standing for `ALL`.
+    run_as: Option<Vec<String>>,
+    /// The allowed commands, `None` standing for `ALL`.
+    commands: Option<Vec<String>>,
+    /// Whether the user must type their password, unless the rule is tagged `NOPASSWD:`.
+    authenticate: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Principal {
+    All,
+    User(String),
+    Group(String),
+}
+
+impl Sudoers {
+    /// Parses the policy, ignoring comments, `Defaults` and malformed lines.
+    #[must_use]
+    pub fn parse(content: &str) -> Self {
+        let rules = content.lines().filter_map(parse_rule).collect();
+        Self { rules }
+    }
+
+    /// Reads the policy of a file system. A missing policy grants nothing.
+    #[must_use]
+    pub fn load(fs: &FileSystem) -> Self {
+        let Some(inode) = fs
+            .find_absolute_inode(SUDOERS_PATH)
+            .and_then(|number| fs.inode(number))
+        else {
+            return Self::default();
+        };
+        match &inode.content {
+            InodeContent::File(file) => Self::parse(&file.content),
+            _ => Self::default(),
+        }
+    }
+
+    /// Checks if any rule of the policy applies to the user.
+    #[must_use]
+    pub fn is_listed(&self, fs: &FileSystem, user: &User) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| rule.principal.matches(fs, user))
+    }
+
+    /// Checks if the user may run the command as the target user. A `None` command is an
+    /// interactive shell, which only `ALL` allows.
+    #[must_use]
+    pub fn allows(
+        &self,
+        fs: &FileSystem,
+        user: &User,
+        target: &User,
+        command: Option<&str>,
+    ) -> bool {
+        self.matching_rules(fs, user, target, command)
+            .next()
+            .is_some()
+    }
+
+    /// Checks if the user must authenticate to run the command, which is not the case when a
+    /// rule allowing it is tagged `NOPASSWD:`.
+    #[must_use]
+    pub fn requires_password(
+        &self,
+        fs: &FileSystem,
+        user: &User,
+        target: &User,
+        command: Option<&str>,
+    ) -> bool {
+        self.matching_rules(fs, user, target, command)
+            .all(|rule| rule.authenticate)
+    }
+
+    fn matching_rules<'a>(
+        &'a self,
+        fs: &'a FileSystem,
+        user: &'a User,
+        target: &'a User,
+        command: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a Rule> {
+        self.rules.iter().filter(move |rule| {
+            rule.principal.matches(fs, user)
+                && rule
+                    .run_as
+                    .as_ref()
+                    .is_none_or(|run_as| run_as.contains(&target.name))
+                && match (&rule.commands, command) {
+                    (None, _) => true,
+                    (Some(_), None) => false,
+                    (Some(commands), Some(command)) => commands
+                        .iter()
+                        .any(|allowed| command_name(allowed) == command_name(command)),
+                }
+        })
+    }
+}
+
+impl Principal {
+    fn matches(&self, fs: &FileSystem, user: &User) -> bool {
+        match self {
+            Principal::All => true,
+            Principal::User(name) => *name == user.name,
+            Principal::Group(name) => fs
+                .find_group_by_name(name)
+                .is_some_and(|group| user.groups.contains(&group.id)),
+        }
+    }
+}
+
+fn parse_rule(line: &str) -> Option<Rule> {
+    let line = line.split('#').next().unwrap_or_default().trim();
+    let (principal, spec) = line.split_once(char::is_whitespace)?;
+    if principal == "Defaults" || principal.starts_with("Defaults:") {
+        return None;
+    }
+    let principal = match principal {
+        "ALL" => Principal::All,
+        group if group.starts_with('%') => Principal::Group(group[1..].to_string()),
+        user => Principal::User(user.to_string()),
+    };
+
+    // The host part is ignored, the shell runs on a single machine.
+    let (_, spec) = spec.split_once('=')?;
+    let mut spec = spec.trim();
+    // Without a run-as list, commands can only be run as root.
+    let mut run_as = Some(vec!["root".to_string()]);
+    if let Some(rest) = spec.strip_prefix('(') {
+        let (list, rest) = rest.split_once(')')?;
+        let users = list.split(':').next().unwrap_or_default();
+        run_as = if users.trim() == "ALL" {
+            None
+        } else {
+            Some(
+                users
+                    .split(',')
+                    .map(|user| user.trim().to_string())
+                    .filter(|user| !user.is_empty())
+                    .collect(),
+            )
+        };
+        spec = rest.trim();
+    }
+    let mut authenticate = true;
+    if let Some(rest) = spec.strip_prefix("NOPASSWD:") {
+        authenticate = false;
+        spec = rest.trim();
+    } else if let Some(rest) = spec.strip_prefix("PASSWD:") {
+        spec = rest.trim();
+    }
+
+    let commands = spec
+        .split(',')
+        .map(|command| command.split_whitespace().next().unwrap_or_default())
+        .filter(|command| !command.is_empty())
+        .collect::<Vec<_>>();
+    if commands.is_empty() {
+        return None;
+    }
+    let commands = if commands.contains(&"ALL") {
+        None
+    } else {
+        Some(commands.into_iter().map(str::to_string).collect())
+    };
+
+    Some(Rule {
+        principal,
+        run_as,
+        commands,
+        authenticate,
+    })
+}
+
+/// Returns the name of a command, commands being matched regardless of their directory.
+fn command_name(command: &str) -> &str {
+    command.rsplit('/').next().unwrap_or(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rules() {
+        let mut fs = FileSystem::new();
+        let alice = fs.add_user("alice").expect("Failed to add user");
+        let bob = fs.add_user("bob").expect("Failed to add user");
+        let root = fs.get_user(0).expect("Missing root").clone();
+        let alice = fs.get_user(alice).expect("Missing user").clone();
+        let bob = fs.get_user(bob).expect("Missing user").clone();
+
+        let sudoers = Sudoers::parse(
+            "Defaults env_reset\n\
+             # comment\n\
+             alice ALL=(ALL) NOPASSWD: ALL\n\
+             %bob ALL = /bin/ls, /usr/bin/touch\n",
+        );
+        assert!(sudoers.allows(&fs, &alice, &bob, Some("rm")));
+        assert!(sudoers.allows(&fs, &alice, &root, None));
+        assert!(sudoers.allows(&fs, &bob, &root, Some("ls")));
+        assert!(sudoers.allows(&fs, &bob, &root, Some("/usr/bin/touch")));
+        assert!(!sudoers.allows(&fs, &bob, &root, Some("rm")));
+        assert!(!sudoers.allows(&fs, &bob, &alice, Some("ls")));
+        assert!(!sudoers.allows(&fs, &bob, &root, None));
+        assert!(!sudoers.is_listed(&fs, &root));
+        assert!(!sudoers.requires_password(&fs, &alice, &root, Some("ls")));
+        assert!(sudoers.requires_password(&fs, &bob, &root, Some("ls")));
+    }
+}
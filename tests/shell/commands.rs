@@ -1,11 +1,51 @@
 //! Tests for the commands.
 
+mod acl;
+mod awk;
+mod cat;
 mod cd;
+mod chmod;
+mod chown;
+mod cmp;
+mod cut;
+mod date;
+mod df;
+mod diff;
+mod du;
 mod echo;
 mod exit;
+mod find;
+mod gpasswd;
+mod grep;
+mod groupadd;
+mod groups;
+mod head;
 mod history;
+mod id;
+mod ln;
 mod ls;
 mod mkdir;
+mod passwd;
+mod paste;
+mod patch;
 mod pwd;
 mod rm;
+mod sed;
+mod sort;
+mod special_bits;
+mod stat;
+mod su;
+mod sudo;
+mod tail;
 mod touch;
+mod tr;
+mod tree;
+mod umask;
+mod uniq;
+mod useradd;
+mod userdel;
+mod usermod;
+mod wc;
+mod who;
+mod whoami;
+mod write;
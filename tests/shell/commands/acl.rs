@@ -0,0 +1,232 @@
+//! Tests for the getfacl and setfacl commands and the enforcement of ACLs.
+
+use shelljougahara::Shell;
+
+#[test]
+fn test_getfacl_minimal() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.execute("touch f").expect("Failed to execute touch");
+    let getfacl = shell
+        .execute("getfacl f")
+        .expect("Failed to execute getfacl");
+    assert_eq!(
+        getfacl.0,
+        Some(
+            "# file: f\n# owner: alice\n# group: alice\nuser::rw-\ngroup::r--\nother::r--"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_named_user_entry() {
+    let mut shell = Shell::new_with_user("alice");
+    let bob = shell.fs.lock().add_user("bob").expect("Failed to add user");
+    let alice = shell.current_session.current_user();
+    shell
+        .execute("mkdir private")
+        .expect("Failed to execute mkdir");
+    shell
+        .execute("chmod 700 private")
+        .expect("Failed to execute chmod");
+    shell
+        .execute("touch private/notes")
+        .expect("Failed to execute touch");
+
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), bob)
+        .expect("Failed to change user");
+    let ls = shell
+        .execute("ls /home/alice/private")
+        .expect("Failed to execute ls");
+    assert_eq!(
+        ls.0,
+        Some("ls: cannot open directory '/home/alice/private': Permission denied".to_string())
+    );
+
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), alice)
+        .expect("Failed to change user");
+    let setfacl = shell
+        .execute("setfacl -m u:bob:rx private")
+        .expect("Failed to execute setfacl");
+    assert_eq!(setfacl.0, None);
+    let ls = shell.execute("ls -l").expect("Failed to execute ls");
+    assert!(
+        ls.0.expect("Missing ls output")
+            .lines()
+            .any(|line| line.starts_with("drwxr-x---+ ") && line.ends_with(" private"))
+    );
+
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), bob)
+        .expect("Failed to change user");
+    let ls = shell
+        .execute("ls /home/alice/private")
+        .expect("Failed to execute ls");
+    assert_eq!(ls.0, Some("notes".to_string()));
+    let setfacl = shell
+        .execute("setfacl -m u:bob:rwx /home/alice/private")
+        .expect("Failed to execute setfacl");
+    assert_eq!(
+        setfacl.0,
+        Some("setfacl: /home/alice/private: Operation not permitted".to_string())
+    );
+}
+
+#[test]
+fn test_mask_limits_entries() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.fs.lock().add_user("bob").expect("Failed to add user");
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell
+        .execute("setfacl -m u:bob:rw-,m::r f")
+        .expect("Failed to execute setfacl");
+    let getfacl = shell
+        .execute("getfacl f")
+        .expect("Failed to execute getfacl");
+    assert_eq!(
+        getfacl.0,
+        Some(
+            "# file: f\n# owner: alice\n# group: alice\nuser::rw-\nuser:bob:rw-\t#effective:r--\n\
+             group::r--\nmask::r--\nother::r--"
+                .to_string()
+        )
+    );
+
+    // chmod changes the mask when the file has an extended ACL.
+    shell
+        .execute("chmod g+w f")
+        .expect("Failed to execute chmod");
+    let getfacl = shell
+        .execute("getfacl f")
+        .expect("Failed to execute getfacl");
+    assert!(
+        getfacl
+            .0
+            .expect("Missing getfacl output")
+            .contains("user:bob:rw-\ngroup::r--\nmask::rw-")
+    );
+}
+
+#[test]
+fn test_remove_entries() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.fs.lock().add_user("bob").expect("Failed to add user");
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell
+        .execute("setfacl -m u:bob:rw,g:bob:r f")
+        .expect("Failed to execute setfacl");
+    shell
+        .execute("setfacl -x u:bob f")
+        .expect("Failed to execute setfacl");
+    let getfacl = shell
+        .execute("getfacl f")
+        .expect("Failed to execute getfacl");
+    assert_eq!(
+        getfacl.0,
+        Some(
+            "# file: f\n# owner: alice\n# group: alice\nuser::rw-\ngroup::r--\ngroup:bob:r--\n\
+             mask::r--\nother::r--"
+                .to_string()
+        )
+    );
+
+    shell
+        .execute("setfacl -b f")
+        .expect("Failed to execute setfacl");
+    let ls = shell.execute("ls -l f").expect("Failed to execute ls");
+    assert!(ls.0.expect("Missing ls output").starts_with("-rw-r--r-- "));
+    let setfacl = shell
+        .execute("setfacl -x u:: f")
+        .expect("Failed to execute setfacl");
+    assert_eq!(
+        setfacl.0,
+        Some("setfacl: Option -x: Invalid argument near character 1".to_string())
+    );
+}
+
+#[test]
+fn test_remove_all_keeps_masked_group() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.fs.lock().add_user("bob").expect("Failed to add user");
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell
+        .execute("setfacl -m g::rx,u:bob:rwx,m::rwx f")
+        .expect("Failed to execute setfacl");
+    shell
+        .execute("setfacl -b f")
+        .expect("Failed to execute setfacl");
+    let getfacl = shell
+        .execute("getfacl f")
+        .expect("Failed to execute getfacl");
+    assert_eq!(
+        getfacl.0,
+        Some(
+            "# file: f\n# owner: alice\n# group: alice\nuser::rw-\ngroup::r-x\nother::r--"
+                .to_string()
+        )
+    );
+
+    shell
+        .execute("setfacl -m g::rwx,u:bob:r,m::r f")
+        .expect("Failed to execute setfacl");
+    shell
+        .execute("setfacl -b f")
+        .expect("Failed to execute setfacl");
+    let ls = shell.execute("ls -l f").expect("Failed to execute ls");
+    assert!(ls.0.expect("Missing ls output").starts_with("-rw-r--r-- "));
+}
+
+#[test]
+fn test_default_acl_inheritance() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.fs.lock().add_user("bob").expect("Failed to add user");
+    shell
+        .execute("mkdir shared")
+        .expect("Failed to execute mkdir");
+    shell.execute("touch f").expect("Failed to execute touch");
+    let setfacl = shell
+        .execute("setfacl -d -m u:bob:rwx f")
+        .expect("Failed to execute setfacl");
+    assert_eq!(
+        setfacl.0,
+        Some("setfacl: f: Only directories can have default ACLs".to_string())
+    );
+
+    shell
+        .execute("setfacl -m d:u:bob:rwx,d:o::- shared")
+        .expect("Failed to execute setfacl");
+    let getfacl = shell
+        .execute("getfacl shared")
+        .expect("Failed to execute getfacl");
+    assert!(getfacl.0.expect("Missing getfacl output").ends_with(
+        "other::r-x\ndefault:user::rwx\ndefault:user:bob:rwx\ndefault:group::r-x\n\
+         default:mask::rwx\ndefault:other::---"
+    ));
+
+    shell
+        .execute("touch shared/f")
+        .expect("Failed to execute touch");
+    shell
+        .execute("mkdir shared/d")
+        .expect("Failed to execute mkdir");
+    let getfacl = shell
+        .execute("getfacl shared/f")
+        .expect("Failed to execute getfacl");
+    assert!(getfacl.0.expect("Missing getfacl output").ends_with(
+        "user::rw-\nuser:bob:rwx\t#effective:rw-\ngroup::r-x\t#effective:r--\nmask::rw-\nother::---"
+    ));
+    let getfacl = shell
+        .execute("getfacl shared/d")
+        .expect("Failed to execute getfacl");
+    assert!(
+        getfacl
+            .0
+            .expect("Missing getfacl output")
+            .contains("default:user:bob:rwx")
+    );
+}
@@ -0,0 +1,266 @@
+//! Tests for the awk command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::{run, shell_with_files, write_file};
+
+/// Returns a shell with an `access.log` file:
+///
+/// ```text
+/// 10.0.0.1 GET /index.html 200 512
+/// 10.0.0.2 GET /missing 404 128
+/// 10.0.0.1 POST /login 200 64
+/// 10.0.0.3 GET /index.html 500 0
+/// ```
+fn shell_with_log() -> Shell {
+    shell_with_files(
+        &[],
+        &[(
+            "access.log",
+            "10.0.0.1 GET /index.html 200 512\n10.0.0.2 GET /missing 404 128\n10.0.0.1 POST \
+             /login 200 64\n10.0.0.3 GET /index.html 500 0\n",
+        )],
+    )
+}
+
+#[test]
+fn test_awk_fields() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(&mut shell, "awk '{ print $1, $4 }' access.log"),
+        Some("10.0.0.1 200\n10.0.0.2 404\n10.0.0.1 200\n10.0.0.3 500".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk '{ print NR \": \" $NF, NF }' access.log"),
+        Some("1: 512 5\n2: 128 5\n3: 64 5\n4: 0 5".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk 'BEGIN { OFS = \"-\" } { $2 = \"\"; print $2, $0 }' access.log"),
+        Some(
+            "-10.0.0.1--/index.html-200-512\n-10.0.0.2--/missing-404-128\n-10.0.0.1--/login-200-64\n\
+             -10.0.0.3--/index.html-500-0"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_awk_field_separator() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "passwd", "root:x:0:0\nalice:x:1000:1000\n");
+    assert_eq!(
+        run(&mut shell, "awk -F: '{ print $1 }' passwd"),
+        Some("root\nalice".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk -F '[:x]+' '{ print $2 }' passwd"),
+        Some("0\n1000".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk 'BEGIN { FS = \":\"; OFS = \"\\t\" } { print $1, $3 }' passwd"
+        ),
+        Some("root\t0\nalice\t1000".to_string())
+    );
+}
+
+#[test]
+fn test_awk_patterns() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(&mut shell, "awk '$4 >= 400' access.log"),
+        Some("10.0.0.2 GET /missing 404 128\n10.0.0.3 GET /index.html 500 0".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk '/POST/ { print $3 }' access.log"),
+        Some("/login".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '$3 !~ /index/ && NR > 1 { print NR }' access.log"
+        ),
+        Some("2\n3".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk 'NR == 2, NR == 3 { print $1 }' access.log"),
+        Some("10.0.0.2\n10.0.0.1".to_string())
+    );
+}
+
+#[test]
+fn test_awk_begin_end() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk 'BEGIN { print \"start\" } { total += $5 } END { print \"total\", total, NR }' \
+             access.log"
+        ),
+        Some("start\ntotal 704 4".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk 'BEGIN { print 7 / 2, 2 ^ 10, 10 % 3 }'"),
+        Some("3.5 1024 1".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk 'END { print $0 }' access.log"),
+        Some("10.0.0.3 GET /index.html 500 0".to_string())
+    );
+}
+
+#[test]
+fn test_awk_arrays() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '{ hits[$1]++; bytes[$1] += $5 } END { for (ip in hits) print ip, hits[ip], \
+             bytes[ip] }' access.log"
+        ),
+        Some("10.0.0.1 2 576\n10.0.0.2 1 128\n10.0.0.3 1 0".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '{ seen[$4] = 1 } END { delete seen[\"200\"]; print length(seen), (\"404\" in \
+             seen), (\"200\" in seen) }' access.log"
+        ),
+        Some("2 1 0".to_string())
+    );
+}
+
+#[test]
+fn test_awk_control_flow() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '{ if ($4 == 200) ok++; else failed++ } END { print ok, failed }' access.log"
+        ),
+        Some("2 2".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk 'BEGIN { for (i = 1; i <= 5; i++) { if (i == 4) break; s = s i } print s }'"
+        ),
+        Some("123".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "awk 'BEGIN { while (n < 3) n++; print n }'"),
+        Some("3".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '$4 != 200 { next } { print $3 }' access.log"
+        ),
+        Some("/index.html\n/login".to_string())
+    );
+}
+
+#[test]
+fn test_awk_printf() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '{ printf \"%-10s|%5d|%6.2f%%\\n\", $1, $5, $5 / 7.04 }' access.log"
+        ),
+        Some(
+            "10.0.0.1  |  512| 72.73%\n10.0.0.2  |  128| 18.18%\n10.0.0.1  |   64|  9.09%\n\
+             10.0.0.3  |    0|  0.00%"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk 'BEGIN { x = sprintf(\"%03d-%s\", 7, \"a\"); print x }'"
+        ),
+        Some("007-a".to_string())
+    );
+}
+
+#[test]
+fn test_awk_string_functions() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(
+            &mut shell,
+            "echo Hello World | awk '{ print length($1), substr($2, 2, 3), toupper($1), \
+             tolower($2), index($0, \"o\") }'"
+        ),
+        Some("5 orl HELLO world 5".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "echo 2025-01-31 | awk '{ n = split($0, date, \"-\"); print n, date[2] date[3] }'"
+        ),
+        Some("3 0131".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "echo a.b.c | awk '{ gsub(/\\./, \"/\"); print }'"
+        ),
+        Some("a/b/c".to_string())
+    );
+}
+
+#[test]
+fn test_awk_stdin_and_variables() {
+    let mut shell = Shell::new_with_user("test");
+    shell.set_stdin(QueuedInput::new(["3", "4"]));
+    assert_eq!(
+        run(&mut shell, "awk -v factor=10 '{ print $1 * factor }'"),
+        Some("30\n40".to_string())
+    );
+    write_file(&mut shell, "numbers", "1\n2\n");
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '{ print prefix $1 }' prefix=a: numbers prefix=b: numbers"
+        ),
+        Some("a:1\na:2\nb:1\nb:2".to_string())
+    );
+}
+
+#[test]
+fn test_awk_exit() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(
+            &mut shell,
+            "awk '$4 == 404 { exit 3 } { print NR } END { print \"end\" }' access.log"
+        ),
+        Some("1\nend".to_string())
+    );
+    assert_eq!(shell.exit_status(), 3);
+}
+
+#[test]
+fn test_awk_errors() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(&mut shell, "awk '{ print $1 '"),
+        Some("awk: syntax error at source line 1".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+    assert_eq!(
+        run(&mut shell, "awk '{ print }' missing"),
+        Some(
+            "awk: fatal: cannot open file 'missing' for reading: No such file or directory"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 2);
+    assert_eq!(
+        run(&mut shell, "awk 'BEGIN { print 1; print 1 / 0 }'"),
+        Some("1\nawk: division by zero".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+}
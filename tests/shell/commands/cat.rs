@@ -0,0 +1,76 @@
+//! Tests for the cat command and the account files of `/etc`.
+
+use shelljougahara::Shell;
+
+#[test]
+fn test_cat_account_files() {
+    let mut shell = Shell::new_with_user("alice");
+    let passwd = shell
+        .execute("cat /etc/passwd")
+        .expect("Failed to execute cat");
+    assert_eq!(
+        passwd.0,
+        Some("root:x:0:0::/root:/bin/sh\nalice:x:1000:1000::/home/alice:/bin/sh".to_string())
+    );
+    let group = shell
+        .execute("cat /etc/group")
+        .expect("Failed to execute cat");
+    assert_eq!(
+        group.0,
+        Some("root:x:0:\nsudo:x:27:\nalice:x:1000:".to_string())
+    );
+
+    // The password hashes are only readable by root.
+    let shadow = shell
+        .execute("cat /etc/shadow")
+        .expect("Failed to execute cat");
+    assert_eq!(
+        shadow.0,
+        Some("cat: /etc/shadow: Permission denied".to_string())
+    );
+}
+
+#[test]
+fn test_cat_follows_account_changes() {
+    let mut shell = Shell::new_with_user("admin");
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    shell
+        .execute("useradd -s /bin/bash bob")
+        .expect("Failed to execute useradd");
+    let passwd = shell
+        .execute("cat /etc/passwd")
+        .expect("Failed to execute cat");
+    assert!(
+        passwd
+            .0
+            .expect("Missing cat output")
+            .ends_with("bob:x:1001:1001::/home/bob:/bin/bash")
+    );
+
+    let shadow = shell
+        .execute("cat /etc/shadow")
+        .expect("Failed to execute cat");
+    let shadow = shadow.0.expect("Missing cat output");
+    assert!(shadow.lines().any(|line| line.starts_with("bob:!:")));
+}
+
+#[test]
+fn test_cat_numbers_lines_and_reports_errors() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.execute("mkdir d").expect("Failed to execute mkdir");
+    let cat = shell
+        .execute("cat -n /etc/group d missing")
+        .expect("Failed to execute cat");
+    assert_eq!(
+        cat.0,
+        Some(
+            "     1\troot:x:0:\n     2\tsudo:x:27:\n     3\talice:x:1000:\n\
+             cat: d: Is a directory\n\
+             cat: missing: No such file or directory"
+                .to_string()
+        )
+    );
+}
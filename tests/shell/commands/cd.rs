@@ -80,3 +80,14 @@ fn test_parent_with_root() {
     let pwd_output = shell.execute("pwd").expect("Failed to execute pwd");
     assert_eq!(pwd_output.0, Some("/".to_string()));
 }
+
+#[test]
+fn test_cd_into_file() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell
+        .execute("touch file")
+        .expect("Failed to execute touch");
+    let output = shell.execute("cd file").expect("Failed to execute cd");
+    assert_eq!(output.0, Some("cd: file: Not a directory".to_string()));
+}
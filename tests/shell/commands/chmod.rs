@@ -0,0 +1,185 @@
+//! Tests for the chmod command.
+
+use shelljougahara::Shell;
+
+use crate::mode_of;
+
+#[test]
+fn test_chmod_octal() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch f").expect("Failed to execute touch");
+    let chmod = shell
+        .execute("chmod 640 f")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, None);
+    assert_eq!(mode_of(&mut shell, "f"), "-rw-r-----");
+}
+
+#[test]
+fn test_chmod_symbolic() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell
+        .execute("chmod 664 f")
+        .expect("Failed to execute chmod");
+    let chmod = shell
+        .execute("chmod u+x,g-w,o= f")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, None);
+    assert_eq!(mode_of(&mut shell, "f"), "-rwxr-----");
+}
+
+#[test]
+fn test_chmod_dash_modes() {
+    let mut shell = Shell::new_with_user("test");
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell
+        .execute("chmod 777 f")
+        .expect("Failed to execute chmod");
+    let chmod = shell
+        .execute("chmod -x f")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, None);
+    assert_eq!(mode_of(&mut shell, "f"), "-rw-rw-rw-");
+    // Without a class, the bits of the umask (022) are left alone.
+    let chmod = shell
+        .execute("chmod -w,+r f")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, None);
+    assert_eq!(mode_of(&mut shell, "f"), "-r--rw-rw-");
+    shell.execute("mkdir d").expect("Failed to execute mkdir");
+    let chmod = shell
+        .execute("chmod -rwx d")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, None);
+    let stat = shell
+        .execute("stat -c %A d")
+        .expect("Failed to execute stat");
+    assert_eq!(stat.0, Some("d---------".to_string()));
+}
+
+#[test]
+fn test_chmod_octal_keeps_directory_setgid() {
+    let mut shell = Shell::new_with_user("test");
+    shell.execute("mkdir d").expect("Failed to execute mkdir");
+    shell
+        .execute("chmod 2775 d")
+        .expect("Failed to execute chmod");
+    shell
+        .execute("chmod 755 d")
+        .expect("Failed to execute chmod");
+    let stat = shell
+        .execute("stat -c %A d")
+        .expect("Failed to execute stat");
+    assert_eq!(stat.0, Some("drwxr-sr-x".to_string()));
+    shell
+        .execute("chmod 00755 d")
+        .expect("Failed to execute chmod");
+    let stat = shell
+        .execute("stat -c %A d")
+        .expect("Failed to execute stat");
+    assert_eq!(stat.0, Some("drwxr-xr-x".to_string()));
+}
+
+#[test]
+fn test_chmod_recursive() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    shell
+        .execute("touch dir/f")
+        .expect("Failed to execute touch");
+    shell
+        .execute("chmod 600 dir/f")
+        .expect("Failed to execute chmod");
+    shell
+        .execute("chmod 700 dir")
+        .expect("Failed to execute chmod");
+    let chmod = shell
+        .execute("chmod a+rX -R dir")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, None);
+    assert_eq!(mode_of(&mut shell, "dir/f"), "-rw-r--r--");
+    let ls = shell.execute("ls -la dir").expect("Failed to execute ls");
+    assert!(ls.0.expect("Missing ls output").starts_with("drwxr-xr-x"));
+}
+
+#[test]
+fn test_chmod_reference() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch a b").expect("Failed to execute touch");
+    shell
+        .execute("chmod 604 a")
+        .expect("Failed to execute chmod");
+    let chmod = shell
+        .execute("chmod --reference=a b")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, None);
+    assert_eq!(mode_of(&mut shell, "b"), "-rw----r--");
+}
+
+#[test]
+fn test_chmod_errors() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let chmod = shell
+        .execute("chmod 755 /home")
+        .expect("Failed to execute chmod");
+    assert_eq!(
+        chmod.0,
+        Some("chmod: changing permissions of '/home': Operation not permitted".to_string())
+    );
+    let chmod = shell
+        .execute("chmod u+z missing")
+        .expect("Failed to execute chmod");
+    assert_eq!(chmod.0, Some("chmod: invalid mode: 'u+z'".to_string()));
+    let chmod = shell
+        .execute("chmod 755 missing")
+        .expect("Failed to execute chmod");
+    assert_eq!(
+        chmod.0,
+        Some("chmod: cannot access 'missing': No such file or directory".to_string())
+    );
+}
+
+#[test]
+fn test_permissions_are_enforced() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    shell
+        .execute("touch dir/f")
+        .expect("Failed to execute touch");
+
+    shell
+        .execute("chmod 300 dir")
+        .expect("Failed to execute chmod");
+    let ls = shell.execute("ls dir").expect("Failed to execute ls");
+    assert_eq!(
+        ls.0,
+        Some("ls: cannot open directory 'dir': Permission denied".to_string())
+    );
+
+    shell
+        .execute("chmod 600 dir")
+        .expect("Failed to execute chmod");
+    let cd = shell.execute("cd dir").expect("Failed to execute cd");
+    assert_eq!(cd.0, Some("cd: dir: Permission denied".to_string()));
+    let ls = shell.execute("ls dir/f").expect("Failed to execute ls");
+    assert_eq!(
+        ls.0,
+        Some("ls: cannot access 'dir/f': Permission denied".to_string())
+    );
+
+    shell
+        .execute("chmod 500 dir")
+        .expect("Failed to execute chmod");
+    let rm = shell.execute("rm dir/f").expect("Failed to execute rm");
+    assert_eq!(
+        rm.0,
+        Some("rm: cannot remove 'dir/f': Permission denied".to_string())
+    );
+}
@@ -0,0 +1,106 @@
+//! Tests for the chown and chgrp commands.
+
+use shelljougahara::Shell;
+
+/// Returns the owner and group columns of `ls -l` for a single file.
+fn ownership_of(shell: &mut Shell, path: &str) -> (String, String) {
+    let ls = shell
+        .execute(format!("ls -l {path}").as_str())
+        .expect("Failed to execute ls");
+    let ls = ls.0.expect("Missing ls output");
+    let mut columns = ls.split_whitespace().skip(2);
+    (
+        columns.next().expect("Missing owner column").to_string(),
+        columns.next().expect("Missing group column").to_string(),
+    )
+}
+
+#[test]
+fn test_chown_to_self() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch f").expect("Failed to execute touch");
+    let chown = shell
+        .execute("chown test:test f")
+        .expect("Failed to execute chown");
+    assert_eq!(chown.0, None);
+    assert_eq!(
+        ownership_of(&mut shell, "f"),
+        ("test".to_string(), "test".to_string())
+    );
+}
+
+#[test]
+fn test_chown_requires_root() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell
+        .fs
+        .lock()
+        .add_user("other")
+        .expect("Failed to add user");
+    shell.execute("touch f").expect("Failed to execute touch");
+    let chown = shell
+        .execute("chown other f")
+        .expect("Failed to execute chown");
+    assert_eq!(
+        chown.0,
+        Some("chown: changing ownership of 'f': Operation not permitted".to_string())
+    );
+    let chgrp = shell
+        .execute("chgrp other f")
+        .expect("Failed to execute chgrp");
+    assert_eq!(
+        chgrp.0,
+        Some("chgrp: changing group of 'f': Operation not permitted".to_string())
+    );
+    let chown = shell
+        .execute("chown :other f")
+        .expect("Failed to execute chown");
+    assert_eq!(
+        chown.0,
+        Some("chown: changing ownership of 'f': Operation not permitted".to_string())
+    );
+}
+
+#[test]
+fn test_chown_not_owner() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let chgrp = shell
+        .execute("chgrp -R test /home")
+        .expect("Failed to execute chgrp");
+    assert_eq!(
+        chgrp.0,
+        Some("chgrp: changing group of '/home': Operation not permitted".to_string())
+    );
+}
+
+#[test]
+fn test_chown_invalid_names() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch f").expect("Failed to execute touch");
+    let chown = shell
+        .execute("chown nobody f")
+        .expect("Failed to execute chown");
+    assert_eq!(chown.0, Some("chown: invalid user: 'nobody'".to_string()));
+    let chown = shell
+        .execute("chown test:nogroup f")
+        .expect("Failed to execute chown");
+    assert_eq!(
+        chown.0,
+        Some("chown: invalid group: 'test:nogroup'".to_string())
+    );
+    let chgrp = shell
+        .execute("chgrp nogroup f")
+        .expect("Failed to execute chgrp");
+    assert_eq!(chgrp.0, Some("chgrp: invalid group: 'nogroup'".to_string()));
+    let chown = shell
+        .execute("chown test missing")
+        .expect("Failed to execute chown");
+    assert_eq!(
+        chown.0,
+        Some("chown: cannot access 'missing': No such file or directory".to_string())
+    );
+}
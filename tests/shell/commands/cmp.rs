@@ -0,0 +1,57 @@
+//! Tests for the cmp command.
+
+use shelljougahara::Shell;
+
+use crate::{run, write_file};
+
+fn shell_with_files() -> Shell {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "a", "first\nsecond\n");
+    write_file(&mut shell, "b", "first\nsecund\n");
+    write_file(&mut shell, "prefix", "first\n");
+    write_file(&mut shell, "empty", "");
+    shell
+}
+
+#[test]
+fn test_cmp() {
+    let mut shell = shell_with_files();
+    assert_eq!(
+        run(&mut shell, "cmp a b"),
+        Some("a b differ: byte 10, line 2".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(run(&mut shell, "cmp a a"), None);
+    assert_eq!(shell.exit_status(), 0);
+    assert_eq!(run(&mut shell, "cmp -s a b"), None);
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_cmp_eof() {
+    let mut shell = shell_with_files();
+    assert_eq!(
+        run(&mut shell, "cmp a prefix"),
+        Some("cmp: EOF on prefix after byte 6, line 1".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "cmp empty a"),
+        Some("cmp: EOF on empty which is empty".to_string())
+    );
+}
+
+#[test]
+fn test_cmp_errors() {
+    let mut shell = shell_with_files();
+    assert_eq!(
+        run(&mut shell, "cmp a missing"),
+        Some("cmp: missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+    assert_eq!(
+        run(&mut shell, "cmp"),
+        Some("cmp: missing operand after 'cmp'".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+}
@@ -0,0 +1,71 @@
+//! Tests for the cut command.
+
+use shelljougahara::Shell;
+
+use crate::{run, write_file};
+
+#[test]
+fn test_cut_fields() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "cut -d : -f 1,3 /etc/passwd"),
+        Some("root:0\ntest:1000".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cut -d: -f6- /etc/passwd"),
+        Some("/root:/bin/sh\n/home/test:/bin/sh".to_string())
+    );
+    write_file(&mut shell, "table", "a\tb\tc\nno tabs\n");
+    assert_eq!(
+        run(&mut shell, "cut -f 2 table"),
+        Some("b\nno tabs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cut -s -f -2 table"),
+        Some("a\tb".to_string())
+    );
+}
+
+#[test]
+fn test_cut_characters() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "words", "héllo\nworld\n");
+    assert_eq!(
+        run(&mut shell, "cut -c 2-3 words"),
+        Some("él\nor".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cut -c 1,5 words"),
+        Some("ho\nwd".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cut -b 1,4- words"),
+        Some("hllo\nwld".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo abcdef | cut -c 4-"),
+        Some("def".to_string())
+    );
+}
+
+#[test]
+fn test_cut_errors() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "cut /etc/passwd"),
+        Some("cut: you must specify a list of bytes, characters, or fields".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "cut -d ab -f 1 /etc/passwd"),
+        Some("cut: the delimiter must be a single character".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cut -f 0 /etc/passwd"),
+        Some("cut: fields and positions are numbered from 1".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cut -f 1 missing"),
+        Some("cut: missing: No such file or directory".to_string())
+    );
+}
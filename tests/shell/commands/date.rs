@@ -0,0 +1,123 @@
+//! Tests for the date command and the clock of the file system.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use shelljougahara::{FixedClock, ManualClock, Shell};
+
+use crate::run;
+
+fn time(year: i32, month: u32, day: u32, hour: u32, minute: u32, second: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, second)
+        .single()
+        .expect("Invalid date")
+}
+
+/// Returns a shell whose clock is stopped on Saturday, October 18, 2025 at 14:03:27.
+fn shell_with_fixed_clock() -> Shell {
+    let shell = Shell::new_with_user("test");
+    shell
+        .fs
+        .lock()
+        .set_clock(Arc::new(FixedClock(time(2025, 10, 18, 14, 3, 27))));
+    shell
+}
+
+#[test]
+fn test_date_default_format() {
+    let mut shell = shell_with_fixed_clock();
+    assert_eq!(
+        run(&mut shell, "date"),
+        Some("Sat Oct 18 14:03:27 UTC 2025".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "date -u"),
+        Some("Sat Oct 18 14:03:27 UTC 2025".to_string())
+    );
+}
+
+#[test]
+fn test_date_format() {
+    let mut shell = shell_with_fixed_clock();
+    assert_eq!(
+        run(&mut shell, "date +%Y-%m-%d"),
+        Some("2025-10-18".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "date '+%H:%M:%S %s'"),
+        Some("14:03:27 1760796207".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "date +%s.%N"),
+        Some("1760796207.000000000".to_string())
+    );
+    assert_eq!(run(&mut shell, "date +100%%"), Some("100%".to_string()));
+    assert_eq!(
+        run(&mut shell, "date +%Q"),
+        Some("date: invalid format '+%Q'".to_string())
+    );
+}
+
+#[test]
+fn test_date_with_date() {
+    let mut shell = shell_with_fixed_clock();
+    assert_eq!(
+        run(&mut shell, "date -d @0 +%F"),
+        Some("1970-01-01".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "date -d yesterday +%F"),
+        Some("2025-10-17".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "date --date '2 hours ago' +%R"),
+        Some("12:03".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "date -d '2024-02-29 08:00'"),
+        Some("Thu Feb 29 08:00:00 UTC 2024".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "date -d soon"),
+        Some("date: invalid date 'soon'".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_date_invalid_operands() {
+    let mut shell = shell_with_fixed_clock();
+    assert_eq!(
+        run(&mut shell, "date 101814032025"),
+        Some("date: cannot set date: Operation not permitted".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "date +%F +%T"),
+        Some("date: extra operand '+%T'".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_manual_clock_stamps_files() {
+    let mut shell = Shell::new_with_user("test");
+    let start = time(2025, 1, 1, 9, 0, 0);
+    let clock = Arc::new(ManualClock::new(start));
+    shell.fs.lock().set_clock(clock.clone());
+
+    shell.execute("touch a").expect("Failed to execute touch");
+    clock.advance(Duration::hours(1));
+    shell.execute("touch b").expect("Failed to execute touch");
+
+    let stat = shell
+        .execute("stat -c '%n %Y %W' a b")
+        .expect("Failed to execute stat");
+    let start = start.timestamp();
+    let later = start + 3600;
+    assert_eq!(
+        stat.0,
+        Some(format!("a {start} {start}\nb {later} {later}"))
+    );
+    assert_eq!(run(&mut shell, "date +%T"), Some("10:00:00".to_string()));
+}
@@ -0,0 +1,39 @@
+//! Tests for the df command.
+
+use shelljougahara::Shell;
+
+use crate::{run, write_file};
+
+#[test]
+fn test_df() {
+    let mut shell = Shell::new_with_user("test");
+    shell.fs.lock().set_capacity(1024 * 1024 * 1024);
+    let used = shell.fs.lock().used_space() / 1024;
+    assert_eq!(
+        run(&mut shell, "df"),
+        Some(format!(
+            "Filesystem     1K-blocks     Used Available Use% Mounted on\n/dev/sda1        \
+             1048576 {used:>8} {:>9}   1% /",
+            1_048_576 - used
+        ))
+    );
+}
+
+#[test]
+fn test_df_human_readable() {
+    let mut shell = Shell::new_with_user("test");
+    shell.fs.lock().set_capacity(400 * 1024 * 1024);
+    write_file(&mut shell, "big", &"x".repeat(100 * 1024 * 1024));
+    let output = run(&mut shell, "df -h .").expect("df should print the disk");
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "Filesystem      Size  Used Avail Use% Mounted on");
+    assert_eq!(lines[1], "/dev/sda1       400M  101M  300M  26% /");
+}
+
+#[test]
+fn test_df_errors() {
+    let mut shell = Shell::new_with_user("test");
+    let output = run(&mut shell, "df missing").expect("df should print an error");
+    assert_eq!(output, "df: missing: No such file or directory");
+    assert_eq!(shell.exit_status(), 1);
+}
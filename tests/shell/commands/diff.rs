@@ -0,0 +1,165 @@
+//! Tests for the diff command.
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use shelljougahara::{Diff, FixedClock, QueuedInput, Shell};
+
+use crate::{run, write_file};
+
+/// Returns a shell with a `config` file and an `expected` file differing on two lines.
+fn shell_with_configs() -> Shell {
+    let mut shell = Shell::new_with_user("test");
+    let time = Utc
+        .with_ymd_and_hms(2025, 1, 1, 12, 0, 0)
+        .single()
+        .expect("Invalid time");
+    shell.fs.lock().set_clock(Arc::new(FixedClock(time)));
+    write_file(
+        &mut shell,
+        "config",
+        "host = localhost\nport = 8080\nlevel = debug\nworkers = 4\n",
+    );
+    write_file(
+        &mut shell,
+        "expected",
+        "host = 0.0.0.0\nport = 8080\nlevel = debug\nworkers = 4\ntimeout = 30\n",
+    );
+    shell
+}
+
+#[test]
+fn test_diff_normal() {
+    let mut shell = shell_with_configs();
+    assert_eq!(
+        run(&mut shell, "diff config expected"),
+        Some("1c1\n< host = localhost\n---\n> host = 0.0.0.0\n4a5\n> timeout = 30".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "diff expected config"),
+        Some("1c1\n< host = 0.0.0.0\n---\n> host = localhost\n5d4\n< timeout = 30".to_string())
+    );
+    assert_eq!(run(&mut shell, "diff config config"), None);
+    assert_eq!(shell.exit_status(), 0);
+}
+
+#[test]
+fn test_diff_unified() {
+    let mut shell = shell_with_configs();
+    assert_eq!(
+        run(&mut shell, "diff -u config expected"),
+        Some(
+            "--- config\t2025-01-01 12:00:00.000000000 +0000\n+++ expected\t2025-01-01 \
+             12:00:00.000000000 +0000\n@@ -1,4 +1,5 @@\n-host = localhost\n+host = 0.0.0.0\n \
+             port = 8080\n level = debug\n workers = 4\n+timeout = 30"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_diff_missing_newline() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "a", "line\n");
+    write_file(&mut shell, "b", "line");
+    assert_eq!(
+        run(&mut shell, "diff a b"),
+        Some("1c1\n< line\n---\n> line\n\\ No newline at end of file".to_string())
+    );
+}
+
+#[test]
+fn test_diff_brief() {
+    let mut shell = shell_with_configs();
+    assert_eq!(
+        run(&mut shell, "diff -q config expected"),
+        Some("Files config and expected differ".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(run(&mut shell, "diff -q config config"), None);
+}
+
+#[test]
+fn test_diff_stdin() {
+    let mut shell = shell_with_configs();
+    shell.set_stdin(QueuedInput::new([
+        "host = localhost",
+        "port = 8080",
+        "level = info",
+        "workers = 4",
+    ]));
+    assert_eq!(
+        run(&mut shell, "diff config -"),
+        Some("3c3\n< level = debug\n---\n> level = info".to_string())
+    );
+}
+
+#[test]
+fn test_diff_directories() {
+    let mut shell = Shell::new_with_user("test");
+    shell
+        .execute("mkdir old new old/sub new/sub old/gone new/added")
+        .expect("Failed to create directories");
+    write_file(&mut shell, "old/same", "a\n");
+    write_file(&mut shell, "new/same", "a\n");
+    write_file(&mut shell, "old/sub/file", "a\nb\n");
+    write_file(&mut shell, "new/sub/file", "a\nc\n");
+    write_file(&mut shell, "old/only", "x\n");
+    assert_eq!(
+        run(&mut shell, "diff old new"),
+        Some(
+            "Only in new: added\nOnly in old: gone\nOnly in old: only\nCommon subdirectories: \
+             old/sub and new/sub"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "diff -r old new"),
+        Some(
+            "Only in new: added\nOnly in old: gone\nOnly in old: only\ndiff -r old/sub/file \
+             new/sub/file\n2c2\n< b\n---\n> c"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "diff -rq old new"),
+        Some(
+            "Only in new: added\nOnly in old: gone\nOnly in old: only\nFiles old/sub/file and \
+             new/sub/file differ"
+                .to_string()
+        )
+    );
+    assert_eq!(run(&mut shell, "diff old/same new"), None);
+
+    assert_eq!(
+        run(&mut shell, "diff old/sub new/sub"),
+        Some("diff old/sub/file new/sub/file\n2c2\n< b\n---\n> c".to_string())
+    );
+    let output = run(&mut shell, "diff -u old/sub new/sub").expect("Expected a diff");
+    assert!(output.starts_with("diff -u old/sub/file new/sub/file\n--- old/sub/file\t"));
+}
+
+#[test]
+fn test_diff_errors() {
+    let mut shell = shell_with_configs();
+    assert_eq!(
+        run(&mut shell, "diff config missing"),
+        Some("diff: missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+    assert_eq!(
+        run(&mut shell, "diff config"),
+        Some("diff: missing operand after 'config'".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+}
+
+#[test]
+fn test_diff_engine() {
+    let diff = Diff::new("a\nb\n", "a\nc\n");
+    assert!(!diff.is_empty());
+    assert_eq!(diff.unified(0), ["@@ -2 +2 @@", "-b", "+c"]);
+}
@@ -0,0 +1,103 @@
+//! Tests for the du command.
+
+use shelljougahara::Shell;
+
+use crate::{run, shell_with_files};
+
+/// Returns a shell with a `project` directory holding a file of two blocks, one of a block and an
+/// empty one:
+///
+/// ```text
+/// project
+/// ├── docs
+/// │   └── readme
+/// ├── empty
+/// └── src
+///     └── main.rs
+/// ```
+fn shell_with_project() -> Shell {
+    shell_with_files(
+        &["project", "project/docs", "project/src"],
+        &[
+            ("project/docs/readme", "Read me\n"),
+            ("project/empty", ""),
+            ("project/src/main.rs", &"x".repeat(5000)),
+        ],
+    )
+}
+
+#[test]
+fn test_du() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "du project"),
+        Some("8\tproject/docs\n12\tproject/src\n24\tproject".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "du -a project"),
+        Some(
+            "4\tproject/docs/readme\n8\tproject/docs\n0\tproject/empty\n8\tproject/src/main.rs\n\
+             12\tproject/src\n24\tproject"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "du project/src/main.rs"),
+        Some("8\tproject/src/main.rs".to_string())
+    );
+}
+
+#[test]
+fn test_du_summarize_and_depth() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "du -s project project/docs"),
+        Some("24\tproject".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "du -sh project/src project/docs"),
+        Some("12K\tproject/src\n8.0K\tproject/docs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "du --max-depth=0 project"),
+        Some("24\tproject".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "du -h -d 1 project"),
+        Some("8.0K\tproject/docs\n12K\tproject/src\n24K\tproject".to_string())
+    );
+}
+
+#[test]
+fn test_du_hard_links() {
+    let mut shell = shell_with_project();
+    shell
+        .execute("ln project/src/main.rs project/copy")
+        .expect("Failed to execute ln");
+    assert_eq!(
+        run(&mut shell, "du -s project"),
+        Some("24\tproject".to_string())
+    );
+}
+
+#[test]
+fn test_du_errors() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "du missing project/docs"),
+        Some("du: cannot access 'missing': No such file or directory\n8\tproject/docs".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    shell
+        .execute("chmod 0 project/src")
+        .expect("Failed to execute chmod");
+    assert_eq!(
+        run(&mut shell, "du project"),
+        Some(
+            "8\tproject/docs\ndu: cannot read directory 'project/src': Permission denied\n4\t\
+             project/src\n16\tproject"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
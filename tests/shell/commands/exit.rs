@@ -1,4 +1,4 @@
-use shelljougahara::{Shell, ShellError};
+use shelljougahara::{QueuedInput, Shell, ShellError};
 
 #[test]
 fn test_exit() {
@@ -8,3 +8,20 @@ fn test_exit() {
     let try_output = shell.execute("echo hello");
     assert_eq!(try_output.unwrap_err(), ShellError::ShellNotActive);
 }
+
+#[test]
+fn test_exit_after_su() {
+    let mut shell = Shell::new_with_user("test");
+    {
+        let mut fs = shell.fs.lock();
+        let now = fs.now();
+        fs.update_user(0, |root| root.set_password("secret", now))
+            .expect("Failed to set password");
+    }
+    shell.set_stdin(QueuedInput::new(["secret"]));
+    shell.execute("su").expect("Failed to execute su");
+    shell.execute("exit").expect("Failed to execute exit");
+    assert!(shell.active);
+    shell.execute("exit").expect("Failed to execute exit");
+    assert!(!shell.active);
+}
@@ -0,0 +1,321 @@
+//! Tests for the find command.
+
+use std::sync::Arc;
+
+use chrono::{TimeZone, Utc};
+use shelljougahara::{FixedClock, Shell};
+
+use crate::{run, shell_with_files, write_file};
+
+/// Returns a shell in a project tree:
+///
+/// ```text
+/// project/
+/// ├── README.md       (12 bytes)
+/// ├── build/          (empty)
+/// ├── notes.TXT       (empty)
+/// └── src/
+///     ├── lib.rs      (2000 bytes)
+///     └── main.rs     (20 bytes)
+/// ```
+fn shell_with_project() -> Shell {
+    let mut shell = shell_with_files(
+        &["project", "project/build", "project/src"],
+        &[
+            ("project/README.md", "# Project\n\n\n"),
+            ("project/notes.TXT", ""),
+            ("project/src/lib.rs", &"x".repeat(2000)),
+            ("project/src/main.rs", "fn main() {}\n\n\n\n\n\n\n\n"),
+        ],
+    );
+    shell.execute("cd project").expect("Failed to execute cd");
+    shell
+}
+
+#[test]
+fn test_find_lists_tree() {
+    let mut shell = shell_with_project();
+    let tree = "./README.md\n./build\n./notes.TXT\n./src\n./src/lib.rs\n./src/main.rs";
+    assert_eq!(run(&mut shell, "find"), Some(format!(".\n{tree}")));
+    assert_eq!(run(&mut shell, "find ."), Some(format!(".\n{tree}")));
+    assert_eq!(
+        run(&mut shell, "find src ../project/build/"),
+        Some("src\nsrc/lib.rs\nsrc/main.rs\n../project/build/".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -maxdepth 1 -mindepth 1"),
+        Some("./README.md\n./build\n./notes.TXT\n./src".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find -mindepth 2"),
+        Some("./src/lib.rs\n./src/main.rs".to_string())
+    );
+}
+
+#[test]
+fn test_find_names_and_types() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "find . -name '*.rs'"),
+        Some("./src/lib.rs\n./src/main.rs".to_string())
+    );
+    assert_eq!(run(&mut shell, "find . -name '*.txt'"), None);
+    assert_eq!(
+        run(&mut shell, "find . -iname '*.txt'"),
+        Some("./notes.TXT".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -path './s*/m*'"),
+        Some("./src/main.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type d"),
+        Some(".\n./build\n./src".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find src -type f,d -name 'l*'"),
+        Some("src/lib.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -empty"),
+        Some("./build\n./notes.TXT".to_string())
+    );
+}
+
+#[test]
+fn test_find_operators() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "find . -name '*.md' -o -name '*.TXT'"),
+        Some("./README.md\n./notes.TXT".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f ! -name '*.rs'"),
+        Some("./README.md\n./notes.TXT".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "find . -not -type d -a \\( -name 'm*' -or -name 'R*' \\)"
+        ),
+        Some("./README.md\n./src/main.rs".to_string())
+    );
+    // The implicit `-print` applies to the whole expression, an explicit one only where it is.
+    assert_eq!(
+        run(&mut shell, "find . -name build -o -name src -print"),
+        Some("./src".to_string())
+    );
+}
+
+#[test]
+fn test_find_size() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "find . -type f -size +1"),
+        Some("./src/lib.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -size -1"),
+        Some("./notes.TXT".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -size 12c"),
+        Some("./README.md".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -size -20c"),
+        Some("./README.md\n./notes.TXT".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -size 2k"),
+        Some("./src/lib.rs".to_string())
+    );
+}
+
+#[test]
+fn test_find_modification_time() {
+    let mut shell = shell_with_project();
+    let now = Utc
+        .with_ymd_and_hms(2025, 6, 15, 12, 0, 0)
+        .single()
+        .expect("Invalid date");
+    shell.fs.lock().set_clock(Arc::new(FixedClock(now)));
+    for (file, date) in [
+        ("README.md", "2025-06-15 11:30"),
+        ("notes.TXT", "2025-06-13 12:00"),
+        ("src/lib.rs", "2025-05-01"),
+        ("src/main.rs", "2025-06-15 11:58"),
+    ] {
+        shell
+            .execute(&format!("touch -d '{date}' {file}"))
+            .expect("Failed to execute touch");
+    }
+    assert_eq!(
+        run(&mut shell, "find . -type f -mtime 0"),
+        Some("./README.md\n./src/main.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -mtime +1"),
+        Some("./notes.TXT\n./src/lib.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -mtime +2"),
+        Some("./src/lib.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -mtime 2"),
+        Some("./notes.TXT".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -mmin -5"),
+        Some("./src/main.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -mmin +5 -mmin -60"),
+        Some("./README.md".to_string())
+    );
+}
+
+#[test]
+fn test_find_owner_and_permissions() {
+    let mut shell = shell_with_project();
+    shell
+        .execute("chmod 755 src/main.rs")
+        .expect("Failed to execute chmod");
+    shell
+        .execute("chmod 4700 README.md")
+        .expect("Failed to execute chmod");
+    assert_eq!(
+        run(&mut shell, "find . -type f -perm 755"),
+        Some("./src/main.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -perm -u+x"),
+        Some("./README.md\n./src/main.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -perm /4000"),
+        Some("./README.md".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type f -perm /o=r"),
+        Some("./notes.TXT\n./src/lib.rs\n./src/main.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find src -user test -group 1000 -name m*"),
+        Some("src/main.rs".to_string())
+    );
+    assert_eq!(run(&mut shell, "find src -user root"), None);
+    assert_eq!(
+        run(&mut shell, "find src -user nobody"),
+        Some("find: 'nobody' is not the name of a known user".to_string())
+    );
+}
+
+#[test]
+fn test_find_print0() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "find src -type f -print0"),
+        Some("src/lib.rs\0src/main.rs\0".to_string())
+    );
+}
+
+#[test]
+fn test_find_exec() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, r"find src -type f -exec echo found {} \;"),
+        Some("found src/lib.rs\nfound src/main.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -name '*.rs' -exec echo {} +"),
+        Some("./src/lib.rs ./src/main.rs".to_string())
+    );
+    write_file(&mut shell, "src/lib.rs", "needle\n");
+    // The command is a test, true when it succeeds.
+    assert_eq!(
+        run(
+            &mut shell,
+            "find src -type f -exec grep -q needle {} ; -print"
+        ),
+        Some("src/lib.rs".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find src -exec echo {}"),
+        Some("find: missing argument to '-exec'".to_string())
+    );
+}
+
+#[test]
+fn test_find_delete() {
+    let mut shell = shell_with_project();
+    assert_eq!(run(&mut shell, "find . -name '*.rs' -delete"), None);
+    assert_eq!(run(&mut shell, "find src"), Some("src".to_string()));
+    // Directories are deleted after their entries, so emptied ones can be deleted too.
+    write_file(&mut shell, "build/out.o", "");
+    assert_eq!(run(&mut shell, "find build src -delete"), None);
+    assert_eq!(
+        run(&mut shell, "find ."),
+        Some(".\n./README.md\n./notes.TXT".to_string())
+    );
+    shell
+        .execute("mkdir docs")
+        .expect("Failed to execute mkdir");
+    write_file(&mut shell, "docs/keep.md", "");
+    assert_eq!(
+        run(&mut shell, "find docs -type d -delete"),
+        Some("find: cannot delete 'docs': Directory not empty".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_find_errors() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "find missing src/main.rs"),
+        Some("find: 'missing': No such file or directory\nsrc/main.rs".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "find . -bogus"),
+        Some("find: unknown predicate '-bogus'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -name"),
+        Some("find: missing argument to '-name'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -type x"),
+        Some("find: Unknown argument to -type: x".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -size 3q"),
+        Some("find: invalid argument '3q' to '-size'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . -name a src"),
+        Some("find: paths must precede expression: 'src'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "find . ( -name a"),
+        Some(
+            "find: invalid expression; I was expecting to find a ')' somewhere but did not see \
+             one."
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "find . -name a )"),
+        Some("find: invalid expression; you have too many ')'".to_string())
+    );
+
+    shell
+        .execute("chmod 000 src")
+        .expect("Failed to execute chmod");
+    assert_eq!(
+        run(&mut shell, "find src"),
+        Some("src\nfind: 'src': Permission denied".to_string())
+    );
+}
@@ -0,0 +1,94 @@
+//! Tests for the gpasswd command.
+
+use shelljougahara::Shell;
+
+use crate::root_shell;
+
+fn is_member(shell: &Shell, user: &str, group: &str) -> bool {
+    let group = shell
+        .fs
+        .lock()
+        .find_group_by_name(group)
+        .expect("Missing group")
+        .id;
+    shell
+        .fs
+        .lock()
+        .find_user_by_name(user)
+        .expect("Missing user")
+        .groups
+        .contains(&group)
+}
+
+#[test]
+fn test_gpasswd_add_and_delete() {
+    let mut shell = root_shell();
+    shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("useradd bob")
+        .expect("Failed to execute useradd");
+
+    let gpasswd = shell
+        .execute("gpasswd -a bob dev")
+        .expect("Failed to execute gpasswd");
+    assert_eq!(gpasswd.0, Some("Adding user bob to group dev".to_string()));
+    assert!(is_member(&shell, "bob", "dev"));
+
+    let gpasswd = shell
+        .execute("gpasswd -d bob dev")
+        .expect("Failed to execute gpasswd");
+    assert_eq!(
+        gpasswd.0,
+        Some("Removing user bob from group dev".to_string())
+    );
+    assert!(!is_member(&shell, "bob", "dev"));
+
+    let gpasswd = shell
+        .execute("gpasswd -d bob dev")
+        .expect("Failed to execute gpasswd");
+    assert_eq!(
+        gpasswd.0,
+        Some("gpasswd: user 'bob' is not a member of 'dev'".to_string())
+    );
+}
+
+#[test]
+fn test_gpasswd_members() {
+    let mut shell = root_shell();
+    shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("useradd -G dev bob")
+        .expect("Failed to execute useradd");
+    shell
+        .execute("useradd carol")
+        .expect("Failed to execute useradd");
+    shell
+        .execute("gpasswd -M carol,admin dev")
+        .expect("Failed to execute gpasswd");
+    assert!(!is_member(&shell, "bob", "dev"));
+    assert!(is_member(&shell, "carol", "dev"));
+    assert!(is_member(&shell, "admin", "dev"));
+}
+
+#[test]
+fn test_gpasswd_errors() {
+    let mut shell = root_shell();
+    let gpasswd = shell
+        .execute("gpasswd -a admin dev")
+        .expect("Failed to execute gpasswd");
+    assert_eq!(
+        gpasswd.0,
+        Some("gpasswd: group 'dev' does not exist in /etc/group".to_string())
+    );
+    let gpasswd = shell
+        .execute("gpasswd -a bob admin")
+        .expect("Failed to execute gpasswd");
+    assert_eq!(
+        gpasswd.0,
+        Some("gpasswd: user 'bob' does not exist".to_string())
+    );
+}
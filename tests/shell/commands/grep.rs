@@ -0,0 +1,299 @@
+//! Tests for the grep command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::{run, shell_with_files, write_file};
+
+const LOG: &str = "\
+INFO starting server
+DEBUG loading config
+WARN disk almost full
+ERROR connection refused
+INFO retrying
+ERROR timeout after 30s
+INFO stopped
+";
+
+/// Returns a shell with `app.log` in the home directory.
+fn shell_with_log() -> Shell {
+    shell_with_files(&[], &[("app.log", LOG)])
+}
+
+/// Returns a shell with a tree of logs, a needle being hidden in `logs/2024/02/app.log`.
+fn shell_with_log_tree() -> Shell {
+    shell_with_files(
+        &["logs", "logs/2024", "logs/2024/01", "logs/2024/02"],
+        &[
+            ("logs/2024/01/app.log", "ok\nok\n"),
+            ("logs/2024/01/db.txt", "needle in text\n"),
+            ("logs/2024/02/app.log", "ok\nfound the needle\nok\n"),
+            ("logs/readme", "no match here\n"),
+        ],
+    )
+}
+
+#[test]
+fn test_grep_basic() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(&mut shell, "grep ERROR app.log"),
+        Some("ERROR connection refused\nERROR timeout after 30s".to_string())
+    );
+    assert_eq!(shell.exit_status(), 0);
+    assert_eq!(run(&mut shell, "grep FATAL app.log"), None);
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "grep -n -i 'info s' app.log"),
+        Some("1:INFO starting server\n7:INFO stopped".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -c INFO app.log"),
+        Some("3".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -vc INFO app.log"),
+        Some("4".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -o '[0-9]\\+s' app.log"),
+        Some("30s".to_string())
+    );
+}
+
+#[test]
+fn test_grep_regex_syntaxes() {
+    let mut shell = shell_with_log();
+    // In basic expressions, `|` and `+` are literal unless escaped.
+    assert_eq!(run(&mut shell, "grep 'WARN|DEBUG' app.log"), None);
+    assert_eq!(
+        run(&mut shell, "grep 'WARN\\|DEBUG' app.log"),
+        Some("DEBUG loading config\nWARN disk almost full".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -E '^(WARN|DEBUG) [a-z]{4,} ' app.log"),
+        Some("DEBUG loading config\nWARN disk almost full".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -E 'p{2}' app.log"),
+        Some("INFO stopped".to_string())
+    );
+    // Fixed strings have no special character.
+    assert_eq!(run(&mut shell, "grep -F 'r.*' app.log"), None);
+    assert_eq!(
+        run(&mut shell, "grep -c 'r.*' app.log"),
+        Some("4".to_string())
+    );
+    assert_eq!(run(&mut shell, "grep -e -x -F app.log"), None);
+    assert_eq!(
+        run(&mut shell, "grep '[[:digit:]]' app.log"),
+        Some("ERROR timeout after 30s".to_string())
+    );
+}
+
+#[test]
+fn test_grep_words_and_lines() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "words", "cat\ncatalog\nthe cat sat\nbobcat\n");
+    assert_eq!(
+        run(&mut shell, "grep -w cat words"),
+        Some("cat\nthe cat sat".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -x cat words"),
+        Some("cat".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -xv 'cat.*' words"),
+        Some("the cat sat\nbobcat".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -ow 'cat\\|sat' words"),
+        Some("cat\ncat\nsat".to_string())
+    );
+}
+
+#[test]
+fn test_grep_context() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(&mut shell, "grep -n -A 1 WARN app.log"),
+        Some("3:WARN disk almost full\n4-ERROR connection refused".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -B1 retrying app.log"),
+        Some("ERROR connection refused\nINFO retrying".to_string())
+    );
+    // Groups that are not contiguous are separated.
+    assert_eq!(
+        run(&mut shell, "grep -n -C1 'DEBUG\\|stopped' app.log"),
+        Some(
+            "1-INFO starting server\n2:DEBUG loading config\n3-WARN disk almost full\n--\n\
+             6-ERROR timeout after 30s\n7:INFO stopped"
+                .to_string()
+        )
+    );
+    // Overlapping context is printed once.
+    assert_eq!(
+        run(&mut shell, "grep -C 1 ERROR app.log"),
+        Some(
+            "WARN disk almost full\nERROR connection refused\nINFO retrying\n\
+             ERROR timeout after 30s\nINFO stopped"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "grep -A -1 ERROR app.log"),
+        Some("grep: -1: invalid context length argument".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+}
+
+#[test]
+fn test_grep_multiple_files() {
+    let mut shell = shell_with_log();
+    write_file(&mut shell, "other.log", "ERROR disk failure\n");
+    assert_eq!(
+        run(&mut shell, "grep 'timeout\\|failure' app.log other.log"),
+        Some("app.log:ERROR timeout after 30s\nother.log:ERROR disk failure".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -h failure app.log other.log"),
+        Some("ERROR disk failure".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -c ERROR app.log other.log"),
+        Some("app.log:2\nother.log:1".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -l refused app.log other.log"),
+        Some("app.log".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -L refused app.log other.log"),
+        Some("other.log".to_string())
+    );
+    assert_eq!(shell.exit_status(), 0);
+    assert_eq!(run(&mut shell, "grep -L INFO app.log"), None);
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "grep -H stopped app.log"),
+        Some("app.log:INFO stopped".to_string())
+    );
+}
+
+#[test]
+fn test_grep_recursive() {
+    let mut shell = shell_with_log_tree();
+    assert_eq!(
+        run(&mut shell, "grep -r needle logs"),
+        Some(
+            "logs/2024/01/db.txt:needle in text\nlogs/2024/02/app.log:found the needle".to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "grep -rn --include=*.log needle logs/"),
+        Some("logs/2024/02/app.log:2:found the needle".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -rl --exclude *.log needle logs"),
+        Some("logs/2024/01/db.txt".to_string())
+    );
+    shell.execute("cd logs/2024").expect("Failed to execute cd");
+    assert_eq!(
+        run(&mut shell, "grep -R -c needle"),
+        Some("01/app.log:0\n01/db.txt:1\n02/app.log:1".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep needle 01"),
+        Some("grep: 01: Is a directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+}
+
+#[test]
+fn test_grep_repeated_options() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(&mut shell, "grep -n -e WARN -e timeout app.log"),
+        Some("3:WARN disk almost full\n6:ERROR timeout after 30s".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep -c -i -e debug -e stopped app.log"),
+        Some("2".to_string())
+    );
+
+    let mut shell = shell_with_log_tree();
+    assert_eq!(
+        run(
+            &mut shell,
+            "grep -rl --include *.log --include *.txt needle logs"
+        ),
+        Some("logs/2024/01/db.txt\nlogs/2024/02/app.log".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "grep -rl --exclude *.log --exclude *.txt o logs"
+        ),
+        Some("logs/readme".to_string())
+    );
+}
+
+#[test]
+fn test_grep_standard_input() {
+    let mut shell = Shell::new_with_user("test");
+    shell.set_stdin(QueuedInput::new(["one", "two", "three"]));
+    assert_eq!(
+        run(&mut shell, "grep -n t"),
+        Some("2:two\n3:three".to_string())
+    );
+    write_file(&mut shell, "file", "two\n");
+    shell.set_stdin(QueuedInput::new(["two"]));
+    assert_eq!(
+        run(&mut shell, "grep two - file"),
+        Some("(standard input):two\nfile:two".to_string())
+    );
+}
+
+#[test]
+fn test_grep_errors() {
+    let mut shell = shell_with_log();
+    assert_eq!(
+        run(&mut shell, "grep"),
+        Some("Usage: grep [OPTION]... PATTERNS [FILE]...".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+    assert_eq!(
+        run(&mut shell, "grep ERROR missing app.log"),
+        Some(
+            "grep: missing: No such file or directory\napp.log:ERROR connection refused\n\
+             app.log:ERROR timeout after 30s"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 2);
+    assert_eq!(run(&mut shell, "grep -s ERROR missing"), None);
+    assert_eq!(shell.exit_status(), 2);
+    // A quiet match succeeds despite the errors, which are still reported.
+    assert_eq!(
+        run(&mut shell, "grep -q ERROR missing app.log"),
+        Some("grep: missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 0);
+    assert_eq!(
+        run(&mut shell, "grep -E '(' app.log"),
+        Some("grep: Invalid regular expression".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "grep '[a' app.log"),
+        Some("grep: Unmatched [, [^, [:, [., or [=".to_string())
+    );
+
+    shell
+        .execute("chmod 200 app.log")
+        .expect("Failed to execute chmod");
+    assert_eq!(
+        run(&mut shell, "grep ERROR app.log"),
+        Some("grep: app.log: Permission denied".to_string())
+    );
+}
@@ -0,0 +1,108 @@
+//! Tests for the groupadd and groupdel commands.
+
+use shelljougahara::Shell;
+
+use crate::root_shell;
+
+#[test]
+fn test_groupadd() {
+    let mut shell = root_shell();
+    let groupadd = shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    assert_eq!(groupadd.0, None);
+    assert!(shell.fs.lock().find_group_by_name("dev").is_some());
+
+    let groupadd = shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    assert_eq!(
+        groupadd.0,
+        Some("groupadd: group 'dev' already exists".to_string())
+    );
+    let groupadd = shell
+        .execute("groupadd Dev")
+        .expect("Failed to execute groupadd");
+    assert_eq!(
+        groupadd.0,
+        Some("groupadd: 'Dev' is not a valid group name".to_string())
+    );
+}
+
+#[test]
+fn test_groupdel() {
+    let mut shell = root_shell();
+    shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("useradd -G dev bob")
+        .expect("Failed to execute useradd");
+    let groupdel = shell
+        .execute("groupdel dev")
+        .expect("Failed to execute groupdel");
+    assert_eq!(groupdel.0, None);
+    assert!(shell.fs.lock().find_group_by_name("dev").is_none());
+    let bob = shell
+        .fs
+        .lock()
+        .find_user_by_name("bob")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(bob.groups.len(), 1);
+
+    let groupdel = shell
+        .execute("groupdel bob")
+        .expect("Failed to execute groupdel");
+    assert_eq!(
+        groupdel.0,
+        Some("groupdel: cannot remove the primary group of user 'bob'".to_string())
+    );
+    let groupdel = shell
+        .execute("groupdel dev")
+        .expect("Failed to execute groupdel");
+    assert_eq!(
+        groupdel.0,
+        Some("groupdel: group 'dev' does not exist".to_string())
+    );
+}
+
+#[test]
+fn test_group_commands_require_root() {
+    let mut shell = Shell::new_with_user("test");
+    let groupadd = shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    assert_eq!(groupadd.0, Some("groupadd: Permission denied.".to_string()));
+    let groupdel = shell
+        .execute("groupdel test")
+        .expect("Failed to execute groupdel");
+    assert_eq!(groupdel.0, Some("groupdel: Permission denied.".to_string()));
+}
+
+#[test]
+fn test_groupadd_id_ranges() {
+    let mut shell = root_shell();
+    shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("groupadd -r daemon")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("groupadd --system adm")
+        .expect("Failed to execute groupadd");
+
+    let id = |shell: &Shell, name: &str| {
+        shell
+            .fs
+            .lock()
+            .find_group_by_name(name)
+            .expect("Missing group")
+            .id
+    };
+    assert_eq!(id(&shell, "admin"), 1000);
+    assert_eq!(id(&shell, "dev"), 1001);
+    assert_eq!(id(&shell, "daemon"), 999);
+    assert_eq!(id(&shell, "adm"), 998);
+}
@@ -0,0 +1,33 @@
+//! Tests for the groups command.
+
+use shelljougahara::Shell;
+
+#[test]
+fn test_groups() {
+    let mut shell = Shell::new_with_user("alice");
+    let alice = shell.current_session.current_user();
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("gpasswd -a alice dev")
+        .expect("Failed to execute gpasswd");
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), alice)
+        .expect("Failed to change user");
+
+    let groups = shell.execute("groups").expect("Failed to execute groups");
+    assert_eq!(groups.0, Some("alice dev".to_string()));
+    let groups = shell
+        .execute("groups root alice bob")
+        .expect("Failed to execute groups");
+    assert_eq!(
+        groups.0,
+        Some("root : root\nalice : alice dev\ngroups: 'bob': no such user".to_string())
+    );
+}
@@ -0,0 +1,85 @@
+//! Tests for the head command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::{run, write_file};
+
+fn numbers(count: usize) -> String {
+    (1..=count).map(|number| format!("{number}\n")).collect()
+}
+
+#[test]
+fn test_head_lines() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "numbers", &numbers(12));
+    assert_eq!(
+        run(&mut shell, "head numbers"),
+        Some("1\n2\n3\n4\n5\n6\n7\n8\n9\n10".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "head -n 3 numbers"),
+        Some("1\n2\n3".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "head -n2 numbers"),
+        Some("1\n2".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "head -n -9 numbers"),
+        Some("1\n2\n3".to_string())
+    );
+    assert_eq!(run(&mut shell, "head -n 0 numbers"), None);
+}
+
+#[test]
+fn test_head_bytes() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "word", "abcdef\n");
+    assert_eq!(run(&mut shell, "head -c 3 word"), Some("abc".to_string()));
+    assert_eq!(run(&mut shell, "head -c -3 word"), Some("abcd".to_string()));
+}
+
+#[test]
+fn test_head_several_files() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "a", "a1\na2\n");
+    write_file(&mut shell, "b", "b1\n");
+    assert_eq!(
+        run(&mut shell, "head -n 1 a b"),
+        Some("==> a <==\na1\n\n==> b <==\nb1".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "head -q -n 1 a b"),
+        Some("a1\nb1".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "head -v a"),
+        Some("==> a <==\na1\na2".to_string())
+    );
+}
+
+#[test]
+fn test_head_standard_input() {
+    let mut shell = Shell::new_with_user("test");
+    shell.set_stdin(QueuedInput::new(["one", "two", "three"]));
+    assert_eq!(run(&mut shell, "head -n 2"), Some("one\ntwo".to_string()));
+    write_file(&mut shell, "numbers", &numbers(5));
+    assert_eq!(
+        run(&mut shell, "cat numbers | head -n 1"),
+        Some("1".to_string())
+    );
+}
+
+#[test]
+fn test_head_errors() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "head -n x missing"),
+        Some("head: invalid number of lines: 'x'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "head missing"),
+        Some("head: cannot open 'missing' for reading: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
@@ -0,0 +1,66 @@
+//! Tests for the id command.
+
+use shelljougahara::Shell;
+
+/// Returns a shell for alice, who is also in the `dev` group.
+fn shell_with_groups() -> Shell {
+    let mut shell = Shell::new_with_user("alice");
+    let alice = shell.current_session.current_user();
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("usermod -aG dev alice")
+        .expect("Failed to execute usermod");
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), alice)
+        .expect("Failed to change user");
+    shell
+}
+
+#[test]
+fn test_id_default_format() {
+    let mut shell = shell_with_groups();
+    let id = shell.execute("id").expect("Failed to execute id");
+    assert_eq!(
+        id.0,
+        Some("uid=1000(alice) gid=1000(alice) groups=1000(alice),1001(dev)".to_string())
+    );
+    let id = shell.execute("id root").expect("Failed to execute id");
+    assert_eq!(
+        id.0,
+        Some("uid=0(root) gid=0(root) groups=0(root)".to_string())
+    );
+}
+
+#[test]
+fn test_id_selected_identity() {
+    let mut shell = shell_with_groups();
+    let id = shell.execute("id -u").expect("Failed to execute id");
+    assert_eq!(id.0, Some("1000".to_string()));
+    let id = shell.execute("id -un").expect("Failed to execute id");
+    assert_eq!(id.0, Some("alice".to_string()));
+    let id = shell.execute("id -g root").expect("Failed to execute id");
+    assert_eq!(id.0, Some("0".to_string()));
+    let id = shell.execute("id -G").expect("Failed to execute id");
+    assert_eq!(id.0, Some("1000 1001".to_string()));
+    let id = shell.execute("id -Gn").expect("Failed to execute id");
+    assert_eq!(id.0, Some("alice dev".to_string()));
+}
+
+#[test]
+fn test_id_errors() {
+    let mut shell = shell_with_groups();
+    let id = shell.execute("id bob").expect("Failed to execute id");
+    assert_eq!(id.0, Some("id: 'bob': no such user".to_string()));
+    let id = shell.execute("id -n").expect("Failed to execute id");
+    assert_eq!(
+        id.0,
+        Some("id: cannot print only names or real IDs in default format".to_string())
+    );
+}
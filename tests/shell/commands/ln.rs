@@ -0,0 +1,103 @@
+//! Tests for the ln command.
+
+use shelljougahara::Shell;
+
+/// Returns the link count column of a single `ls -l` line.
+fn link_count(line: &str) -> String {
+    line.split_whitespace()
+        .nth(1)
+        .expect("Missing link count column")
+        .to_string()
+}
+
+#[test]
+fn test_ln_creates_hard_link() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch a").expect("Failed to execute touch");
+    let ln = shell.execute("ln a b").expect("Failed to execute ln");
+    assert_eq!(ln.0, None);
+    let ls = shell.execute("ls").expect("Failed to execute ls");
+    assert_eq!(ls.0, Some("a  b".to_string()));
+    let ls = shell.execute("ls -l a").expect("Failed to execute ls");
+    assert_eq!(link_count(&ls.0.expect("Missing ls output")), "2");
+}
+
+#[test]
+fn test_rm_keeps_other_links() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch a").expect("Failed to execute touch");
+    shell.execute("ln a b").expect("Failed to execute ln");
+    shell.execute("rm a").expect("Failed to execute rm");
+    let ls = shell.execute("ls -l b").expect("Failed to execute ls");
+    assert_eq!(link_count(&ls.0.expect("Missing ls output")), "1");
+}
+
+#[test]
+fn test_ln_into_directory() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch a").expect("Failed to execute touch");
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    let ln = shell.execute("ln a dir").expect("Failed to execute ln");
+    assert_eq!(ln.0, None);
+    let ls = shell.execute("ls dir").expect("Failed to execute ls");
+    assert_eq!(ls.0, Some("a".to_string()));
+}
+
+#[test]
+fn test_ln_errors() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch a b").expect("Failed to execute touch");
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    let ln = shell.execute("ln a b").expect("Failed to execute ln");
+    assert_eq!(
+        ln.0,
+        Some("ln: failed to create hard link 'b': File exists".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    let ln = shell.execute("ln -f a b").expect("Failed to execute ln");
+    assert_eq!(ln.0, None);
+    assert_eq!(shell.exit_status(), 0);
+    let ln = shell.execute("ln dir other").expect("Failed to execute ln");
+    assert_eq!(
+        ln.0,
+        Some("ln: dir: hard link not allowed for directory".to_string())
+    );
+    let ln = shell.execute("ln missing c").expect("Failed to execute ln");
+    assert_eq!(
+        ln.0,
+        Some("ln: failed to access 'missing': No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    let ln = shell.execute("ln a b c").expect("Failed to execute ln");
+    assert_eq!(ln.0, Some("ln: target 'c': Not a directory".to_string()));
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_directory_link_count() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    shell
+        .execute("mkdir dir/a")
+        .expect("Failed to execute mkdir");
+    shell
+        .execute("mkdir dir/b")
+        .expect("Failed to execute mkdir");
+    shell
+        .execute("touch dir/file")
+        .expect("Failed to execute touch");
+    let ls = shell.execute("ls -la dir").expect("Failed to execute ls");
+    let ls = ls.0.expect("Missing ls output");
+    let mut lines = ls.lines();
+    let dot = lines.next().expect("Missing . entry");
+    assert!(dot.ends_with(" ."));
+    assert_eq!(link_count(dot), "4");
+    let a = lines.nth(1).expect("Missing a entry");
+    assert!(a.ends_with(" a"));
+    assert_eq!(link_count(a), "2");
+}
@@ -106,3 +106,57 @@ fn test_ls_a_flags() {
     let ls_a = shell.execute("ls -a").expect("Failed to execute ls");
     assert_eq!(ls_a.0, Some(".  ..  .hidden  test".to_string()));
 }
+
+#[test]
+fn test_ls_root_skeleton() {
+    let mut shell = Shell::new_with_user("test");
+    let ls = shell.execute("ls /").expect("Failed to execute ls");
+    assert_eq!(
+        ls.0,
+        Some("bin  dev  etc  home  proc  root  tmp  usr  var".to_string())
+    );
+
+    let ls = shell.execute("ls -l /").expect("Failed to execute ls");
+    let ls = ls.0.expect("Missing ls output");
+    let entry = |name: &str| {
+        ls.lines()
+            .find(|line| line.ends_with(&format!(" {name}")))
+            .unwrap_or_else(|| panic!("Missing {name}"))
+            .to_string()
+    };
+    assert!(entry("tmp").starts_with("drwxrwxrwt"));
+    assert!(entry("root").starts_with("drwx------"));
+    assert!(entry("bin").starts_with("drwxr-xr-x"));
+    assert!(ls.lines().skip(1).all(|line| line.contains(" root root ")));
+
+    let ls = shell.execute("ls /var").expect("Failed to execute ls");
+    assert_eq!(ls.0, Some("log".to_string()));
+    let ls = shell.execute("ls /root").expect("Failed to execute ls");
+    assert_eq!(
+        ls.0,
+        Some("ls: cannot open directory '/root': Permission denied".to_string())
+    );
+}
+
+#[test]
+fn test_ls_inode_numbers() {
+    let mut shell = Shell::new_with_user("test");
+    shell.execute("touch a").expect("Failed to execute touch");
+    shell.execute("ln a b").expect("Failed to execute ln");
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    let ls = shell.execute("ls -i").expect("Failed to execute ls");
+    let ls = ls.0.expect("Missing ls output");
+    let entries = ls
+        .split("  ")
+        .map(|entry| entry.split_once(' ').expect("Missing inode number"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries.iter().map(|(_, name)| *name).collect::<Vec<_>>(),
+        ["a", "b", "dir"]
+    );
+    assert_eq!(entries[0].0, entries[1].0);
+    assert_ne!(entries[0].0, entries[2].0);
+
+    let ls = shell.execute("ls -ia /").expect("Failed to execute ls");
+    assert!(ls.0.expect("Missing ls output").starts_with("2 .  2 ..  "));
+}
@@ -85,3 +85,16 @@ fn test_mkdir_long_path() {
     let pwd = shell.execute("pwd").expect("Failed to execute pwd");
     assert_eq!(pwd.0, Some(format!("/home/{username}/test/test2")));
 }
+
+#[test]
+fn test_mkdir_in_unwritable_directory() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let mkdir = shell
+        .execute("mkdir /home/other")
+        .expect("Failed to execute mkdir");
+    assert_eq!(
+        mkdir.0,
+        Some("mkdir: cannot create directory '/home/other': Permission denied".to_string())
+    );
+}
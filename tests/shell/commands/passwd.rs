@@ -0,0 +1,176 @@
+//! Tests for the passwd command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+/// Returns a shell for alice, whose password is "old-password".
+fn shell_with_password() -> Shell {
+    let shell = Shell::new_with_user("alice");
+    let alice = shell.current_session.current_user();
+    {
+        let mut fs = shell.fs.lock();
+        let now = fs.now();
+        fs.update_user(alice, |user| user.set_password("old-password", now))
+            .expect("Failed to set password");
+    }
+    shell
+}
+
+fn status(shell: &mut Shell, username: &str) -> String {
+    let passwd = shell
+        .execute(&format!("passwd -S {username}"))
+        .expect("Failed to execute passwd");
+    passwd.0.expect("Missing passwd output")
+}
+
+#[test]
+fn test_passwd_changes_own_password() {
+    let mut shell = shell_with_password();
+    shell.set_stdin(QueuedInput::new([
+        "old-password",
+        "new-password",
+        "new-password",
+    ]));
+    let passwd = shell.execute("passwd").expect("Failed to execute passwd");
+    assert_eq!(
+        passwd.0,
+        Some("passwd: password updated successfully".to_string())
+    );
+    let alice = shell
+        .fs
+        .lock()
+        .find_user_by_name("alice")
+        .expect("Missing user")
+        .clone();
+    assert!(alice.verify_password("new-password"));
+    assert!(!alice.verify_password("old-password"));
+    assert!(status(&mut shell, "alice").starts_with("alice P "));
+}
+
+#[test]
+fn test_passwd_rejects_wrong_input() {
+    let mut shell = shell_with_password();
+    shell.set_stdin(QueuedInput::new(["wrong-password"]));
+    let passwd = shell.execute("passwd").expect("Failed to execute passwd");
+    assert_eq!(
+        passwd.0,
+        Some(
+            "passwd: Authentication token manipulation error\npasswd: password unchanged"
+                .to_string()
+        )
+    );
+
+    shell.set_stdin(QueuedInput::new(["old-password", "one", "two"]));
+    let passwd = shell.execute("passwd").expect("Failed to execute passwd");
+    assert_eq!(
+        passwd.0,
+        Some(
+            "Sorry, passwords do not match.\n\
+             passwd: Authentication token manipulation error\npasswd: password unchanged"
+                .to_string()
+        )
+    );
+    let alice = shell
+        .fs
+        .lock()
+        .find_user_by_name("alice")
+        .expect("Missing user")
+        .clone();
+    assert!(alice.verify_password("old-password"));
+}
+
+#[test]
+fn test_passwd_other_users() {
+    let mut shell = shell_with_password();
+    shell.fs.lock().add_user("bob").expect("Failed to add user");
+    let passwd = shell
+        .execute("passwd bob")
+        .expect("Failed to execute passwd");
+    assert_eq!(
+        passwd.0,
+        Some("passwd: You may not view or modify password information for bob.".to_string())
+    );
+    let passwd = shell
+        .execute("passwd -l alice")
+        .expect("Failed to execute passwd");
+    assert_eq!(passwd.0, Some("passwd: Permission denied.".to_string()));
+
+    // Root sets passwords without knowing the current one.
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    assert_eq!(status(&mut shell, "bob"), "bob L never 0 99999 7 -1");
+    shell.set_stdin(QueuedInput::new(["bob-password", "bob-password"]));
+    let passwd = shell
+        .execute("passwd bob")
+        .expect("Failed to execute passwd");
+    assert_eq!(
+        passwd.0,
+        Some("passwd: password updated successfully".to_string())
+    );
+    let shadow = shell
+        .execute("cat /etc/shadow")
+        .expect("Failed to execute cat");
+    let shadow = shadow.0.expect("Missing cat output");
+    let bob = shadow
+        .lines()
+        .find(|line| line.starts_with("bob:"))
+        .expect("Missing shadow entry");
+    assert!(bob.starts_with("bob:$6$"));
+}
+
+#[test]
+fn test_passwd_lock_unlock_delete() {
+    let mut shell = shell_with_password();
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+
+    let passwd = shell
+        .execute("passwd -l alice")
+        .expect("Failed to execute passwd");
+    assert_eq!(
+        passwd.0,
+        Some("passwd: password expiry information changed.".to_string())
+    );
+    assert!(status(&mut shell, "alice").starts_with("alice L "));
+    let alice = shell
+        .fs
+        .lock()
+        .find_user_by_name("alice")
+        .expect("Missing user")
+        .clone();
+    assert!(!alice.verify_password("old-password"));
+
+    let passwd = shell
+        .execute("passwd -u alice")
+        .expect("Failed to execute passwd");
+    assert_eq!(
+        passwd.0,
+        Some("passwd: password expiry information changed.".to_string())
+    );
+    assert!(status(&mut shell, "alice").starts_with("alice P "));
+    let alice = shell
+        .fs
+        .lock()
+        .find_user_by_name("alice")
+        .expect("Missing user")
+        .clone();
+    assert!(alice.verify_password("old-password"));
+
+    let passwd = shell
+        .execute("passwd -d alice")
+        .expect("Failed to execute passwd");
+    assert_eq!(passwd.0, Some("passwd: password changed.".to_string()));
+    assert!(status(&mut shell, "alice").starts_with("alice NP "));
+    let passwd = shell
+        .execute("passwd -u alice")
+        .expect("Failed to execute passwd");
+    assert!(
+        passwd
+            .0
+            .expect("Missing passwd output")
+            .starts_with("passwd: unlocking the password would result in a passwordless account.")
+    );
+}
@@ -0,0 +1,63 @@
+//! Tests for the paste command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::{run, write_file};
+
+fn shell_with_columns() -> Shell {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "names", "alice\nbob\ncarol\n");
+    write_file(&mut shell, "ages", "30\n25\n");
+    shell
+}
+
+#[test]
+fn test_paste_columns() {
+    let mut shell = shell_with_columns();
+    assert_eq!(
+        run(&mut shell, "paste names ages"),
+        Some("alice\t30\nbob\t25\ncarol\t".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "paste -d , names ages"),
+        Some("alice,30\nbob,25\ncarol,".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "paste -d ':-' names ages names"),
+        Some("alice:30-alice\nbob:25-bob\ncarol:-carol".to_string())
+    );
+}
+
+#[test]
+fn test_paste_serial() {
+    let mut shell = shell_with_columns();
+    assert_eq!(
+        run(&mut shell, "paste -s names ages"),
+        Some("alice\tbob\tcarol\n30\t25".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "paste -s -d , names"),
+        Some("alice,bob,carol".to_string())
+    );
+}
+
+#[test]
+fn test_paste_standard_input() {
+    let mut shell = shell_with_columns();
+    shell.set_stdin(QueuedInput::new(["a", "b", "c", "d"]));
+    assert_eq!(run(&mut shell, "paste - -"), Some("a\tb\nc\td".to_string()));
+    assert_eq!(
+        run(&mut shell, "cat names | paste -s -d ' ' -"),
+        Some("alice bob carol".to_string())
+    );
+}
+
+#[test]
+fn test_paste_errors() {
+    let mut shell = shell_with_columns();
+    assert_eq!(
+        run(&mut shell, "paste names missing"),
+        Some("paste: missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
@@ -0,0 +1,210 @@
+//! Tests for the patch command.
+
+use shelljougahara::Shell;
+
+use crate::{run, shell_with_files, write_file};
+
+/// A patch changing the first line of `app/config` and adding a line at its end.
+const CONFIG_PATCH: &str = "--- a/app/config\t2025-01-01 12:00:00.000000000 +0000
++++ b/app/config\t2025-01-01 12:00:00.000000000 +0000
+@@ -1,4 +1,5 @@
+-host = localhost
++host = 0.0.0.0
+ port = 8080
+ level = debug
+ workers = 4
++timeout = 30
+";
+
+fn shell_with_config() -> Shell {
+    shell_with_files(
+        &["app"],
+        &[
+            (
+                "app/config",
+                "host = localhost\nport = 8080\nlevel = debug\nworkers = 4\n",
+            ),
+            ("config.patch", CONFIG_PATCH),
+        ],
+    )
+}
+
+#[test]
+fn test_patch_strip() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "patch -p1 -i config.patch"),
+        Some("patching file app/config".to_string())
+    );
+    assert_eq!(shell.exit_status(), 0);
+    assert_eq!(
+        run(&mut shell, "cat app/config"),
+        Some("host = 0.0.0.0\nport = 8080\nlevel = debug\nworkers = 4\ntimeout = 30".to_string())
+    );
+}
+
+#[test]
+fn test_patch_stdin_and_target() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "cat config.patch | patch app/config"),
+        Some("patching file app/config".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cat app/config"),
+        Some("host = 0.0.0.0\nport = 8080\nlevel = debug\nworkers = 4\ntimeout = 30".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "patch -R app/config config.patch"),
+        Some("patching file app/config".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cat app/config"),
+        Some("host = localhost\nport = 8080\nlevel = debug\nworkers = 4".to_string())
+    );
+}
+
+#[test]
+fn test_patch_round_trip_with_diff() {
+    let mut shell = shell_with_config();
+    write_file(
+        &mut shell,
+        "expected",
+        "# generated\nhost = localhost\nport = 9090\nlevel = debug\nworkers = 4\n",
+    );
+    let unified = shell
+        .execute("diff -u app/config expected")
+        .expect("Failed to execute diff")
+        .0
+        .expect("The files should differ");
+    write_file(&mut shell, "fix.patch", &format!("{unified}\n"));
+    assert_eq!(
+        run(&mut shell, "patch -p0 -i fix.patch"),
+        Some("patching file app/config".to_string())
+    );
+    assert_eq!(
+        shell
+            .execute("cmp app/config expected")
+            .expect("Failed to execute cmp")
+            .0,
+        None
+    );
+}
+
+#[test]
+fn test_patch_offset_and_rejects() {
+    let mut shell = shell_with_config();
+    write_file(
+        &mut shell,
+        "app/config",
+        "# header\n\nhost = localhost\nport = 8080\nlevel = debug\nworkers = 4\n",
+    );
+    assert_eq!(
+        run(&mut shell, "patch -p1 -i config.patch"),
+        Some("patching file app/config\nHunk #1 succeeded at 3 (offset 2 lines).".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "patch -p1 -i config.patch"),
+        Some(
+            "patching file app/config\nHunk #1 FAILED at 1.\n1 out of 1 hunk FAILED -- saving \
+             rejects to file app/config.rej"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "cat app/config.rej"),
+        Some(
+            "--- a/app/config\n+++ b/app/config\n@@ -1,4 +1,5 @@\n-host = localhost\n+host = \
+             0.0.0.0\n port = 8080\n level = debug\n workers = 4\n+timeout = 30"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_patch_errors() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "patch -p3 -i config.patch"),
+        Some(
+            "can't find file to patch at input line 1\nNo file to patch.  Skipping patch.\n1 out \
+             of 1 hunk ignored"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+    write_file(&mut shell, "garbage", "not a patch\n");
+    assert_eq!(
+        run(&mut shell, "patch -i garbage"),
+        Some("patch: **** Only garbage was found in the patch input.".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+}
+
+#[test]
+fn test_patch_short_and_missing_targets() {
+    let mut shell = shell_with_config();
+    write_file(&mut shell, "short", "host = localhost\n");
+    assert_eq!(
+        run(&mut shell, "patch short config.patch"),
+        Some(
+            "patching file short\nHunk #1 FAILED at 1.\n1 out of 1 hunk FAILED -- saving rejects \
+             to file short.rej"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "cat short short.rej"),
+        Some(
+            "host = localhost\n--- a/app/config\n+++ b/app/config\n@@ -1,4 +1,5 @@\n-host = \
+             localhost\n+host = 0.0.0.0\n port = 8080\n level = debug\n workers = 4\n+timeout = 30"
+                .to_string()
+        )
+    );
+
+    assert_eq!(
+        run(&mut shell, "patch missing config.patch"),
+        Some(
+            "can't find file to patch at input line 1\nNo file to patch.  Skipping patch.\n1 out \
+             of 1 hunk ignored"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "cat missing missing.rej"),
+        Some(
+            "cat: missing: No such file or directory\ncat: missing.rej: No such file or directory"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn test_patch_creation_with_failed_hunks() {
+    let mut shell = shell_with_config();
+    write_file(
+        &mut shell,
+        "new.patch",
+        "--- /dev/null\n+++ new\n@@ -1,2 +1,3 @@\n first\n second\n+third\n",
+    );
+    assert_eq!(
+        run(&mut shell, "cat new.patch | patch -p0"),
+        Some(
+            "patching file new\nHunk #1 FAILED at 1.\n1 out of 1 hunk FAILED -- saving rejects \
+             to file new.rej"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "cat new"),
+        Some("cat: new: No such file or directory".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "cat new.rej"),
+        Some("--- /dev/null\n+++ new\n@@ -1,2 +1,3 @@\n first\n second\n+third".to_string())
+    );
+}
@@ -34,3 +34,37 @@ fn test_multiple_rm() {
     let rm = shell.execute("rm -rf test").expect("Failed to execute rm");
     assert_eq!(rm.0, None);
 }
+
+#[test]
+fn test_rm_directory_without_recursive() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell
+        .execute("mkdir test")
+        .expect("Failed to execute mkdir");
+    let rm = shell.execute("rm test").expect("Failed to execute rm");
+    assert_eq!(
+        rm.0,
+        Some("rm: cannot remove 'test': Is a directory".to_string())
+    );
+}
+
+#[test]
+fn test_rm_in_unwritable_directory() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let rm = shell
+        .execute(format!("rm -rf /home/{username}").as_str())
+        .expect("Failed to execute rm");
+    assert_eq!(
+        rm.0,
+        Some(format!(
+            "rm: cannot remove '/home/{username}': Permission denied"
+        ))
+    );
+    let rm = shell.execute("rm missing").expect("Failed to execute rm");
+    assert_eq!(
+        rm.0,
+        Some("rm: cannot remove 'missing': No such file or directory".to_string())
+    );
+}
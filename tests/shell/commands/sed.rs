@@ -0,0 +1,259 @@
+//! Tests for the sed command.
+
+use std::sync::Arc;
+
+use chrono::{Duration, TimeZone, Utc};
+use shelljougahara::{ManualClock, QueuedInput, Shell};
+
+use crate::{run, shell_with_files, write_file};
+
+/// Returns a shell with a `config` file:
+///
+/// ```text
+/// # Server settings
+/// host = localhost
+/// port = 8080
+///
+/// # Logging
+/// level = debug
+/// ```
+fn shell_with_config() -> Shell {
+    shell_with_files(
+        &[],
+        &[(
+            "config",
+            "# Server settings\nhost = localhost\nport = 8080\n\n# Logging\nlevel = debug\n",
+        )],
+    )
+}
+
+#[test]
+fn test_sed_substitute() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "sed 's/localhost/0.0.0.0/' config"),
+        Some(
+            "# Server settings\nhost = 0.0.0.0\nport = 8080\n\n# Logging\nlevel = debug"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "echo aaa | sed s/a/b/"),
+        Some("baa".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo aaa | sed s/a/b/g"),
+        Some("bbb".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo aaa | sed s/a/b/2"),
+        Some("aba".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo Hello | sed s/hello/bye/I"),
+        Some("bye".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo /usr/bin | sed 's|/usr|/opt|'"),
+        Some("/opt/bin".to_string())
+    );
+}
+
+#[test]
+fn test_sed_backreferences() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(
+            &mut shell,
+            r"sed -n 's/^\([a-z]*\) = \(.*\)$/\2=\1/p' config"
+        ),
+        Some("localhost=host\n8080=port\ndebug=level".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -E -n 's/^([a-z]+) = .*/[&] \\1/p' config"),
+        Some("[host = localhost] host\n[port = 8080] port\n[level = debug] level".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo a,b | sed 's/,/\\n/'"),
+        Some("a\nb".to_string())
+    );
+}
+
+#[test]
+fn test_sed_addresses() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "sed -n 2p config"),
+        Some("host = localhost".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -n '$p' config"),
+        Some("level = debug".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed '/^#/d;/^$/d' config"),
+        Some("host = localhost\nport = 8080\nlevel = debug".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -n '/Server/,/^$/p' config"),
+        Some("# Server settings\nhost = localhost\nport = 8080\n".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed '2,5d' config"),
+        Some("# Server settings\nlevel = debug".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -n '/^#/!p' config"),
+        Some("host = localhost\nport = 8080\n\nlevel = debug".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -n '/Logging/,${s/debug/info/;p}' config"),
+        Some("# Logging\nlevel = info".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed 3q config"),
+        Some("# Server settings\nhost = localhost\nport = 8080".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -n '/port/=' config"),
+        Some("3".to_string())
+    );
+}
+
+#[test]
+fn test_sed_text_commands() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "sed '/^host/a timeout = 30' config"),
+        Some(
+            "# Server settings\nhost = localhost\ntimeout = 30\nport = 8080\n\n# Logging\nlevel \
+             = debug"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "sed -n '1i\\\n[server]\n1p' config"),
+        Some("[server]\n# Server settings".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed '/^port/c\\port = 9090' config"),
+        Some(
+            "# Server settings\nhost = localhost\nport = 9090\n\n# Logging\nlevel = debug"
+                .to_string()
+        )
+    );
+    // A changed range is replaced by a single copy of the text.
+    assert_eq!(
+        run(&mut shell, "sed '1,4c settings' config"),
+        Some("settings\n# Logging\nlevel = debug".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo hello | sed y/elo/ipa/"),
+        Some("hippa".to_string())
+    );
+}
+
+#[test]
+fn test_sed_expressions_and_input() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "sed -n -e 2p -e 3p config"),
+        Some("host = localhost\nport = 8080".to_string())
+    );
+    write_file(&mut shell, "more", "extra\n");
+    // Files are a single stream, so `$` is the last line of the last file.
+    assert_eq!(
+        run(&mut shell, "sed -n '1p;$p' config more"),
+        Some("# Server settings\nextra".to_string())
+    );
+    shell.set_stdin(QueuedInput::new(["typed"]));
+    assert_eq!(
+        run(&mut shell, "sed s/typed/edited/"),
+        Some("edited".to_string())
+    );
+}
+
+#[test]
+fn test_sed_in_place() {
+    let mut shell = shell_with_config();
+    let start = Utc
+        .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+        .single()
+        .expect("Invalid date");
+    let clock = Arc::new(ManualClock::new(start));
+    shell.fs.lock().set_clock(clock.clone());
+    shell
+        .execute("touch config")
+        .expect("Failed to execute touch");
+    let inode = shell
+        .execute("stat -c %i config")
+        .expect("Failed to execute stat");
+
+    clock.advance(Duration::hours(1));
+    assert_eq!(
+        run(&mut shell, "sed -i -e 's/8080/9090/' -e '/^#/d' config"),
+        None
+    );
+    assert_eq!(
+        shell
+            .execute("cat config")
+            .expect("Failed to execute cat")
+            .0,
+        Some("host = localhost\nport = 9090\n\nlevel = debug".to_string())
+    );
+    // The file is rewritten in place, and its modification time updated.
+    assert_eq!(
+        shell
+            .execute("stat -c %i config")
+            .expect("Failed to execute stat")
+            .0,
+        inode.0
+    );
+    assert_eq!(
+        shell
+            .execute("stat -c %y config")
+            .expect("Failed to execute stat")
+            .0,
+        Some("2025-01-01 01:00:00.000000000 +0000".to_string())
+    );
+
+    assert_eq!(
+        run(&mut shell, "sed -i s/a/b/ missing"),
+        Some("sed: can't read missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 4);
+    assert_eq!(
+        run(&mut shell, "sed -i s/a/b/"),
+        Some("sed: no input files".to_string())
+    );
+}
+
+#[test]
+fn test_sed_errors() {
+    let mut shell = shell_with_config();
+    assert_eq!(
+        run(&mut shell, "sed"),
+        Some("Usage: sed [OPTION]... {script-only-if-no-other-script} [input-file]...".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed s/a/b config"),
+        Some("sed: -e expression #1, char 5: unterminated `s' command".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -e p -e k config"),
+        Some("sed: -e expression #2, char 1: unknown command: `k'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed y/ab/c/ config"),
+        Some(
+            "sed: -e expression #1, char 7: strings for `y' command are different lengths"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "sed p missing"),
+        Some("sed: can't read missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+}
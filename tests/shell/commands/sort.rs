@@ -0,0 +1,111 @@
+//! Tests for the sort command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::{run, write_file};
+
+#[test]
+fn test_sort_lines() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "fruits", "cherry\nBanana\napple\ncherry\n");
+    assert_eq!(
+        run(&mut shell, "sort fruits"),
+        Some("Banana\napple\ncherry\ncherry".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -r fruits"),
+        Some("cherry\ncherry\napple\nBanana".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -u fruits"),
+        Some("Banana\napple\ncherry".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -f fruits"),
+        Some("apple\nBanana\ncherry\ncherry".to_string())
+    );
+}
+
+#[test]
+fn test_sort_numbers() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "numbers", "10\n9\n-3\n100\n2.5\n");
+    assert_eq!(
+        run(&mut shell, "sort numbers"),
+        Some("-3\n10\n100\n2.5\n9".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -n numbers"),
+        Some("-3\n2.5\n9\n10\n100".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -nr numbers"),
+        Some("100\n10\n9\n2.5\n-3".to_string())
+    );
+    write_file(&mut shell, "sizes", "1G\n12K\n3M\n512\n");
+    assert_eq!(
+        run(&mut shell, "sort -h sizes"),
+        Some("512\n12K\n3M\n1G".to_string())
+    );
+}
+
+#[test]
+fn test_sort_keys() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(
+        &mut shell,
+        "scores",
+        "carol 90 b\nalice 85 a\nbob 90 a\ndave 7 c\n",
+    );
+    assert_eq!(
+        run(&mut shell, "sort -k2n scores"),
+        Some("dave 7 c\nalice 85 a\nbob 90 a\ncarol 90 b".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -k2,2nr -k1,1 scores"),
+        Some("bob 90 a\ncarol 90 b\nalice 85 a\ndave 7 c".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -k 3 -k 1r scores"),
+        Some("bob 90 a\nalice 85 a\ncarol 90 b\ndave 7 c".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -t : -k 3n /etc/passwd"),
+        Some("root:x:0:0::/root:/bin/sh\ntest:x:1000:1000::/home/test:/bin/sh".to_string())
+    );
+    // Keys compare equal, so only the first line of each is kept.
+    assert_eq!(
+        run(&mut shell, "sort -u -k2,2n scores"),
+        Some("dave 7 c\nalice 85 a\ncarol 90 b".to_string())
+    );
+}
+
+#[test]
+fn test_sort_standard_input() {
+    let mut shell = Shell::new_with_user("test");
+    shell.set_stdin(QueuedInput::new(["b", "c", "a"]));
+    assert_eq!(run(&mut shell, "sort"), Some("a\nb\nc".to_string()));
+    write_file(&mut shell, "letters", "z\ny\n");
+    assert_eq!(
+        run(&mut shell, "cat letters | sort"),
+        Some("y\nz".to_string())
+    );
+}
+
+#[test]
+fn test_sort_errors() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "sort missing"),
+        Some("sort: cannot read: missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 2);
+    assert_eq!(
+        run(&mut shell, "sort -k 0 /etc/group"),
+        Some("sort: invalid key '0'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sort -t ab /etc/group"),
+        Some("sort: multi-character tab 'ab'".to_string())
+    );
+}
@@ -0,0 +1,121 @@
+//! Tests for the setuid, setgid and sticky bits.
+
+use shelljougahara::Shell;
+
+/// Returns the columns of `ls -l` for a single file.
+fn long_listing(shell: &mut Shell, path: &str) -> Vec<String> {
+    let ls = shell
+        .execute(format!("ls -l {path}").as_str())
+        .expect("Failed to execute ls");
+    ls.0.expect("Missing ls output")
+        .split_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn test_special_bits_display() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell
+        .execute("chmod 4755 f")
+        .expect("Failed to execute chmod");
+    assert_eq!(long_listing(&mut shell, "f")[0], "-rwsr-xr-x");
+    shell
+        .execute("chmod 6644 f")
+        .expect("Failed to execute chmod");
+    assert_eq!(long_listing(&mut shell, "f")[0], "-rwSr-Sr--");
+    shell
+        .execute("chmod 1777 f")
+        .expect("Failed to execute chmod");
+    assert_eq!(long_listing(&mut shell, "f")[0], "-rwxrwxrwt");
+    shell
+        .execute("chmod o-x f")
+        .expect("Failed to execute chmod");
+    assert_eq!(long_listing(&mut shell, "f")[0], "-rwxrwxrwT");
+}
+
+#[test]
+fn test_sticky_directory() {
+    let mut shell = Shell::new_with_user("alice");
+    let bob = shell.fs.lock().add_user("bob").expect("Failed to add user");
+    shell
+        .execute("mkdir shared")
+        .expect("Failed to execute mkdir");
+    shell
+        .execute("chmod 1777 shared")
+        .expect("Failed to execute chmod");
+    shell
+        .execute("touch shared/alice_file")
+        .expect("Failed to execute touch");
+    shell
+        .execute("chmod 666 shared/alice_file")
+        .expect("Failed to execute chmod");
+
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), bob)
+        .expect("Failed to change user");
+    let rm = shell
+        .execute("rm /home/alice/shared/alice_file")
+        .expect("Failed to execute rm");
+    assert_eq!(
+        rm.0,
+        Some(
+            "rm: cannot remove '/home/alice/shared/alice_file': Operation not permitted"
+                .to_string()
+        )
+    );
+    shell
+        .execute("touch /home/alice/shared/bob_file")
+        .expect("Failed to execute touch");
+    let rm = shell
+        .execute("rm /home/alice/shared/bob_file")
+        .expect("Failed to execute rm");
+    assert_eq!(rm.0, None);
+}
+
+#[test]
+fn test_setgid_directory() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.fs.lock().add_user("bob").expect("Failed to add user");
+    let alice = shell.current_session.current_user();
+    shell
+        .execute("mkdir project")
+        .expect("Failed to execute mkdir");
+
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    shell
+        .execute("chgrp bob /home/alice/project")
+        .expect("Failed to execute chgrp");
+    shell
+        .execute("chmod 2777 /home/alice/project")
+        .expect("Failed to execute chmod");
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), alice)
+        .expect("Failed to change user");
+
+    shell
+        .execute("touch project/f")
+        .expect("Failed to execute touch");
+    shell
+        .execute("mkdir project/sub")
+        .expect("Failed to execute mkdir");
+    assert_eq!(long_listing(&mut shell, "project/f")[3], "bob");
+    let ls = shell
+        .execute("ls -l project")
+        .expect("Failed to execute ls");
+    let sub =
+        ls.0.expect("Missing ls output")
+            .lines()
+            .find(|line| line.ends_with(" sub"))
+            .expect("Missing sub entry")
+            .to_string();
+    assert!(sub.starts_with("drwxr-sr-x"));
+    assert_eq!(sub.split_whitespace().nth(3), Some("bob"));
+}
@@ -0,0 +1,137 @@
+//! Tests for the stat command.
+
+use shelljougahara::Shell;
+
+#[test]
+fn test_stat_default_format() {
+    let mut shell = Shell::new_with_user("test");
+    shell.execute("touch a").expect("Failed to execute touch");
+    let stat = shell.execute("stat a").expect("Failed to execute stat");
+    let stat = stat.0.expect("Missing stat output");
+    let lines = stat.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 8);
+    assert_eq!(lines[0], "  File: a");
+    assert_eq!(
+        lines[1],
+        "  Size: 0         \tBlocks: 0          IO Block: 4096   regular empty file"
+    );
+    assert!(lines[2].starts_with("Device: 8,1\tInode: "));
+    assert!(lines[2].ends_with(" Links: 1"));
+    assert_eq!(
+        lines[3],
+        "Access: (0644/-rw-r--r--)  Uid: ( 1000/    test)   Gid: ( 1000/    test)"
+    );
+    for (line, label) in lines[4..]
+        .iter()
+        .zip(["Access", "Modify", "Change", " Birth"])
+    {
+        assert!(line.starts_with(&format!("{label}: ")));
+        assert!(line.ends_with(" +0000"));
+    }
+}
+
+#[test]
+fn test_stat_format() {
+    let mut shell = Shell::new_with_user("test");
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    let stat = shell
+        .execute("stat -c '%n %s %b %a %A %U:%G %u:%g %h %F' dir /tmp")
+        .expect("Failed to execute stat");
+    assert_eq!(
+        stat.0,
+        Some(
+            "dir 4096 8 755 drwxr-xr-x test:test 1000:1000 2 directory\n\
+             /tmp 4096 8 1777 drwxrwxrwt root:root 0:0 2 directory"
+                .to_string()
+        )
+    );
+
+    let stat = shell
+        .execute("stat --format='[%-5n] [%5n] [%.2n] %% %q' dir")
+        .expect("Failed to execute stat");
+    assert_eq!(stat.0, Some("[dir  ] [  dir] [di] % %q".to_string()));
+}
+
+#[test]
+fn test_stat_inode_numbers() {
+    let mut shell = Shell::new_with_user("test");
+    shell.execute("touch a").expect("Failed to execute touch");
+    shell.execute("ln a b").expect("Failed to execute ln");
+    let stat = shell
+        .execute("stat -c %i a b /")
+        .expect("Failed to execute stat");
+    let stat = stat.0.expect("Missing stat output");
+    let numbers = stat.lines().collect::<Vec<_>>();
+    assert_eq!(numbers[0], numbers[1]);
+    assert_eq!(numbers[2], "2");
+}
+
+#[test]
+fn test_stat_errors() {
+    let mut shell = Shell::new_with_user("test");
+    let stat = shell.execute("stat").expect("Failed to execute stat");
+    assert_eq!(stat.0, Some("stat: missing operand".to_string()));
+    assert_eq!(shell.exit_status(), 1);
+    let stat = shell
+        .execute("stat missing")
+        .expect("Failed to execute stat");
+    assert_eq!(
+        stat.0,
+        Some("stat: cannot statx 'missing': No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_stat_timestamps() {
+    let mut shell = Shell::new_with_user("test");
+    shell
+        .execute("touch -d '2020-01-02 03:04:05' a")
+        .expect("Failed to execute touch");
+    let stat = shell
+        .execute("stat -c '%x|%Y' a")
+        .expect("Failed to execute stat");
+    assert_eq!(
+        stat.0,
+        Some("2020-01-02 03:04:05.000000000 +0000|1577934245".to_string())
+    );
+
+    // Reading the file updates its access time only.
+    shell.execute("cat a").expect("Failed to execute cat");
+    let stat = shell
+        .execute("stat -c '%X %Y' a")
+        .expect("Failed to execute stat");
+    let stat = stat.0.expect("Missing stat output");
+    let (accessed, modified) = stat.split_once(' ').expect("Missing times");
+    assert!(accessed.parse::<i64>().expect("Invalid time") > 1_577_934_245);
+    assert_eq!(modified, "1577934245");
+
+    // Changing the mode updates the change time only.
+    shell
+        .execute("chmod 600 a")
+        .expect("Failed to execute chmod");
+    let stat = shell
+        .execute("stat -c '%Y %Z' a")
+        .expect("Failed to execute stat");
+    let stat = stat.0.expect("Missing stat output");
+    let (modified, changed) = stat.split_once(' ').expect("Missing times");
+    assert_eq!(modified, "1577934245");
+    assert!(changed.parse::<i64>().expect("Invalid time") > 1_577_934_245);
+}
+
+#[test]
+fn test_stat_directory_modified_by_entries() {
+    let mut shell = Shell::new_with_user("test");
+    shell.execute("mkdir dir").expect("Failed to execute mkdir");
+    shell
+        .execute("touch -d 2020-01-01 dir")
+        .expect("Failed to execute touch");
+    shell
+        .execute("touch dir/a")
+        .expect("Failed to execute touch");
+    let stat = shell
+        .execute("stat -c %Y dir")
+        .expect("Failed to execute stat");
+    let modified = stat.0.expect("Missing stat output");
+    assert!(modified.parse::<i64>().expect("Invalid time") > 1_577_836_800);
+}
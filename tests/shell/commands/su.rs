@@ -0,0 +1,121 @@
+//! Tests for the su command and the identity stack of sessions.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::whoami;
+
+/// Returns a shell for alice, with bob and root having passwords.
+fn shell_with_passwords() -> Shell {
+    let shell = Shell::new_with_user("alice");
+    let bob = shell.fs.lock().add_user("bob").expect("Failed to add user");
+    for (user, password) in [(bob, "bob-password"), (0, "root-password")] {
+        let mut fs = shell.fs.lock();
+        let now = fs.now();
+        fs.update_user(user, |user| user.set_password(password, now))
+            .expect("Failed to set password");
+    }
+    shell
+}
+
+fn pwd(shell: &mut Shell) -> String {
+    let pwd = shell.execute("pwd").expect("Failed to execute pwd");
+    pwd.0.expect("Missing pwd output")
+}
+
+#[test]
+fn test_su_keeps_directory() {
+    let mut shell = shell_with_passwords();
+    shell.execute("cd /home").expect("Failed to execute cd");
+
+    shell.set_stdin(QueuedInput::new(["bob-password"]));
+    let su = shell.execute("su bob").expect("Failed to execute su");
+    assert_eq!(su.0, None);
+    assert_eq!(whoami(&mut shell), "bob");
+    assert_eq!(pwd(&mut shell), "/home");
+    assert_eq!(shell.current_session.variable("USER"), Some("bob"));
+    assert_eq!(shell.current_session.variable("HOME"), Some("/home/bob"));
+    assert_eq!(
+        shell.current_session.variable("PATH"),
+        Some("/usr/local/bin:/usr/bin:/bin")
+    );
+
+    shell.execute("exit").expect("Failed to execute exit");
+    assert!(shell.active);
+    assert_eq!(whoami(&mut shell), "alice");
+    assert_eq!(shell.current_session.variable("USER"), Some("alice"));
+}
+
+#[test]
+fn test_su_login() {
+    let mut shell = shell_with_passwords();
+    shell.execute("cd /home").expect("Failed to execute cd");
+    shell.current_session.set_variable("EDITOR", "vi");
+
+    shell.set_stdin(QueuedInput::new(["bob-password", "root-password"]));
+    shell.execute("su - bob").expect("Failed to execute su");
+    assert_eq!(whoami(&mut shell), "bob");
+    assert_eq!(pwd(&mut shell), "/home/bob");
+    assert_eq!(shell.current_session.variable("EDITOR"), None);
+    assert_eq!(shell.current_session.variable("LOGNAME"), Some("bob"));
+
+    // Identities stack up, each exit returning to the previous one.
+    shell.execute("su -l").expect("Failed to execute su");
+    assert_eq!(whoami(&mut shell), "root");
+    assert_eq!(pwd(&mut shell), "/root");
+    shell.execute("exit").expect("Failed to execute exit");
+    assert_eq!(whoami(&mut shell), "bob");
+    shell.execute("exit").expect("Failed to execute exit");
+    assert_eq!(whoami(&mut shell), "alice");
+    assert_eq!(pwd(&mut shell), "/home");
+    assert_eq!(shell.current_session.variable("EDITOR"), Some("vi"));
+
+    shell.execute("exit").expect("Failed to execute exit");
+    assert!(!shell.active);
+}
+
+#[test]
+fn test_su_command() {
+    let mut shell = shell_with_passwords();
+    shell.set_stdin(QueuedInput::new(["bob-password"]));
+    let su = shell
+        .execute("su -c whoami bob")
+        .expect("Failed to execute su");
+    assert_eq!(su.0, Some("bob".to_string()));
+    assert_eq!(whoami(&mut shell), "alice");
+}
+
+#[test]
+fn test_su_unknown_user() {
+    let mut shell = Shell::new_with_user("alice");
+    let su = shell.execute("su - bob").expect("Failed to execute su");
+    assert_eq!(
+        su.0,
+        Some(
+            "su: user bob does not exist or the user entry does not contain all the required fields"
+                .to_string()
+        )
+    );
+    assert_eq!(whoami(&mut shell), "alice");
+}
+
+#[test]
+fn test_su_authentication_failure() {
+    let mut shell = shell_with_passwords();
+    shell.set_stdin(QueuedInput::new(["wrong"]));
+    let su = shell.execute("su bob").expect("Failed to execute su");
+    assert_eq!(su.0, Some("su: Authentication failure".to_string()));
+    assert_eq!(whoami(&mut shell), "alice");
+
+    // Without a password set, the account is locked.
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    shell
+        .execute("useradd carol")
+        .expect("Failed to execute useradd");
+    shell.execute("su alice").expect("Failed to execute su");
+    shell.set_stdin(QueuedInput::new([""]));
+    let su = shell.execute("su carol").expect("Failed to execute su");
+    assert_eq!(su.0, Some("su: Authentication failure".to_string()));
+}
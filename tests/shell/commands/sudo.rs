@@ -0,0 +1,211 @@
+//! Tests for the sudo command and the sudoers policy.
+
+use shelljougahara::{InodeContent, QueuedInput, Shell, StdinProvider};
+
+use crate::whoami;
+
+/// A user typing the same line at every prompt.
+#[derive(Debug)]
+struct Typist(&'static str);
+
+impl StdinProvider for Typist {
+    fn read_line(&mut self, _: &str) -> Option<String> {
+        Some(self.0.to_string())
+    }
+}
+
+/// Returns a shell for alice, a member of the `sudo` group, and bob, a regular user, both
+/// having `password` as their password and typing it when asked.
+fn shell_with_sudoer() -> Shell {
+    let mut shell = Shell::new_with_user("alice");
+    let bob = shell.fs.lock().add_user("bob").expect("Failed to add user");
+    let alice = shell.current_session.current_user();
+    for user in [alice, bob] {
+        let mut fs = shell.fs.lock();
+        let now = fs.now();
+        fs.update_user(user, |user| user.set_password("password", now))
+            .expect("Failed to set password");
+    }
+    shell.set_stdin(Typist("password"));
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    shell
+        .execute("usermod -aG sudo alice")
+        .expect("Failed to execute usermod");
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), alice)
+        .expect("Failed to change user");
+    shell
+}
+
+#[test]
+fn test_sudo_command() {
+    let mut shell = shell_with_sudoer();
+    let sudo = shell
+        .execute("sudo whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(sudo.0, Some("root".to_string()));
+    let sudo = shell
+        .execute("sudo -u bob id -un")
+        .expect("Failed to execute sudo");
+    assert_eq!(sudo.0, Some("bob".to_string()));
+    assert_eq!(whoami(&mut shell), "alice");
+
+    // The command runs with the privileges of the target user.
+    shell
+        .execute("sudo mkdir /opt")
+        .expect("Failed to execute sudo");
+    let ls = shell.execute("ls -l /").expect("Failed to execute ls");
+    assert!(
+        ls.0.expect("Missing ls output")
+            .lines()
+            .any(|line| line.contains(" root root ") && line.ends_with(" opt"))
+    );
+}
+
+#[test]
+fn test_sudo_login_shell() {
+    let mut shell = shell_with_sudoer();
+    shell.execute("sudo -i").expect("Failed to execute sudo");
+    assert_eq!(whoami(&mut shell), "root");
+    let pwd = shell.execute("pwd").expect("Failed to execute pwd");
+    assert_eq!(pwd.0, Some("/root".to_string()));
+    shell.execute("exit").expect("Failed to execute exit");
+    assert_eq!(whoami(&mut shell), "alice");
+
+    // A shell opened by the command is kept, and exiting it returns to the user.
+    shell
+        .execute("sudo su - bob")
+        .expect("Failed to execute sudo");
+    assert_eq!(whoami(&mut shell), "bob");
+    shell.execute("exit").expect("Failed to execute exit");
+    assert_eq!(whoami(&mut shell), "alice");
+    assert!(shell.active);
+}
+
+#[test]
+fn test_sudo_denied() {
+    let mut shell = shell_with_sudoer();
+    shell.execute("su bob").expect("Failed to execute su");
+    let sudo = shell
+        .execute("sudo whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(
+        sudo.0,
+        Some("bob is not in the sudoers file.  This incident will be reported.".to_string())
+    );
+}
+
+#[test]
+fn test_sudoers_command_restriction() {
+    let mut shell = shell_with_sudoer();
+    append_to_sudoers(&shell, "bob ALL=(root) /usr/bin/whoami");
+
+    shell.execute("su bob").expect("Failed to execute su");
+    let sudo = shell
+        .execute("sudo whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(sudo.0, Some("root".to_string()));
+    let sudo = shell
+        .execute("sudo mkdir /opt")
+        .expect("Failed to execute sudo");
+    assert_eq!(
+        sudo.0,
+        Some(
+            "Sorry, user bob is not allowed to execute 'mkdir /opt' as root on localhost."
+                .to_string()
+        )
+    );
+    let sudo = shell
+        .execute("sudo -u alice whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(
+        sudo.0,
+        Some(
+            "Sorry, user bob is not allowed to execute 'whoami' as alice on localhost.".to_string()
+        )
+    );
+}
+
+#[test]
+fn test_sudo_authentication() {
+    let mut shell = shell_with_sudoer();
+    shell.set_stdin(QueuedInput::new(["wrong", "password"]));
+    let sudo = shell
+        .execute("sudo whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(sudo.0, Some("Sorry, try again.\nroot".to_string()));
+
+    shell.set_stdin(Typist("wrong"));
+    let sudo = shell
+        .execute("sudo whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(
+        sudo.0,
+        Some(
+            "Sorry, try again.\nSorry, try again.\nsudo: 3 incorrect password attempts".to_string()
+        )
+    );
+
+    shell.set_stdin(QueuedInput::default());
+    let sudo = shell
+        .execute("sudo whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(sudo.0, Some("sudo: no password was provided".to_string()));
+}
+
+#[test]
+fn test_sudoers_no_password() {
+    let mut shell = shell_with_sudoer();
+    append_to_sudoers(&shell, "alice ALL=(ALL) NOPASSWD: /usr/bin/whoami");
+
+    shell.set_stdin(QueuedInput::default());
+    let sudo = shell
+        .execute("sudo whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(sudo.0, Some("root".to_string()));
+}
+
+/// Adds a rule at the end of the sudoers file.
+fn append_to_sudoers(shell: &Shell, rule: &str) {
+    let mut fs = shell.fs.lock();
+    let sudoers = fs
+        .find_absolute_inode("/etc/sudoers")
+        .expect("Missing sudoers file");
+    if let Some(InodeContent::File(file)) = fs.inode_mut(sudoers).map(|inode| &mut inode.content) {
+        file.content.push_str(rule);
+        file.content.push('\n');
+    }
+}
+
+#[test]
+fn test_sudo_group_is_seeded() {
+    let mut shell = shell_with_sudoer();
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    let useradd = shell
+        .execute("useradd -m -G sudo carol")
+        .expect("Failed to execute useradd");
+    assert_eq!(useradd.0, None);
+    let carol = {
+        let mut fs = shell.fs.lock();
+        let now = fs.now();
+        let carol = fs.find_user_by_name("carol").expect("Missing user").id;
+        fs.update_user(carol, |user| user.set_password("password", now))
+            .expect("Failed to set password");
+        carol
+    };
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), carol)
+        .expect("Failed to change user");
+    let sudo = shell
+        .execute("sudo -u root whoami")
+        .expect("Failed to execute sudo");
+    assert_eq!(sudo.0, Some("root".to_string()));
+}
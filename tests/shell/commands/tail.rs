@@ -0,0 +1,61 @@
+//! Tests for the tail command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::{run, write_file};
+
+#[test]
+fn test_tail_lines() {
+    let mut shell = Shell::new_with_user("test");
+    let numbers = (1..=12)
+        .map(|number| format!("{number}\n"))
+        .collect::<String>();
+    write_file(&mut shell, "numbers", &numbers);
+    assert_eq!(
+        run(&mut shell, "tail numbers"),
+        Some("3\n4\n5\n6\n7\n8\n9\n10\n11\n12".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "tail -n 2 numbers"),
+        Some("11\n12".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "tail -n +10 numbers"),
+        Some("10\n11\n12".to_string())
+    );
+    assert_eq!(run(&mut shell, "tail -n 0 numbers"), None);
+}
+
+#[test]
+fn test_tail_bytes() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "word", "abcdef\n");
+    assert_eq!(run(&mut shell, "tail -c 3 word"), Some("ef".to_string()));
+    assert_eq!(run(&mut shell, "tail -c +5 word"), Some("ef".to_string()));
+}
+
+#[test]
+fn test_tail_several_files() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "a", "a1\na2\n");
+    write_file(&mut shell, "b", "b1\n");
+    assert_eq!(
+        run(&mut shell, "tail -n 1 a b"),
+        Some("==> a <==\na2\n\n==> b <==\nb1".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "tail -n 1 a missing"),
+        Some(
+            "==> a <==\na2\ntail: cannot open 'missing' for reading: No such file or directory"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_tail_standard_input() {
+    let mut shell = Shell::new_with_user("test");
+    shell.set_stdin(QueuedInput::new(["one", "two", "three"]));
+    assert_eq!(run(&mut shell, "tail -n 1"), Some("three".to_string()));
+}
@@ -1,6 +1,21 @@
 //! Tests for the touch command.
 
-use shelljougahara::Shell;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use shelljougahara::{Clock, Shell};
+
+/// A clock moving a second forward every time it is read.
+#[derive(Debug)]
+struct TickingClock(Mutex<DateTime<Utc>>);
+
+impl Clock for TickingClock {
+    fn now(&self) -> DateTime<Utc> {
+        let mut time = self.0.lock().expect("Poisoned clock");
+        *time += Duration::seconds(1);
+        *time
+    }
+}
 
 #[test]
 fn test_touch_file() {
@@ -65,3 +80,153 @@ fn test_touch_in_non_existent_directory() {
         Some("touch: cannot touch 'test/test2': No such file or directory".to_string())
     );
 }
+
+#[test]
+fn test_touch_in_unwritable_directory() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let touch = shell
+        .execute("touch /test")
+        .expect("Failed to execute touch");
+    assert_eq!(
+        touch.0,
+        Some("touch: cannot touch '/test': Permission denied".to_string())
+    );
+}
+
+#[test]
+fn test_touch_date() {
+    let mut shell = Shell::new_with_user("test");
+    let touch = shell
+        .execute("touch -d 2020-01-02 a")
+        .expect("Failed to execute touch");
+    assert_eq!(touch.0, None);
+    let ls = shell.execute("ls -l a").expect("Failed to execute ls");
+    assert!(ls.0.expect("Missing ls output").ends_with(" Jan 2 2020 a"));
+
+    let touch = shell
+        .execute("touch --date=tomorrow-ish a")
+        .expect("Failed to execute touch");
+    assert_eq!(
+        touch.0,
+        Some("touch: invalid date format 'tomorrow-ish'".to_string())
+    );
+}
+
+#[test]
+fn test_touch_changes_new_file_after_birth() {
+    let mut shell = Shell::new_with_user("test");
+    let start = Utc
+        .with_ymd_and_hms(2025, 1, 1, 0, 0, 0)
+        .single()
+        .expect("Invalid date");
+    shell
+        .fs
+        .lock()
+        .set_clock(Arc::new(TickingClock(Mutex::new(start))));
+    shell
+        .execute("touch -d '2020-01-01 00:00' new")
+        .expect("Failed to execute touch");
+    let stat = shell
+        .execute("stat -c '%W %Z' new")
+        .expect("Failed to execute stat")
+        .0
+        .expect("Missing stat output");
+    let (birth, change) = stat.split_once(' ').expect("Missing change time");
+    assert!(
+        birth.parse::<i64>().expect("Invalid birth time")
+            <= change.parse::<i64>().expect("Invalid change time")
+    );
+}
+
+#[test]
+fn test_touch_access_or_modification_only() {
+    let mut shell = Shell::new_with_user("test");
+    shell
+        .execute("touch -d 2020-01-02 a")
+        .expect("Failed to execute touch");
+    shell
+        .execute("touch -a -d 2021-01-02 a")
+        .expect("Failed to execute touch");
+    let stat = shell
+        .execute("stat -c '%x|%y' a")
+        .expect("Failed to execute stat");
+    assert_eq!(
+        stat.0,
+        Some("2021-01-02 00:00:00.000000000 +0000|2020-01-02 00:00:00.000000000 +0000".to_string())
+    );
+
+    shell
+        .execute("touch -m -t 202203041506.07 a")
+        .expect("Failed to execute touch");
+    let stat = shell
+        .execute("stat -c '%x|%y' a")
+        .expect("Failed to execute stat");
+    assert_eq!(
+        stat.0,
+        Some("2021-01-02 00:00:00.000000000 +0000|2022-03-04 15:06:07.000000000 +0000".to_string())
+    );
+}
+
+#[test]
+fn test_touch_reference() {
+    let mut shell = Shell::new_with_user("test");
+    shell
+        .execute("touch -d '2020-05-06 07:08:09' a")
+        .expect("Failed to execute touch");
+    shell
+        .execute("touch -r a b")
+        .expect("Failed to execute touch");
+    let stat = shell
+        .execute("stat -c %y b")
+        .expect("Failed to execute stat");
+    assert_eq!(
+        stat.0,
+        Some("2020-05-06 07:08:09.000000000 +0000".to_string())
+    );
+
+    let touch = shell
+        .execute("touch -r missing b")
+        .expect("Failed to execute touch");
+    assert_eq!(
+        touch.0,
+        Some("touch: failed to get attributes of 'missing': No such file or directory".to_string())
+    );
+    let touch = shell
+        .execute("touch -r a -d 2020-01-01 b")
+        .expect("Failed to execute touch");
+    assert_eq!(
+        touch.0,
+        Some("touch: cannot specify times from more than one source".to_string())
+    );
+}
+
+#[test]
+fn test_touch_no_create() {
+    let mut shell = Shell::new_with_user("test");
+    let touch = shell
+        .execute("touch -c a")
+        .expect("Failed to execute touch");
+    assert_eq!(touch.0, None);
+    let ls = shell.execute("ls").expect("Failed to execute ls");
+    assert_eq!(ls.0, None);
+}
+
+#[test]
+fn test_touch_times_of_other_users() {
+    let mut shell = Shell::new_with_user("test");
+    let touch = shell
+        .execute("touch -d 2020-01-01 /etc/passwd")
+        .expect("Failed to execute touch");
+    assert_eq!(
+        touch.0,
+        Some("touch: setting times of '/etc/passwd': Operation not permitted".to_string())
+    );
+    let touch = shell
+        .execute("touch /etc/passwd")
+        .expect("Failed to execute touch");
+    assert_eq!(
+        touch.0,
+        Some("touch: cannot touch '/etc/passwd': Permission denied".to_string())
+    );
+}
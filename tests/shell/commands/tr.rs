@@ -0,0 +1,84 @@
+//! Tests for the tr command.
+
+use shelljougahara::Shell;
+
+use crate::run;
+
+#[test]
+fn test_tr_translate() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "echo 'Hello, World' | tr a-z A-Z"),
+        Some("HELLO, WORLD".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "echo 'Hello, World' | tr '[:upper:][:lower:]' '[:lower:][:upper:]'"
+        ),
+        Some("hELLO, wORLD".to_string())
+    );
+    // The second set is padded with its last character.
+    assert_eq!(
+        run(&mut shell, "echo abcdef | tr a-e xy"),
+        Some("xyyyyf".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo 'a b c' | tr ' ' '\\n'"),
+        Some("a\nb\nc".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo 'a1b2' | tr -c '[:digit:]' _"),
+        Some("_1_2_".to_string())
+    );
+}
+
+#[test]
+fn test_tr_delete_and_squeeze() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "echo 'r2d2 c3po' | tr -d '[:digit:]'"),
+        Some("rd cpo".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo 'r2d2 c3po' | tr -cd '[:digit:]\\n'"),
+        Some("223".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo 'aaa   bbb' | tr -s ' '"),
+        Some("aaa bbb".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo 'aaa   bbb' | tr -s a-z"),
+        Some("a   b".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "echo 'a1 22 b' | tr -ds '[:digit:]' ' '"),
+        Some("a b".to_string())
+    );
+}
+
+#[test]
+fn test_tr_errors() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "tr"),
+        Some("tr: missing operand".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "tr abc"),
+        Some(
+            "tr: missing operand after 'abc'\nTwo strings must be given when translating."
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "tr -d a b"),
+        Some("tr: extra operand 'b'".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "tr z-a x"),
+        Some("tr: range-endpoints of 'z-a' are in reverse collating sequence order".to_string())
+    );
+}
@@ -0,0 +1,94 @@
+//! Tests for the tree command.
+
+use shelljougahara::Shell;
+
+use crate::{run, shell_with_files};
+
+/// Returns a shell with a `project` directory:
+///
+/// ```text
+/// project
+/// ├── .env
+/// ├── docs
+/// │   └── readme
+/// ├── empty
+/// └── src
+///     ├── lib
+///     │   └── util.rs
+///     └── main.rs
+/// ```
+fn shell_with_project() -> Shell {
+    shell_with_files(
+        &["project", "project/docs", "project/src", "project/src/lib"],
+        &[
+            ("project/.env", ""),
+            ("project/docs/readme", ""),
+            ("project/empty", ""),
+            ("project/src/lib/util.rs", ""),
+            ("project/src/main.rs", ""),
+        ],
+    )
+}
+
+#[test]
+fn test_tree() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "tree project"),
+        Some(
+            "project\n├── docs\n│   └── readme\n├── empty\n└── src\n    ├── lib\n    │   └── \
+             util.rs\n    └── main.rs\n\n3 directories, 4 files"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "tree -a project/docs"),
+        Some("project/docs\n└── readme\n\n0 directories, 1 file".to_string())
+    );
+}
+
+#[test]
+fn test_tree_hidden_and_directories() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "tree -a -L 1 project"),
+        Some(
+            "project\n├── .env\n├── docs\n├── empty\n└── src\n\n2 directories, 2 files".to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "tree -d project"),
+        Some("project\n├── docs\n└── src\n    └── lib\n\n3 directories".to_string())
+    );
+}
+
+#[test]
+fn test_tree_full_path() {
+    let mut shell = shell_with_project();
+    shell.execute("cd project").expect("Failed to execute cd");
+    assert_eq!(
+        run(&mut shell, "tree -f src"),
+        Some(
+            "src\n├── src/lib\n│   └── src/lib/util.rs\n└── src/main.rs\n\n1 directory, 2 files"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        run(&mut shell, "tree -L 1"),
+        Some(".\n├── docs\n├── empty\n└── src\n\n2 directories, 1 file".to_string())
+    );
+}
+
+#[test]
+fn test_tree_errors() {
+    let mut shell = shell_with_project();
+    assert_eq!(
+        run(&mut shell, "tree missing"),
+        Some("missing [error opening dir]\n\n0 directories, 0 files".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "tree -L 0 project"),
+        Some("tree: Invalid level, must be greater than 0.".to_string())
+    );
+}
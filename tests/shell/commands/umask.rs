@@ -0,0 +1,80 @@
+//! Tests for the umask command and the mode of created files.
+
+use shelljougahara::Shell;
+
+use crate::mode_of;
+
+#[test]
+fn test_default_umask() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let umask = shell.execute("umask").expect("Failed to execute umask");
+    assert_eq!(umask.0, Some("0022".to_string()));
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell.execute("mkdir d").expect("Failed to execute mkdir");
+    assert_eq!(mode_of(&mut shell, "f"), "-rw-r--r--");
+    assert_eq!(mode_of(&mut shell, "d"), "drwxr-xr-x");
+}
+
+#[test]
+fn test_octal_umask() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let umask = shell.execute("umask 077").expect("Failed to execute umask");
+    assert_eq!(umask.0, None);
+    shell.execute("touch f").expect("Failed to execute touch");
+    shell.execute("mkdir d").expect("Failed to execute mkdir");
+    assert_eq!(mode_of(&mut shell, "f"), "-rw-------");
+    assert_eq!(mode_of(&mut shell, "d"), "drwx------");
+}
+
+#[test]
+fn test_symbolic_umask() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell
+        .execute("umask u=rwx,g=rx,o=")
+        .expect("Failed to execute umask");
+    let umask = shell.execute("umask").expect("Failed to execute umask");
+    assert_eq!(umask.0, Some("0027".to_string()));
+    shell.execute("umask g+w").expect("Failed to execute umask");
+    let umask = shell.execute("umask -S").expect("Failed to execute umask");
+    assert_eq!(umask.0, Some("u=rwx,g=rwx,o=".to_string()));
+}
+
+#[test]
+fn test_invalid_umask() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    let umask = shell
+        .execute("umask 7777")
+        .expect("Failed to execute umask");
+    assert_eq!(
+        umask.0,
+        Some("umask: 7777: octal number out of range".to_string())
+    );
+    let umask = shell
+        .execute("umask 0888")
+        .expect("Failed to execute umask");
+    assert_eq!(
+        umask.0,
+        Some("umask: 0888: octal number out of range".to_string())
+    );
+    let umask = shell.execute("umask u+z").expect("Failed to execute umask");
+    assert_eq!(
+        umask.0,
+        Some("umask: u+z: invalid symbolic mode".to_string())
+    );
+}
+
+#[test]
+fn test_created_files_belong_to_user() {
+    let username = "test";
+    let mut shell = Shell::new_with_user(username);
+    shell.execute("touch f").expect("Failed to execute touch");
+    let ls = shell.execute("ls -l f").expect("Failed to execute ls");
+    let ls = ls.0.expect("Missing ls output");
+    let columns = ls.split_whitespace().collect::<Vec<_>>();
+    assert_eq!(columns[2], "test");
+    assert_eq!(columns[3], "test");
+}
@@ -0,0 +1,50 @@
+//! Tests for the uniq command.
+
+use shelljougahara::Shell;
+
+use crate::{run, write_file};
+
+#[test]
+fn test_uniq() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "words", "a\na\nb\nc\nc\nc\na\n");
+    assert_eq!(
+        run(&mut shell, "uniq words"),
+        Some("a\nb\nc\na".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "uniq -c words"),
+        Some("      2 a\n      1 b\n      3 c\n      1 a".to_string())
+    );
+    assert_eq!(run(&mut shell, "uniq -d words"), Some("a\nc".to_string()));
+    assert_eq!(run(&mut shell, "uniq -u words"), Some("b\na".to_string()));
+    assert_eq!(
+        run(&mut shell, "sort words | uniq -c"),
+        Some("      3 a\n      1 b\n      3 c".to_string())
+    );
+}
+
+#[test]
+fn test_uniq_ignore_case() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "words", "Hello\nhello\nHELLO\n");
+    assert_eq!(
+        run(&mut shell, "uniq words"),
+        Some("Hello\nhello\nHELLO".to_string())
+    );
+    assert_eq!(run(&mut shell, "uniq -i words"), Some("Hello".to_string()));
+}
+
+#[test]
+fn test_uniq_errors() {
+    let mut shell = Shell::new_with_user("test");
+    assert_eq!(
+        run(&mut shell, "uniq missing"),
+        Some("uniq: missing: No such file or directory".to_string())
+    );
+    assert_eq!(shell.exit_status(), 1);
+    assert_eq!(
+        run(&mut shell, "uniq a b"),
+        Some("uniq: extra operand 'b'".to_string())
+    );
+}
@@ -0,0 +1,171 @@
+//! Tests for the useradd command.
+
+use shelljougahara::Shell;
+
+use crate::root_shell;
+
+#[test]
+fn test_useradd_creates_user_and_group() {
+    let mut shell = root_shell();
+    let useradd = shell
+        .execute("useradd -m -s /bin/bash bob")
+        .expect("Failed to execute useradd");
+    assert_eq!(useradd.0, None);
+
+    let bob = shell
+        .fs
+        .lock()
+        .find_user_by_name("bob")
+        .expect("Missing user")
+        .clone();
+    let group = shell
+        .fs
+        .lock()
+        .find_group_by_name("bob")
+        .expect("Missing group")
+        .clone();
+    assert_eq!(bob.primary_group(), Some(group.id));
+    assert_eq!(bob.home, "/home/bob");
+    assert_eq!(bob.shell, "/bin/bash");
+
+    let ls = shell.execute("ls -l /home").expect("Failed to execute ls");
+    assert!(
+        ls.0.expect("Missing ls output")
+            .lines()
+            .any(|line| line.starts_with("drwxr-xr-x 2 bob bob ") && line.ends_with(" bob"))
+    );
+}
+
+#[test]
+fn test_useradd_without_home() {
+    let mut shell = root_shell();
+    shell
+        .execute("useradd bob")
+        .expect("Failed to execute useradd");
+    assert!(shell.fs.lock().find_user_by_name("bob").is_some());
+    assert!(shell.fs.lock().find_absolute_inode("/home/bob").is_none());
+}
+
+#[test]
+fn test_useradd_groups() {
+    let mut shell = root_shell();
+    shell
+        .execute("groupadd dev")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("groupadd ops")
+        .expect("Failed to execute groupadd");
+    shell
+        .execute("useradd -g dev -G ops,admin bob")
+        .expect("Failed to execute useradd");
+
+    let dev = shell
+        .fs
+        .lock()
+        .find_group_by_name("dev")
+        .expect("Missing group")
+        .id;
+    let ops = shell
+        .fs
+        .lock()
+        .find_group_by_name("ops")
+        .expect("Missing group")
+        .id;
+    let admin = shell
+        .fs
+        .lock()
+        .find_group_by_name("admin")
+        .expect("Missing group")
+        .id;
+    let bob = shell
+        .fs
+        .lock()
+        .find_user_by_name("bob")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(bob.groups, vec![dev, ops, admin]);
+    assert!(shell.fs.lock().find_group_by_name("bob").is_none());
+}
+
+#[test]
+fn test_useradd_errors() {
+    let mut shell = root_shell();
+    let useradd = shell
+        .execute("useradd admin")
+        .expect("Failed to execute useradd");
+    assert_eq!(
+        useradd.0,
+        Some("useradd: user 'admin' already exists".to_string())
+    );
+    let useradd = shell
+        .execute("useradd -g missing bob")
+        .expect("Failed to execute useradd");
+    assert_eq!(
+        useradd.0,
+        Some("useradd: group 'missing' does not exist".to_string())
+    );
+    let useradd = shell
+        .execute("useradd Bad:Name")
+        .expect("Failed to execute useradd");
+    assert_eq!(
+        useradd.0,
+        Some("useradd: invalid user name 'Bad:Name'".to_string())
+    );
+    assert!(shell.fs.lock().find_user_by_name("bob").is_none());
+}
+
+#[test]
+fn test_useradd_requires_root() {
+    let mut shell = Shell::new_with_user("test");
+    let useradd = shell
+        .execute("useradd bob")
+        .expect("Failed to execute useradd");
+    assert_eq!(useradd.0, Some("useradd: Permission denied.".to_string()));
+    assert!(shell.fs.lock().find_user_by_name("bob").is_none());
+}
+
+#[test]
+fn test_useradd_id_ranges() {
+    let mut shell = root_shell();
+    shell
+        .execute("useradd bob")
+        .expect("Failed to execute useradd");
+    shell
+        .execute("useradd -r www-data")
+        .expect("Failed to execute useradd");
+    let id = shell.execute("id www-data").expect("Failed to execute id");
+    assert_eq!(
+        id.0,
+        Some("uid=999(www-data) gid=999(www-data) groups=999(www-data)".to_string())
+    );
+    let id = shell.execute("id bob").expect("Failed to execute id");
+    assert_eq!(
+        id.0,
+        Some("uid=1001(bob) gid=1001(bob) groups=1001(bob)".to_string())
+    );
+
+    // Regular ids follow the highest one in use, system ids take the highest free one.
+    shell
+        .execute("userdel bob")
+        .expect("Failed to execute userdel");
+    shell
+        .execute("useradd carol")
+        .expect("Failed to execute useradd");
+    let carol = shell
+        .fs
+        .lock()
+        .find_user_by_name("carol")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(carol.id, 1001);
+    shell
+        .execute("useradd --system sshd")
+        .expect("Failed to execute useradd");
+    let sshd = shell
+        .fs
+        .lock()
+        .find_user_by_name("sshd")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(sshd.id, 998);
+}
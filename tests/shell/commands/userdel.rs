@@ -0,0 +1,78 @@
+//! Tests for the userdel command.
+
+use crate::root_shell;
+
+#[test]
+fn test_userdel_keeps_home() {
+    let mut shell = root_shell();
+    shell
+        .execute("useradd -m bob")
+        .expect("Failed to execute useradd");
+    let userdel = shell
+        .execute("userdel bob")
+        .expect("Failed to execute userdel");
+    assert_eq!(userdel.0, None);
+    assert!(shell.fs.lock().find_user_by_name("bob").is_none());
+    assert!(shell.fs.lock().find_group_by_name("bob").is_none());
+    assert!(shell.fs.lock().find_absolute_inode("/home/bob").is_some());
+}
+
+#[test]
+fn test_userdel_remove_home() {
+    let mut shell = root_shell();
+    shell
+        .execute("useradd -m bob")
+        .expect("Failed to execute useradd");
+    shell
+        .execute("touch /home/bob/notes")
+        .expect("Failed to execute touch");
+    shell
+        .execute("userdel -r bob")
+        .expect("Failed to execute userdel");
+    assert!(shell.fs.lock().find_absolute_inode("/home/bob").is_none());
+
+    shell
+        .execute("useradd carol")
+        .expect("Failed to execute useradd");
+    let userdel = shell
+        .execute("userdel -r carol")
+        .expect("Failed to execute userdel");
+    assert_eq!(
+        userdel.0,
+        Some("userdel: carol home directory (/home/carol) not found".to_string())
+    );
+}
+
+#[test]
+fn test_userdel_errors() {
+    let mut shell = root_shell();
+    let userdel = shell
+        .execute("userdel bob")
+        .expect("Failed to execute userdel");
+    assert_eq!(
+        userdel.0,
+        Some("userdel: user 'bob' does not exist".to_string())
+    );
+    let userdel = shell
+        .execute("userdel root")
+        .expect("Failed to execute userdel");
+    assert_eq!(
+        userdel.0,
+        Some("userdel: user root is currently used by process 1".to_string())
+    );
+}
+
+#[test]
+fn test_userdel_keeps_shared_group() {
+    let mut shell = root_shell();
+    shell
+        .execute("useradd bob")
+        .expect("Failed to execute useradd");
+    shell
+        .execute("useradd -g bob carol")
+        .expect("Failed to execute useradd");
+    shell
+        .execute("userdel bob")
+        .expect("Failed to execute userdel");
+    assert!(shell.fs.lock().find_group_by_name("bob").is_some());
+}
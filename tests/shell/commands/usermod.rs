@@ -0,0 +1,118 @@
+//! Tests for the usermod command.
+
+use shelljougahara::Shell;
+
+use crate::root_shell;
+
+#[test]
+fn test_usermod_groups() {
+    let mut shell = root_shell();
+    for group in ["dev", "ops", "qa"] {
+        shell
+            .execute(format!("groupadd {group}").as_str())
+            .expect("Failed to execute groupadd");
+    }
+    shell
+        .execute("useradd -G dev bob")
+        .expect("Failed to execute useradd");
+    let group_id = |shell: &Shell, name: &str| {
+        shell
+            .fs
+            .lock()
+            .find_group_by_name(name)
+            .expect("Missing group")
+            .id
+    };
+    let (bob, dev, ops, qa) = (
+        group_id(&shell, "bob"),
+        group_id(&shell, "dev"),
+        group_id(&shell, "ops"),
+        group_id(&shell, "qa"),
+    );
+
+    shell
+        .execute("usermod -aG ops bob")
+        .expect("Failed to execute usermod");
+    let user = shell
+        .fs
+        .lock()
+        .find_user_by_name("bob")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(user.groups, vec![bob, dev, ops]);
+
+    shell
+        .execute("usermod -G qa bob")
+        .expect("Failed to execute usermod");
+    let user = shell
+        .fs
+        .lock()
+        .find_user_by_name("bob")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(user.groups, vec![bob, qa]);
+
+    shell
+        .execute("usermod -g dev bob")
+        .expect("Failed to execute usermod");
+    let user = shell
+        .fs
+        .lock()
+        .find_user_by_name("bob")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(user.groups, vec![dev, qa]);
+}
+
+#[test]
+fn test_usermod_account() {
+    let mut shell = root_shell();
+    shell
+        .execute("useradd bob")
+        .expect("Failed to execute useradd");
+    shell
+        .execute("usermod -s /bin/zsh -d /srv/bob -l robert bob")
+        .expect("Failed to execute usermod");
+    assert!(shell.fs.lock().find_user_by_name("bob").is_none());
+    let user = shell
+        .fs
+        .lock()
+        .find_user_by_name("robert")
+        .expect("Missing user")
+        .clone();
+    assert_eq!(user.shell, "/bin/zsh");
+    assert_eq!(user.home, "/srv/bob");
+}
+
+#[test]
+fn test_usermod_errors() {
+    let mut shell = root_shell();
+    let usermod = shell
+        .execute("usermod -aG dev bob")
+        .expect("Failed to execute usermod");
+    assert_eq!(
+        usermod.0,
+        Some("usermod: user 'bob' does not exist".to_string())
+    );
+    let usermod = shell
+        .execute("usermod -aG dev admin")
+        .expect("Failed to execute usermod");
+    assert_eq!(
+        usermod.0,
+        Some("usermod: group 'dev' does not exist".to_string())
+    );
+    let usermod = shell
+        .execute("usermod -a admin")
+        .expect("Failed to execute usermod");
+    assert_eq!(
+        usermod.0,
+        Some("usermod: -a flag is ONLY allowed with the -G flag".to_string())
+    );
+    let usermod = shell
+        .execute("usermod -l root admin")
+        .expect("Failed to execute usermod");
+    assert_eq!(
+        usermod.0,
+        Some("usermod: user 'root' already exists".to_string())
+    );
+}
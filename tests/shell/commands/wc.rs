@@ -0,0 +1,56 @@
+//! Tests for the wc command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::{run, write_file};
+
+#[test]
+fn test_wc_counts() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "poem", "roses are red\nviolets are blue\n");
+    write_file(&mut shell, "accents", "café\n");
+    assert_eq!(
+        run(&mut shell, "wc poem"),
+        Some(" 2  6 31 poem".to_string())
+    );
+    assert_eq!(run(&mut shell, "wc -l poem"), Some("2 poem".to_string()));
+    assert_eq!(run(&mut shell, "wc -w poem"), Some("6 poem".to_string()));
+    assert_eq!(
+        run(&mut shell, "wc -c accents"),
+        Some("6 accents".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "wc -m accents"),
+        Some("5 accents".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "wc -lc poem"),
+        Some(" 2 31 poem".to_string())
+    );
+}
+
+#[test]
+fn test_wc_several_files() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "poem", "roses are red\nviolets are blue\n");
+    write_file(&mut shell, "empty", "");
+    assert_eq!(
+        run(&mut shell, "wc poem missing empty"),
+        Some(
+            " 2  6 31 poem\nwc: missing: No such file or directory\n 0  0  0 empty\n 2  6 31 total"
+                .to_string()
+        )
+    );
+    assert_eq!(shell.exit_status(), 1);
+}
+
+#[test]
+fn test_wc_standard_input() {
+    let mut shell = Shell::new_with_user("test");
+    shell.set_stdin(QueuedInput::new(["one two", "three"]));
+    assert_eq!(
+        run(&mut shell, "wc"),
+        Some("      2       3      14".to_string())
+    );
+    assert_eq!(run(&mut shell, "echo a b c | wc -w"), Some("3".to_string()));
+}
@@ -0,0 +1,74 @@
+//! Tests for the who and w commands.
+
+use shelljougahara::Shell;
+
+#[test]
+fn test_who() {
+    let mut shell = Shell::new_with_user("alice");
+    let who = shell.execute("who").expect("Failed to execute who");
+    let who = who.0.expect("Missing who output");
+    assert!(who.starts_with("alice    pts/0        "));
+    assert_eq!(who.lines().count(), 1);
+
+    let who = shell.execute("who -H").expect("Failed to execute who");
+    assert!(
+        who.0
+            .expect("Missing who output")
+            .starts_with("NAME     LINE         TIME\nalice    pts/0 ")
+    );
+    let who = shell.execute("who -q").expect("Failed to execute who");
+    assert_eq!(who.0, Some("alice\n# users=1".to_string()));
+}
+
+#[test]
+fn test_w() {
+    let mut shell = Shell::new_with_user("alice");
+    shell.execute("pwd").expect("Failed to execute pwd");
+    let w = shell.execute("w").expect("Failed to execute w");
+    let w = w.0.expect("Missing w output");
+    let lines = w.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains(" 1 user,  load average: "));
+    assert!(lines[1].starts_with("USER     TTY      LOGIN@"));
+    assert!(lines[2].starts_with("alice    pts/0    "));
+    assert!(lines[2].ends_with(" w"));
+
+    let w = shell.execute("w -h").expect("Failed to execute w");
+    assert_eq!(w.0.expect("Missing w output").lines().count(), 1);
+}
+
+#[test]
+fn test_who_lists_every_terminal() {
+    let mut alice = Shell::new_with_user("alice");
+    let host = alice.host().clone();
+    host.fs.lock().add_user("bob").expect("Failed to add user");
+    let mut bob = host.login("bob").expect("Failed to log in");
+    bob.execute("pwd").expect("Failed to execute pwd");
+
+    let who = alice.execute("who").expect("Failed to execute who");
+    let who = who.0.expect("Missing who output");
+    let lines = who.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("alice    pts/0 "));
+    assert!(lines[1].starts_with("bob      pts/1 "));
+
+    // Switching users doesn't change who logged in.
+    let alice_id = alice.current_session.current_user();
+    {
+        let mut fs = host.fs.lock();
+        let now = fs.now();
+        fs.update_user(alice_id, |user| user.delete_password(now))
+            .expect("Failed to delete password");
+    }
+    let su = bob.execute("su alice").expect("Failed to execute su");
+    assert_eq!(su.0, None);
+    let who = alice.execute("who -q").expect("Failed to execute who");
+    assert_eq!(who.0, Some("alice bob\n# users=2".to_string()));
+
+    let w = alice.execute("w -h").expect("Failed to execute w");
+    let w = w.0.expect("Missing w output");
+    let lines = w.lines().collect::<Vec<_>>();
+    assert!(lines[0].ends_with(" w"));
+    assert!(lines[1].starts_with("bob      pts/1 "));
+    assert!(lines[1].ends_with(" su"));
+}
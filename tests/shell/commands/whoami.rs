@@ -0,0 +1,17 @@
+//! Tests for the whoami command.
+
+use shelljougahara::Shell;
+
+#[test]
+fn test_whoami() {
+    let mut shell = Shell::new_with_user("alice");
+    let whoami = shell.execute("whoami").expect("Failed to execute whoami");
+    assert_eq!(whoami.0, Some("alice".to_string()));
+
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    let whoami = shell.execute("whoami").expect("Failed to execute whoami");
+    assert_eq!(whoami.0, Some("root".to_string()));
+}
@@ -0,0 +1,70 @@
+//! Tests for the write command.
+
+use shelljougahara::{QueuedInput, Shell};
+
+/// Returns the shells of alice and bob, logged in on the same host.
+fn two_terminals() -> (Shell, Shell) {
+    let alice = Shell::new_with_user("alice");
+    let host = alice.host().clone();
+    host.fs.lock().add_user("bob").expect("Failed to add user");
+    let bob = host.login("bob").expect("Failed to log in");
+    (alice, bob)
+}
+
+#[test]
+fn test_write_delivers_message() {
+    let (mut alice, bob) = two_terminals();
+    alice.set_stdin(QueuedInput::new(["hello bob", "lunch?"]));
+    let write = alice.execute("write bob").expect("Failed to execute write");
+    assert_eq!(write.0, None);
+
+    let messages = bob.take_messages();
+    assert_eq!(messages.len(), 1);
+    assert!(messages[0].starts_with("Message from alice@localhost on pts/0 at "));
+    assert!(messages[0].ends_with(" ...\nhello bob\nlunch?\nEOF"));
+    assert!(bob.take_messages().is_empty());
+    assert!(alice.take_messages().is_empty());
+}
+
+#[test]
+fn test_write_to_terminal() {
+    let (mut alice, bob) = two_terminals();
+    let second_bob = alice.host().login("bob").expect("Failed to log in");
+
+    alice.set_stdin(QueuedInput::new(["first"]));
+    let write = alice.execute("write bob").expect("Failed to execute write");
+    assert_eq!(
+        write.0,
+        Some("write: bob is logged in more than once; writing to pts/1".to_string())
+    );
+    alice.set_stdin(QueuedInput::new(["second"]));
+    let write = alice
+        .execute("write bob pts/2")
+        .expect("Failed to execute write");
+    assert_eq!(write.0, None);
+    assert!(bob.take_messages()[0].ends_with("\nfirst\nEOF"));
+    assert!(second_bob.take_messages()[0].ends_with("\nsecond\nEOF"));
+
+    let write = alice
+        .execute("write bob pts/0")
+        .expect("Failed to execute write");
+    assert_eq!(
+        write.0,
+        Some("write: bob is not logged in on pts/0".to_string())
+    );
+}
+
+#[test]
+fn test_write_to_absent_user() {
+    let mut shell = Shell::new_with_user("alice");
+    let write = shell
+        .execute("write root")
+        .expect("Failed to execute write");
+    assert_eq!(write.0, Some("write: root is not logged in".to_string()));
+    let write = shell
+        .execute("write nobody")
+        .expect("Failed to execute write");
+    assert_eq!(write.0, Some("write: nobody is not logged in".to_string()));
+    let write = shell.execute("write").expect("Failed to execute write");
+    assert_eq!(write.0, Some("usage: write user [tty]".to_string()));
+}
@@ -0,0 +1,139 @@
+//! Tests for shells of one host used concurrently from several threads and tasks.
+
+use std::{panic, thread};
+
+use shelljougahara::{Host, SharedFileSystem, Shell};
+
+const TERMINALS: usize = 8;
+const ROUNDS: usize = 25;
+
+fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+#[test]
+fn test_shell_is_send_and_sync() {
+    assert_send_sync::<Host>();
+    assert_send_sync::<Shell>();
+    assert_send_sync::<SharedFileSystem>();
+}
+
+/// Returns a host with one user per terminal, named `user0`, `user1` and so on.
+fn host_with_users() -> Host {
+    let host = Host::new();
+    let mut fs = host.fs.lock();
+    for index in 0..TERMINALS {
+        fs.add_user(&format!("user{index}"))
+            .expect("Failed to add user");
+    }
+    drop(fs);
+    host
+}
+
+/// Creates, lists and removes files in `/tmp`, returning the number of entries seen by `ls`.
+fn work(shell: &mut Shell, name: &str, round: usize) -> usize {
+    let directory = format!("/tmp/{name}-{round}");
+    let mkdir = shell
+        .execute(&format!("mkdir {directory}"))
+        .expect("Failed to execute mkdir");
+    assert_eq!(mkdir.0, None);
+    shell
+        .execute(&format!("cd {directory}"))
+        .expect("Failed to execute cd");
+    shell
+        .execute("touch first second")
+        .expect("Failed to execute touch");
+    let ls = shell.execute("ls").expect("Failed to execute ls");
+    let entries = ls.0.map_or(0, |ls| ls.split_whitespace().count());
+    shell.execute("cd -").expect("Failed to execute cd");
+    let rm = shell
+        .execute(&format!("rm -r {directory}"))
+        .expect("Failed to execute rm");
+    assert_eq!(rm.0, None);
+    entries
+}
+
+#[test]
+fn test_concurrent_terminals() {
+    let host = host_with_users();
+    let handles = (0..TERMINALS)
+        .map(|index| {
+            let host = host.clone();
+            thread::spawn(move || {
+                let name = format!("user{index}");
+                let mut shell = host.login(&name).expect("Failed to log in");
+                for round in 0..ROUNDS {
+                    assert_eq!(work(&mut shell, &name, round), 2);
+                    shell.execute("who").expect("Failed to execute who");
+                }
+                shell.execute("exit").expect("Failed to execute exit");
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Accounts change while the terminals are busy.
+    let mut root = host.login("root").expect("Failed to log in");
+    for round in 0..ROUNDS {
+        let useradd = root
+            .execute(&format!("useradd -m extra{round}"))
+            .expect("Failed to execute useradd");
+        assert_eq!(useradd.0, None);
+    }
+    for handle in handles {
+        handle.join().expect("A terminal panicked");
+    }
+
+    let ls = root.execute("ls /tmp").expect("Failed to execute ls");
+    assert_eq!(ls.0, None);
+    let who = root.execute("who -q").expect("Failed to execute who");
+    assert_eq!(who.0, Some("root\n# users=1".to_string()));
+    let fs = host.fs.lock();
+    assert_eq!(fs.users().count(), 1 + TERMINALS + ROUNDS);
+    let mut ids = fs.users().map(|user| user.id).collect::<Vec<_>>();
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), 1 + TERMINALS + ROUNDS);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_terminals_in_tasks() {
+    let host = host_with_users();
+    let tasks = (0..TERMINALS)
+        .map(|index| {
+            let mut shell = host
+                .login(&format!("user{index}"))
+                .expect("Failed to log in");
+            tokio::spawn(async move {
+                let name = format!("user{index}");
+                let mut entries = 0;
+                for round in 0..ROUNDS {
+                    entries += work(&mut shell, &name, round);
+                    tokio::task::yield_now().await;
+                }
+                entries
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for task in tasks {
+        assert_eq!(task.await.expect("A terminal panicked"), 2 * ROUNDS);
+    }
+    assert!(host.logins().is_empty());
+}
+
+#[test]
+fn test_panic_does_not_poison_host() {
+    let mut shell = Shell::new_with_user("alice");
+    let fs = shell.fs.clone();
+    let result = thread::spawn(move || {
+        let _guard = fs.lock();
+        panic::panic_any("A command failed while holding the file system");
+    })
+    .join();
+    assert!(result.is_err());
+
+    let mkdir = shell
+        .execute("mkdir still-working")
+        .expect("Failed to execute mkdir");
+    assert_eq!(mkdir.0, None);
+    let ls = shell.execute("ls").expect("Failed to execute ls");
+    assert_eq!(ls.0, Some("still-working".to_string()));
+}
@@ -0,0 +1,83 @@
+//! Tests for hosts sharing a file system between several terminals.
+
+use shelljougahara::{Host, Shell};
+
+/// Returns the shells of alice and bob, logged in on the same host.
+fn two_terminals() -> (Shell, Shell) {
+    let alice = Shell::new_with_user("alice");
+    let host = alice.host().clone();
+    host.fs.lock().add_user("bob").expect("Failed to add user");
+    let bob = host.login("bob").expect("Failed to log in");
+    (alice, bob)
+}
+
+#[test]
+fn test_terminals_share_files() {
+    let (mut alice, mut bob) = two_terminals();
+    assert_eq!(alice.current_session.terminal(), "pts/0");
+    assert_eq!(bob.current_session.terminal(), "pts/1");
+
+    alice
+        .execute("mkdir /tmp/shared")
+        .expect("Failed to execute mkdir");
+    let ls = bob.execute("ls /tmp").expect("Failed to execute ls");
+    assert_eq!(ls.0, Some("shared".to_string()));
+
+    // Each terminal has its own directory and history.
+    alice.execute("cd /tmp").expect("Failed to execute cd");
+    let pwd = bob.execute("pwd").expect("Failed to execute pwd");
+    assert_eq!(pwd.0, Some("/home/bob".to_string()));
+    assert_eq!(alice.current_session.get_history(), ["mkdir", "cd"]);
+    assert_eq!(bob.current_session.get_history(), ["ls", "pwd"]);
+}
+
+#[test]
+fn test_terminals_share_accounts() {
+    let host = Host::new();
+    host.fs
+        .lock()
+        .add_user("admin")
+        .expect("Failed to add user");
+    let mut root = host.login("root").expect("Failed to log in");
+    root.execute("useradd -m carol")
+        .expect("Failed to execute useradd");
+
+    let mut carol = host.login("carol").expect("Failed to log in");
+    let whoami = carol.execute("whoami").expect("Failed to execute whoami");
+    assert_eq!(whoami.0, Some("carol".to_string()));
+
+    // A user can't be removed while logged in.
+    let userdel = root
+        .execute("userdel carol")
+        .expect("Failed to execute userdel");
+    assert_eq!(
+        userdel.0,
+        Some("userdel: user carol is currently used by process 1".to_string())
+    );
+    carol.execute("exit").expect("Failed to execute exit");
+    let userdel = root
+        .execute("userdel carol")
+        .expect("Failed to execute userdel");
+    assert_eq!(userdel.0, None);
+
+    assert!(host.login("carol").is_err());
+}
+
+#[test]
+fn test_logout_frees_terminal() {
+    let (mut alice, bob) = two_terminals();
+    let host = alice.host().clone();
+    drop(bob);
+    let who = alice.execute("who -q").expect("Failed to execute who");
+    assert_eq!(who.0, Some("alice\n# users=1".to_string()));
+
+    let mut root = host.login("root").expect("Failed to log in");
+    assert_eq!(root.current_session.terminal(), "pts/1");
+    alice.execute("exit").expect("Failed to execute exit");
+    let who = root.execute("who").expect("Failed to execute who");
+    assert!(
+        who.0
+            .expect("Missing who output")
+            .starts_with("root     pts/1        ")
+    );
+}
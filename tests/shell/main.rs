@@ -1,3 +1,59 @@
 //! Simple tests for the shell.
 
+use std::path::Path;
+
+use shelljougahara::Shell;
+
 pub mod commands;
+mod concurrency;
+mod host;
+mod pipelines;
+mod quota;
+
+/// Executes a command line, returning its output.
+pub fn run(shell: &mut Shell, command: &str) -> Option<String> {
+    shell.execute(command).expect("Failed to execute command").0
+}
+
+/// Returns the symbolic mode of a file, like `-rw-r--r--`.
+pub fn mode_of(shell: &mut Shell, path: &str) -> String {
+    run(shell, &format!("stat -c %A {path}")).expect("Missing stat output")
+}
+
+/// Returns the name of the user of the shell.
+pub fn whoami(shell: &mut Shell) -> String {
+    run(shell, "whoami").expect("Missing whoami output")
+}
+
+/// Replaces the content of a file as the user of the shell, creating it if needed.
+pub fn write_file(shell: &mut Shell, path: &str, content: &str) {
+    shell
+        .current_session
+        .write_file(&mut shell.fs.lock(), Path::new(path), content)
+        .expect("Failed to write file");
+}
+
+/// Returns a shell of a regular user whose home directory holds the given directories, created in
+/// order, and files.
+pub fn shell_with_files(directories: &[&str], files: &[(&str, &str)]) -> Shell {
+    let mut shell = Shell::new_with_user("test");
+    for directory in directories {
+        shell
+            .execute(&format!("mkdir {directory}"))
+            .expect("Failed to create a directory");
+    }
+    for (path, content) in files {
+        write_file(&mut shell, path, content);
+    }
+    shell
+}
+
+/// Returns a shell whose session belongs to root.
+pub fn root_shell() -> Shell {
+    let mut shell = Shell::new_with_user("admin");
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    shell
+}
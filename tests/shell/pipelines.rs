@@ -0,0 +1,67 @@
+//! Tests for pipelines and exit statuses.
+
+use shelljougahara::{QueuedInput, Shell};
+
+use crate::write_file;
+
+#[test]
+fn test_pipeline_feeds_standard_input() {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "notes", "apple\nbanana\ncherry\n");
+    let grep = shell
+        .execute("cat notes | grep an")
+        .expect("Failed to execute pipeline");
+    assert_eq!(grep.0, Some("banana".to_string()));
+    let grep = shell
+        .execute("cat notes | grep -v an | grep -c e")
+        .expect("Failed to execute pipeline");
+    assert_eq!(grep.0, Some("2".to_string()));
+}
+
+#[test]
+fn test_pipeline_quoted_bar() {
+    let mut shell = Shell::new_with_user("test");
+    let echo = shell
+        .execute("echo 'a|b' \"c|d\" e\\|f")
+        .expect("Failed to execute echo");
+    assert_eq!(echo.0, Some("a|b c|d e|f".to_string()));
+    let grep = shell
+        .execute("echo 'a|b' | grep -F '|'")
+        .expect("Failed to execute pipeline");
+    assert_eq!(grep.0, Some("a|b".to_string()));
+
+    assert!(shell.execute("echo a |").is_err());
+    assert!(shell.execute("| grep a").is_err());
+}
+
+#[test]
+fn test_pipeline_restores_standard_input() {
+    let mut shell = Shell::new_with_user("test");
+    shell.set_stdin(QueuedInput::new(["typed"]));
+    let grep = shell
+        .execute("echo piped | grep piped")
+        .expect("Failed to execute pipeline");
+    assert_eq!(grep.0, Some("piped".to_string()));
+    let grep = shell.execute("grep typed").expect("Failed to execute grep");
+    assert_eq!(grep.0, Some("typed".to_string()));
+}
+
+#[test]
+fn test_exit_status() {
+    let mut shell = Shell::new_with_user("test");
+    shell
+        .execute("echo hello | grep bye")
+        .expect("Failed to execute pipeline");
+    assert_eq!(shell.exit_status(), 1);
+    // The status is the one of the last command of the pipeline.
+    shell
+        .execute("echo hello | grep bye | echo done")
+        .expect("Failed to execute pipeline");
+    assert_eq!(shell.exit_status(), 0);
+    shell
+        .execute("grep hello missing")
+        .expect("Failed to execute grep");
+    assert_eq!(shell.exit_status(), 2);
+    shell.execute("pwd").expect("Failed to execute pwd");
+    assert_eq!(shell.exit_status(), 0);
+}
@@ -0,0 +1,146 @@
+//! Tests for the limits on the space of the file system.
+
+use shelljougahara::{
+    AccountKind, FilePermissions, FileSystem, InodeContent, InodeMetadata, Quota, Shell,
+};
+
+use crate::{run, write_file};
+
+/// Returns a shell whose disk is filled by a log file of root, the user having a small `notes`
+/// file.
+fn shell_with_full_disk() -> Shell {
+    let mut shell = Shell::new_with_user("test");
+    write_file(&mut shell, "notes", "hello\n");
+    {
+        let mut fs = shell.fs.lock();
+        fs.create_file(
+            "/var/log/app.log",
+            InodeMetadata::new(FilePermissions::from_mode(0o644), 0, 0),
+        )
+        .expect("Failed to create the log file");
+        let used_space = fs.used_space();
+        fs.set_capacity(used_space + 1024 * 1024);
+        fs.write_file("/var/log/app.log", "x".repeat(1024 * 1024))
+            .expect("Failed to fill the disk");
+    }
+    shell
+}
+
+#[test]
+fn test_full_disk() {
+    let mut shell = shell_with_full_disk();
+    assert_eq!(
+        run(&mut shell, "mkdir project"),
+        Some("mkdir: cannot create directory 'project': No space left on device".to_string())
+    );
+    assert_eq!(run(&mut shell, "touch empty"), None);
+    assert_eq!(run(&mut shell, "sed -i s/hello/bye/ notes"), None);
+    assert_eq!(
+        run(
+            &mut shell,
+            &format!("sed -i s/bye/{}/ notes", "x".repeat(5000))
+        ),
+        Some("sed: couldn't edit notes: No space left on device".to_string())
+    );
+    assert!(
+        run(&mut shell, "df -h")
+            .expect("df should print the disk")
+            .ends_with("0 100% /")
+    );
+    assert_eq!(
+        run(&mut shell, "du -sh /var/log"),
+        Some("1.1M\t/var/log".to_string())
+    );
+
+    shell
+        .fs
+        .lock()
+        .remove_inode("/var/log/app.log")
+        .expect("Failed to remove the log file");
+    assert_eq!(run(&mut shell, "mkdir project"), None);
+}
+
+#[test]
+fn test_quota() {
+    let mut shell = Shell::new_with_user("test");
+    {
+        let mut fs = shell.fs.lock();
+        let user = shell.current_session.current_user();
+        fs.set_quota(
+            user,
+            Quota {
+                space: Some(2 * 4096),
+                inodes: Some(3),
+            },
+        );
+    }
+    write_file(&mut shell, "notes", "hello\n");
+    assert_eq!(
+        run(&mut shell, "touch a b"),
+        Some("touch: cannot touch 'b': Disk quota exceeded".to_string())
+    );
+    assert_eq!(
+        run(&mut shell, "sed -i 1ihello notes"),
+        None,
+        "Writes within the quota should succeed"
+    );
+    write_file(&mut shell, "notes", &"x".repeat(4096));
+    assert_eq!(
+        run(&mut shell, "sed -i 1ihello notes"),
+        Some("sed: couldn't edit notes: Disk quota exceeded".to_string())
+    );
+}
+
+#[test]
+fn test_useradd_on_full_disk() {
+    let mut shell = Shell::new_with_user("test");
+    {
+        // Fill /etc/passwd up to the end of its first block, so that adding a user needs another
+        // block.
+        let mut fs = shell.fs.lock();
+        let passwd_size = |fs: &FileSystem| {
+            let passwd = fs
+                .find_absolute_inode("/etc/passwd")
+                .expect("Missing passwd");
+            match &fs.inode(passwd).expect("Missing inode").content {
+                InodeContent::File(file) => file.content.len(),
+                _ => panic!("/etc/passwd should be a file"),
+            }
+        };
+        let mut count = 0;
+        while passwd_size(&fs) < 4096 - 64 {
+            let group = fs
+                .create_group(&format!("user{count}"), AccountKind::Regular)
+                .expect("Failed to add group");
+            fs.create_user(&format!("user{count}"), group, AccountKind::Regular)
+                .expect("Failed to add user");
+            count += 1;
+        }
+        let used_space = fs.used_space();
+        fs.set_capacity(used_space);
+    }
+    shell
+        .current_session
+        .change_user(&shell.fs.lock(), 0)
+        .expect("Failed to change user");
+    assert_eq!(run(&mut shell, "useradd bob"), None);
+    assert_eq!(
+        run(&mut shell, "useradd -m carol"),
+        Some("useradd: cannot create directory /home/carol".to_string())
+    );
+    assert_eq!(
+        run(
+            &mut shell,
+            "grep -c -E '^(bob|carol):' /etc/passwd /etc/shadow /etc/group"
+        ),
+        Some("/etc/passwd:2\n/etc/shadow:2\n/etc/group:2".to_string())
+    );
+    assert_eq!(run(&mut shell, "userdel carol"), None);
+    assert_eq!(
+        run(
+            &mut shell,
+            "grep -c ^carol: /etc/passwd /etc/shadow /etc/group"
+        ),
+        Some("/etc/passwd:0\n/etc/shadow:0\n/etc/group:0".to_string())
+    );
+}